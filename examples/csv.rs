@@ -24,7 +24,7 @@ fn main() {
     .unwrap();
     table.printstd();
 
-    println!("");
+    println!();
     println!(
         "{}",
         String::from_utf8(table.to_csv(Vec::new()).unwrap().into_inner().unwrap()).unwrap()
@@ -14,7 +14,7 @@ fn main() {
     println!("FORMAT_NO_LINESEP_WITH_TITLE :");
     table.set_format(*format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
     table.printstd();
-    println!("");
+    println!();
 
     // Print
     // -------------------------
@@ -27,7 +27,7 @@ fn main() {
     println!("FORMAT_NO_COLSEP :");
     table.set_format(*format::consts::FORMAT_NO_COLSEP);
     table.printstd();
-    println!("");
+    println!();
 
     // Print
     // +-------------------------+
@@ -39,7 +39,7 @@ fn main() {
     println!("FORMAT_BORDERS_ONLY :");
     table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
     table.printstd();
-    println!("");
+    println!();
 
     // Custom format can be implemented using `prettytable::format::FormatBuilder`
     // Example to print
@@ -4,9 +4,9 @@ use std::io;
 use std::io::Write;
 use std::str::FromStr;
 
-const CROSS: &'static str = "X";
-const EMPTY: &'static str = " ";
-const ROUND: &'static str = "O";
+const CROSS: &str = "X";
+const EMPTY: &str = " ";
+const ROUND: &str = "O";
 
 fn main() {
     let mut table = table![
@@ -33,7 +33,7 @@ fn main() {
                 continue;
             }
         };
-        if i < 1 || i > 9 {
+        if !(1..=9).contains(&i) {
             println!("Bad input, should be between 1 and 9");
             height += 1;
             continue;
@@ -0,0 +1,310 @@
+//! Appending the rows or the columns of a table to another
+
+use super::{AsTableSlice, Cell, Row, Table};
+
+/// Error returned when appending a table whose titles do not match
+const TITLES_MISMATCH: &str = "Titles do not match";
+
+impl Table {
+    /// Move the rows of `other` at the end of this table. The format of this table is kept.
+    ///
+    /// If both tables have titles, they must have the same text, ignoring ANSI escape
+    /// sequences, otherwise an error is returned and this table is unchanged. If only
+    /// `other` has titles, they become the titles of this table.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut table = table![["a", 1]];
+    /// table.set_titles(row!["Name", "Count"]);
+    /// let mut other = table![["b", 2]];
+    /// other.set_titles(row!["Name", "Count"]);
+    /// table.append(other).unwrap();
+    /// assert_eq!(table.len(), 2);
+    ///
+    /// let mut other = table![["c", 3]];
+    /// other.set_titles(row!["Count", "Name"]);
+    /// assert!(table.append(other).is_err());
+    /// # }
+    /// ```
+    pub fn append(&mut self, other: Table) -> Result<(), &str> {
+        let titles = self.merge_titles(*other.titles)?;
+        *self.titles = titles;
+        self.rows.extend(other.rows);
+        Ok(())
+    }
+
+    /// Copy the rows of `other` at the end of this table, like `append()`
+    pub fn append_ref(&mut self, other: &Table) -> Result<(), &str> {
+        self.append(other.clone())
+    }
+
+    /// Move the rows of `other` at the end of this table, like `append()`. If both tables have
+    /// titles, the columns of `other` are reordered to match the titles of this table.
+    ///
+    /// An error is returned if the titles of the tables are not the same once reordered.
+    /// Cells of `other` after its titles are kept after the reordered ones.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut table = table![["a", 1]];
+    /// table.set_titles(row!["Name", "Count"]);
+    /// let mut other = table![[2, "b"]];
+    /// other.set_titles(row!["Count", "Name"]);
+    /// table.append_reordered(other).unwrap();
+    /// assert_eq!(table[1][0].get_content(), "b");
+    /// # }
+    /// ```
+    pub fn append_reordered(&mut self, mut other: Table) -> Result<(), &str> {
+        if let (Some(titles), Some(other_titles)) = (self.titles.as_ref(), other.titles.as_ref()) {
+            let order = column_order(titles, other_titles).ok_or(TITLES_MISMATCH)?;
+            *other.titles = Some(reorder_row(other_titles, &order));
+            for row in &mut other.rows {
+                *row = reorder_row(row, &order);
+            }
+        }
+        self.append(other)
+    }
+
+    /// Return a new table with the columns of `other` placed to the right of the columns
+    /// of this table, row by row.
+    ///
+    /// Rows of this table are padded with empty cells up to its number of columns, so that
+    /// the columns of `other` line up. When a table has fewer rows than the other one, empty
+    /// cells take the place of its missing rows. Titles, header rows and footers are joined
+    /// the same way, and if only one of the tables has titles, the other one gets empty titles.
+    /// The format, caption and display settings of this table are kept, along with the
+    /// settings of the columns of `other`, like alignments, which follow its columns.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let table = table![["a", 1], ["b"]].hconcat(&table![["x"], ["y"], ["z"]]);
+    /// assert_eq!(table, table![["a", 1, "x"], ["b", "", "y"], ["", "", "z"]]);
+    /// # }
+    /// ```
+    pub fn hconcat(&self, other: &Table) -> Table {
+        self.concat_columns(other, None)
+    }
+
+    /// Return a new table with the columns of `other` placed to the right of the columns of
+    /// this table, like `hconcat()`, separated by a column holding `separator` in every row,
+    /// like `"│"`, or nothing with `""`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// use prettytable::format::consts::FORMAT_CLEAN;
+    /// # fn main() {
+    /// let mut services = table![["db", "up"], ["web", "down"]];
+    /// services.set_titles(row!["Service", "State"]);
+    /// services.set_format(*FORMAT_CLEAN);
+    /// let mut hosts = table![["eu-1", 4]];
+    /// hosts.set_titles(row!["Host", "Load"]);
+    /// let dashboard = services.hconcat_with_separator(&hosts, "│").to_string();
+    /// let lines = dashboard.lines().map(str::trim_end).collect::<Vec<_>>();
+    /// assert_eq!(lines, [
+    ///     " Service  State  │  Host  Load",
+    ///     " db       up     │  eu-1  4",
+    ///     " web      down   │",
+    /// ]);
+    /// # }
+    /// ```
+    pub fn hconcat_with_separator(&self, other: &Table, separator: &str) -> Table {
+        self.concat_columns(other, Some(separator))
+    }
+
+    /// Return a table with the columns of `other` placed to the right of the columns of
+    /// this table, separated by a column holding `separator` if any
+    fn concat_columns(&self, other: &Table, separator: Option<&str>) -> Table {
+        let columns = (self.column_count(), other.column_count());
+        let join =
+            |left: Option<&Row>, right: Option<&Row>| concat_row(left, right, columns, separator);
+        let join_all = |left: &[Row], right: &[Row]| {
+            (0..left.len().max(right.len()))
+                .map(|i| join(left.get(i), right.get(i)))
+                .collect::<Vec<_>>()
+        };
+        let mut table = self.as_slice().slice(0..0).to_table();
+        let offset = columns.0 + usize::from(separator.is_some());
+        table.render.add_columns_of(&other.render, offset);
+        table.rows = join_all(&self.rows, &other.rows);
+        table.header_rows = join_all(&self.header_rows, &other.header_rows);
+        if self.titles.is_some() || other.titles.is_some() {
+            *table.titles = Some(join((*self.titles).as_ref(), (*other.titles).as_ref()));
+        }
+        if self.footer.is_some() || other.footer.is_some() {
+            *table.footer = Some(join((*self.footer).as_ref(), (*other.footer).as_ref()));
+        }
+        table
+    }
+
+    /// Return the titles of this table once `other` is appended to it, or an error
+    /// if they do not match
+    fn merge_titles(&self, other: Option<Row>) -> Result<Option<Row>, &'static str> {
+        match (self.titles.as_ref(), other) {
+            (Some(titles), Some(other)) if titles.plain_text() != other.plain_text() => {
+                Err(TITLES_MISMATCH)
+            }
+            (Some(titles), _) => Ok(Some(titles.clone())),
+            (None, other) => Ok(other),
+        }
+    }
+}
+
+/// Return, for each title of `titles`, the index of the title with the same text
+/// in `other`, or `None` if they do not have the same titles
+fn column_order(titles: &Row, other: &Row) -> Option<Vec<usize>> {
+    let other = other.plain_text();
+    if titles.len() != other.len() {
+        return None;
+    }
+    let mut used = vec![false; other.len()];
+    titles
+        .plain_text()
+        .iter()
+        .map(|title| {
+            let index = (0..other.len()).find(|&i| !used[i] && other[i] == *title)?;
+            used[index] = true;
+            Some(index)
+        })
+        .collect()
+}
+
+/// Return a row holding the cells of `left`, padded up to the first count of `columns`,
+/// followed by a cell holding `separator` if any and the cells of `right`.
+/// A missing row is replaced by as many empty cells as its count of `columns`.
+/// The row keeps the style of `left`, or of `right` if `left` is missing.
+fn concat_row(
+    left: Option<&Row>,
+    right: Option<&Row>,
+    columns: (usize, usize),
+    separator: Option<&str>,
+) -> Row {
+    let mut cells = Vec::with_capacity(columns.0 + columns.1 + 1);
+    cells.extend(left.into_iter().flat_map(Row::iter).cloned());
+    let filled = left.map_or(0, Row::column_count);
+    cells.extend((filled..columns.0).map(|_| Cell::default()));
+    if let Some(separator) = separator {
+        cells.push(Cell::new(separator));
+    }
+    match right {
+        Some(right) => cells.extend(right.iter().cloned()),
+        None => cells.extend((0..columns.1).map(|_| Cell::default())),
+    }
+    match left.or(right) {
+        Some(row) => row.with_cells(cells),
+        None => Row::new(cells),
+    }
+}
+
+/// Return a copy of `row` with its cells taken in `order`, followed by the other ones
+fn reorder_row(row: &Row, order: &[usize]) -> Row {
+    let mut cells = order
+        .iter()
+        .map(|&i| row.get_cell(i).cloned().unwrap_or_else(Cell::default))
+        .collect::<Vec<_>>();
+    cells.extend(row.iter().skip(order.len()).cloned());
+    row.with_cells(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cell, Row, Table};
+
+    fn table(titles: Option<&[&str]>, rows: &[&[&str]]) -> Table {
+        let mut table = Table::from_rows(rows.iter().map(|r| r.iter()));
+        if let Some(titles) = titles {
+            table.set_titles(Row::new(titles.iter().map(|t| Cell::new(t)).collect()));
+        }
+        table
+    }
+
+    #[test]
+    fn append() {
+        let mut a = table(None, &[&["1", "2"]]);
+        a.append(table(Some(&["x", "y"]), &[&["3", "4"]])).unwrap();
+        assert_eq!(a.titles.as_ref().as_ref().unwrap(), &["x", "y"]);
+        assert_eq!(a[1], ["3", "4"]);
+
+        let b = table(None, &[&["5"]]);
+        a.append_ref(&b).unwrap();
+        assert_eq!(a.len(), 3);
+        assert_eq!(b.len(), 1);
+        assert_eq!(a.titles.as_ref().as_ref().unwrap(), &["x", "y"]);
+
+        let mut c = table(Some(&["\x1B[1mx\x1B[0m", "y"]), &[&["6", "7"]]);
+        c.set_format(*crate::format::consts::FORMAT_CLEAN);
+        a.append(c).unwrap();
+        assert_eq!(a.len(), 4);
+        assert_eq!(*a.format, *crate::format::consts::FORMAT_DEFAULT);
+
+        assert!(a.append(table(Some(&["y", "x"]), &[&["8", "9"]])).is_err());
+        assert!(a.append(table(Some(&["x"]), &[])).is_err());
+        assert_eq!(a.len(), 4);
+    }
+
+    #[test]
+    fn append_reordered() {
+        let mut a = table(Some(&["x", "y", "y"]), &[&["1", "2", "3"]]);
+        let b = table(Some(&["y", "x", "y"]), &[&["4", "5", "6", "7"], &["8"]]);
+        a.append_reordered(b).unwrap();
+        assert_eq!(a[1], ["5", "4", "6", "7"]);
+        assert_eq!(a[2], ["", "8", ""]);
+        assert!(a
+            .append_reordered(table(Some(&["x", "z", "y"]), &[]))
+            .is_err());
+        assert!(a.append_reordered(table(None, &[&["9"]])).is_ok());
+    }
+
+    #[test]
+    fn hconcat() {
+        let a = table(Some(&["x", "y"]), &[&["1", "2"], &["3"]]);
+        let mut b = table(None, &[&["4"], &["5"], &["6"]]);
+        b.set_footer(Row::new(vec![Cell::new("t")]));
+        let c = a.hconcat(&b);
+        assert_eq!(c.titles.as_ref().as_ref().unwrap(), &["x", "y", ""]);
+        assert_eq!(c[0], ["1", "2", "4"]);
+        assert_eq!(c[1], ["3", "", "5"]);
+        assert_eq!(c[2], ["", "", "6"]);
+        assert_eq!(c.footer.as_ref().as_ref().unwrap(), &["", "", "t"]);
+        assert_eq!(a.len(), 2);
+
+        let c = b.hconcat_with_separator(&a, "|");
+        assert_eq!(c.titles.as_ref().as_ref().unwrap(), &["", "|", "x", "y"]);
+        assert_eq!(c[1], ["5", "|", "3"]);
+        assert_eq!(c[2], ["6", "|", "", ""]);
+        assert_eq!(c.footer.as_ref().as_ref().unwrap(), &["t", "|", "", ""]);
+
+        let mut spanning = table(None, &[&[]]);
+        spanning[0].add_cell(Cell::new("a").with_hspan(2));
+        let c = spanning.hconcat(&table(None, &[&["b", "c"], &["d"]]));
+        assert_eq!(c[0], ["a", "b", "c"]);
+        assert_eq!(c[1], ["", "", "d"]);
+        assert!(table(None, &[]).hconcat(&table(None, &[])).titles.is_none());
+    }
+
+    #[test]
+    fn hconcat_settings() {
+        use crate::format::Alignment;
+
+        let mut a = table(None, &[&["a", "b"]]);
+        a.set_column_alignment(1, Alignment::CENTER);
+        a.set_column_alignment(2, Alignment::CENTER);
+        let mut b = table(None, &[&["x", "2"], &[""]]);
+        b.set_column_alignment(1, Alignment::RIGHT);
+        b.set_column_placeholder(0, "-");
+        let rendered = a.hconcat(&b).rendered();
+        assert_eq!(rendered[0][1].get_align(), Alignment::CENTER);
+        assert_eq!(rendered[0][2].get_align(), Alignment::LEFT);
+        assert_eq!(rendered[0][3].get_align(), Alignment::RIGHT);
+        assert_eq!(rendered[1][2].get_content(), "-");
+        let rendered = a.hconcat_with_separator(&b, "|").rendered();
+        assert_eq!(rendered[1][3].get_content(), "-");
+        assert_eq!(rendered[0][4].get_align(), Alignment::RIGHT);
+    }
+}
@@ -0,0 +1,355 @@
+//! Parsing of tables printed with a `TableFormat`
+
+use unicode_width::UnicodeWidthChar;
+
+use super::format::{Alignment, ColumnPosition, LinePosition, TableFormat};
+use super::utils::display_width;
+use super::{Cell, ParseError, Row, Table};
+
+/// Characters of a line, with the display column each one starts at
+struct Line<'a> {
+    number: usize,
+    text: &'a str,
+    chars: Vec<(usize, char)>,
+    width: usize,
+}
+
+impl<'a> Line<'a> {
+    fn new(number: usize, text: &'a str) -> Line<'a> {
+        let mut width = 0;
+        let chars = text
+            .chars()
+            .map(|c| {
+                let start = width;
+                width += c.width().unwrap_or(0);
+                (start, c)
+            })
+            .collect();
+        Line {
+            number,
+            text,
+            chars,
+            width,
+        }
+    }
+
+    /// Return the characters starting between display columns `from` and `to`
+    fn slice(&self, from: usize, to: usize) -> String {
+        self.chars
+            .iter()
+            .filter(|&&(pos, _)| pos >= from && pos < to)
+            .map(|&(_, c)| c)
+            .collect()
+    }
+
+    /// Check if `sep` is at display column `pos`. A column past the end of the line
+    /// is considered a separator, since the last cells may not be filled
+    fn has_separator_at(&self, pos: usize, sep: char) -> bool {
+        pos >= self.width || self.chars.iter().any(|&(p, c)| p == pos && c == sep)
+    }
+}
+
+/// Infer the width of each column from the first separator line, or from the
+/// widest data lines when the format prints no usable separator
+fn infer_widths(lines: &[Line], format: &TableFormat, csep: char) -> Vec<usize> {
+    let (lpad, rpad) = format.get_padding();
+    let lborder = format.get_column_separator(ColumnPosition::Left).is_some();
+    let rborder = format.get_column_separator(ColumnPosition::Right).is_some();
+    let positions = [
+        LinePosition::Top,
+        LinePosition::Title,
+        LinePosition::Intern,
+        LinePosition::Bottom,
+    ];
+    for sep in positions
+        .iter()
+        .filter_map(|pos| *format.get_sep_for_line(*pos))
+    {
+        let (line_c, junc, ljunc, rjunc) = sep.get_chars();
+        if junc == line_c {
+            continue;
+        }
+        for line in lines {
+            let mut chars: Vec<char> = line.text.chars().collect();
+            if !chars.contains(&line_c)
+                || !chars
+                    .iter()
+                    .all(|c| [line_c, junc, ljunc, rjunc].contains(c))
+            {
+                continue;
+            }
+            if rborder {
+                chars.pop();
+            }
+            if lborder && !chars.is_empty() {
+                chars.remove(0);
+            }
+            let widths: Option<Vec<usize>> = chars
+                .split(|&c| c == junc)
+                .map(|seg| seg.len().checked_sub(lpad + rpad))
+                .collect();
+            if let Some(widths) = widths {
+                return widths;
+            }
+        }
+    }
+    let segments: Vec<Vec<usize>> = lines
+        .iter()
+        .map(|line| {
+            let mut text = line.text;
+            if let Some(b) = format.get_column_separator(ColumnPosition::Left) {
+                text = text.strip_prefix(b).unwrap_or(text);
+            }
+            if let Some(b) = format.get_column_separator(ColumnPosition::Right) {
+                text = text.strip_suffix(b).unwrap_or(text);
+            }
+            text.split(csep)
+                .map(|s| display_width(s).saturating_sub(lpad + rpad))
+                .collect()
+        })
+        .collect();
+    let col_num = segments.iter().map(Vec::len).max().unwrap_or(0);
+    let mut widths = vec![0; col_num];
+    for seg in segments.iter().filter(|s| s.len() == col_num) {
+        for (w, s) in widths.iter_mut().zip(seg) {
+            *w = (*w).max(*s);
+        }
+    }
+    widths
+}
+
+/// Parse a group of lines printed for a single row
+fn parse_row(group: &[&Line], format: &TableFormat, csep: char, widths: &[usize]) -> Row {
+    let (lpad, rpad) = format.get_padding();
+    let mut bounds = Vec::with_capacity(widths.len());
+    let mut x = match format.get_column_separator(ColumnPosition::Left) {
+        Some(_) => 1,
+        None => 0,
+    };
+    for w in widths {
+        bounds.push((x, x + lpad + w + rpad));
+        x += lpad + w + rpad + 1;
+    }
+    let first = group[0];
+    let mut cells = Vec::new();
+    let mut k = 0;
+    while k < bounds.len() {
+        let mut m = k;
+        while m + 1 < bounds.len() && !first.has_separator_at(bounds[m].1, csep) {
+            m += 1;
+        }
+        let (from, to) = (bounds[k].0 + lpad, bounds[m].1 - rpad);
+        let mut lines: Vec<String> = group.iter().map(|l| l.slice(from, to)).collect();
+        let align = lines
+            .iter()
+            .find(|l| !l.trim().is_empty())
+            .map_or(Alignment::LEFT, |l| {
+                let lead = l.len() - l.trim_start_matches(' ').len();
+                let trail = (to - from).saturating_sub(lead + display_width(l.trim_matches(' ')));
+                match (lead, trail) {
+                    (0, _) => Alignment::LEFT,
+                    (_, 0) => Alignment::RIGHT,
+                    _ => Alignment::CENTER,
+                }
+            });
+        while lines.last().is_some_and(|l| l.trim().is_empty()) {
+            lines.pop();
+        }
+        let content: Vec<&str> = lines.iter().map(|l| l.trim_matches(' ')).collect();
+        cells.push(Cell::new_align(&content.join("\n"), align).with_hspan(m - k + 1));
+        k = m + 1;
+    }
+    Row::new(cells)
+}
+
+impl Table {
+    /// Parse a table printed with `format`, such as the output of `print()`.
+    ///
+    /// Continuation lines of multi-line cells are merged back into a single cell when the
+    /// format has a separator between rows, otherwise each line is a row. Titles are only
+    /// recognized when the format has a title separator distinct from the row separator.
+    /// Cell alignment and horizontal spans are recovered from the layout of the text,
+    /// cells filling their whole column being read as left aligned.
+    ///
+    /// Formats without column separator are rejected.
+    pub fn from_ascii(text: &str, format: &TableFormat) -> Result<Table, ParseError> {
+        let csep = format
+            .get_column_separator(ColumnPosition::Intern)
+            .ok_or_else(|| {
+                ParseError::new(1, "Formats without column separator are not supported")
+            })?;
+        let indent = format.get_indent();
+        let lines: Vec<Line> = text
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+            .map(|(i, l)| {
+                let skip = l.len() - l.trim_start_matches(' ').len();
+                Line::new(i + 1, &l[skip.min(indent)..])
+            })
+            .collect();
+        if lines.is_empty() {
+            return Ok(Table::new());
+        }
+        let widths = infer_widths(&lines, format, csep);
+        let render = |pos: LinePosition| -> Option<String> {
+            let mut out = Vec::new();
+            format.print_line_separator(&mut out, &widths, pos).ok()?;
+            let line = String::from_utf8(out).ok()?;
+            let line = line.trim_end_matches(['\r', '\n']);
+            Some(line[indent.min(line.len())..].to_string()).filter(|l| !l.is_empty())
+        };
+        let title_sep = render(LinePosition::Title);
+        let intern_sep = render(LinePosition::Intern);
+        let separators: Vec<String> = [
+            LinePosition::Top,
+            LinePosition::Title,
+            LinePosition::Intern,
+            LinePosition::Bottom,
+        ]
+        .iter()
+        .filter_map(|pos| render(*pos))
+        .collect();
+
+        // Split lines into groups delimited by separator lines, along with the index
+        // of the separator line following each group
+        let mut groups: Vec<(Vec<&Line>, Option<usize>)> = Vec::new();
+        let mut current = Vec::new();
+        for (i, line) in lines.iter().enumerate() {
+            if separators.iter().any(|s| s == line.text) {
+                if !current.is_empty() {
+                    groups.push((current, Some(i)));
+                    current = Vec::new();
+                }
+                continue;
+            }
+            let lborder = format.get_column_separator(ColumnPosition::Left);
+            let rborder = format.get_column_separator(ColumnPosition::Right);
+            if lborder.is_some_and(|b| !line.text.starts_with(b))
+                || rborder.is_some_and(|b| !line.text.ends_with(b))
+            {
+                return Err(ParseError::new(
+                    line.number,
+                    "Line does not match the table format",
+                ));
+            }
+            current.push(line);
+        }
+        if !current.is_empty() {
+            groups.push((current, None));
+        }
+
+        let mut table = Table::new();
+        let mut groups = groups.into_iter().peekable();
+        let has_titles = match groups.peek() {
+            Some((_, Some(end))) => {
+                title_sep.is_some()
+                    && title_sep != intern_sep
+                    && title_sep.as_deref() == Some(lines[*end].text)
+                    && end + 1 < lines.len()
+            }
+            _ => false,
+        };
+        if has_titles {
+            if let Some((group, _)) = groups.next() {
+                table.set_titles(parse_row(&group, format, csep, &widths));
+            }
+        }
+        for (group, _) in groups {
+            if intern_sep.is_some() {
+                table.add_row(parse_row(&group, format, csep, &widths));
+            } else {
+                for line in group {
+                    table.add_row(parse_row(&[line], format, csep, &widths));
+                }
+            }
+        }
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::consts::*;
+    use crate::format::{Alignment, TableFormat};
+    use crate::{Cell, Row, Table};
+
+    fn test_table() -> Table {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![
+            Cell::new("t1"),
+            Cell::new_align("t2", Alignment::CENTER),
+            Cell::new("t3"),
+        ]));
+        table.add_row(Row::new(vec![
+            Cell::new("由系统"),
+            Cell::new_align("a\nbcdef", Alignment::RIGHT),
+            Cell::new("x | y"),
+        ]));
+        table.add_row(Row::new(vec![
+            Cell::new("spanning cell").with_hspan(2),
+            Cell::new("z"),
+        ]));
+        table.add_row(Row::new(vec![Cell::new("e")]));
+        table
+    }
+
+    fn round_trip(table: &mut Table, format: TableFormat) {
+        table.set_format(format);
+        let printed = table.to_string();
+        let parsed = Table::from_ascii(&printed, &format).unwrap();
+        let mut reprinted = parsed.clone();
+        reprinted.set_format(format);
+        assert_eq!(reprinted.to_string(), printed);
+    }
+
+    #[test]
+    fn from_ascii() {
+        let table = test_table();
+        let parsed = Table::from_ascii(&table.to_string(), &FORMAT_DEFAULT).unwrap();
+        let titles = parsed.titles.as_ref().as_ref().unwrap();
+        assert_eq!(titles.get_cell(1).unwrap().get_content(), "t2");
+        assert_eq!(titles.get_cell(1).unwrap().get_align(), Alignment::CENTER);
+        assert_eq!(parsed.len(), 3);
+        let row = parsed.get_row(0).unwrap();
+        assert_eq!(row.get_cell(1).unwrap().get_content(), "a\nbcdef");
+        assert_eq!(row.get_cell(1).unwrap().get_align(), Alignment::RIGHT);
+        assert_eq!(row.get_cell(2).unwrap().get_content(), "x | y");
+        let row = parsed.get_row(1).unwrap();
+        assert_eq!(row.get_cell(0).unwrap().get_hspan(), 2);
+        assert_eq!(row.get_cell(0).unwrap().get_content(), "spanning cell");
+    }
+
+    #[test]
+    fn ascii_round_trip() {
+        let mut table = test_table();
+        round_trip(&mut table, *FORMAT_DEFAULT);
+        round_trip(&mut table, *FORMAT_NO_BORDER);
+        round_trip(&mut table, *FORMAT_BOX_CHARS);
+        let mut indented = *FORMAT_DEFAULT;
+        indented.indent(4);
+        round_trip(&mut table, indented);
+
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2")]));
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc")]));
+        table.add_row(Row::new(vec![
+            Cell::new("d"),
+            Cell::new_align("e", Alignment::RIGHT),
+        ]));
+        round_trip(&mut table, *FORMAT_NO_LINESEP_WITH_TITLE);
+        round_trip(&mut table, *FORMAT_NO_BORDER_LINE_SEPARATOR);
+        round_trip(&mut table, *FORMAT_NO_LINESEP);
+        table.unset_titles();
+        round_trip(&mut table, *FORMAT_NO_LINESEP_WITH_TITLE);
+        round_trip(&mut table, *FORMAT_NO_BORDER_LINE_SEPARATOR);
+    }
+
+    #[test]
+    fn from_ascii_errors() {
+        assert!(Table::from_ascii("a b", &FORMAT_CLEAN).is_err());
+        let err = Table::from_ascii("+---+\n| a |\nb\n+---+", &FORMAT_DEFAULT).unwrap_err();
+        assert_eq!(err.line(), 3);
+        assert!(Table::from_ascii("", &FORMAT_DEFAULT).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,164 @@
+//! AsciiDoc output for tables
+
+use std::io::{Error, Write};
+
+use super::format::Alignment;
+use super::utils::NEWLINE;
+use super::{AsTableSlice, Cell, Row, Table, TableSlice};
+
+/// Format `cell` as an AsciiDoc cell, including its span and style prefix.
+/// Multi-line cells use the `a` (AsciiDoc content) style so block content renders
+fn format_cell(cell: &Cell) -> String {
+    let content = cell.get_content().replace('|', "\\|");
+    let mut spec = String::new();
+    if cell.get_hspan() > 1 {
+        spec += &format!("{}+", cell.get_hspan());
+    }
+    if content.contains('\n') {
+        spec.push('a');
+    }
+    format!("{}|{}", spec, content)
+}
+
+/// Print a single row, padded with empty cells up to `col_num` columns
+fn print_row<T: Write + ?Sized>(out: &mut T, row: &Row, col_num: usize) -> Result<(), Error> {
+    let mut cells: Vec<String> = row.iter().map(format_cell).collect();
+    let printed_columns: usize = row.iter().map(Cell::get_hspan).sum();
+    for _ in printed_columns..col_num {
+        cells.push("|".to_string());
+    }
+    out.write_all(cells.join(" ").as_bytes())?;
+    out.write_all(NEWLINE)
+}
+
+impl<'a> TableSlice<'a> {
+    /// Print table in AsciiDoc format to `out`.
+    ///
+    /// Column alignment is taken from the titles, or from the first row if there are no titles.
+    /// The footer is printed as the last row, marked with the `footer` option, and the
+    /// caption as the block title of the table.
+    pub fn print_asciidoc<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        let table = self.exported();
+        let slice = table.as_slice();
+        let column_num = slice.get_column_num();
+        if let Some(caption) = table.caption.line() {
+            out.write_all(format!(".{}", caption).as_bytes())?;
+            out.write_all(NEWLINE)?;
+        }
+        let cols: Vec<&str> = slice
+            .get_column_alignments()
+            .into_iter()
+            .map(|a| match a {
+                Alignment::LEFT => "<",
+                Alignment::CENTER => "^",
+                Alignment::RIGHT => ">",
+            })
+            .collect();
+        let mut attrs = format!("[cols=\"{}\"", cols.join(","));
+        let options: Vec<&str> = [
+            slice.titles.as_ref().map(|_| "header"),
+            self.footer.as_ref().map(|_| "footer"),
+        ]
+        .iter()
+        .flatten()
+        .copied()
+        .collect();
+        if !options.is_empty() {
+            attrs += &format!(",options=\"{}\"", options.join(","));
+        }
+        attrs.push(']');
+        out.write_all(attrs.as_bytes())?;
+        out.write_all(NEWLINE)?;
+        out.write_all(b"|===")?;
+        out.write_all(NEWLINE)?;
+        if let Some(ref t) = *slice.titles {
+            print_row(out, t, column_num)?;
+            out.write_all(NEWLINE)?;
+        }
+        for r in slice.rows {
+            print_row(out, r, column_num)?;
+        }
+        out.write_all(b"|===")?;
+        out.write_all(NEWLINE)?;
+        out.flush()
+    }
+}
+
+impl Table {
+    /// Print table in AsciiDoc format to `out`.
+    ///
+    /// See `TableSlice::print_asciidoc()` for details.
+    pub fn print_asciidoc<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        self.as_slice().print_asciidoc(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::Alignment;
+    use crate::utils::StringWriter;
+    use crate::{Cell, Row, Table};
+
+    #[test]
+    fn asciidoc() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![
+            Cell::new("t1"),
+            Cell::new_align("t2", Alignment::CENTER),
+            Cell::new_align("t3", Alignment::RIGHT),
+        ]));
+        table.add_row(Row::new(vec![
+            Cell::new("a|b"),
+            Cell::new("* c\n* d"),
+            Cell::new("e"),
+        ]));
+        table.add_row(Row::new(vec![Cell::new("f").with_hspan(2)]));
+        let out = "\
+[cols=\"<,^,>\",options=\"header\"]
+|===
+|t1 |t2 |t3
+
+|a\\|b a|* c
+* d |e
+2+|f |
+|===
+";
+        let mut writer = StringWriter::new();
+        assert!(table.print_asciidoc(&mut writer).is_ok());
+        assert_eq!(writer.as_string().replace("\r\n", "\n"), out);
+    }
+
+    #[test]
+    fn asciidoc_no_titles() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("b")]));
+        let out = "\
+[cols=\"<,<\"]
+|===
+|a |b
+|===
+";
+        let mut writer = StringWriter::new();
+        assert!(table.print_asciidoc(&mut writer).is_ok());
+        assert_eq!(writer.as_string().replace("\r\n", "\n"), out);
+    }
+
+    #[test]
+    fn asciidoc_caption_footer() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("b")]));
+        table.set_footer(Row::new(vec![Cell::new("c")]));
+        table.set_caption("Some\nletters", Alignment::CENTER);
+        let out = "\
+.Some letters
+[cols=\"<,<\",options=\"footer\"]
+|===
+|a |b
+|c |
+|===
+";
+        let mut writer = StringWriter::new();
+        assert!(table.print_asciidoc(&mut writer).is_ok());
+        assert_eq!(writer.as_string().replace("\r\n", "\n"), out);
+    }
+}
@@ -0,0 +1,105 @@
+//! BBCode output for tables
+
+use super::{AsTableSlice, Attr, Cell, Row, Table, TableSlice};
+
+/// Format `cell` as a BBCode cell using `tag`, translating bold, italic
+/// and underline attributes
+fn format_cell(cell: &Cell, tag: &str) -> String {
+    let mut content = cell.get_content().replace('[', "&#91;");
+    for (attr, wrapper) in &[
+        (Attr::Underline(true), "u"),
+        (Attr::Italic(true), "i"),
+        (Attr::Bold, "b"),
+    ] {
+        if cell.get_style().contains(attr) {
+            content = format!("[{0}]{1}[/{0}]", wrapper, content);
+        }
+    }
+    format!("[{0}]{1}[/{0}]", tag, content)
+}
+
+/// Format a BBCode row, padded with empty cells up to `col_num` columns
+fn format_row(row: &Row, col_num: usize, tag: &str) -> String {
+    let mut line = String::from("[tr]");
+    let mut printed_columns = 0;
+    for cell in row {
+        line += &format_cell(cell, tag);
+        printed_columns += cell.get_hspan();
+    }
+    for _ in printed_columns..col_num {
+        line += &format_cell(&Cell::default(), tag);
+    }
+    line += "[/tr]";
+    line
+}
+
+impl<'a> TableSlice<'a> {
+    /// Return the table formatted as BBCode markup.
+    ///
+    /// When `use_th` is `true`, title cells are emitted as `[th]` cells, which is not
+    /// supported by every BBCode dialect. Otherwise they are emitted as bold `[td]` cells.
+    /// Opening brackets in cells content are replaced with `&#91;`. The footer is
+    /// emitted as the last row, while the caption is left out, BBCode having no
+    /// markup for it.
+    pub fn to_bbcode(&self, use_th: bool) -> String {
+        let table = self.exported();
+        let slice = table.as_slice();
+        let column_num = slice.get_column_num();
+        let mut bbcode = String::from("[table]");
+        if let Some(ref t) = *slice.titles {
+            if use_th {
+                bbcode += &format_row(t, column_num, "th");
+            } else {
+                let bold = Row::new(t.iter().map(|c| c.clone().with_style(Attr::Bold)).collect());
+                bbcode += &format_row(&bold, column_num, "td");
+            }
+        }
+        for r in slice.rows {
+            bbcode += &format_row(r, column_num, "td");
+        }
+        bbcode += "[/table]";
+        bbcode
+    }
+}
+
+impl Table {
+    /// Return the table formatted as BBCode markup.
+    ///
+    /// See `TableSlice::to_bbcode()` for details.
+    pub fn to_bbcode(&self, use_th: bool) -> String {
+        self.as_slice().to_bbcode(use_th)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cell, Row, Table};
+
+    #[test]
+    fn bbcode() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2")]));
+        table.add_row(Row::new(vec![
+            Cell::new("[a]").style_spec("bi"),
+            Cell::new("b"),
+        ]));
+        table.add_row(Row::new(vec![Cell::new("c")]));
+        assert_eq!(
+            table.to_bbcode(true),
+            "[table][tr][th]t1[/th][th]t2[/th][/tr]\
+             [tr][td][b][i]&#91;a][/i][/b][/td][td]b[/td][/tr]\
+             [tr][td]c[/td][td][/td][/tr][/table]"
+        );
+        assert!(table
+            .to_bbcode(false)
+            .starts_with("[table][tr][td][b]t1[/b][/td][td][b]t2[/b][/td][/tr]"));
+        table.add_row(Row::new(vec![Cell::new("d").with_hspan(2)]));
+        assert!(table
+            .to_bbcode(true)
+            .ends_with("[tr][td]d[/td][/tr][/table]"));
+        table.set_footer(Row::new(vec![Cell::new("d")]));
+        assert!(table
+            .to_bbcode(true)
+            .ends_with("[tr][td]d[/td][td][/td][/tr][/table]"));
+    }
+}
@@ -0,0 +1,150 @@
+//! Fluent construction of tables
+
+use std::iter::FromIterator;
+
+use super::format::{Alignment, ColumnWidth, TableFormat};
+use super::{Row, Table};
+
+/// A builder to create a `Table` in a single expression.
+///
+/// Each method calls the equivalent method of `Table`, so a table built this way is
+/// identical to one set up with those methods. Cells are created from any value
+/// implementing `ToString`.
+///
+/// # Examples
+/// ```rust
+/// use prettytable::format::{consts, Alignment, FormatBuilder};
+/// use prettytable::TableBuilder;
+/// let table = TableBuilder::new()
+///     .titles(["Name", "Age"])
+///     .format(FormatBuilder::from(*consts::FORMAT_CLEAN).padding(0, 1))
+///     .column_align(1, Alignment::RIGHT)
+///     .row(["Alice", "34"])
+///     .rows(vec![vec!["Bob", "7"]])
+///     .build();
+/// assert_eq!(table.to_string().replace("\r\n", "\n"), "Name  Age \nAlice  34 \nBob     7 \n");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TableBuilder {
+    table: Table,
+}
+
+impl TableBuilder {
+    /// Create a builder of an empty table, with the default format
+    pub fn new() -> TableBuilder {
+        TableBuilder::default()
+    }
+
+    /// Set the titles of the table, as with `Table::set_titles()`
+    pub fn titles<I, T>(mut self, titles: I) -> TableBuilder
+    where
+        I: IntoIterator<Item = T>,
+        T: ToString,
+    {
+        self.table.set_titles(Row::from_iter(titles));
+        self
+    }
+
+    /// Set the format of the table, given as a `TableFormat` or a `FormatBuilder`
+    pub fn format<F: Into<TableFormat>>(mut self, format: F) -> TableBuilder {
+        self.table.set_format(format.into());
+        self
+    }
+
+    /// Set the default alignment of column `column`, as with `Table::set_column_alignment()`
+    pub fn column_align(mut self, column: usize, align: Alignment) -> TableBuilder {
+        self.table.set_column_alignment(column, align);
+        self
+    }
+
+    /// Constrain the width of column `column`, as with `Table::set_column_width()`
+    pub fn column_width(mut self, column: usize, width: ColumnWidth) -> TableBuilder {
+        self.table.set_column_width(column, width);
+        self
+    }
+
+    /// Append a row to the table
+    pub fn row<I, T>(mut self, cells: I) -> TableBuilder
+    where
+        I: IntoIterator<Item = T>,
+        T: ToString,
+    {
+        self.table.add_row(Row::from_iter(cells));
+        self
+    }
+
+    /// Append rows to the table
+    pub fn rows<I, R, T>(mut self, rows: I) -> TableBuilder
+    where
+        I: IntoIterator<Item = R>,
+        R: IntoIterator<Item = T>,
+        T: ToString,
+    {
+        for row in rows {
+            self.table.add_row(Row::from_iter(row));
+        }
+        self
+    }
+
+    /// Set the footer of the table, as with `Table::set_footer()`
+    pub fn footer<I, T>(mut self, cells: I) -> TableBuilder
+    where
+        I: IntoIterator<Item = T>,
+        T: ToString,
+    {
+        self.table.set_footer(Row::from_iter(cells));
+        self
+    }
+
+    /// Set the caption of the table, as with `Table::set_caption()`
+    pub fn caption(mut self, text: &str, align: Alignment) -> TableBuilder {
+        self.table.set_caption(text, align);
+        self
+    }
+
+    /// Return the built table
+    pub fn build(self) -> Table {
+        self.table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TableBuilder;
+    use crate::format::consts::FORMAT_BOX_CHARS;
+    use crate::format::{Alignment, ColumnWidth, FormatBuilder};
+    use crate::{Cell, Row, Table};
+
+    #[test]
+    fn builder() {
+        let built = TableBuilder::new()
+            .titles(["id", "name"])
+            .format(*FORMAT_BOX_CHARS)
+            .column_align(0, Alignment::RIGHT)
+            .column_width(1, ColumnWidth::Min(6))
+            .row([1.to_string(), "a".to_string()])
+            .rows((2..4).map(|i| vec![i.to_string(), "b".repeat(i)]))
+            .footer(["", "total"])
+            .caption("Items", Alignment::LEFT)
+            .build();
+
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("id"), Cell::new("name")]));
+        table.set_format(*FORMAT_BOX_CHARS);
+        table.set_column_alignment(0, Alignment::RIGHT);
+        table.set_column_width(1, ColumnWidth::Min(6));
+        table.add_row(Row::new(vec![Cell::new("1"), Cell::new("a")]));
+        table.add_row(Row::new(vec![Cell::new("2"), Cell::new("bb")]));
+        table.add_row(Row::new(vec![Cell::new("3"), Cell::new("bbb")]));
+        table.set_footer(Row::new(vec![Cell::new(""), Cell::new("total")]));
+        table.set_caption("Items", Alignment::LEFT);
+        assert!(built.strict_eq(&table));
+        assert_eq!(built.to_string(), table.to_string());
+
+        let mut built = TableBuilder::new()
+            .format(FormatBuilder::new().padding(2, 0))
+            .build();
+        assert_eq!(built.get_format().get_padding(), (2, 0));
+        assert!(built.is_empty());
+    }
+}
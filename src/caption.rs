@@ -0,0 +1,235 @@
+//! Caption printed above or below a table
+
+use std::io::{Error, Write};
+
+use super::fit::table_width;
+use super::format::{Alignment, TableFormat};
+use super::utils::{print_align, wrap_text, HtmlEscape, NEWLINE};
+use super::Table;
+
+/// Where the caption of a table is printed
+#[derive(Clone, Debug, Copy, Hash, PartialEq, Eq, Default)]
+pub enum CaptionPosition {
+    /// Above the table
+    #[default]
+    Above,
+    /// Below the table
+    Below,
+}
+
+/// Caption of a table, as set with `Table::set_caption()`
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub(crate) struct Caption {
+    pub(crate) text: Option<String>,
+    pub(crate) align: Alignment,
+    pub(crate) position: CaptionPosition,
+}
+
+impl Default for Caption {
+    fn default() -> Caption {
+        Caption {
+            text: None,
+            align: Alignment::CENTER,
+            position: CaptionPosition::default(),
+        }
+    }
+}
+
+impl Caption {
+    /// Tell if there is a caption to print at `position`
+    pub(crate) fn is_at(&self, position: CaptionPosition) -> bool {
+        self.text.is_some() && self.position == position
+    }
+
+    /// Print the caption to `out`, wrapped and aligned across the width of a table printed
+    /// with `format` and columns `col_width` wide, text widths being measured with the width
+    /// mode of `format`. Returns the number of lines printed.
+    pub(crate) fn print<T: Write + ?Sized>(
+        &self,
+        out: &mut T,
+        format: &TableFormat,
+        col_width: &[usize],
+    ) -> Result<usize, Error> {
+        let text = match self.text {
+            Some(ref text) => text,
+            None => return Ok(0),
+        };
+        let indent = format.get_indent();
+        let width = table_width(format, col_width).saturating_sub(indent).max(1);
+        let mode = format.get_width_mode();
+        let lines = wrap_text(text, width, mode);
+        for line in &lines {
+            out.write_all(&vec![b' '; indent])?;
+            print_align(out, self.align, line, mode.width(line), ' ', width, true)?;
+            out.write_all(NEWLINE)?;
+        }
+        Ok(lines.len())
+    }
+
+    /// Return the text of the caption on a single line, with newlines replaced by spaces,
+    /// for markups where a caption cannot span several lines
+    pub(crate) fn line(&self) -> Option<String> {
+        self.text.as_ref().map(|text| text.replace('\n', " "))
+    }
+
+    /// Print the caption to `out` as a pandoc `Table:` paragraph if it is at `position`,
+    /// separated from the table by an empty line. Returns the number of lines printed.
+    pub(crate) fn print_pandoc<T: Write + ?Sized>(
+        &self,
+        out: &mut T,
+        position: CaptionPosition,
+    ) -> Result<usize, Error> {
+        let line = match self.line().filter(|_| self.is_at(position)) {
+            Some(line) => line,
+            None => return Ok(0),
+        };
+        if position == CaptionPosition::Below {
+            out.write_all(NEWLINE)?;
+        }
+        out.write_all(format!("Table: {}", line).as_bytes())?;
+        out.write_all(NEWLINE)?;
+        if position == CaptionPosition::Above {
+            out.write_all(NEWLINE)?;
+        }
+        Ok(2)
+    }
+
+    /// Print the caption to `out` as an HTML `<caption>` element
+    pub(crate) fn print_html<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        let text = match self.text {
+            Some(ref text) => text,
+            None => return Ok(()),
+        };
+        let align = match self.align {
+            Alignment::LEFT => "left",
+            Alignment::CENTER => "center",
+            Alignment::RIGHT => "right",
+        };
+        let side = match self.position {
+            CaptionPosition::Above => "",
+            CaptionPosition::Below => "caption-side: bottom;",
+        };
+        let caption = format!(
+            "<caption style=\"{}text-align: {};\">{}</caption>",
+            side,
+            align,
+            HtmlEscape(text)
+        );
+        out.write_all(caption.as_bytes())
+    }
+}
+
+impl Table {
+    /// Set a caption, printed above the table unless changed with `set_caption_position()`,
+    /// and aligned to `align` across the width of the table.
+    ///
+    /// The caption does not change the width of the columns, and is wrapped when wider
+    /// than the table. Slices and filtered views of the table have the same caption.
+    /// Markup exports write it with their own caption markup, like a `<caption>` element
+    /// in HTML, while data exports like CSV leave it out.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// use prettytable::format::Alignment;
+    /// # fn main() {
+    /// let mut table = table![["apples", 3], ["pears", 12]];
+    /// table.set_caption("Fruits", Alignment::CENTER);
+    /// assert_eq!(table.to_string().replace("\r\n", "\n"), "\
+    /// \x20   Fruits
+    /// +--------+----+
+    /// | apples | 3  |
+    /// +--------+----+
+    /// | pears  | 12 |
+    /// +--------+----+
+    /// ");
+    /// # }
+    /// ```
+    pub fn set_caption(&mut self, text: &str, align: Alignment) {
+        self.caption.text = Some(text.to_string());
+        self.caption.align = align;
+    }
+
+    /// Print the caption at `position`, above or below the table
+    pub fn set_caption_position(&mut self, position: CaptionPosition) {
+        self.caption.position = position;
+    }
+
+    /// Remove the caption
+    pub fn unset_caption(&mut self) {
+        self.caption.text = None;
+    }
+
+    /// Get the text of the caption, if any
+    pub fn get_caption(&self) -> Option<&str> {
+        self.caption.text.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CaptionPosition;
+    use crate::format::consts::FORMAT_CLEAN;
+    use crate::format::Alignment;
+    use crate::utils::StringWriter;
+    use crate::{Cell, Row, Slice, Table};
+
+    fn sample() -> Table {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("b")]));
+        table.add_row(Row::new(vec![Cell::new("c"), Cell::new("d")]));
+        table.set_caption("A caption wider than the table", Alignment::RIGHT);
+        table
+    }
+
+    #[test]
+    fn caption() {
+        let mut table = sample();
+        let out = "\
+A caption
+    wider
+ than the
+    table
++---+---+
+| a | b |
++---+---+
+| c | d |
++---+---+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(table.get_caption(), Some("A caption wider than the table"));
+
+        table.set_caption("Total", Alignment::LEFT);
+        table.set_caption_position(CaptionPosition::Below);
+        table.set_format(*FORMAT_CLEAN);
+        table.get_format().indent(2);
+        let out = "   c  d \n  Total\n";
+        assert_eq!(table.slice(1..).to_string().replace("\r\n", "\n"), out);
+        let filtered = table.filtered(|r| r[0].get_content() == "a");
+        assert!(filtered.to_string().ends_with("  Total\n"));
+
+        table.unset_caption();
+        assert_eq!(table.get_caption(), None);
+        assert_eq!(
+            table.to_string().replace("\r\n", "\n"),
+            "   a  b \n   c  d \n"
+        );
+    }
+
+    #[test]
+    fn caption_html() {
+        let mut table = sample();
+        table.set_caption_position(CaptionPosition::Below);
+        let mut writer = StringWriter::new();
+        table.print_html(&mut writer).unwrap();
+        assert!(writer.as_string().starts_with(
+            "<table><caption style=\"caption-side: bottom;text-align: right;\">\
+             A caption wider than the table</caption><tr>"
+        ));
+        #[cfg(feature = "csv")]
+        {
+            let csv = table.to_csv(Vec::new()).unwrap().into_inner().unwrap();
+            assert_eq!(String::from_utf8(csv).unwrap(), "a,b\nc,d\n");
+        }
+    }
+}
@@ -1,55 +1,667 @@
 //! This module contains definition of table/row cells stuff
 
-use super::format::Alignment;
-use super::utils::{display_width, print_align, HtmlEscape};
-use super::{color, Attr, Terminal};
+use super::format::{Alignment, VerticalAlignment, WidthMode};
+use super::utils::{
+    display_width, print_align, segments, strip_ansi, truncate_text, wrap_text, HtmlEscape, Segment,
+};
+use super::{color, Attr, CellStyle, Color, HtmlOptions, Table, Terminal};
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::io::{Error, Write};
+use std::mem;
+use std::panic::{RefUnwindSafe, UnwindSafe};
 use std::str::FromStr;
 use std::string::ToString;
+use std::sync::{Arc, OnceLock};
+
+/// Lines of a cell, along with the lines as printed and their width
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+struct Layout {
+    content: Vec<String>,
+    /// Content lines as printed, when they differ from `content`
+    wrapped: Option<Vec<String>>,
+    width: usize,
+}
+
+/// Closure producing the content of a lazy cell
+type ContentFn = dyn Fn() -> String + Send + Sync + RefUnwindSafe + UnwindSafe;
+
+/// Content of a cell, either given at creation or produced on demand.
+///
+/// Produced content keeps its lines, cached once computed, apart from the layout which
+/// is cleared whenever the settings of the cell change.
+#[derive(Clone)]
+enum Content {
+    Text(Layout),
+    Lazy(Arc<ContentFn>, OnceLock<Vec<String>>, OnceLock<Layout>),
+    /// A nested table, printed when the content is needed
+    Table(Arc<Table>, OnceLock<Vec<String>>, OnceLock<Layout>),
+}
+
+impl Content {
+    /// Return the lines of content, producing and caching them if needed
+    fn lines(&self) -> &[String] {
+        match *self {
+            Content::Text(ref layout) => &layout.content,
+            Content::Lazy(ref f, ref lines, _) => lines.get_or_init(|| split_lines(&f())),
+            Content::Table(ref table, ref lines, _) => {
+                lines.get_or_init(|| split_lines(&table.to_string()))
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Content {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Content::Text(ref layout) => layout.fmt(fmt),
+            Content::Lazy(_, ref lines, _) => fmt.debug_tuple("Lazy").field(&lines.get()).finish(),
+            Content::Table(ref table, _, _) => fmt.debug_tuple("Table").field(table).finish(),
+        }
+    }
+}
+
+impl PartialEq for Content {
+    fn eq(&self, other: &Content) -> bool {
+        self.lines() == other.lines()
+    }
+}
+
+impl Eq for Content {}
+
+impl Hash for Content {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.lines().hash(state);
+    }
+}
+
+/// Return the terminal attributes set by the parameters `params` of an SGR sequence,
+/// or `None` if the sequence resets the style
+fn sgr_attrs(params: &str) -> Option<Vec<Attr>> {
+    let mut attrs = Vec::new();
+    let mut codes = params.split(';').map(|p| p.parse::<u32>().unwrap_or(0));
+    while let Some(code) = codes.next() {
+        match code {
+            0 => return None,
+            1 => attrs.push(Attr::Bold),
+            2 => attrs.push(Attr::Dim),
+            3 => attrs.push(Attr::Italic(true)),
+            4 => attrs.push(Attr::Underline(true)),
+            5 => attrs.push(Attr::Blink),
+            7 => attrs.push(Attr::Reverse),
+            8 => attrs.push(Attr::Secure),
+            30..=37 => attrs.push(Attr::ForegroundColor(code - 30)),
+            40..=47 => attrs.push(Attr::BackgroundColor(code - 40)),
+            90..=97 => attrs.push(Attr::ForegroundColor(code - 90 + 8)),
+            100..=107 => attrs.push(Attr::BackgroundColor(code - 100 + 8)),
+            38 | 48 => match codes.next() {
+                // 256 colors palette
+                Some(5) => {
+                    if let Some(n) = codes.next() {
+                        attrs.push(if code == 38 {
+                            Attr::ForegroundColor(n)
+                        } else {
+                            Attr::BackgroundColor(n)
+                        });
+                    }
+                }
+                // RGB colors cannot be represented, skip their components
+                Some(2) => {
+                    codes.nth(2);
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+    Some(attrs)
+}
+
+/// Split `string` into content lines
+fn split_lines(string: &str) -> Vec<String> {
+    string.lines().map(|x| x.to_string()).collect()
+}
 
 /// Represent a table cell containing a string.
 ///
 /// Once created, a cell's content cannot be modified.
 /// The cell would have to be replaced by another one
-#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[derive(Clone, Debug)]
 pub struct Cell {
-    content: Vec<String>,
-    width: usize,
-    align: Alignment,
+    content: Content,
+    align: Option<Alignment>,
+    valign: VerticalAlignment,
     style: Vec<Attr>,
     hspan: usize,
+    max_width: Option<usize>,
+    truncate: Option<(usize, String)>,
+    url: Option<String>,
+    padding: Option<(usize, usize)>,
+    fill: char,
+    html_attrs: Vec<(String, String)>,
+}
+
+/// Cells are compared on their alignment as returned by `get_align()`, so that a cell
+/// aligned to `LEFT` equals the same cell with the default alignment
+impl PartialEq for Cell {
+    fn eq(&self, other: &Cell) -> bool {
+        self.content == other.content
+            && self.get_align() == other.get_align()
+            && self.valign == other.valign
+            && self.style == other.style
+            && self.hspan == other.hspan
+            && self.max_width == other.max_width
+            && self.truncate == other.truncate
+            && self.url == other.url
+            && self.padding == other.padding
+            && self.fill == other.fill
+            && self.html_attrs == other.html_attrs
+    }
+}
+
+impl Eq for Cell {}
+
+impl Hash for Cell {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.content.hash(state);
+        self.get_align().hash(state);
+        self.valign.hash(state);
+        self.style.hash(state);
+        self.hspan.hash(state);
+        self.max_width.hash(state);
+        self.truncate.hash(state);
+        self.url.hash(state);
+        self.padding.hash(state);
+        self.fill.hash(state);
+        self.html_attrs.hash(state);
+    }
 }
 
 impl Cell {
     /// Create a new `Cell` initialized with content from `string`.
     /// Text alignment in cell is configurable with the `align` argument
     pub fn new_align(string: &str, align: Alignment) -> Cell {
-        let content: Vec<String> = string.lines().map(|x| x.to_string()).collect();
-        let mut width = 0;
-        for cont in &content {
-            let l = display_width(&cont[..]);
-            if l > width {
-                width = l;
-            }
+        let mut cell = Cell {
+            content: Content::Text(Layout {
+                content: split_lines(string),
+                wrapped: None,
+                width: 0,
+            }),
+            align: Some(align),
+            valign: VerticalAlignment::TOP,
+            style: Vec::new(),
+            hspan: 1,
+            max_width: None,
+            truncate: None,
+            url: None,
+            padding: None,
+            fill: ' ',
+            html_attrs: Vec::new(),
+        };
+        cell.layout();
+        cell
+    }
+
+    /// Create a new `Cell` initialized with content from `string`.
+    /// By default, content is align to `LEFT`, or to the alignment of its column
+    /// in the table (see `Table::set_column_alignment()`)
+    pub fn new(string: &str) -> Cell {
+        Cell {
+            align: None,
+            ..Cell::new_align(string, Alignment::LEFT)
+        }
+    }
+
+    /// Create a new `Cell` holding `value`, as displayed by `Display`.
+    ///
+    /// This inherent constructor takes precedence over the `From` implementations when
+    /// calling `Cell::from`, which are still available through `Into` or `From::from`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use prettytable::Cell;
+    /// assert_eq!(Cell::from(42), Cell::new("42"));
+    /// assert_eq!(Cell::from(format_args!("{:.1}%", 99.25)), Cell::new("99.2%"));
+    /// let cell: Cell = None::<u32>.into();
+    /// assert_eq!(cell.get_content(), "");
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn from<T: fmt::Display>(value: T) -> Cell {
+        Cell::new(&value.to_string())
+    }
+
+    /// Create a new `Cell` whose content is produced by calling `f`, the first time
+    /// the content is needed.
+    ///
+    /// The content is then cached, so `f` is called at most once, including when
+    /// comparing or hashing the cell. This avoids building the content of cells which
+    /// are never printed, like when printing a slice of a large table.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use prettytable::Cell;
+    /// let cell = Cell::from_fn(|| format!("{}", 6 * 7));
+    /// assert_eq!(cell.get_content(), "42");
+    /// ```
+    pub fn from_fn<F>(f: F) -> Cell
+    where
+        F: Fn() -> String + Send + Sync + RefUnwindSafe + UnwindSafe + 'static,
+    {
+        Cell {
+            content: Content::Lazy(Arc::new(f), OnceLock::new(), OnceLock::new()),
+            ..Cell::default()
         }
+    }
+
+    /// Create a new `Cell` holding a copy of `table`, printed with its own format as
+    /// the lines of the cell.
+    ///
+    /// The nested table is printed the first time the content is needed, so its columns
+    /// are sized independently of the outer table. The HTML output holds a nested
+    /// `<table>` element instead.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # use prettytable::Cell;
+    /// # fn main() {
+    /// let cell = Cell::from_table(&table![["a", "b"]]);
+    /// assert_eq!(cell.get_content(), "+---+---+\n| a | b |\n+---+---+");
+    /// # }
+    /// ```
+    pub fn from_table(table: &Table) -> Cell {
         Cell {
+            content: Content::Table(Arc::new(table.clone()), OnceLock::new(), OnceLock::new()),
+            ..Cell::default()
+        }
+    }
+
+    /// Create a new `Cell` from `text` styled with terminal escape sequences, like the
+    /// strings produced by the `colored` or `ansi_term` crates.
+    ///
+    /// The SGR sequences (colors and attributes) applied before the first visible char
+    /// become the style of the cell, and all escape sequences are removed from the
+    /// content. This way, exporters like HTML can translate the style, and plain exports
+    /// don't contain escape sequences. Styles changing within the text are dropped.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use prettytable::{color, Attr, Cell};
+    /// // Like `"x".red().bold().to_string()` with the `colored` crate
+    /// let cell = Cell::from_ansi("\x1b[1;31mx\x1b[0m");
+    /// assert_eq!(cell, Cell::new("x").with_style(Attr::Bold).with_style(Attr::ForegroundColor(color::RED)));
+    /// ```
+    pub fn from_ansi(text: &str) -> Cell {
+        let mut cell = Cell::new(&strip_ansi(text));
+        for segment in segments(text) {
+            let params = match segment {
+                Segment::Cluster(..) => break,
+                Segment::Escape(esc) if esc.starts_with("\x1b[") && esc.ends_with('m') => {
+                    &esc[2..esc.len() - 1]
+                }
+                Segment::Escape(..) => continue,
+            };
+            match sgr_attrs(params) {
+                Some(attrs) => cell.style.extend(attrs),
+                None => cell.style.clear(),
+            }
+        }
+        cell
+    }
+
+    /// Create a new `Cell` from `result`, showing `Ok` values normally and
+    /// `Err` values in red, prefixed with `!`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use prettytable::Cell;
+    /// let ok: Result<u32, &str> = Ok(42);
+    /// assert_eq!(Cell::from_result(ok).get_content(), "42");
+    /// let err: Result<u32, &str> = Err("timeout");
+    /// assert_eq!(Cell::from_result(err).get_content(), "!timeout");
+    /// ```
+    pub fn from_result<T: ToString, E: fmt::Display>(result: Result<T, E>) -> Cell {
+        Cell::from_result_with_style(result, CellStyle::new().fg(Color::Red))
+    }
+
+    /// Create a new `Cell` from `result`, showing `Ok` values normally and
+    /// `Err` values with `error_style`, prefixed with `!`
+    pub fn from_result_with_style<T: ToString, E: fmt::Display>(
+        result: Result<T, E>,
+        error_style: CellStyle,
+    ) -> Cell {
+        match result {
+            Ok(value) => Cell::new(&value.to_string()),
+            Err(err) => Cell::new(&format!("!{}", err)).with_cell_style(error_style),
+        }
+    }
+
+    /// Create a new `Cell` from `result` like `from_result()`, styled with the style
+    /// specifier `spec` as with `style_spec()`. `Err` values keep their red color on top of
+    /// the style of the specifier.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use prettytable::Cell;
+    /// let err: Result<u32, &str> = Err("timeout");
+    /// let cell = Cell::from_result_spec(err, "bc");
+    /// assert_eq!(cell.get_content(), "!timeout");
+    /// assert_eq!(cell, Cell::new("!timeout").style_spec("bcFr"));
+    /// ```
+    pub fn from_result_spec<T: ToString, E: fmt::Display>(
+        result: Result<T, E>,
+        spec: &str,
+    ) -> Cell {
+        let failed = result.is_err();
+        let mut cell = Cell::from_result(result);
+        let error_style = cell.get_style().to_vec();
+        cell = cell.style_spec(spec);
+        if failed {
+            for attr in error_style {
+                cell.style(attr);
+            }
+        }
+        cell
+    }
+
+    /// Create a new `Cell` showing `text` as a hyperlink to `url`.
+    ///
+    /// See `with_hyperlink()` for details.
+    pub fn new_hyperlink(text: &str, url: &str) -> Cell {
+        Cell::new(text).with_hyperlink(url)
+    }
+
+    /// Apply the style `style` of the row holding this cell. The attributes apply only if the
+    /// cell has no style of its own, and the alignment only if the cell has no explicit
+    /// alignment. With `attrs` set to `false`, only the alignment is applied.
+    pub(crate) fn apply_row_style(&mut self, style: &CellStyle, attrs: bool) {
+        if attrs && self.style.is_empty() {
+            self.style = style.attrs();
+        }
+        if let (None, Some(align)) = (self.align, style.get_align()) {
+            self.align = Some(align);
+        }
+    }
+
+    /// Apply the style `stripe` of the row stripe holding this cell, if the cell
+    /// has no background color. The current style of the cell takes precedence.
+    pub(crate) fn apply_stripe_style(&mut self, stripe: &CellStyle) {
+        if !self
+            .style
+            .iter()
+            .any(|a| matches!(a, Attr::BackgroundColor(_)))
+        {
+            let mut style = stripe.attrs();
+            style.append(&mut self.style);
+            self.style = style;
+        }
+    }
+
+    /// Apply the style `style` of a formatting rule holding for this cell. The attributes
+    /// and alignment of the style replace the ones of the cell of the same kind.
+    pub(crate) fn apply_rule_style(&mut self, style: &CellStyle) {
+        for attr in style.attrs() {
+            self.style
+                .retain(|a| mem::discriminant(a) != mem::discriminant(&attr));
+            self.style.push(attr);
+        }
+        if let Some(align) = style.get_align() {
+            self.align = Some(align);
+        }
+    }
+
+    /// Return a copy of this cell showing only its first `lines` lines as printed,
+    /// the last one followed by `marker` with `{}` replaced by the count of hidden lines.
+    /// Return `None` if the cell is not higher than `lines`.
+    pub(crate) fn clipped(&self, lines: usize, marker: &str) -> Option<Cell> {
+        let shown = self.lines();
+        let hidden = shown.len().checked_sub(lines).filter(|&h| h > 0)?;
+        let mut content = shown[..lines].to_vec();
+        if let Some(last) = content.last_mut() {
+            last.push_str(&marker.replace("{}", &hidden.to_string()));
+        }
+        let mut cell = self.with_content(&content.join("\n"));
+        // Lines are already wrapped, and the marker must stay on the last one
+        cell.max_width = None;
+        cell.layout();
+        Some(cell)
+    }
+
+    /// Return a copy of this cell, with its content replaced by `string`
+    pub(crate) fn with_content(&self, string: &str) -> Cell {
+        let mut cell = Cell {
+            style: self.style.clone(),
+            hspan: self.hspan,
+            valign: self.valign,
+            max_width: self.max_width,
+            truncate: self.truncate.clone(),
+            url: self.url.clone(),
+            padding: self.padding,
+            fill: self.fill,
+            html_attrs: self.html_attrs.clone(),
+            align: self.align,
+            ..Cell::new(string)
+        };
+        cell.layout();
+        cell
+    }
+
+    /// Return the content lines `content` wrapped and truncated to the maximum width of the
+    /// cell, with text widths measured with `mode`, or `None` if the cell has no maximum width
+    fn wrap_lines(&self, content: &[String], mode: WidthMode) -> Option<Vec<String>> {
+        let mut wrapped: Option<Vec<String>> = None;
+        if let Some(width) = self.max_width {
+            let lines = content
+                .iter()
+                .flat_map(|line| wrap_text(line, width, mode))
+                .collect();
+            wrapped = Some(lines);
+        }
+        if let Some((width, ref marker)) = self.truncate {
+            let lines = wrapped
+                .as_deref()
+                .unwrap_or(content)
+                .iter()
+                .map(|line| truncate_text(line, width, marker, mode))
+                .collect();
+            wrapped = Some(lines);
+        }
+        wrapped
+    }
+
+    /// Tell if this cell is wrapped or truncated differently when text widths are measured
+    /// with `mode` rather than `WidthMode::Unicode`
+    pub(crate) fn depends_on_width_mode(&self, mode: WidthMode) -> bool {
+        !matches!(mode, WidthMode::Unicode) && (self.max_width.is_some() || self.truncate.is_some())
+    }
+
+    /// Return a copy of this cell holding its lines as printed, wrapped and truncated with text
+    /// widths measured with `mode`, if that changes them. See `depends_on_width_mode()`.
+    pub(crate) fn measured_with(&self, mode: WidthMode) -> Option<Cell> {
+        if !self.depends_on_width_mode(mode) {
+            return None;
+        }
+        let lines = self.wrap_lines(&self.get_layout().content, mode)?;
+        let mut cell = self.with_content(&lines.join("\n"));
+        // Lines are already wrapped and truncated
+        cell.max_width = None;
+        cell.truncate = None;
+        cell.layout();
+        Some(cell)
+    }
+
+    /// Compute the printed lines and the width of the cell
+    /// from its content lines `content`
+    fn build_layout(&self, content: Vec<String>) -> Layout {
+        let wrapped = self.wrap_lines(&content, WidthMode::Unicode);
+        let width = wrapped
+            .as_ref()
+            .unwrap_or(&content)
+            .iter()
+            .map(|line| display_width(line))
+            .max()
+            .unwrap_or(0);
+        Layout {
             content,
+            wrapped,
             width,
-            align,
-            style: Vec::new(),
-            hspan: 1,
         }
     }
 
-    /// Create a new `Cell` initialized with content from `string`.
-    /// By default, content is align to `LEFT`
-    pub fn new(string: &str) -> Cell {
-        Cell::new_align(string, Alignment::LEFT)
+    /// Update the printed lines and the width of the cell after its settings changed.
+    /// Lazy content is only cleared, to be computed again when needed
+    fn layout(&mut self) {
+        let content = match self.content {
+            Content::Text(ref mut layout) => mem::take(&mut layout.content),
+            Content::Lazy(_, _, ref mut cache) | Content::Table(_, _, ref mut cache) => {
+                *cache = OnceLock::new();
+                return;
+            }
+        };
+        self.content = Content::Text(self.build_layout(content));
+    }
+
+    /// Return the lines and the width of the cell, producing lazy content if needed
+    fn get_layout(&self) -> &Layout {
+        match self.content {
+            Content::Text(ref layout) => layout,
+            Content::Lazy(_, _, ref cache) | Content::Table(_, _, ref cache) => {
+                cache.get_or_init(|| self.build_layout(self.content.lines().to_vec()))
+            }
+        }
+    }
+
+    /// Return the content lines as printed
+    fn lines(&self) -> &[String] {
+        let layout = self.get_layout();
+        layout.wrapped.as_deref().unwrap_or(&layout.content)
+    }
+
+    /// Wrap the content of the cell when printed, so that no line is wider than
+    /// `width` display columns.
+    ///
+    /// Lines are broken at whitespace when possible, and long words are cut between
+    /// characters. Colors set with escape sequences in the content are carried over
+    /// to the wrapped lines. Only the printed text is wrapped: `get_content()` and
+    /// exporters reading it still see the original content. In a table, text widths are
+    /// measured with the width mode of its format.
+    /// A `width` of 0 removes the limit.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use prettytable::Cell;
+    /// let mut cell = Cell::new("A rather long sentence");
+    /// cell.set_max_width(10);
+    /// assert_eq!(cell.get_content(), "A rather long sentence");
+    /// ```
+    pub fn set_max_width(&mut self, width: usize) {
+        self.max_width = if width == 0 { None } else { Some(width) };
+        self.layout();
+    }
+
+    /// Wrap the content of the cell at `width` display columns. Can be chained.
+    ///
+    /// See `set_max_width()` for details.
+    pub fn with_max_width(mut self, width: usize) -> Cell {
+        self.set_max_width(width);
+        self
+    }
+
+    /// Get the width at which the content of the cell is wrapped, if any
+    pub fn get_max_width(&self) -> Option<usize> {
+        self.max_width
+    }
+
+    /// Cut the lines of the cell when printed, so that they are at most `width` display
+    /// columns wide, ending with `marker` (like `"…"`) when something was cut.
+    ///
+    /// Characters are never split, and colors still applied at the cut are reset before
+    /// the marker. When the cell is also wrapped, each wrapped line is truncated.
+    /// Like wrapping, this only changes the printed text, not `get_content()`.
+    /// A `width` of 0 removes the limit.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use prettytable::{Cell, Row, Table};
+    /// let mut cell = Cell::new("A rather long sentence");
+    /// cell.set_truncate(10, "…");
+    /// let table = Table::init(vec![Row::new(vec![cell])]);
+    /// assert!(table.to_string().contains("| A rather … |"));
+    /// ```
+    pub fn set_truncate(&mut self, width: usize, marker: &str) {
+        self.truncate = if width == 0 {
+            None
+        } else {
+            Some((width, marker.to_string()))
+        };
+        self.layout();
+    }
+
+    /// Get the width the printed lines of the cell are truncated at, and the marker
+    /// ending them, if any
+    pub fn get_truncate(&self) -> Option<(usize, &str)> {
+        self.truncate
+            .as_ref()
+            .map(|(width, marker)| (*width, marker.as_str()))
+    }
+
+    /// Truncate the content of the cell at `width` display columns. Can be chained.
+    ///
+    /// See `set_truncate()` for details.
+    pub fn with_truncate(mut self, width: usize, marker: &str) -> Cell {
+        self.set_truncate(width, marker);
+        self
+    }
+
+    /// Make the printed lines of the cell at most `width` display columns wide, by
+    /// truncating them if the cell is truncated, or wrapping them otherwise
+    pub(crate) fn fit_width(&mut self, width: usize) {
+        match self.truncate.clone() {
+            Some((current, marker)) if width < current => self.set_truncate(width, &marker),
+            Some(_) => {}
+            None if self.max_width.is_none_or(|current| width < current) => {
+                self.set_max_width(width)
+            }
+            None => {}
+        }
     }
 
     /// Set text alignment in the cell
     pub fn align(&mut self, align: Alignment) {
-        self.align = align;
+        self.align = Some(align);
+    }
+
+    /// Get text alignment in the cell
+    pub fn get_align(&self) -> Alignment {
+        self.align.unwrap_or(Alignment::LEFT)
+    }
+
+    /// Return the alignment set on the cell, or `None` if it has the default alignment
+    pub fn get_explicit_align(&self) -> Option<Alignment> {
+        self.align
+    }
+
+    /// Set the vertical alignment of the content, when the cell is
+    /// shorter than other cells of its row
+    pub fn set_vertical_align(&mut self, valign: VerticalAlignment) {
+        self.valign = valign;
+    }
+
+    /// Get the vertical alignment of the content in the cell
+    pub fn get_vertical_align(&self) -> VerticalAlignment {
+        self.valign
+    }
+
+    /// Return the index of the line of content to print at line `line` of a row
+    /// `height` lines high. Past the content, the returned index is the cell's height
+    pub(crate) fn get_line_index(&self, line: usize, height: usize) -> usize {
+        let h = self.get_height();
+        let offset = match self.valign {
+            VerticalAlignment::TOP => 0,
+            VerticalAlignment::MIDDLE => height.saturating_sub(h) / 2,
+            VerticalAlignment::BOTTOM => height.saturating_sub(h),
+        };
+        line.checked_sub(offset).map_or(h, |idx| idx.min(h))
     }
 
     /// Add a style attribute to the cell
@@ -57,12 +669,116 @@ impl Cell {
         self.style.push(attr);
     }
 
+    /// Get the style attributes of the cell
+    pub(crate) fn get_style(&self) -> &[Attr] {
+        &self.style
+    }
+
     /// Add a style attribute to the cell. Can be chained
     pub fn with_style(mut self, attr: Attr) -> Cell {
         self.style(attr);
         self
     }
 
+    /// Make the cell a hyperlink to `url`. Can be chained
+    ///
+    /// The link is written as an OSC 8 escape sequence around the content when printing
+    /// to a terminal, and as an `<a>` element in HTML. Other outputs only show the
+    /// content of the cell, which is the visible text of the link.
+    pub fn with_hyperlink(mut self, url: &str) -> Cell {
+        self.url = Some(url.to_string());
+        self
+    }
+
+    /// Get the URL the cell links to, if any
+    pub fn get_hyperlink(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+
+    /// Return the content of the cell, followed by the URL in parentheses
+    /// if the cell is a hyperlink, like `text (url)`
+    pub fn get_content_with_url(&self) -> String {
+        match self.url {
+            Some(ref url) => format!("{} ({})", self.get_content(), url),
+            None => self.get_content(),
+        }
+    }
+
+    /// Set the left and right padding of the cell, overriding the padding of the table format
+    pub fn set_padding(&mut self, left: usize, right: usize) {
+        self.padding = Some((left, right));
+    }
+
+    /// Set the left and right padding of the cell. Can be chained
+    pub fn with_padding(mut self, left: usize, right: usize) -> Cell {
+        self.set_padding(left, right);
+        self
+    }
+
+    /// Get the padding of the cell, if it overrides the padding of the table format
+    pub fn get_padding(&self) -> Option<(usize, usize)> {
+        self.padding
+    }
+
+    /// Set the HTML attribute `name` of the cell to `value`, replacing its previous value.
+    ///
+    /// Attributes are only used by HTML output, in the order they were first set.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use prettytable::Cell;
+    /// let mut cell = Cell::new("Long va…");
+    /// cell.set_html_attr("title", "Long value");
+    /// let mut out = Vec::new();
+    /// cell.print_html(&mut out).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(out).unwrap(),
+    ///     r#"<td style="text-align: left;" title="Long value">Long va…</td>"#
+    /// );
+    /// ```
+    pub fn set_html_attr(&mut self, name: &str, value: &str) {
+        match self.html_attrs.iter_mut().find(|(n, _)| n == name) {
+            Some(attr) => attr.1 = value.to_string(),
+            None => self.html_attrs.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    /// Set several HTML attributes of the cell, as with `set_html_attr()`
+    pub fn set_html_attrs<I, K, V>(&mut self, attrs: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        for (name, value) in attrs {
+            self.set_html_attr(name.as_ref(), value.as_ref());
+        }
+    }
+
+    /// Set the HTML attribute `name` of the cell to `value`. Can be chained
+    pub fn with_html_attr(mut self, name: &str, value: &str) -> Cell {
+        self.set_html_attr(name, value);
+        self
+    }
+
+    /// Get the HTML attributes of the cell, as `(name, value)` pairs
+    pub fn get_html_attrs(&self) -> &[(String, String)] {
+        &self.html_attrs
+    }
+
+    /// Set the char used to fill the cell up to the column width, instead of spaces.
+    ///
+    /// For example, a `'.'` fill on a `LEFT` aligned cell gives dotted leaders.
+    /// The padding around the cell is still made of spaces.
+    pub fn set_fill(&mut self, fill: char) {
+        self.fill = fill;
+    }
+
+    /// Get the char used to fill the cell up to the column width
+    pub fn get_fill(&self) -> char {
+        self.fill
+    }
+
     /// Add horizontal spanning to the cell
     pub fn with_hspan(mut self, hspan: usize) -> Cell {
         self.set_hspan(hspan);
@@ -72,7 +788,7 @@ impl Cell {
     /// Remove all style attributes and reset alignment to default (LEFT)
     pub fn reset_style(&mut self) {
         self.style.clear();
-        self.align(Alignment::LEFT);
+        self.align = None;
     }
 
     /// Set the cell's style by applying the given specifier string
@@ -112,7 +828,14 @@ impl Cell {
     /// * **R** : Bright Red
     /// * **B** : Bright Blue
     /// * ... and so on ...
+    ///
+    /// Unknown specifiers are ignored, and reported on the standard error in debug builds.
+    /// Use `try_style_spec()` to get an error instead.
     pub fn style_spec(mut self, spec: &str) -> Cell {
+        #[cfg(debug_assertions)]
+        if let Err(e) = CellStyle::try_from_spec(spec) {
+            eprintln!("prettytable: {} {:?}", e, spec);
+        }
         self.reset_style();
         let mut foreground = false;
         let mut background = false;
@@ -175,16 +898,29 @@ impl Cell {
         self
     }
 
+    /// Return the width of the cell, measured with `mode`
+    pub(crate) fn get_width_with(&self, mode: WidthMode) -> usize {
+        match mode {
+            WidthMode::Unicode => self.get_width(),
+            _ => self
+                .lines()
+                .iter()
+                .map(|line| mode.width(line))
+                .max()
+                .unwrap_or(0),
+        }
+    }
+
     /// Return the height of the cell
     // #[deprecated(since="0.8.0", note="Will become private in future release. See [issue #87](https://github.com/phsym/prettytable-rs/issues/87)")]
     pub(crate) fn get_height(&self) -> usize {
-        self.content.len()
+        self.lines().len()
     }
 
     /// Return the width of the cell
     // #[deprecated(since="0.8.0", note="Will become private in future release. See [issue #87](https://github.com/phsym/prettytable-rs/issues/87)")]
     pub(crate) fn get_width(&self) -> usize {
-        self.width
+        self.get_layout().width
     }
 
     /// Set horizontal span for this cell (must be > 0)
@@ -199,7 +935,7 @@ impl Cell {
 
     /// Return a copy of the full string contained in the cell
     pub fn get_content(&self) -> String {
-        self.content.join("\n")
+        self.get_layout().content.join("\n")
     }
 
     /// Print a partial cell to `out`. Since the cell may be multi-lined,
@@ -207,26 +943,76 @@ impl Cell {
     /// fill the cells with blanks so it fits in the table.
     /// If `ìdx` is higher than this cell's height, it will print empty content
     // #[deprecated(since="0.8.0", note="Will become private in future release. See [issue #87](https://github.com/phsym/prettytable-rs/issues/87)")]
+    /// Text widths are measured with `mode`.
     pub(crate) fn print<T: Write + ?Sized>(
         &self,
         out: &mut T,
         idx: usize,
         col_width: usize,
+        mode: WidthMode,
         skip_right_fill: bool,
     ) -> Result<(), Error> {
-        let c = self.content.get(idx).map(|s| s.as_ref()).unwrap_or("");
-        print_align(out, self.align, c, ' ', col_width, skip_right_fill)
+        match self.lines().get(idx) {
+            Some(line) => print_align(
+                out,
+                self.get_align(),
+                line,
+                mode.width(line),
+                self.fill,
+                col_width,
+                skip_right_fill,
+            ),
+            // Lines below the content are left blank
+            None => print_align(
+                out,
+                self.get_align(),
+                "",
+                0,
+                ' ',
+                col_width,
+                skip_right_fill,
+            ),
+        }
     }
 
-    /// Apply style then call `print` to print the cell into a terminal
+    /// Print a partial cell to `out` like `print`, surrounded by `padding` spaces
+    /// on the left and on the right
+    pub(crate) fn print_padded<T: Write + ?Sized>(
+        &self,
+        out: &mut T,
+        idx: usize,
+        col_width: usize,
+        padding: (usize, usize),
+        mode: WidthMode,
+        skip_right_fill: bool,
+    ) -> Result<(), Error> {
+        out.write_all(&vec![b' '; padding.0])?;
+        self.print(out, idx, col_width, mode, skip_right_fill)?;
+        out.write_all(&vec![b' '; padding.1])
+    }
+
+    /// Apply style then print the cell into a terminal, like `print_padded`.
+    /// Hyperlinks are written as OSC 8 sequences.
+    ///
+    /// When the cell has a background color, the padding and the whole alignment fill
+    /// are printed with the style applied, so the color covers the full cell.
     // #[deprecated(since="0.8.0", note="Will become private in future release. See [issue #87](https://github.com/phsym/prettytable-rs/issues/87)")]
     pub(crate) fn print_term<T: Terminal + ?Sized>(
         &self,
         out: &mut T,
         idx: usize,
         col_width: usize,
+        padding: (usize, usize),
+        mode: WidthMode,
         skip_right_fill: bool,
     ) -> Result<(), Error> {
+        let background = self
+            .style
+            .iter()
+            .any(|a| matches!(a, Attr::BackgroundColor(..)));
+        if !background {
+            out.write_all(&vec![b' '; padding.0])?;
+        }
         for a in &self.style {
             match out.attr(*a) {
                 Ok(..) | Err(::term::Error::NotSupported) | Err(::term::Error::ColorOutOfRange) => {
@@ -234,42 +1020,49 @@ impl Cell {
                 Err(e) => return Err(term_error_to_io_error(e)),
             };
         }
-        self.print(out, idx, col_width, skip_right_fill)?;
-        match out.reset() {
-            Ok(..) | Err(::term::Error::NotSupported) | Err(::term::Error::ColorOutOfRange) => {
-                Ok(())
+        let skip_right_fill = skip_right_fill && !background;
+        if background {
+            out.write_all(&vec![b' '; padding.0])?;
+        }
+        match (self.url.as_ref(), self.lines().get(idx)) {
+            (Some(url), Some(line)) if !line.is_empty() => {
+                let link = format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, line);
+                print_align(
+                    out,
+                    self.get_align(),
+                    &link,
+                    mode.width(line),
+                    self.fill,
+                    col_width,
+                    skip_right_fill,
+                )?;
             }
-            Err(e) => Err(term_error_to_io_error(e)),
+            _ => self.print(out, idx, col_width, mode, skip_right_fill)?,
+        }
+        if background {
+            out.write_all(&vec![b' '; padding.1])?;
+        }
+        match out.reset() {
+            Ok(..) | Err(::term::Error::NotSupported) | Err(::term::Error::ColorOutOfRange) => {}
+            Err(e) => return Err(term_error_to_io_error(e)),
+        }
+        if !background {
+            out.write_all(&vec![b' '; padding.1])?;
         }
+        Ok(())
     }
 
     /// Print the cell in HTML format to `out`.
     pub fn print_html<T: Write + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
-        /// Convert the color to a hex value useful in CSS
-        fn color2hex(color: color::Color) -> &'static str {
-            match color {
-                color::BLACK => "#000000",
-                color::RED => "#aa0000",
-                color::GREEN => "#00aa00",
-                color::YELLOW => "#aa5500",
-                color::BLUE => "#0000aa",
-                color::MAGENTA => "#aa00aa",
-                color::CYAN => "#00aaaa",
-                color::WHITE => "#aaaaaa",
-                color::BRIGHT_BLACK => "#555555",
-                color::BRIGHT_RED => "#ff5555",
-                color::BRIGHT_GREEN => "#55ff55",
-                color::BRIGHT_YELLOW => "#ffff55",
-                color::BRIGHT_BLUE => "#5555ff",
-                color::BRIGHT_MAGENTA => "#ff55ff",
-                color::BRIGHT_CYAN => "#55ffff",
-                color::BRIGHT_WHITE => "#ffffff",
-
-                // Unknown colors, fallback to blakc
-                _ => "#000000",
-            }
-        }
+        self.print_html_with_options(out, &HtmlOptions::default())
+    }
 
+    /// Print the cell in HTML format to `out`, following `options`.
+    pub(crate) fn print_html_with_options<T: Write + ?Sized>(
+        &self,
+        out: &mut T,
+        options: &HtmlOptions,
+    ) -> Result<usize, Error> {
         let colspan = if self.hspan > 1 {
             format!(" colspan=\"{}\"", self.hspan)
         } else {
@@ -277,39 +1070,52 @@ impl Cell {
         };
 
         // Process style properties like color
-        let mut styles = String::new();
-        for style in &self.style {
-            match style {
-                Attr::Bold => styles += "font-weight: bold;",
-                Attr::Italic(true) => styles += "font-style: italic;",
-                Attr::Underline(true) => styles += "text-decoration: underline;",
-                Attr::ForegroundColor(c) => {
-                    styles += "color: ";
-                    styles += color2hex(*c);
-                    styles += ";";
-                }
-                Attr::BackgroundColor(c) => {
-                    styles += "background-color: ";
-                    styles += color2hex(*c);
-                    styles += ";";
-                }
-                _ => {}
-            }
-        }
+        let (mut styles, classes) = html_style(&self.style, options);
         // Process alignment
-        match self.align {
+        match self.get_align() {
             Alignment::LEFT => styles += "text-align: left;",
             Alignment::CENTER => styles += "text-align: center;",
             Alignment::RIGHT => styles += "text-align: right;",
         }
+        match self.valign {
+            VerticalAlignment::TOP => {}
+            VerticalAlignment::MIDDLE => styles += "vertical-align: middle;",
+            VerticalAlignment::BOTTOM => styles += "vertical-align: bottom;",
+        }
+        let class = if classes.is_empty() {
+            String::new()
+        } else {
+            format!(" class=\"{}\"", classes.join(" "))
+        };
 
-        let content = self.content.join("<br />");
+        let attrs: String = self
+            .html_attrs
+            .iter()
+            .map(|(name, value)| format!(" {}=\"{}\"", HtmlEscape(name), HtmlEscape(value)))
+            .collect();
+
+        let mut content = match self.content {
+            Content::Table(ref table, _, _) => {
+                // Ids and classes are only meant for the outer table
+                let nested = HtmlOptions {
+                    style_classes: options.style_classes,
+                    ..HtmlOptions::default()
+                };
+                let mut html = Vec::new();
+                table.print_html_with_options(&mut html, &nested)?;
+                String::from_utf8_lossy(&html).into_owned()
+            }
+            _ => HtmlEscape(&self.get_content())
+                .to_string()
+                .replace('\n', "<br />"),
+        };
+        if let Some(ref url) = self.url {
+            content = format!("<a href=\"{}\">{}</a>", HtmlEscape(url), content);
+        }
         out.write_all(
             format!(
-                "<td{1} style=\"{2}\">{0}</td>",
-                HtmlEscape(&content),
-                colspan,
-                styles
+                "<td{1}{2} style=\"{3}\"{4}>{0}</td>",
+                content, colspan, class, styles, attrs
             )
             .as_bytes(),
         )?;
@@ -317,22 +1123,120 @@ impl Cell {
     }
 }
 
+/// Convert the style attributes `attrs` into CSS declarations and classes,
+/// following `options`
+pub(crate) fn html_style(attrs: &[Attr], options: &HtmlOptions) -> (String, Vec<String>) {
+    let mut styles = String::new();
+    let mut classes = Vec::new();
+    for style in attrs {
+        if options.style_classes {
+            match style {
+                Attr::Bold => classes.push("bold".to_string()),
+                Attr::Italic(true) => classes.push("italic".to_string()),
+                Attr::Underline(true) => classes.push("underline".to_string()),
+                Attr::ForegroundColor(c) => classes.push(format!("fg-{}", color2name(*c))),
+                Attr::BackgroundColor(c) => classes.push(format!("bg-{}", color2name(*c))),
+                _ => {}
+            }
+            continue;
+        }
+        match style {
+            Attr::Bold => styles += "font-weight: bold;",
+            Attr::Italic(true) => styles += "font-style: italic;",
+            Attr::Underline(true) => styles += "text-decoration: underline;",
+            Attr::ForegroundColor(c) => {
+                styles += "color: ";
+                styles += color2hex(*c);
+                styles += ";";
+            }
+            Attr::BackgroundColor(c) => {
+                styles += "background-color: ";
+                styles += color2hex(*c);
+                styles += ";";
+            }
+            _ => {}
+        }
+    }
+    (styles, classes)
+}
+
+/// Convert the color to a hex value useful in CSS
+fn color2hex(color: color::Color) -> &'static str {
+    match color {
+        color::BLACK => "#000000",
+        color::RED => "#aa0000",
+        color::GREEN => "#00aa00",
+        color::YELLOW => "#aa5500",
+        color::BLUE => "#0000aa",
+        color::MAGENTA => "#aa00aa",
+        color::CYAN => "#00aaaa",
+        color::WHITE => "#aaaaaa",
+        color::BRIGHT_BLACK => "#555555",
+        color::BRIGHT_RED => "#ff5555",
+        color::BRIGHT_GREEN => "#55ff55",
+        color::BRIGHT_YELLOW => "#ffff55",
+        color::BRIGHT_BLUE => "#5555ff",
+        color::BRIGHT_MAGENTA => "#ff55ff",
+        color::BRIGHT_CYAN => "#55ffff",
+        color::BRIGHT_WHITE => "#ffffff",
+
+        // Unknown colors, fallback to blakc
+        _ => "#000000",
+    }
+}
+
+/// Convert the color to a name useful in CSS class names
+fn color2name(color: color::Color) -> &'static str {
+    match color {
+        color::BLACK => "black",
+        color::RED => "red",
+        color::GREEN => "green",
+        color::YELLOW => "yellow",
+        color::BLUE => "blue",
+        color::MAGENTA => "magenta",
+        color::CYAN => "cyan",
+        color::WHITE => "white",
+        color::BRIGHT_BLACK => "bright-black",
+        color::BRIGHT_RED => "bright-red",
+        color::BRIGHT_GREEN => "bright-green",
+        color::BRIGHT_YELLOW => "bright-yellow",
+        color::BRIGHT_BLUE => "bright-blue",
+        color::BRIGHT_MAGENTA => "bright-magenta",
+        color::BRIGHT_CYAN => "bright-cyan",
+        color::BRIGHT_WHITE => "bright-white",
+
+        // Unknown colors, fallback to black
+        _ => "black",
+    }
+}
+
 fn term_error_to_io_error(te: ::term::Error) -> Error {
     match te {
         ::term::Error::Io(why) => why,
-        _ => Error::new(::std::io::ErrorKind::Other, te),
+        _ => Error::other(te),
     }
 }
 
-impl<'a, T: ToString> From<&'a T> for Cell {
+impl<T: ToString> From<&T> for Cell {
     fn from(f: &T) -> Cell {
         Cell::new(&f.to_string())
     }
 }
 
-impl ToString for Cell {
-    fn to_string(&self) -> String {
-        self.get_content()
+impl<T: ToString> From<Option<T>> for Cell {
+    /// Create a cell holding the value of `opt`, or an empty cell if it is `None`.
+    /// Empty cells show the placeholder of the table, if any.
+    fn from(opt: Option<T>) -> Cell {
+        match opt {
+            Some(value) => Cell::new(&value.to_string()),
+            None => Cell::default(),
+        }
+    }
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(&self.get_content())
     }
 }
 
@@ -340,11 +1244,21 @@ impl Default for Cell {
     /// Return a cell initialized with a single empty `String`, with LEFT alignment
     fn default() -> Cell {
         Cell {
-            content: vec!["".to_string(); 1],
-            width: 0,
-            align: Alignment::LEFT,
+            content: Content::Text(Layout {
+                content: vec!["".to_string(); 1],
+                wrapped: None,
+                width: 0,
+            }),
+            align: None,
+            valign: VerticalAlignment::TOP,
             style: Vec::new(),
             hspan: 1,
+            max_width: None,
+            truncate: None,
+            url: None,
+            padding: None,
+            fill: ' ',
+            html_attrs: Vec::new(),
         }
     }
 }
@@ -361,7 +1275,15 @@ impl Default for Cell {
 /// ```text
 /// cell!(spec->value);
 /// ```
-/// Value must implement the `std::string::ToString` trait
+/// Value must implement the `std::string::ToString` trait.
+///
+/// Values of type `Option` and `Result` are supported with the `@option` and `@result`
+/// prefixes, creating cells with `From<Option<T>>` and `Cell::from_result()`, or
+/// `Cell::from_result_spec()` with a style specifier:
+/// ```text
+/// cell!(@option value);
+/// cell!(spec->@result value);
+/// ```
 ///
 /// For details about style specifier syntax, check doc for [`Cell::style_spec`](cell/struct.Cell.html#method.style_spec) method
 /// # Example
@@ -374,6 +1296,12 @@ impl Default for Cell {
 /// // Create a cell with style (Red foreground, Bold, aligned to left);
 /// let styled = cell!(Frbl->"value");
 /// # drop(styled);
+/// let missing = cell!(@option None::<u32>);
+/// # assert_eq!(missing.get_content(), "");
+/// let failed = cell!(c->@result "x".parse::<u32>());
+/// # assert!(failed.get_content().starts_with('!'));
+/// // Errors stay red
+/// assert_eq!(failed, cell!(cFr->failed.get_content()));
 /// # }
 /// ```
 #[macro_export]
@@ -381,6 +1309,18 @@ macro_rules! cell {
     () => {
         $crate::Cell::default()
     };
+    (@option $value:expr) => {
+        <$crate::Cell as ::std::convert::From<_>>::from($value)
+    };
+    (@result $value:expr) => {
+        $crate::Cell::from_result($value)
+    };
+    ($style:ident -> @option $value:expr) => {
+        $crate::cell!(@option $value).style_spec(stringify!($style))
+    };
+    ($style:ident -> @result $value:expr) => {
+        $crate::Cell::from_result_spec($value, stringify!($style))
+    };
     ($value:expr) => {
         $crate::Cell::new(&$value.to_string())
     };
@@ -392,8 +1332,10 @@ macro_rules! cell {
 #[cfg(test)]
 mod tests {
     use super::Cell;
-    use crate::format::Alignment;
+    use crate::format::consts::FORMAT_CLEAN;
+    use crate::format::{Alignment, VerticalAlignment, WidthMode};
     use crate::utils::StringWriter;
+    use crate::{HtmlOptions, Row, Table};
     use term::{color, Attr};
 
     #[test]
@@ -408,7 +1350,7 @@ mod tests {
         assert_eq!(ascii_cell.get_width(), 5);
 
         let mut out = StringWriter::new();
-        let _ = ascii_cell.print(&mut out, 0, 10, false);
+        let _ = ascii_cell.print(&mut out, 0, 10, WidthMode::Unicode, false);
         assert_eq!(out.as_string(), "hello     ");
     }
 
@@ -418,7 +1360,7 @@ mod tests {
         assert_eq!(unicode_cell.get_width(), 6);
 
         let mut out = StringWriter::new();
-        let _ = unicode_cell.print(&mut out, 0, 10, false);
+        let _ = unicode_cell.print(&mut out, 0, 10, WidthMode::Unicode, false);
         assert_eq!(out.as_string(), "привет    ");
     }
 
@@ -427,7 +1369,7 @@ mod tests {
         let unicode_cell = Cell::new("由系统自动更新");
         assert_eq!(unicode_cell.get_width(), 14);
         let mut out = StringWriter::new();
-        let _ = unicode_cell.print(&mut out, 0, 20, false);
+        let _ = unicode_cell.print(&mut out, 0, 20, WidthMode::Unicode, false);
         assert_eq!(out.as_string(), "由系统自动更新      ");
     }
 
@@ -444,6 +1386,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn from_table() {
+        let mut inner = Table::new();
+        inner.add_row(Row::new(vec![Cell::new("x"), Cell::new("yy")]));
+        inner.set_format(*FORMAT_CLEAN);
+        let mut outer = Table::new();
+        outer.add_row(Row::new(vec![Cell::new("host"), Cell::from_table(&inner)]));
+        let out = "\
++------+---------+
+| host |  x  yy  |
++------+---------+
+";
+        assert_eq!(outer.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(outer[0][1], Cell::new(" x  yy "));
+
+        let mut html = Vec::new();
+        outer[0][1].print_html(&mut html).unwrap();
+        assert_eq!(
+            String::from_utf8(html).unwrap(),
+            "<td style=\"text-align: left;\"><table><tr><td style=\"text-align: left;\">x</td>\
+             <td style=\"text-align: left;\">yy</td></tr></table></td>"
+        );
+    }
+
     #[test]
     fn print_html_special_chars() {
         let ascii_cell = Cell::new("<abc\">&'");
@@ -456,11 +1422,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn print_html_style_classes() {
+        let cell = Cell::new("hello").style_spec("FrBYbc");
+        let options = HtmlOptions {
+            style_classes: true,
+            ..HtmlOptions::default()
+        };
+        let mut out = StringWriter::new();
+        let _ = cell.print_html_with_options(&mut out, &options);
+        assert_eq!(
+            out.as_string(),
+            r#"<td class="fg-red bg-bright-yellow bold" style="text-align: center;">hello</td>"#
+        );
+    }
+
     #[test]
     fn align_left() {
         let cell = Cell::new_align("test", Alignment::LEFT);
         let mut out = StringWriter::new();
-        let _ = cell.print(&mut out, 0, 10, false);
+        let _ = cell.print(&mut out, 0, 10, WidthMode::Unicode, false);
         assert_eq!(out.as_string(), "test      ");
     }
 
@@ -468,7 +1449,7 @@ mod tests {
     fn align_center() {
         let cell = Cell::new_align("test", Alignment::CENTER);
         let mut out = StringWriter::new();
-        let _ = cell.print(&mut out, 0, 10, false);
+        let _ = cell.print(&mut out, 0, 10, WidthMode::Unicode, false);
         assert_eq!(out.as_string(), "   test   ");
     }
 
@@ -476,10 +1457,26 @@ mod tests {
     fn align_right() {
         let cell = Cell::new_align("test", Alignment::RIGHT);
         let mut out = StringWriter::new();
-        let _ = cell.print(&mut out, 0, 10, false);
+        let _ = cell.print(&mut out, 0, 10, WidthMode::Unicode, false);
         assert_eq!(out.as_string(), "      test");
     }
 
+    #[test]
+    fn eq_default_align() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+        let hash = |cell: &Cell| {
+            let mut hasher = DefaultHasher::new();
+            cell.hash(&mut hasher);
+            hasher.finish()
+        };
+        let left = Cell::new_align("a", Alignment::LEFT);
+        assert_eq!(left, Cell::new("a"));
+        assert_eq!(hash(&left), hash(&Cell::new("a")));
+        assert_ne!(Cell::new_align("a", Alignment::RIGHT), Cell::new("a"));
+        assert_ne!(left, Cell::new("b"));
+    }
+
     #[test]
     fn style_spec() {
         let mut cell = Cell::new("test").style_spec("FrBBbuic");
@@ -491,7 +1488,7 @@ mod tests {
         assert!(cell
             .style
             .contains(&Attr::BackgroundColor(color::BRIGHT_BLUE)));
-        assert_eq!(cell.align, Alignment::CENTER);
+        assert_eq!(cell.get_align(), Alignment::CENTER);
 
         cell = cell.style_spec("FDBwr");
         assert_eq!(cell.style.len(), 2);
@@ -499,10 +1496,12 @@ mod tests {
             .style
             .contains(&Attr::ForegroundColor(color::BRIGHT_BLACK)));
         assert!(cell.style.contains(&Attr::BackgroundColor(color::WHITE)));
-        assert_eq!(cell.align, Alignment::RIGHT);
+        assert_eq!(cell.get_align(), Alignment::RIGHT);
 
-        // Test with invalid sepcifier chars
-        cell = cell.clone();
+        // Test with invalid sepcifier chars, which are reported in debug builds
+        assert!(cell.clone().try_style_spec("FzBr").is_err());
+        assert!(cell.clone().try_style_spec("zzz").is_err());
+        assert!(cell.clone().try_style_spec("FDBwH03r").is_ok());
         cell = cell.style_spec("FzBr");
         assert!(cell.style.contains(&Attr::BackgroundColor(color::RED)));
         assert_eq!(cell.style.len(), 1);
@@ -522,20 +1521,242 @@ mod tests {
 
         //style_spec("FDBwr");
         assert_eq!(cell.style.len(), 2);
-        assert_eq!(cell.align, Alignment::RIGHT);
+        assert_eq!(cell.get_align(), Alignment::RIGHT);
         cell.reset_style();
         assert_eq!(cell.style.len(), 0);
-        assert_eq!(cell.align, Alignment::LEFT);
+        assert_eq!(cell.get_align(), Alignment::LEFT);
+    }
+
+    #[test]
+    fn truncate() {
+        let cell = Cell::new("hello world\nok").with_truncate(8, "…");
+        assert_eq!(cell.get_width(), 8);
+        assert_eq!(cell.get_content(), "hello world\nok");
+        let mut out = StringWriter::new();
+        let _ = cell.print(&mut out, 0, 8, WidthMode::Unicode, false);
+        let _ = cell.print(&mut out, 1, 8, WidthMode::Unicode, false);
+        assert_eq!(out.as_string(), "hello w…ok      ");
+
+        let wrapped = Cell::new("aaa bbbbbbbb")
+            .with_max_width(4)
+            .with_truncate(4, ".");
+        assert_eq!(wrapped.get_height(), 3);
+        let mut out = StringWriter::new();
+        for i in 0..3 {
+            let _ = wrapped.print(&mut out, i, 4, WidthMode::Unicode, false);
+        }
+        assert_eq!(out.as_string(), "aaa bbbbbbbb");
+    }
+
+    /// Return a terminal writing into a buffer, supporting colors
+    fn test_terminal() -> term::TerminfoTerminal<Vec<u8>> {
+        let mut info = term::terminfo::TermInfo {
+            names: Vec::new(),
+            bools: Default::default(),
+            numbers: Default::default(),
+            strings: Default::default(),
+        };
+        info.numbers.insert("colors", 8);
+        info.strings.insert("setaf", b"\x1b[3%p1%dm".to_vec());
+        info.strings.insert("setab", b"\x1b[4%p1%dm".to_vec());
+        info.strings.insert("sgr0", b"\x1b[0m".to_vec());
+        term::TerminfoTerminal::new_with_terminfo(Vec::new(), info)
+    }
+
+    #[test]
+    fn background_fill() {
+        let mut term = test_terminal();
+        let cell = Cell::new("ab").with_style(Attr::BackgroundColor(color::BLUE));
+        cell.print_term(&mut term, 0, 4, (1, 2), WidthMode::Unicode, true)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(term::Terminal::get_ref(&term)),
+            "\x1b[44m ab    \x1b[0m"
+        );
+
+        let mut term = test_terminal();
+        let cell = Cell::new("ab").with_style(Attr::ForegroundColor(color::RED));
+        cell.print_term(&mut term, 0, 4, (1, 2), WidthMode::Unicode, true)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(term::Terminal::get_ref(&term)),
+            " \x1b[31mab\x1b[0m  "
+        );
+    }
+
+    #[test]
+    fn hyperlink() {
+        let cell = Cell::new_hyperlink("docs", "https://example.com/?a=1&b=2");
+        assert_eq!(cell.get_width(), 4);
+        assert_eq!(cell.get_content(), "docs");
+        assert_eq!(
+            cell.get_content_with_url(),
+            "docs (https://example.com/?a=1&b=2)"
+        );
+        assert_eq!(Cell::new("plain").get_content_with_url(), "plain");
+
+        let mut out = StringWriter::new();
+        let _ = cell.print(&mut out, 0, 6, WidthMode::Unicode, false);
+        assert_eq!(out.as_string(), "docs  ");
+
+        let mut term = test_terminal();
+        cell.print_term(&mut term, 0, 6, (0, 0), WidthMode::Unicode, false)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(term::Terminal::get_ref(&term)),
+            "\x1b]8;;https://example.com/?a=1&b=2\x1b\\docs\x1b]8;;\x1b\\  \x1b[0m"
+        );
+
+        let mut out = StringWriter::new();
+        let _ = cell.print_html(&mut out);
+        assert_eq!(
+            out.as_string(),
+            r#"<td style="text-align: left;"><a href="https://example.com/?a=1&amp;b=2">docs</a></td>"#
+        );
+    }
+
+    #[test]
+    fn vertical_align() {
+        let mut cell = Cell::new("a\nb");
+        assert_eq!(cell.get_vertical_align(), VerticalAlignment::TOP);
+        let lines = |cell: &Cell| {
+            (0..5)
+                .map(|i| cell.get_line_index(i, 5))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(lines(&cell), [0, 1, 2, 2, 2]);
+        cell.set_vertical_align(VerticalAlignment::MIDDLE);
+        assert_eq!(lines(&cell), [2, 0, 1, 2, 2]);
+        cell.set_vertical_align(VerticalAlignment::BOTTOM);
+        assert_eq!(lines(&cell), [2, 2, 2, 0, 1]);
+    }
+
+    #[test]
+    fn lazy_content() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let mut cell = Cell::from_fn(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            "lazy\ncontent".to_string()
+        });
+        let copy = cell.clone();
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        assert_eq!(cell.get_width(), 7);
+        assert_eq!(cell.get_height(), 2);
+        assert_eq!(cell.get_content(), "lazy\ncontent");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(cell, Cell::new("lazy\ncontent"));
+        assert_eq!(copy, cell);
+        assert_eq!(copy, cell);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        cell.set_max_width(4);
+        assert_eq!(cell.get_height(), 3);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let lazy = Cell::from_fn(|| "a".to_string());
+        assert_eq!(
+            std::panic::catch_unwind(|| lazy.get_content()).unwrap(),
+            "a"
+        );
+        fn unwind_safe<T: std::panic::UnwindSafe + std::panic::RefUnwindSafe>() {}
+        unwind_safe::<Cell>();
+        unwind_safe::<Row>();
+        unwind_safe::<Table>();
+    }
+
+    #[test]
+    fn from_ansi() {
+        // `"x".red().bold()` with the `colored` crate
+        let cell = Cell::from_ansi("\x1b[1;31mx\x1b[0m");
+        assert_eq!(cell.get_content(), "x");
+        assert_eq!(
+            cell.style,
+            vec![Attr::Bold, Attr::ForegroundColor(color::RED)]
+        );
+
+        let cell = Cell::from_ansi("\x1b[4m\x1b[38;5;208;48;2;1;2;3;103mwarm\x1b[31m ink\x1b[0m");
+        assert_eq!(cell.get_content(), "warm ink");
+        assert_eq!(
+            cell.style,
+            vec![
+                Attr::Underline(true),
+                Attr::ForegroundColor(208),
+                Attr::BackgroundColor(color::BRIGHT_YELLOW)
+            ]
+        );
+
+        let cell = Cell::from_ansi("\x1b[31m\x1b[0mplain");
+        assert_eq!(cell, Cell::new("plain"));
+        let mut out = StringWriter::new();
+        let _ = Cell::from_ansi("\x1b[1;31mx\x1b[0m").print_html(&mut out);
+        assert_eq!(
+            out.as_string(),
+            r#"<td style="font-weight: bold;color: #aa0000;text-align: left;">x</td>"#
+        );
     }
 
     #[test]
     fn default_empty_cell() {
         let cell = Cell::default();
-        assert_eq!(cell.align, Alignment::LEFT);
+        assert_eq!(cell.get_align(), Alignment::LEFT);
         assert!(cell.style.is_empty());
         assert_eq!(cell.get_content(), "");
         assert_eq!(cell.to_string(), "");
         assert_eq!(cell.get_height(), 1);
         assert_eq!(cell.get_width(), 0);
     }
+
+    #[test]
+    fn from_option_and_result() {
+        assert_eq!(<Cell as From<_>>::from(Some(3)), Cell::new("3"));
+        let cell: Cell = None::<&str>.into();
+        assert_eq!(cell, Cell::default());
+        assert_eq!(cell!(@option Some("x")), Cell::new("x"));
+        assert_eq!(
+            cell!(b->@option Some(1)),
+            Cell::new("1").with_style(Attr::Bold)
+        );
+
+        let ok: Result<f32, String> = Ok(1.5);
+        assert_eq!(Cell::from_result(ok), Cell::new("1.5"));
+        let err: Result<f32, String> = Err("bad".to_string());
+        assert_eq!(
+            Cell::from_result(err.clone()),
+            Cell::new("!bad").with_style(Attr::ForegroundColor(color::RED))
+        );
+        assert_eq!(
+            Cell::from_result_with_style(err.clone(), crate::CellStyle::new().bold()),
+            Cell::new("!bad").with_style(Attr::Bold)
+        );
+        assert_eq!(cell!(@result err), Cell::from_result(Err::<f32, _>("bad")));
+    }
+
+    #[test]
+    fn html_attrs() {
+        let mut cell = Cell::new("a");
+        cell.set_html_attr("title", "<full> \"value\"");
+        cell.set_html_attrs(vec![("data-id", "7"), ("title", "x & y")]);
+        let cell = cell.with_html_attr("data-kind", "k");
+        assert_eq!(
+            cell.get_html_attrs(),
+            &[
+                ("title".to_string(), "x & y".to_string()),
+                ("data-id".to_string(), "7".to_string()),
+                ("data-kind".to_string(), "k".to_string()),
+            ]
+        );
+        let mut out = StringWriter::new();
+        let _ = cell.print_html(&mut out);
+        assert_eq!(
+            out.as_string(),
+            r#"<td style="text-align: left;" title="x &amp; y" data-id="7" data-kind="k">a</td>"#
+        );
+        let mut out = StringWriter::new();
+        let _ = cell.print(&mut out, 0, 1, WidthMode::Unicode, false);
+        assert_eq!(out.as_string(), "a");
+    }
 }
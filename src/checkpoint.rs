@@ -0,0 +1,95 @@
+//! Checkpoints of tables, to undo changes
+
+use super::Table;
+
+/// A saved state of a table, as returned by `Table::checkpoint()`, to restore it later
+/// with `Table::restore()`, or to find the cells changed since with `Table::edits_since()`.
+///
+/// The checkpoint holds its own copy of the table, which is not affected by
+/// later changes to the table.
+///
+/// # Examples
+/// ```rust
+/// # #[macro_use] extern crate prettytable;
+/// # use prettytable::Cell;
+/// # fn main() {
+/// let mut table = table![["a", "b"], ["c", "d"]];
+/// let checkpoint = table.checkpoint();
+/// table.set_cell(1, 0, Cell::new("e")).unwrap();
+/// assert_eq!(table.edits_since(&checkpoint), [(1, 0)]);
+/// table.restore(&checkpoint);
+/// assert_eq!(table, table![["a", "b"], ["c", "d"]]);
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TableCheckpoint {
+    table: Table,
+}
+
+impl Table {
+    /// Save the current state of the table, to restore it later with `restore()`
+    pub fn checkpoint(&self) -> TableCheckpoint {
+        TableCheckpoint {
+            table: self.clone(),
+        }
+    }
+
+    /// Bring the table back to the state saved in `checkpoint`, including its titles,
+    /// footer, format and display settings. The checkpoint can be restored again.
+    pub fn restore(&mut self, checkpoint: &TableCheckpoint) {
+        self.clone_from(&checkpoint.table);
+    }
+
+    /// Return the coordinates `(row, column)` of the cells changed since `checkpoint`,
+    /// in order, where `column` is the index of the cell in its row.
+    ///
+    /// A cell is changed when its content, style or any other setting differs. Cells
+    /// added or removed since, including the ones of added or removed rows, are also
+    /// listed. Changes to the titles, header rows, footer, format and display settings
+    /// are not listed.
+    pub fn edits_since(&self, checkpoint: &TableCheckpoint) -> Vec<(usize, usize)> {
+        let (old, new) = (&checkpoint.table.rows, &self.rows);
+        let mut edits = Vec::new();
+        for row in 0..old.len().max(new.len()) {
+            let (a, b) = (old.get(row), new.get(row));
+            let len = a.map_or(0, |r| r.len()).max(b.map_or(0, |r| r.len()));
+            for column in 0..len {
+                if a.and_then(|r| r.get_cell(column)) != b.and_then(|r| r.get_cell(column)) {
+                    edits.push((row, column));
+                }
+            }
+        }
+        edits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Attr, Cell, Row, Table};
+
+    #[test]
+    fn checkpoint() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("b")]));
+        table.add_row(Row::new(vec![Cell::new("c")]));
+        let checkpoint = table.checkpoint();
+        assert!(table.edits_since(&checkpoint).is_empty());
+
+        table[0][1].style(Attr::Bold);
+        table[1].add_cell(Cell::new("d"));
+        table.add_row(Row::new(vec![Cell::new("e"), Cell::new("f")]));
+        table.set_titles(Row::new(vec![Cell::new("t")]));
+        assert_eq!(
+            table.edits_since(&checkpoint),
+            [(0, 1), (1, 1), (2, 0), (2, 1)]
+        );
+
+        table.restore(&checkpoint);
+        assert!(table.edits_since(&checkpoint).is_empty());
+        assert!(table.titles.is_none());
+        table.remove_row(0);
+        assert_eq!(table.edits_since(&checkpoint), [(0, 0), (0, 1), (1, 0)]);
+        table.restore(&checkpoint);
+        assert_eq!(table.len(), 2);
+    }
+}
@@ -0,0 +1,502 @@
+//! Column-wise editing of tables: insertion, removal, reordering and transposition
+
+use std::cmp::Ordering;
+use std::ops::{Bound, RangeBounds};
+
+use super::{column_count, Cell, Row, Table};
+
+/// Append empty cells to `row` until it holds at least `len` cells
+pub(crate) fn pad_row(row: &mut Row, len: usize) {
+    while row.len() < len {
+        row.add_cell(Cell::default());
+    }
+}
+
+/// Append `cell` to `row` in column `column`, after empty cells up to that column
+fn add_cell_at(row: &mut Row, column: usize, cell: Cell) {
+    for _ in row.column_count()..column {
+        row.add_cell(Cell::default());
+    }
+    row.add_cell(cell);
+}
+
+impl Table {
+    /// Insert a column at position `index`, holding `cells` from the first row on,
+    /// and titled `title` if any.
+    ///
+    /// Rows shorter than `index` are first padded with empty cells, as well as header
+    /// rows and footer. The header rows above the titles and the footer get an empty cell.
+    /// Rows beyond the end of `cells` get an empty cell, while the remaining cells
+    /// are added in new rows. If the table has titles and `title` is `None`,
+    /// an empty title is inserted. Group headers are skipped, and span the new column.
+    ///
+    /// The display settings of the columns after `index`, like alignments, move along
+    /// with their column.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// use prettytable::Cell;
+    /// # fn main() {
+    /// let mut table = table![["a", "c"], ["d"]];
+    /// table.insert_column(1, None, vec![Cell::new("b")]);
+    /// assert_eq!(table[0], ["a", "b", "c"]);
+    /// assert_eq!(table[1], ["d", ""]);
+    /// # }
+    /// ```
+    pub fn insert_column(&mut self, index: usize, title: Option<Cell>, cells: Vec<Cell>) {
+        if title.is_some() || self.titles.is_some() {
+            let titles = self.titles.get_or_insert_with(Row::empty);
+            pad_row(titles, index);
+            titles.insert_cell(index, title.unwrap_or_default());
+        }
+        for row in self.header_rows.iter_mut().chain(self.footer.iter_mut()) {
+            pad_row(row, index);
+            row.insert_cell(index, Cell::default());
+        }
+        let mut cells = cells.into_iter();
+        for row in self.rows.iter_mut().filter(|r| !r.is_group_header()) {
+            pad_row(row, index);
+            row.insert_cell(index, cells.next().unwrap_or_default());
+        }
+        for cell in cells {
+            let mut row = Row::empty();
+            pad_row(&mut row, index);
+            row.add_cell(cell);
+            self.rows.push(row);
+        }
+        self.render = self
+            .render
+            .move_columns(|c| Some(c + usize::from(c >= index)));
+        self.span_group_headers();
+    }
+
+    /// Remove the column at position `index` from the rows, header rows and footer, and
+    /// return the removed cells of the rows. Rows shorter than `index` are left unchanged.
+    ///
+    /// The display settings of the removed column are dropped, and the ones of the
+    /// columns after it move along with their column. Group headers span the remaining
+    /// columns.
+    pub fn remove_column(&mut self, index: usize) -> Vec<Cell> {
+        if let Some(titles) = self.titles.as_mut() {
+            titles.remove_cell(index);
+        }
+        for row in self.header_rows.iter_mut().chain(self.footer.iter_mut()) {
+            row.remove_cell(index);
+        }
+        let removed = self
+            .rows
+            .iter_mut()
+            .filter(|r| !r.is_group_header())
+            .filter_map(|row| row.remove_cell(index))
+            .collect();
+        self.render = self.render.move_columns(|c| match c.cmp(&index) {
+            Ordering::Less => Some(c),
+            Ordering::Equal => None,
+            Ordering::Greater => Some(c - 1),
+        });
+        self.span_group_headers();
+        removed
+    }
+
+    /// Append a column titled `title`, holding for each row the cell returned by `f`
+    /// given the row.
+    ///
+    /// The column is added after the last column of the table, as counted by
+    /// `column_count()`, so rows shorter than the others are first padded with empty
+    /// cells, once given to `f` as they are. The header rows and footer get an empty cell,
+    /// and so do the other titles if the table had none. Group headers span the new column.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// use prettytable::Cell;
+    /// # fn main() {
+    /// let mut table = table![["db", 3, 4], ["web", 1, 4], ["api"]];
+    /// table.set_titles(row!["Service", "Failed", "Total"]);
+    /// table.add_computed_column("Failed %", |row| {
+    ///     let value = |i: usize| row.get_cell(i)?.get_content().parse::<f64>().ok();
+    ///     match (value(1), value(2)) {
+    ///         (Some(failed), Some(total)) => Cell::from_f64(failed / total * 100.0, 1),
+    ///         _ => Cell::default(),
+    ///     }
+    /// });
+    /// assert_eq!(table[0], ["db", "3", "4", "75.0"]);
+    /// assert_eq!(table[2], ["api", "", "", ""]);
+    /// # }
+    /// ```
+    pub fn add_computed_column<F: Fn(&Row) -> Cell>(&mut self, title: &str, f: F) {
+        let column = self.column_count();
+        add_cell_at(
+            self.titles.get_or_insert_with(Row::empty),
+            column,
+            Cell::new(title),
+        );
+        for row in self.header_rows.iter_mut().chain(self.footer.iter_mut()) {
+            add_cell_at(row, column, Cell::default());
+        }
+        for row in self.rows.iter_mut().filter(|r| !r.is_group_header()) {
+            let cell = f(row);
+            add_cell_at(row, column, cell);
+        }
+        self.span_group_headers();
+    }
+
+    /// Replace each cell of column `column` of the rows by the cell returned by `f` given
+    /// that cell. Rows without a cell in the column and group headers are left unchanged.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// use prettytable::Cell;
+    /// # fn main() {
+    /// let mut table = table![["db", "512"], ["web"]];
+    /// table.map_column(1, |cell| Cell::new(&format!("{} MB", cell.get_content())));
+    /// assert_eq!(table[0], ["db", "512 MB"]);
+    /// assert_eq!(table[1], ["web"]);
+    /// # }
+    /// ```
+    pub fn map_column<F: Fn(&Cell) -> Cell>(&mut self, column: usize, f: F) {
+        for row in self.rows.iter_mut().filter(|r| !r.is_group_header()) {
+            if let Some(cell) = row.get_mut_cell(column) {
+                *cell = f(cell);
+            }
+        }
+    }
+
+    /// Rearrange the columns of the rows, header rows and footer, so that column `i` becomes the
+    /// column `order[i]` of the table.
+    ///
+    /// Columns not listed in `order` are removed, and columns listed several times are
+    /// copied. Cells missing from short rows are replaced with empty cells. The display
+    /// settings of the columns, like alignments, follow them, and group headers span
+    /// the new columns.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut table = table![["a", "b", "c"], ["d"]];
+    /// table.reorder_columns(&[2, 0]);
+    /// assert_eq!(table[0], ["c", "a"]);
+    /// assert_eq!(table[1], ["", "d"]);
+    /// # }
+    /// ```
+    pub fn reorder_columns(&mut self, order: &[usize]) {
+        let reorder = |row: &mut Row| {
+            let cells = order
+                .iter()
+                .map(|&i| row.get_cell(i).cloned().unwrap_or_default())
+                .collect();
+            *row = row.with_cells(cells);
+        };
+        if let Some(titles) = self.titles.as_mut() {
+            reorder(titles);
+        }
+        self.header_rows
+            .iter_mut()
+            .chain(self.footer.iter_mut())
+            .for_each(reorder);
+        self.rows
+            .iter_mut()
+            .filter(|r| !r.is_group_header())
+            .for_each(reorder);
+        self.render = self.render.select_columns(order);
+        self.span_group_headers();
+    }
+}
+
+impl Table {
+    /// Return a copy of this table holding only the columns `columns`, in that order.
+    ///
+    /// Titles and the display settings of the columns, like alignments, follow the selected
+    /// columns. Cells missing from short rows are replaced with empty cells. An error is
+    /// returned if a column is beyond the number of columns of the table.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let table = table![["a", "b", "c"], ["d", "e", "f"]];
+    /// let selected = table.select_columns(&[2, 0]).unwrap();
+    /// assert_eq!(selected[1], ["f", "d"]);
+    /// assert!(table.select_columns(&[3]).is_err());
+    /// # }
+    /// ```
+    pub fn select_columns(&self, columns: &[usize]) -> Result<Table, &str> {
+        let count = column_count(&self.titles, &self.rows);
+        if columns.iter().any(|&c| c >= count) {
+            return Err("Column index out of range");
+        }
+        let mut table = self.clone();
+        table.reorder_columns(columns);
+        Ok(table)
+    }
+
+    /// Return a copy of this table holding only the columns in `columns`.
+    /// See `select_columns()` for details.
+    pub fn column_slice<R: RangeBounds<usize>>(&self, columns: R) -> Result<Table, &str> {
+        let count = column_count(&self.titles, &self.rows);
+        let start = match columns.start_bound() {
+            Bound::Included(&s) => s,
+            Bound::Excluded(&s) => s + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match columns.end_bound() {
+            Bound::Included(&e) => e + 1,
+            Bound::Excluded(&e) => e,
+            Bound::Unbounded => count,
+        };
+        if start > end || end > count {
+            return Err("Column index out of range");
+        }
+        self.select_columns(&(start..end).collect::<Vec<_>>())
+    }
+
+    /// Return a copy of this table with its rows turned into columns, the titles becoming
+    /// the first column. See `transposed_with()` for details.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut record = table![["Alice", 34]];
+    /// record.set_titles(row!["Name", "Age"]);
+    /// let listing = record.transposed();
+    /// assert_eq!(listing[0], ["Name", "Alice"]);
+    /// assert_eq!(listing[1], ["Age", "34"]);
+    /// # }
+    /// ```
+    pub fn transposed(&self) -> Table {
+        self.transposed_with(true)
+    }
+
+    /// Return a copy of this table with its rows turned into columns. If `keep_titles` is
+    /// `true`, the titles become the first column, otherwise they are dropped. The copy
+    /// has no titles.
+    ///
+    /// Short rows are padded with empty cells, so that all the rows of the copy have the
+    /// same length. Cells keep their style and alignment, but no longer span several
+    /// columns. The copy has the same format, but none of the display settings of columns.
+    pub fn transposed_with(&self, keep_titles: bool) -> Table {
+        let titles = self.titles.as_ref().as_ref().filter(|_| keep_titles);
+        let columns: Vec<&Row> = titles.into_iter().chain(&self.rows).collect();
+        let height = columns.iter().map(|r| r.len()).max().unwrap_or(0);
+        let rows = (0..height)
+            .map(|i| {
+                let cells = columns
+                    .iter()
+                    .map(|column| {
+                        let mut cell = column.get_cell(i).cloned().unwrap_or_default();
+                        cell.set_hspan(1);
+                        cell
+                    })
+                    .collect();
+                Row::new(cells)
+            })
+            .collect();
+        let mut table = Table::init(rows);
+        table.set_format(*self.format);
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cell, Row, Table};
+
+    fn titles(table: &Table) -> &Row {
+        table.titles.as_ref().as_ref().unwrap()
+    }
+
+    #[test]
+    fn insert_column() {
+        let mut table = Table::from_rows(vec![vec!["a"], vec![], vec!["b", "c", "d"]]);
+        table.insert_column(
+            2,
+            Some(Cell::new("t")),
+            vec![Cell::new("x"), Cell::new("y")],
+        );
+        assert_eq!(*titles(&table), ["", "", "t"]);
+        assert_eq!(table[0], ["a", "", "x"]);
+        assert_eq!(table[1], ["", "", "y"]);
+        assert_eq!(table[2], ["b", "c", "", "d"]);
+
+        table.insert_column(
+            0,
+            None,
+            vec![
+                Cell::new("1"),
+                Cell::new("2"),
+                Cell::new("3"),
+                Cell::new("4"),
+            ],
+        );
+        assert_eq!(*titles(&table), ["", "", "", "t"]);
+        assert_eq!(table.len(), 4);
+        assert_eq!(table[3], ["4"]);
+
+        let mut table = Table::new();
+        table.insert_column(0, None, vec![Cell::new("a")]);
+        assert!(table.titles.is_none());
+        assert_eq!(table[0], ["a"]);
+    }
+
+    #[test]
+    fn remove_column() {
+        let mut table = Table::from_rows(vec![vec!["a", "b", "c"], vec!["d"], vec!["e", "f"]]);
+        table.set_titles(Row::new(vec![Cell::new("x"), Cell::new("y")]));
+        let removed = table.remove_column(1);
+        assert_eq!(
+            removed.iter().map(Cell::get_content).collect::<Vec<_>>(),
+            ["b", "f"]
+        );
+        assert_eq!(*titles(&table), ["x"]);
+        assert_eq!(table[0], ["a", "c"]);
+        assert_eq!(table[1], ["d"]);
+        assert_eq!(table[2], ["e"]);
+        assert!(table.remove_column(5).is_empty());
+    }
+
+    #[test]
+    fn column_settings() {
+        use crate::format::Alignment;
+
+        let mut table = Table::from_rows(vec![vec!["a", "b", ""], vec!["a", "d", "e"]]);
+        table.set_column_alignment(1, Alignment::RIGHT);
+        table.set_column_placeholder(2, "-");
+        table.set_frozen_columns(2);
+        table.insert_column(1, None, vec![Cell::new("x")]);
+        let rendered = table.rendered();
+        assert_eq!(rendered[0][1].get_align(), Alignment::LEFT);
+        assert_eq!(rendered[0][2].get_align(), Alignment::RIGHT);
+        assert_eq!(rendered[0][3].get_content(), "-");
+        assert_eq!(rendered[1][1].get_content(), "");
+        assert_eq!(table.render.frozen_columns(), 3);
+
+        table.remove_column(2);
+        let rendered = table.rendered();
+        assert_eq!(rendered[0][1].get_align(), Alignment::LEFT);
+        assert_eq!(rendered[0][2].get_content(), "-");
+        assert_eq!(table.render.frozen_columns(), 2);
+
+        table.reorder_columns(&[2, 0]);
+        assert_eq!(table.rendered()[0], ["-", "a"]);
+
+        let mut table = Table::from_rows(vec![vec!["a", "b"], vec!["c", "d"]]);
+        table.group_by(0, false);
+        table.insert_column(1, Some(Cell::new("t")), vec![Cell::new("x")]);
+        assert_eq!(table[0].len(), 1);
+        assert_eq!(table[0][0].get_hspan(), 3);
+        assert_eq!(table[1], ["a", "x", "b"]);
+        table.remove_column(0);
+        assert_eq!(table[0][0].get_content(), "a");
+        assert_eq!(table[0][0].get_hspan(), 2);
+        table.reorder_columns(&[1]);
+        assert_eq!(table[2][0].get_content(), "c");
+        assert_eq!(table[2][0].get_hspan(), 1);
+        assert_eq!(table[3], ["d"]);
+    }
+
+    #[test]
+    fn transpose() {
+        let mut table = Table::from_rows(vec![vec!["a", "b", "c"], vec!["d"]]);
+        table.set_titles(Row::new(vec![Cell::new("x"), Cell::new("y")]));
+        table[0][1] = Cell::new("b").style_spec("rb").with_hspan(2);
+        table.set_format(*crate::format::consts::FORMAT_CLEAN);
+        let transposed = table.transposed();
+        assert!(transposed.titles.is_none());
+        assert_eq!(transposed.len(), 3);
+        assert_eq!(transposed[0], ["x", "a", "d"]);
+        assert_eq!(transposed[1], ["y", "b", ""]);
+        assert_eq!(transposed[2], ["", "c", ""]);
+        assert_eq!(
+            transposed[1][1].get_align(),
+            crate::format::Alignment::RIGHT
+        );
+        assert_eq!(transposed[1][1].get_style(), &[crate::Attr::Bold]);
+        assert_eq!(transposed[1][1].get_hspan(), 1);
+        assert_eq!(*transposed.format, *table.format);
+
+        let data = table.transposed_with(false);
+        assert_eq!(data[0], ["a", "d"]);
+        assert_eq!(data.transposed_with(true)[0], ["a", "b", "c"]);
+        assert!(Table::new().transposed().is_empty());
+    }
+
+    #[test]
+    fn select_columns() {
+        use crate::format::Alignment;
+
+        let mut table = Table::from_rows(vec![vec!["a", "b", "c"], vec!["d"]]);
+        table.set_titles(Row::new(vec![Cell::new("x"), Cell::new("y")]));
+        table.set_max_column_width(2, 3);
+        table.set_column_alignment(2, Alignment::RIGHT);
+        table.set_column_placeholder(0, "-");
+        let selected = table.select_columns(&[2, 0]).unwrap();
+        assert_eq!(*titles(&selected), ["", "x"]);
+        assert_eq!(selected[0], ["c", "a"]);
+        assert_eq!(selected[1], ["", "d"]);
+        let rendered = selected.rendered();
+        assert_eq!(rendered[0][0].get_align(), Alignment::RIGHT);
+        assert_eq!(rendered[1][0].get_content(), "");
+        assert_eq!(rendered[1][1].get_content(), "d");
+
+        let sliced = table.column_slice(1..).unwrap();
+        assert_eq!(sliced[0], ["b", "c"]);
+        assert_eq!(sliced[1], ["", ""]);
+        assert_eq!(sliced.rendered()[1][1].get_align(), Alignment::RIGHT);
+        assert_eq!(table.column_slice(..=0).unwrap()[0], ["a"]);
+        assert_eq!(table.column_slice(3..).unwrap()[0], [""; 0]);
+        assert!(table.column_slice(..4).is_err());
+        assert!(table.select_columns(&[0, 3]).is_err());
+    }
+
+    #[test]
+    fn reorder_columns() {
+        let mut table = Table::from_rows(vec![vec!["a", "b", "c"], vec!["d"]]);
+        table.set_titles(Row::new(vec![
+            Cell::new("x"),
+            Cell::new("y"),
+            Cell::new("z"),
+        ]));
+        table[0][1].style(crate::Attr::Bold);
+        table.reorder_columns(&[1, 2, 1]);
+        assert_eq!(*titles(&table), ["y", "z", "y"]);
+        assert_eq!(table[0], ["b", "c", "b"]);
+        assert_eq!(table[0][2].get_style(), &[crate::Attr::Bold]);
+        assert_eq!(table[1], ["", "", ""]);
+    }
+
+    #[test]
+    fn computed_column() {
+        let mut table = Table::from_rows(vec![vec!["a", "1"], vec!["b"]]);
+        let mut spanning = Row::new(vec![Cell::new("c").with_hspan(2)]);
+        spanning.add_cell(Cell::new("2"));
+        table.add_row(spanning);
+        table.group_by(0, false);
+        table.set_footer(Row::new(vec![Cell::new("total")]));
+        table.add_computed_column("len", |row| Cell::from(row.len()));
+        assert_eq!(*titles(&table), ["", "", "", "len"]);
+        let rows = table.row_iter().filter(|r| !r.is_group_header());
+        let rows = rows.map(|r| r.plain_text()).collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            [
+                vec!["a", "1", "", "2"],
+                vec!["b", "", "", "1"],
+                vec!["c", "2", "2"]
+            ]
+        );
+        assert_eq!(table.get_footer().unwrap(), &["total", "", "", ""]);
+        assert_eq!(table[0].len(), 1);
+        assert_eq!(table[0][0].get_hspan(), 4);
+        let out = table.to_string().replace("\r\n", "\n");
+        assert!(out.contains("\n| a                   |\n"));
+
+        table.map_column(3, |cell| Cell::new(&format!("{}!", cell.get_content())));
+        assert_eq!(table[1], ["a", "1", "", "2!"]);
+        assert_eq!(table[5], ["c", "2", "2"]);
+        assert_eq!(table[0].len(), 1);
+    }
+}
@@ -2,11 +2,178 @@
 
 use csv;
 
-pub use self::csv::{Reader, ReaderBuilder, Result, Writer};
-use crate::AsTableSlice;
+pub use self::csv::{QuoteStyle, Reader, ReaderBuilder, Result, Trim, Writer, WriterBuilder};
+use crate::{AsTableSlice, GroupHeaders};
 use std::io::{Read, Write};
 use std::path::Path;
 
+/// Turn a CSV record into a row
+fn record_row(record: csv::StringRecord) -> super::Row {
+    super::Row::new(record.into_iter().map(super::Cell::new).collect())
+}
+
+/// Characters of the 0x80..=0x9F range in Windows-1252. Undefined bytes map to
+/// the matching C1 control character, as in Latin-1
+#[cfg(feature = "encoding")]
+const WINDOWS_1252: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8d}', 'Ž', '\u{8f}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9d}', 'ž', 'Ÿ',
+];
+
+/// Decode `bytes` as UTF-8, falling back to Windows-1252 if it is not valid UTF-8
+#[cfg(feature = "encoding")]
+fn decode_field(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(s) => s.to_string(),
+        Err(_) => bytes
+            .iter()
+            .map(|&b| match b {
+                0x80..=0x9F => WINDOWS_1252[usize::from(b - 0x80)],
+                _ => char::from(b),
+            })
+            .collect(),
+    }
+}
+
+/// Iterator lazily reading rows from a CSV reader, created with `Table::csv_rows()`
+pub struct CsvRows<R> {
+    records: csv::StringRecordsIntoIter<R>,
+}
+
+impl<R: Read> Iterator for CsvRows<R> {
+    type Item = Result<super::Row>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.records.next().map(|record| record.map(record_row))
+    }
+}
+
+/// Options controlling how CSV data is read into a table
+///
+/// # Examples
+/// ```rust
+/// use prettytable::csv::CsvReadOptions;
+/// use prettytable::Table;
+///
+/// let options = CsvReadOptions::new().delimiter(b';').quote(b'\'').has_headers(true);
+/// let table = Table::from_reader_with("a;'b;c'\n1;2\n".as_bytes(), &options).unwrap();
+/// assert_eq!(table.len(), 1);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct CsvReadOptions {
+    /// Use the first record as the table titles. Disabled by default
+    pub has_headers: bool,
+    /// Field delimiter. Defaults to `,`
+    pub delimiter: u8,
+    /// Trim leading and trailing whitespaces from fields. Disabled by default
+    pub trim: bool,
+    /// Quote character. Defaults to `"`
+    pub quote: u8,
+    /// Escape character for quotes inside quoted fields. When unset, quotes are escaped
+    /// by doubling them. Unset by default
+    pub escape: Option<u8>,
+    /// Accept records with differing numbers of fields, padding the shorter ones
+    /// with empty cells. When disabled, such records are an error. Disabled by default
+    pub flexible: bool,
+}
+
+impl Default for CsvReadOptions {
+    fn default() -> CsvReadOptions {
+        CsvReadOptions {
+            has_headers: false,
+            delimiter: b',',
+            trim: false,
+            quote: b'"',
+            escape: None,
+            flexible: false,
+        }
+    }
+}
+
+impl CsvReadOptions {
+    /// Create options with default values
+    pub fn new() -> CsvReadOptions {
+        CsvReadOptions::default()
+    }
+
+    /// Use the first record as the table titles
+    pub fn has_headers(mut self, yes: bool) -> Self {
+        self.has_headers = yes;
+        self
+    }
+
+    /// Set the field delimiter
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Trim leading and trailing whitespaces from fields
+    pub fn trim(mut self, yes: bool) -> Self {
+        self.trim = yes;
+        self
+    }
+
+    /// Set the quote character
+    pub fn quote(mut self, quote: u8) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Set the escape character for quotes inside quoted fields
+    pub fn escape(mut self, escape: Option<u8>) -> Self {
+        self.escape = escape;
+        self
+    }
+
+    /// Accept records with differing numbers of fields, padding the shorter ones
+    pub fn flexible(mut self, yes: bool) -> Self {
+        self.flexible = yes;
+        self
+    }
+
+    /// Create a `ReaderBuilder` following these options. Headers are always read as
+    /// a record, so they can be turned into titles
+    fn reader_builder(&self) -> ReaderBuilder {
+        let mut builder = ReaderBuilder::new();
+        builder
+            .has_headers(false)
+            .delimiter(self.delimiter)
+            .trim(if self.trim { Trim::All } else { Trim::None })
+            .quote(self.quote)
+            .escape(self.escape)
+            .double_quote(self.escape.is_none())
+            .flexible(self.flexible);
+        builder
+    }
+}
+
+/// Split `line` into the fields of a single CSV record, or return `None`
+/// if it cannot be read as one
+pub(crate) fn split_record(line: &str) -> Option<csv::StringRecord> {
+    let mut reader = ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(line.as_bytes());
+    let record = reader.records().next()?.ok()?;
+    // Several lines are several records, which do not make a single row
+    match reader.records().next() {
+        None => Some(record),
+        Some(_) => None,
+    }
+}
+
+/// Return the fields of the CSV record for `row`, with empty fields
+/// filling the columns covered by spanning cells
+fn record_fields(row: &super::Row) -> Vec<String> {
+    let mut fields = Vec::with_capacity(row.column_count());
+    for cell in row {
+        fields.push(cell.get_content());
+        fields.resize(fields.len() + cell.get_hspan() - 1, String::new());
+    }
+    fields
+}
+
 impl<'a> super::TableSlice<'a> {
     /// Write the table to the specified writer.
     pub fn to_csv<W: Write>(&self, w: W) -> Result<Writer<W>> {
@@ -15,18 +182,113 @@ impl<'a> super::TableSlice<'a> {
 
     /// Write the table to the specified writer.
     ///
-    /// This allows for format customisation.
-    pub fn to_csv_writer<W: Write>(&self, mut writer: Writer<W>) -> Result<Writer<W>> {
-        for title in self.titles {
-            writer.write_record(title.iter().map(|c| c.get_content()))?;
+    /// This allows for format customisation. A cell spanning `n` columns is written as
+    /// its content followed by `n - 1` empty fields. Group headers are skipped.
+    /// Row numbers are written as the first field when set to be exported, see `RowNumbers`.
+    pub fn to_csv_writer<W: Write>(&self, writer: Writer<W>) -> Result<Writer<W>> {
+        self.to_csv_writer_with_groups(writer, GroupHeaders::Skip)
+    }
+
+    /// Write the table to the specified writer, like `to_csv_writer()`, with group
+    /// headers written as set by `headers`.
+    ///
+    /// With `GroupHeaders::Column`, titles and rows before the first group start with an
+    /// empty field.
+    pub fn to_csv_writer_with_groups<W: Write>(
+        &self,
+        writer: Writer<W>,
+        headers: GroupHeaders,
+    ) -> Result<Writer<W>> {
+        self.write_csv_records(writer, headers, false)
+    }
+
+    /// Write the table to the specified writer, like `to_csv_writer()`, with the footer
+    /// written as the last record if `footer` is `true` and the table has one.
+    pub fn to_csv_writer_with_footer<W: Write>(
+        &self,
+        writer: Writer<W>,
+        footer: bool,
+    ) -> Result<Writer<W>> {
+        self.write_csv_records(writer, GroupHeaders::Skip, footer)
+    }
+
+    /// Write the table to `writer`, with group headers written as set by `headers`,
+    /// and the footer if `footer` is `true`
+    fn write_csv_records<W: Write>(
+        &self,
+        mut writer: Writer<W>,
+        headers: GroupHeaders,
+        footer: bool,
+    ) -> Result<Writer<W>> {
+        let group_column = headers == GroupHeaders::Column;
+        let mut group = String::new();
+        let numbers = self.render.numbered(false);
+        let positions = self.row_positions();
+        let mut numbered = 0;
+        if let Some(title) = self.titles {
+            let mut fields = record_fields(title);
+            if let Some(numbers) = numbers {
+                fields.insert(0, numbers.header.clone());
+            }
+            if group_column {
+                fields.insert(0, String::new());
+            }
+            writer.write_record(fields)?;
+        }
+        for (i, row) in self.rows.iter().enumerate() {
+            if row.is_group_header() {
+                group = row.get_cell(0).map_or(String::new(), |c| c.get_content());
+                continue;
+            }
+            let mut fields = record_fields(row);
+            if let Some(numbers) = numbers {
+                let position = positions.get(i).copied().unwrap_or(i);
+                fields.insert(0, numbers.number(numbered, position).to_string());
+                numbered += 1;
+            }
+            if group_column {
+                fields.insert(0, group.clone());
+            }
+            writer.write_record(fields)?;
         }
-        for row in self.rows {
-            writer.write_record(row.iter().map(|c| c.get_content()))?;
+        if let Some(row) = self.footer.as_ref().filter(|_| footer) {
+            let mut fields = record_fields(row);
+            if numbers.is_some() {
+                fields.insert(0, String::new());
+            }
+            if group_column {
+                fields.insert(0, String::new());
+            }
+            writer.write_record(fields)?;
         }
 
         writer.flush()?;
         Ok(writer)
     }
+
+    /// Write the table to the specified writer, using tabulations as field delimiter.
+    pub fn to_tsv<W: Write>(&self, w: W) -> Result<Writer<W>> {
+        self.write_delimited(w, b'\t', QuoteStyle::Necessary)
+    }
+
+    /// Write the table to the specified writer, using `delimiter` as field delimiter
+    /// and `quote_style` to decide which fields get quoted.
+    ///
+    /// With `QuoteStyle::Necessary`, only fields containing the delimiter, a quote
+    /// or a newline are quoted.
+    pub fn write_delimited<W: Write>(
+        &self,
+        w: W,
+        delimiter: u8,
+        quote_style: QuoteStyle,
+    ) -> Result<Writer<W>> {
+        self.to_csv_writer(
+            WriterBuilder::new()
+                .delimiter(delimiter)
+                .quote_style(quote_style)
+                .from_writer(w),
+        )
+    }
 }
 
 impl super::Table {
@@ -50,18 +312,100 @@ impl super::Table {
         ))
     }
 
+    /// Create a table from a CSV string, following `options`
+    pub fn from_csv_string_with(csv_s: &str, options: &CsvReadOptions) -> Result<Self> {
+        Self::from_reader_with(csv_s.as_bytes(), options)
+    }
+
+    /// Create a table from a CSV file, following `options`
+    pub fn from_csv_file_with<P: AsRef<Path>>(csv_p: P, options: &CsvReadOptions) -> Result<Self> {
+        Self::from_csv_records(&mut options.reader_builder().from_path(csv_p)?, options)
+    }
+
+    /// Create a table from CSV data read from `reader`, following `options`
+    pub fn from_reader_with<R: Read>(reader: R, options: &CsvReadOptions) -> Result<Self> {
+        Self::from_csv_records(&mut options.reader_builder().from_reader(reader), options)
+    }
+
+    /// Create a table from the records of `reader`, using the first one as titles
+    /// if `has_headers` is set in `options`
+    fn from_csv_records<R: Read>(reader: &mut Reader<R>, options: &CsvReadOptions) -> Result<Self> {
+        let mut rows = Vec::new();
+        for record in reader.records() {
+            rows.push(record_row(record?));
+        }
+        if options.flexible {
+            let col_num = rows.iter().map(super::Row::len).max().unwrap_or(0);
+            for row in &mut rows {
+                while row.len() < col_num {
+                    row.add_cell(super::Cell::default());
+                }
+            }
+        }
+        let mut table = Self::new();
+        if options.has_headers && !rows.is_empty() {
+            table.set_titles(rows.remove(0));
+        }
+        table.rows = rows;
+        Ok(table)
+    }
+
+    /// Return an iterator lazily reading the records of `reader` as rows, without
+    /// loading the whole data in memory.
+    ///
+    /// Each item is either a row or the error for that record, so bad records
+    /// can be skipped.
+    pub fn csv_rows<R: Read>(reader: Reader<R>) -> CsvRows<R> {
+        CsvRows {
+            records: reader.into_records(),
+        }
+    }
+
+    /// Append the records of `reader` to the table as rows, stopping after `limit` records
+    /// if set. Return the number of rows added, or the first error encountered.
+    pub fn extend_from_csv<R: Read>(
+        &mut self,
+        reader: &mut Reader<R>,
+        limit: Option<usize>,
+    ) -> Result<usize> {
+        let mut count = 0;
+        for record in reader.records().take(limit.unwrap_or(usize::MAX)) {
+            self.add_row(record_row(record?));
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Create a table from CSV data read from any reader, such as `std::io::stdin()`.
+    ///
+    /// With the `encoding` feature, fields which are not valid UTF-8 are decoded as
+    /// Windows-1252 (a superset of Latin-1) instead of failing.
+    pub fn from_csv_reader<R: Read>(r: R) -> Result<Self> {
+        let mut reader = ReaderBuilder::new().has_headers(false).from_reader(r);
+        #[cfg(feature = "encoding")]
+        {
+            let mut table = Self::new();
+            for record in reader.byte_records() {
+                table.add_row(super::Row::new(
+                    record?
+                        .iter()
+                        .map(|f| super::Cell::new(&decode_field(f)))
+                        .collect(),
+                ));
+            }
+            Ok(table)
+        }
+        #[cfg(not(feature = "encoding"))]
+        Self::from_csv_records(&mut reader, &CsvReadOptions::default())
+    }
+
     /// Create a table from a CSV reader
     pub fn from_csv<R: Read>(reader: &mut Reader<R>) -> Self {
         Self::init(
             reader
                 .records()
                 .map(|row| {
-                    super::Row::new(
-                        row.unwrap()
-                            .into_iter()
-                            .map(super::Cell::new)
-                            .collect(),
-                    )
+                    super::Row::new(row.unwrap().into_iter().map(super::Cell::new).collect())
                 })
                 .collect(),
         )
@@ -78,11 +422,48 @@ impl super::Table {
     pub fn to_csv_writer<W: Write>(&self, writer: Writer<W>) -> Result<Writer<W>> {
         self.as_slice().to_csv_writer(writer)
     }
+
+    /// Write the table to the specified writer, with group headers written as set
+    /// by `headers`. See `TableSlice::to_csv_writer_with_groups()` for details.
+    pub fn to_csv_writer_with_groups<W: Write>(
+        &self,
+        writer: Writer<W>,
+        headers: GroupHeaders,
+    ) -> Result<Writer<W>> {
+        self.as_slice().to_csv_writer_with_groups(writer, headers)
+    }
+
+    /// Write the table to the specified writer, with the footer written as the last
+    /// record if `footer` is `true`. See `TableSlice::to_csv_writer_with_footer()`.
+    pub fn to_csv_writer_with_footer<W: Write>(
+        &self,
+        writer: Writer<W>,
+        footer: bool,
+    ) -> Result<Writer<W>> {
+        self.as_slice().to_csv_writer_with_footer(writer, footer)
+    }
+
+    /// Write the table to the specified writer, using tabulations as field delimiter.
+    pub fn to_tsv<W: Write>(&self, w: W) -> Result<Writer<W>> {
+        self.as_slice().to_tsv(w)
+    }
+
+    /// Write the table to the specified writer, using `delimiter` as field delimiter
+    /// and `quote_style` to decide which fields get quoted.
+    pub fn write_delimited<W: Write>(
+        &self,
+        w: W,
+        delimiter: u8,
+        quote_style: QuoteStyle,
+    ) -> Result<Writer<W>> {
+        self.as_slice().write_delimited(w, delimiter, quote_style)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Cell, Row, Table};
+    use super::{CsvReadOptions, QuoteStyle, ReaderBuilder, Writer};
+    use crate::{Cell, GroupHeaders, Row, Table};
 
     static CSV_S: &str = "ABC,DEFG,HIJKLMN\n\
                         foobar,bar,foo\n\
@@ -134,6 +515,201 @@ mod tests {
         );
     }
 
+    #[test]
+    fn to_with_hspan() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("Q1 results").with_hspan(3)]));
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("b").with_hspan(2)]));
+        let csv = table.to_csv(Vec::new()).unwrap().into_inner().unwrap();
+        assert_eq!(String::from_utf8(csv).unwrap(), "Q1 results,,\na,b,\n");
+    }
+
+    #[test]
+    fn to_with_footer() {
+        let mut table = test_table();
+        table.set_footer(Row::new(vec![
+            Cell::new("Total").with_hspan(2),
+            Cell::new("3"),
+        ]));
+        let csv = |footer| {
+            let writer = table.to_csv_writer_with_footer(Writer::from_writer(Vec::new()), footer);
+            String::from_utf8(writer.unwrap().into_inner().unwrap()).unwrap()
+        };
+        assert!(csv(true).ends_with("Total,,3\n"));
+        assert_eq!(
+            csv(false),
+            String::from_utf8(table.to_csv(Vec::new()).unwrap().into_inner().unwrap()).unwrap()
+        );
+        assert!(!csv(false).contains("Total"));
+    }
+
+    #[test]
+    fn to_tsv() {
+        let mut table = test_table();
+        table.set_titles(Row::new(vec![
+            Cell::new("t\t1"),
+            Cell::new("t,2"),
+            Cell::new("t3"),
+        ]));
+        assert_eq!(
+            String::from_utf8(table.to_tsv(Vec::new()).unwrap().into_inner().unwrap()).unwrap(),
+            "\"t\t1\"\tt,2\tt3\n\
+             ABC\tDEFG\tHIJKLMN\n\
+             foobar\tbar\tfoo\n\
+             foobar2\tbar2\tfoo2\n"
+        );
+    }
+
+    #[test]
+    fn write_delimited() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![
+            Cell::new("a;b"),
+            Cell::new("c,d"),
+            Cell::new("e\"f"),
+        ]));
+        let out = table
+            .write_delimited(Vec::new(), b';', QuoteStyle::Necessary)
+            .unwrap()
+            .into_inner()
+            .unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\"a;b\";c,d;\"e\"\"f\"\n");
+        let out = table
+            .write_delimited(Vec::new(), b';', QuoteStyle::Always)
+            .unwrap()
+            .into_inner()
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\"a;b\";\"c,d\";\"e\"\"f\"\n"
+        );
+    }
+
+    #[test]
+    fn from_with_titles() {
+        let options = CsvReadOptions {
+            has_headers: true,
+            ..CsvReadOptions::default()
+        };
+        let table = Table::from_csv_string_with(CSV_S, &options).unwrap();
+        assert_eq!(table.len(), 2);
+        assert_eq!(
+            table
+                .titles
+                .as_ref()
+                .as_ref()
+                .unwrap()
+                .get_cell(1)
+                .unwrap()
+                .get_content(),
+            "DEFG"
+        );
+        let out = table.to_csv(Vec::new()).unwrap().into_inner().unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), CSV_S);
+
+        let options = CsvReadOptions {
+            delimiter: b';',
+            trim: true,
+            ..CsvReadOptions::default()
+        };
+        let table = Table::from_csv_string_with(" a ; b\nc;d \n", &options).unwrap();
+        assert!(table.titles.is_none());
+        assert_eq!(
+            table.get_row(0).unwrap().get_cell(0).unwrap().get_content(),
+            "a"
+        );
+        assert_eq!(
+            table.get_row(1).unwrap().get_cell(1).unwrap().get_content(),
+            "d"
+        );
+    }
+
+    #[test]
+    fn from_reader_with() {
+        let options = CsvReadOptions::new().delimiter(b';').quote(b'\'');
+        let table = Table::from_reader_with("'a;b';'c''d'\ne\n".as_bytes(), &options);
+        assert!(table.is_err());
+
+        let table =
+            Table::from_reader_with("'a;b';'c''d'\ne\n".as_bytes(), &options.flexible(true))
+                .unwrap();
+        assert_eq!(
+            table.get_row(0).unwrap().get_cell(0).unwrap().get_content(),
+            "a;b"
+        );
+        assert_eq!(
+            table.get_row(0).unwrap().get_cell(1).unwrap().get_content(),
+            "c'd"
+        );
+        assert_eq!(table.get_row(1).unwrap().len(), 2);
+
+        let options = CsvReadOptions::new().escape(Some(b'\\'));
+        let table = Table::from_reader_with(r#""a\"b",c"#.as_bytes(), &options).unwrap();
+        assert_eq!(
+            table.get_row(0).unwrap().get_cell(0).unwrap().get_content(),
+            "a\"b"
+        );
+    }
+
+    #[test]
+    fn csv_rows() {
+        let reader = ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader("a,b\nc\nd,e\n".as_bytes());
+        let rows: Vec<_> = Table::csv_rows(reader).collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(
+            rows[0].as_ref().unwrap().get_cell(1).unwrap().get_content(),
+            "b"
+        );
+        assert!(rows[1].is_err());
+        assert_eq!(
+            rows[2].as_ref().unwrap().get_cell(0).unwrap().get_content(),
+            "d"
+        );
+    }
+
+    #[test]
+    fn extend_from_csv() {
+        let mut table = test_table();
+        let mut reader = ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(CSV_S.as_bytes());
+        assert_eq!(table.extend_from_csv(&mut reader, Some(2)).unwrap(), 2);
+        assert_eq!(table.len(), 5);
+        assert_eq!(
+            table.get_row(4).unwrap().get_cell(0).unwrap().get_content(),
+            "foobar"
+        );
+        assert_eq!(table.extend_from_csv(&mut reader, None).unwrap(), 1);
+        assert_eq!(table.len(), 6);
+    }
+
+    #[test]
+    fn from_csv_reader() {
+        let table = Table::from_csv_reader(std::io::Cursor::new(CSV_S)).unwrap();
+        assert_eq!(table.to_string(), test_table().to_string());
+    }
+
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn from_csv_reader_latin1() {
+        let bytes = std::io::Cursor::new(b"caf\xE9,\x80 5\nna\xC3\xAFve,x\n".to_vec());
+        let table = Table::from_csv_reader(bytes).unwrap();
+        assert_eq!(
+            table.get_row(0).unwrap().get_cell(0).unwrap().get_content(),
+            "café"
+        );
+        assert_eq!(
+            table.get_row(0).unwrap().get_cell(1).unwrap().get_content(),
+            "€ 5"
+        );
+        assert_eq!(
+            table.get_row(1).unwrap().get_cell(0).unwrap().get_content(),
+            "naïve"
+        );
+    }
+
     #[test]
     fn trans() {
         assert_eq!(
@@ -154,6 +730,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn group_headers() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("item")]));
+        table.add_row(Row::new(vec![Cell::new("loose")]));
+        table.add_group("g1", vec![Row::new(vec![Cell::new("a")])]);
+        table.add_group("g,2", vec![Row::new(vec![Cell::new("b")])]);
+        let skip = table.to_csv(Vec::new()).unwrap().into_inner().unwrap();
+        assert_eq!(String::from_utf8(skip).unwrap(), "item\nloose\na\nb\n");
+        let column = table
+            .to_csv_writer_with_groups(Writer::from_writer(Vec::new()), GroupHeaders::Column)
+            .unwrap()
+            .into_inner()
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(column).unwrap(),
+            ",item\n,loose\ng1,a\n\"g,2\",b\n"
+        );
+    }
+
     #[test]
     fn extend_table() {
         let mut table = Table::new();
@@ -0,0 +1,129 @@
+//! Detection and removal of duplicate rows
+
+use std::collections::{HashMap, HashSet};
+
+use super::utils::strip_ansi;
+use super::{Row, Table};
+
+impl Row {
+    /// Get the content of each cell, without the ANSI escape sequences.
+    ///
+    /// Rows are compared on this text when looking for duplicates, so that rows only
+    /// differing by their style are considered identical.
+    pub fn plain_text(&self) -> Vec<String> {
+        self.iter()
+            .map(|cell| strip_ansi(&cell.get_content()))
+            .collect()
+    }
+}
+
+impl Table {
+    /// Remove consecutive rows with the same text as the row before them.
+    /// See `Row::plain_text()` for how rows are compared.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut table = table![["a"], ["a"], ["b"], ["a"]];
+    /// table.dedup();
+    /// assert_eq!(table.len(), 3);
+    /// # }
+    /// ```
+    pub fn dedup(&mut self) {
+        let mut previous: Option<Vec<String>> = None;
+        self.rows.retain(|row| {
+            let text = row.plain_text();
+            let duplicate = previous.as_ref() == Some(&text);
+            previous = Some(text);
+            !duplicate
+        });
+    }
+
+    /// Keep only the first row for each distinct text in column `column`,
+    /// without the ANSI escape sequences. Rows without a cell in `column`
+    /// are all kept. Titles are not affected.
+    pub fn dedup_by_column(&mut self, column: usize) {
+        let mut seen = HashSet::new();
+        self.rows.retain(|row| match row.get_cell(column) {
+            Some(cell) => seen.insert(strip_ansi(&cell.get_content())),
+            None => true,
+        });
+    }
+
+    /// Count the rows with each distinct text, as given by `Row::plain_text()`.
+    /// Titles are not counted.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let table = table![["a", 1], ["b", 2], ["a", 1]];
+    /// let counts = table.count_duplicates();
+    /// assert_eq!(counts[&vec!["a".to_string(), "1".to_string()]], 2);
+    /// assert_eq!(counts.len(), 2);
+    /// # }
+    /// ```
+    pub fn count_duplicates(&self) -> HashMap<Vec<String>, usize> {
+        let mut counts = HashMap::new();
+        for row in &self.rows {
+            *counts.entry(row.plain_text()).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cell, Row, Table};
+
+    fn table() -> Table {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("1")]));
+        table.add_row(Row::new(vec![
+            Cell::new("\x1B[31ma\x1B[0m"),
+            Cell::new("1").style_spec("b"),
+        ]));
+        table.add_row(Row::new(vec![Cell::new("b"), Cell::new("1")]));
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("2")]));
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("1")]));
+        table.add_row(Row::new(vec![]));
+        table.add_row(Row::new(vec![]));
+        table
+    }
+
+    #[test]
+    fn dedup() {
+        let mut table = table();
+        assert_eq!(table[1].plain_text(), vec!["a", "1"]);
+        table.dedup();
+        let rows = table.row_iter().map(Row::plain_text).collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            vec![
+                vec!["a", "1"],
+                vec!["b", "1"],
+                vec!["a", "2"],
+                vec!["a", "1"],
+                vec![]
+            ]
+        );
+    }
+
+    #[test]
+    fn dedup_by_column() {
+        let mut table = table();
+        table.dedup_by_column(1);
+        let rows = table.row_iter().map(Row::plain_text).collect::<Vec<_>>();
+        assert_eq!(rows, vec![vec!["a", "1"], vec!["a", "2"], vec![], vec![]]);
+    }
+
+    #[test]
+    fn count_duplicates() {
+        let counts = table().count_duplicates();
+        assert_eq!(counts.len(), 4);
+        assert_eq!(counts[&vec!["a".to_string(), "1".to_string()]], 3);
+        assert_eq!(counts[&vec!["b".to_string(), "1".to_string()]], 1);
+        assert_eq!(counts[&Vec::new()], 2);
+    }
+}
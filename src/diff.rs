@@ -0,0 +1,210 @@
+//! Comparison of two tables, highlighting the changes between them
+
+use std::collections::HashMap;
+
+use super::utils::strip_ansi;
+use super::{color, Attr, Cell, Row, Table};
+
+/// Count of rows added, removed and changed between two tables, as found by `Table::diff()`
+#[derive(Clone, Debug, Copy, Hash, PartialEq, Eq, Default)]
+pub struct DiffStats {
+    /// Rows only found in the new table
+    pub added: usize,
+    /// Rows only found in the old table
+    pub removed: usize,
+    /// Rows found in both tables, with different contents
+    pub changed: usize,
+}
+
+/// Return a copy of `row` with every cell printed in `color`
+fn colored(row: &Row, color: color::Color) -> Row {
+    let cells = row
+        .iter()
+        .map(|cell| cell.clone().with_style(Attr::ForegroundColor(color)))
+        .collect();
+    row.with_cells(cells)
+}
+
+/// Return the row showing the changes from `old` to `new`, and whether there are any.
+/// Changed cells read `old → new`, in yellow.
+fn diff_row(old: &Row, new: &Row) -> (Row, bool) {
+    let mut changed = false;
+    let cells = (0..old.len().max(new.len()))
+        .map(|i| {
+            let (a, b) = (old.get_cell(i), new.get_cell(i));
+            let text = |c: Option<&Cell>| c.map_or(String::new(), |c| strip_ansi(&c.get_content()));
+            let (before, after) = (text(a), text(b));
+            if before == after {
+                return b.or(a).cloned().unwrap_or_default();
+            }
+            changed = true;
+            Cell::new(&format!("{} → {}", before, after))
+                .with_style(Attr::ForegroundColor(color::YELLOW))
+        })
+        .collect();
+    (new.with_cells(cells), changed)
+}
+
+impl Table {
+    /// Compare this table with `other`, its new version, matching the rows by index.
+    ///
+    /// Return a table holding the rows of both tables, with the rows only in `other`
+    /// printed in green, the rows only in this table in red, and the changed cells of
+    /// the other rows showing `old → new` in yellow. Contents are compared without
+    /// their ANSI escape sequences. The result has the format and titles of this table,
+    /// or the titles of `other` if this one has none.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let before = table![["a", 1], ["b", 2]];
+    /// let after = table![["a", 1], ["b", 3], ["c", 4]];
+    /// let (diff, stats) = before.diff(&after);
+    /// assert_eq!(diff[1], ["b", "2 → 3"]);
+    /// assert_eq!((stats.added, stats.removed, stats.changed), (1, 0, 1));
+    /// # }
+    /// ```
+    pub fn diff(&self, other: &Table) -> (Table, DiffStats) {
+        let mut stats = DiffStats::default();
+        let mut rows = Vec::new();
+        for i in 0..self.rows.len().max(other.rows.len()) {
+            rows.push(match (self.rows.get(i), other.rows.get(i)) {
+                (Some(old), Some(new)) => {
+                    let (row, changed) = diff_row(old, new);
+                    stats.changed += usize::from(changed);
+                    row
+                }
+                (Some(old), None) => {
+                    stats.removed += 1;
+                    colored(old, color::RED)
+                }
+                (None, Some(new)) => {
+                    stats.added += 1;
+                    colored(new, color::GREEN)
+                }
+                (None, None) => unreachable!(),
+            });
+        }
+        (self.diff_table(other, rows), stats)
+    }
+
+    /// Compare this table with `other`, like `diff()`, matching the rows by the content
+    /// of their cell in column `key` rather than by index.
+    ///
+    /// Rows are kept in the order of this table, the rows found only in `other` being
+    /// added at the end. When several rows have the same key, they are matched in order.
+    pub fn diff_by_key(&self, other: &Table, key: usize) -> (Table, DiffStats) {
+        let key_of = |row: &Row| row.get_cell(key).map(|c| strip_ansi(&c.get_content()));
+        let mut unmatched: HashMap<Option<String>, Vec<usize>> = HashMap::new();
+        for (i, row) in other.rows.iter().enumerate().rev() {
+            unmatched.entry(key_of(row)).or_default().push(i);
+        }
+        let mut stats = DiffStats::default();
+        let mut matched = vec![false; other.rows.len()];
+        let mut rows = Vec::new();
+        for old in &self.rows {
+            match unmatched.get_mut(&key_of(old)).and_then(Vec::pop) {
+                Some(i) => {
+                    matched[i] = true;
+                    let (row, changed) = diff_row(old, &other.rows[i]);
+                    stats.changed += usize::from(changed);
+                    rows.push(row);
+                }
+                None => {
+                    stats.removed += 1;
+                    rows.push(colored(old, color::RED));
+                }
+            }
+        }
+        for (new, _) in other.rows.iter().zip(matched).filter(|&(_, m)| !m) {
+            stats.added += 1;
+            rows.push(colored(new, color::GREEN));
+        }
+        (self.diff_table(other, rows), stats)
+    }
+
+    /// Return a table holding `rows`, with the format of this table and the titles of
+    /// this table or `other`
+    fn diff_table(&self, other: &Table, rows: Vec<Row>) -> Table {
+        let mut table = Table::init(rows);
+        table.set_format(*self.format);
+        if let Some(titles) = self
+            .titles
+            .as_ref()
+            .as_ref()
+            .or(other.titles.as_ref().as_ref())
+        {
+            table.set_titles(titles.clone());
+        }
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DiffStats;
+    use crate::{color, Attr, Cell, Row, Table};
+
+    fn table(rows: &[(&str, &str)]) -> Table {
+        let mut table = Table::new();
+        for &(key, value) in rows {
+            table.add_row(Row::new(vec![Cell::new(key), Cell::new(value)]));
+        }
+        table
+    }
+
+    #[test]
+    fn diff_by_index() {
+        let mut before = table(&[("a", "1"), ("b", "2"), ("c", "3")]);
+        before.set_titles(Row::new(vec![Cell::new("k"), Cell::new("v")]));
+        let after = table(&[("a", "1"), ("b", "\x1b[1m2\x1b[0m")]);
+        let (diff, stats) = before.diff(&after);
+        let expected = DiffStats {
+            added: 0,
+            removed: 1,
+            changed: 0,
+        };
+        assert_eq!(stats, expected);
+        assert_eq!(diff.len(), 3);
+        assert_eq!(diff[1][1].get_content(), "\x1b[1m2\x1b[0m");
+        assert_eq!(diff[2][0].get_style(), &[Attr::ForegroundColor(color::RED)]);
+        assert!(diff.titles.is_some());
+
+        let (diff, stats) = after.diff(&before);
+        assert_eq!((stats.added, stats.removed), (1, 0));
+        assert_eq!(
+            diff[2][1].get_style(),
+            &[Attr::ForegroundColor(color::GREEN)]
+        );
+        assert_eq!(diff.titles, before.titles);
+    }
+
+    #[test]
+    fn diff_by_key() {
+        let before = table(&[("a", "1"), ("b", "2"), ("a", "3"), ("c", "4")]);
+        let after = table(&[("d", "5"), ("a", "1"), ("a", "6"), ("b", "2")]);
+        let (diff, stats) = before.diff_by_key(&after, 0);
+        assert_eq!((stats.added, stats.removed, stats.changed), (1, 1, 1));
+        let contents = diff.row_iter().map(Row::plain_text).collect::<Vec<_>>();
+        assert_eq!(
+            contents,
+            vec![
+                vec!["a", "1"],
+                vec!["b", "2"],
+                vec!["a", "3 → 6"],
+                vec!["c", "4"],
+                vec!["d", "5"],
+            ]
+        );
+        assert_eq!(
+            diff[2][1].get_style(),
+            &[Attr::ForegroundColor(color::YELLOW)]
+        );
+        assert!(diff[0][1].get_style().is_empty());
+
+        let (diff, stats) = before.diff_by_key(&before, 1);
+        assert_eq!(stats, DiffStats::default());
+        assert_eq!(diff.to_string(), before.to_string());
+    }
+}
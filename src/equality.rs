@@ -0,0 +1,210 @@
+//! Comparison and hashing of tables on their content
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use super::utils::strip_ansi;
+use super::{AsTableSlice, Row, Table, TableSlice};
+
+/// Return the plain text of each cell of `row`, without its ANSI escape sequences,
+/// along with the count of columns it spans
+fn plain_cells(row: &Row) -> impl Iterator<Item = (String, usize)> + '_ {
+    row.iter()
+        .map(|cell| (strip_ansi(&cell.get_content()), cell.get_hspan()))
+}
+
+/// Tell if `a` and `b` have the same cells, once their styles are ignored
+fn same_row(a: &Row, b: &Row) -> bool {
+    plain_cells(a).eq(plain_cells(b))
+}
+
+/// Tell if `a` and `b` have the same rows, once their styles are ignored
+fn same_rows<'a, I: Iterator<Item = &'a Row>>(mut a: I, mut b: I) -> bool {
+    loop {
+        match (a.next(), b.next()) {
+            (Some(a), Some(b)) if same_row(a, b) => continue,
+            (None, None) => return true,
+            _ => return false,
+        }
+    }
+}
+
+/// Feed the plain text of `rows` to `state`
+fn hash_rows<'a, H: Hasher, I: ExactSizeIterator<Item = &'a Row>>(rows: I, state: &mut H) {
+    rows.len().hash(state);
+    for row in rows {
+        row.len().hash(state);
+        for cell in plain_cells(row) {
+            cell.hash(state);
+        }
+    }
+}
+
+/// Tables are equal when their titles, header rows, rows and footer hold the same plain text,
+/// regardless of the styles of the cells, the format, the caption and the display settings
+/// of the table. Cells spanning a different number of columns are not equal.
+///
+/// Rows are compared cell by cell, so a row of `["a", ""]` does not equal a row of `["a"]`,
+/// although they may be printed the same way. Use `Table::strict_eq()` to compare styles too.
+///
+/// # Examples
+/// ```rust
+/// # #[macro_use] extern crate prettytable;
+/// # fn main() {
+/// let mut styled = table![[Fr->"a", "\x1b[1mb\x1b[0m"]];
+/// styled.set_format(*prettytable::format::consts::FORMAT_CLEAN);
+/// assert_eq!(styled, table![["a", "b"]]);
+/// assert!(!styled.strict_eq(&table![["a", "b"]]));
+/// assert_ne!(table![["a", ""]], table![["a"]]);
+/// # }
+/// ```
+impl PartialEq for Table {
+    fn eq(&self, other: &Table) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for Table {}
+
+/// Tables are hashed on their plain text, consistently with `PartialEq`
+impl Hash for Table {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state);
+    }
+}
+
+/// Slices are compared on their plain text like tables, regardless of their position in
+/// their table, so that a slice equals a table holding the same rows
+impl PartialEq for TableSlice<'_> {
+    fn eq(&self, other: &TableSlice<'_>) -> bool {
+        let titles = match (self.titles, other.titles) {
+            (Some(a), Some(b)) => same_row(a, b),
+            (a, b) => a.is_none() && b.is_none(),
+        };
+        let footers = match (self.footer, other.footer) {
+            (Some(a), Some(b)) => same_row(a, b),
+            (a, b) => a.is_none() && b.is_none(),
+        };
+        titles
+            && footers
+            && same_rows(self.header_rows.iter(), other.header_rows.iter())
+            && same_rows(self.rows.iter(), other.rows.iter())
+    }
+}
+
+impl Eq for TableSlice<'_> {}
+
+/// Slices are hashed on their plain text, consistently with `PartialEq`
+impl Hash for TableSlice<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_rows(self.titles.iter(), state);
+        hash_rows(self.header_rows.iter(), state);
+        hash_rows(self.rows.iter(), state);
+        hash_rows(self.footer.iter(), state);
+    }
+}
+
+impl Table {
+    /// Tell if this table equals `other` including the styles of the cells and rows, the
+    /// format, the caption and the display settings, unlike `==` which only compares the
+    /// plain text of the tables
+    pub fn strict_eq(&self, other: &Table) -> bool {
+        self.format == other.format
+            && self.titles == other.titles
+            && self.header_rows == other.header_rows
+            && self.rows == other.rows
+            && self.footer == other.footer
+            && self.caption == other.caption
+            && self.render == other.render
+    }
+
+    /// Return a hash of the plain text of the table, as compared by `==`, to detect changes
+    /// of its content, for instance to only print it again when it has changed.
+    ///
+    /// The hash is the same for equal tables within a program, but may differ across
+    /// versions of Rust, so it should not be stored.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    use crate::format::consts::FORMAT_BOX_CHARS;
+    use crate::format::ColumnWidth;
+
+    use crate::{AsTableSlice, Attr, Cell, Row, Slice, Table, TableSlice};
+
+    fn table(rows: &[&[&str]]) -> Table {
+        let mut table = Table::new();
+        for row in rows {
+            table.add_row(Row::new(row.iter().map(|c| Cell::new(c)).collect()));
+        }
+        table
+    }
+
+    #[test]
+    fn content_eq() {
+        let plain = table(&[&["a", "b"], &["c", "d"]]);
+        let mut styled = table(&[&["a", "\u{1b}[31mb\u{1b}[0m"], &["c", "d"]]);
+        styled[1][0].style(Attr::Bold);
+        styled.set_format(*FORMAT_BOX_CHARS);
+        styled.set_max_width(10);
+        assert_eq!(styled, plain);
+        assert_eq!(styled.content_hash(), plain.content_hash());
+        assert!(!styled.strict_eq(&plain));
+        assert!(plain.strict_eq(&plain.clone()));
+
+        let mut titled = plain.clone();
+        titled.set_titles(Row::new(vec![Cell::new("a"), Cell::new("b")]));
+        assert_ne!(titled, plain);
+        assert_ne!(titled.content_hash(), plain.content_hash());
+        let mut footed = plain.clone();
+        footed.set_footer(Row::new(vec![Cell::new("t")]));
+        assert_ne!(footed, plain);
+        assert_ne!(plain, table(&[&["a", "b"], &["c", "e"]]));
+        assert_ne!(plain, table(&[&["a", "b"]]));
+    }
+
+    #[test]
+    fn content_eq_ragged() {
+        let short = table(&[&["a"]]);
+        let ragged = table(&[&["a", ""]]);
+        assert_ne!(short, ragged);
+        assert_ne!(short.content_hash(), ragged.content_hash());
+
+        let mut spanning = table(&[&[]]);
+        spanning[0].add_cell(Cell::new("a").with_hspan(2));
+        assert_ne!(spanning, ragged);
+        // Titles are not compared to rows
+        let mut titled = table(&[]);
+        titled.set_titles(Row::new(vec![Cell::new("a")]));
+        assert_ne!(titled, short);
+        assert_ne!(titled.content_hash(), short.content_hash());
+    }
+
+    fn slice_hash(slice: TableSlice) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        slice.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn slice_content_eq() {
+        let plain = table(&[&["a", "b"], &["a", "b"]]);
+        let mut styled = plain.clone();
+        styled[1][0].style(Attr::Bold);
+        styled.set_format(*FORMAT_BOX_CHARS);
+        styled.set_column_width(0, ColumnWidth::Fixed(4));
+        assert_eq!(styled.slice(1..), plain.slice(..1));
+        assert_eq!(slice_hash(styled.slice(1..)), slice_hash(plain.slice(..1)));
+        assert_eq!(slice_hash(plain.as_slice()), plain.content_hash());
+        assert_ne!(plain.slice(1..), plain.as_slice());
+        assert_ne!(plain.slice(1..), table(&[&["a", "c"]]).as_slice());
+    }
+}
@@ -0,0 +1,202 @@
+//! Filtering of table rows
+
+use std::fmt;
+use std::io::{self, Error, Write};
+
+use super::format::TableFormat;
+use super::utils::StringWriter;
+use super::{print_rows, stdout, AsTableSlice, Row, Table, TableSlice, Terminal};
+
+/// A borrowed view on the rows of a table matching a predicate.
+///
+/// The view shares the format and titles of its table, and prints like it, without
+/// copying the rows. Rows are indexed by their position in the view.
+/// A `FilteredTable` is obtained with the `filtered` method of `Table` or `TableSlice`.
+///
+/// # Examples
+/// ```rust
+/// # #[macro_use] extern crate prettytable;
+/// # fn main() {
+/// let table = table![["Name", "Age"], ["Alice", 34], ["Bob", 12], ["Carol", 51]];
+/// let adults = table.filtered(|row| row[1].get_content().parse::<u32>().map_or(true, |a| a >= 18));
+/// assert_eq!(adults.len(), 3);
+/// assert_eq!(adults.get_row(1).unwrap()[0].get_content(), "Alice");
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct FilteredTable<'a> {
+    table: TableSlice<'a>,
+    rows: Vec<&'a Row>,
+    /// Positions of the rows in their table
+    positions: Vec<usize>,
+}
+
+impl<'a> FilteredTable<'a> {
+    /// Get the number of rows in the view
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Check if no row matches
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Get the row at position `row` in the view
+    pub fn get_row(&self, row: usize) -> Option<&'a Row> {
+        self.rows.get(row).copied()
+    }
+
+    /// Returns an iterator over the rows of the view
+    pub fn row_iter(&self) -> impl Iterator<Item = &'a Row> + '_ {
+        self.rows.iter().copied()
+    }
+
+    /// Internal only
+    fn __print<T: Write + ?Sized, F>(&self, out: &mut T, f: F) -> Result<usize, Error>
+    where
+        F: Fn(&Row, &mut T, &TableFormat, &[usize]) -> Result<usize, Error>,
+    {
+        print_rows(out, &self.table, &self.rows, &self.positions, None, f)
+    }
+
+    /// Print the view to `out` and returns the number of
+    /// line printed, or an error
+    pub fn print<T: Write + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
+        self.__print(out, Row::print)
+    }
+
+    /// Print the view to terminal `out`, applying styles when needed and returns the number of
+    /// line printed, or an error
+    pub fn print_term<T: Terminal + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
+        self.__print(out, Row::print_term)
+    }
+
+    /// Print the view to standard output, like `TableSlice::print_tty()`
+    pub fn print_tty(&self, force_colorize: bool) -> Result<usize, Error> {
+        use is_terminal::IsTerminal;
+        match (stdout(), io::stdout().is_terminal() || force_colorize) {
+            (Some(mut o), true) => self.print_term(&mut *o),
+            _ => self.print(&mut io::stdout()),
+        }
+    }
+
+    /// Print the view to standard output, like `TableSlice::printstd()`.
+    /// Any failure to print is ignored.
+    pub fn printstd(&self) {
+        let _ = self.print_tty(false); // Ignore result
+    }
+}
+
+impl fmt::Display for FilteredTable<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let mut writer = StringWriter::new();
+        if self.print(&mut writer).is_err() {
+            return Err(fmt::Error);
+        }
+        fmt.write_str(writer.as_string())
+    }
+}
+
+impl<'a> TableSlice<'a> {
+    /// Return a view on the rows of this slice for which `f` returns `true`
+    pub fn filtered<F: Fn(&Row) -> bool>(&self, f: F) -> FilteredTable<'a> {
+        let (positions, rows) = self
+            .rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| f(row))
+            .map(|(i, row)| (self.offset + i, row))
+            .unzip();
+        FilteredTable {
+            table: *self,
+            rows,
+            positions,
+        }
+    }
+}
+
+impl Table {
+    /// Keep only the rows for which `f` returns `true`. Titles are not affected.
+    pub fn retain<F: Fn(&Row) -> bool>(&mut self, f: F) {
+        self.rows.retain(f);
+    }
+
+    /// Return a view on the rows of the table for which `f` returns `true`,
+    /// leaving the table unchanged. See `FilteredTable` for details.
+    pub fn filtered<F: Fn(&Row) -> bool>(&self, f: F) -> FilteredTable<'_> {
+        self.as_slice().filtered(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cell, Row, Slice, Table};
+
+    fn sample() -> Table {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("n"), Cell::new("parity")]));
+        for n in 1..=5 {
+            let parity = if n % 2 == 0 { "even" } else { "odd" };
+            table.add_row(Row::new(vec![Cell::from(n), Cell::new(parity)]));
+        }
+        table
+    }
+
+    #[test]
+    fn filtered_view() {
+        let table = sample();
+        let even = table.filtered(|row| row[1].get_content() == "even");
+        let out = "\
++---+--------+
+| n | parity |
++===+========+
+| 2 | even   |
++---+--------+
+| 4 | even   |
++---+--------+
+";
+        assert_eq!(even.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(even.len(), 2);
+        assert_eq!(even.get_row(1).unwrap()[0].get_content(), "4");
+        assert!(even.get_row(2).is_none());
+        let indices = even
+            .row_iter()
+            .enumerate()
+            .map(|(i, r)| format!("{}:{}", i, r[0].get_content()))
+            .collect::<Vec<_>>();
+        assert_eq!(indices, vec!["0:2", "1:4"]);
+        assert_eq!(table.len(), 5);
+
+        let none = table.slice(..2).filtered(|row| row[0].get_content() == "5");
+        assert!(none.is_empty());
+        assert_eq!(
+            none.to_string().replace("\r\n", "\n"),
+            "+---+--------+\n| n | parity |\n+===+========+\n+---+--------+\n"
+        );
+    }
+
+    #[test]
+    fn filtered_view_render() {
+        let mut table = sample();
+        table.merge_column_duplicates(1);
+        table.set_format(*crate::format::consts::FORMAT_CLEAN);
+        let odd = table.filtered(|row| row[1].get_content() == "odd");
+        assert_eq!(
+            odd.to_string().replace("\r\n", "\n"),
+            " n  parity \n 1  odd \n 3   \n 5   \n"
+        );
+    }
+
+    #[test]
+    fn retain() {
+        let mut table = sample();
+        table.retain(|row| row[0].get_content() != "3");
+        let numbers = table
+            .row_iter()
+            .map(|r| r[0].get_content())
+            .collect::<Vec<_>>();
+        assert_eq!(numbers, vec!["1", "2", "4", "5"]);
+        assert!(table.titles.is_some());
+    }
+}
@@ -0,0 +1,277 @@
+//! Fitting of tables to a given width, like the one of the terminal
+
+use std::env;
+use std::io::{Error, Write};
+
+use super::format::{ColumnPosition, TableFormat};
+use super::{column_widths, Table};
+
+/// Width assumed for the terminal when it cannot be found
+const DEFAULT_TERMINAL_WIDTH: usize = 80;
+
+/// Minimum width of the columns shrunk by `print_fit_width()`
+pub(crate) const DEFAULT_MIN_COLUMN_WIDTH: usize = 4;
+
+/// Return the width of the terminal, as given by the `COLUMNS` environment variable
+pub(crate) fn terminal_width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.trim().parse().ok())
+        .filter(|&c| c > 0)
+        .unwrap_or(DEFAULT_TERMINAL_WIDTH)
+}
+
+/// Return the width of a table printed with `format` and columns `col_width` wide
+pub(crate) fn table_width(format: &TableFormat, col_width: &[usize]) -> usize {
+    let (lp, rp) = format.get_padding();
+    let border = |pos| format.get_column_separator(pos).map_or(0, |_| 1);
+    let separators = col_width.len().saturating_sub(1) * border(ColumnPosition::Intern);
+    format.get_indent()
+        + border(ColumnPosition::Left)
+        + border(ColumnPosition::Right)
+        + separators
+        + col_width.iter().map(|w| w + lp + rp).sum::<usize>()
+}
+
+/// Shrink the widest of `col_width`, not below `min_width` for each column, until a table
+/// printed with `format` is at most `width` wide. Return `false` if it cannot fit.
+fn shrink_columns(
+    format: &TableFormat,
+    col_width: &mut [usize],
+    width: usize,
+    min_width: &[usize],
+) -> bool {
+    let mut excess = table_width(format, col_width).saturating_sub(width);
+    while excess > 0 {
+        let widest = (0..col_width.len())
+            .filter(|&i| col_width[i] > min_width[i])
+            .max_by_key(|&i| col_width[i]);
+        let widest = match widest {
+            Some(widest) => widest,
+            None => return false,
+        };
+        col_width[widest] -= 1;
+        excess -= 1;
+    }
+    true
+}
+
+impl Table {
+    /// Keep the first `columns` columns at their width when the table is fitted to a given
+    /// width, the other columns being shrunk instead. The frozen columns are only shrunk
+    /// if the table cannot fit otherwise. See `fitted()` for details.
+    pub fn set_frozen_columns(&mut self, columns: usize) {
+        self.render.set_frozen_columns(columns);
+    }
+
+    /// Return a copy of this table, as it is displayed, fitting in `width` display columns.
+    ///
+    /// The widest columns are shrunk first, down to `min_column_width`, by wrapping their
+    /// cells, or truncating the cells already truncated with `Cell::set_truncate()`.
+    /// Columns are not shrunk below their minimum width set with `set_column_width()`
+    /// either, and the columns frozen with `set_frozen_columns()` keep their width. If this
+    /// is not enough, the padding of the cells is removed too, then the frozen columns are
+    /// shrunk as well. If the table still does not fit, or already fits, the copy is not
+    /// shrunk.
+    /// Cells spanning several columns are not shrunk.
+    pub fn fitted(&self, width: usize, min_column_width: usize) -> Table {
+        let mut table = self.rendered();
+        let sized = table
+            .rows
+            .iter()
+            .chain(table.header_rows.iter())
+            .chain(table.footer.iter())
+            .collect::<Vec<_>>();
+        let mut natural = column_widths(&table.format, &table.titles, &sized);
+        // Columns are not shrunk below the minimum width set with `set_column_width()`
+        let mut min_width = table.render.min_column_widths();
+        min_width.resize(natural.len().max(min_width.len()), 0);
+        for (width, min) in natural.iter_mut().zip(&mut min_width) {
+            *width = (*width).max(*min);
+            *min = (*min).max(min_column_width);
+        }
+        // Frozen columns keep their natural width, unless the table cannot fit otherwise
+        let mut frozen_min_width = min_width.clone();
+        let frozen = table.render.frozen_columns();
+        for (min, &natural) in frozen_min_width.iter_mut().zip(&natural).take(frozen) {
+            *min = natural;
+        }
+        let mut unpadded = *table.format;
+        unpadded.padding(0, 0);
+        let mut fit = None;
+        'fit: for min_width in [&frozen_min_width, &min_width] {
+            for (padded, format) in [(true, *table.format), (false, unpadded)] {
+                let mut col_width = natural.clone();
+                if shrink_columns(&format, &mut col_width, width, min_width) {
+                    fit = Some((padded, format, col_width));
+                    break 'fit;
+                }
+            }
+        }
+        let (padded, format, col_width) = match fit {
+            Some(fit) => fit,
+            None => return table,
+        };
+        if !padded {
+            table.set_format(format);
+            for row in table
+                .titles
+                .iter_mut()
+                .chain(table.header_rows.iter_mut())
+                .chain(table.rows.iter_mut())
+                .chain(table.footer.iter_mut())
+            {
+                row.set_padding(0, 0);
+            }
+        }
+        for (column, (&target, &current)) in col_width.iter().zip(&natural).enumerate() {
+            if target >= current {
+                continue;
+            }
+            for row in table
+                .titles
+                .iter_mut()
+                .chain(table.header_rows.iter_mut())
+                .chain(table.rows.iter_mut())
+                .chain(table.footer.iter_mut())
+            {
+                let mut col = 0;
+                for cell in row.iter_mut() {
+                    if col == column && cell.get_hspan() == 1 {
+                        cell.fit_width(target);
+                    }
+                    col += cell.get_hspan();
+                }
+            }
+        }
+        table
+    }
+
+    /// Print the table to `out`, shrinking its columns so that it fits in `width`
+    /// display columns. See `fitted()` for details. Returns the number of lines printed,
+    /// or an error.
+    pub fn print_fit_width<T: Write + ?Sized>(
+        &self,
+        out: &mut T,
+        width: usize,
+    ) -> Result<usize, Error> {
+        self.fitted(width, DEFAULT_MIN_COLUMN_WIDTH).print(out)
+    }
+
+    /// Print the table to standard output like `printstd()`, shrinking its columns so
+    /// that it fits in the terminal. See `fitted()` for details.
+    ///
+    /// The width of the terminal is read from the `COLUMNS` environment variable,
+    /// 80 columns being assumed if it is not set.
+    pub fn print_fit_tty(&self) -> Result<usize, Error> {
+        self.fitted(terminal_width(), DEFAULT_MIN_COLUMN_WIDTH)
+            .print_tty(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::StringWriter;
+    use crate::{Cell, Row, Table};
+
+    fn print(table: &Table, width: usize) -> String {
+        let mut out = StringWriter::new();
+        table.print_fit_width(&mut out, width).unwrap();
+        out.as_string().replace("\r\n", "\n")
+    }
+
+    fn table() -> Table {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![
+            Cell::new("id"),
+            Cell::new("description"),
+            Cell::new("note"),
+        ]));
+        table.add_row(Row::new(vec![
+            Cell::new("1"),
+            Cell::new("a rather long description"),
+            Cell::new("short"),
+        ]));
+        table
+    }
+
+    #[test]
+    fn fit_width() {
+        let table = table();
+        assert_eq!(print(&table, 80), table.to_string().replace("\r\n", "\n"));
+        let out = "\
++----+---------------+-------+
+| id | description   | note  |
++====+===============+=======+
+| 1  | a rather long | short |
+|    | description   |       |
++----+---------------+-------+
+";
+        assert_eq!(print(&table, 30), out);
+    }
+
+    #[test]
+    fn fit_width_truncate() {
+        let mut table = table();
+        table[0][1].set_truncate(20, "~");
+        let out = "\
++----+---------------+-------+
+| id | description   | note  |
++====+===============+=======+
+| 1  | a rather lon~ | short |
++----+---------------+-------+
+";
+        assert_eq!(print(&table, 30), out);
+    }
+
+    #[test]
+    fn fit_width_padding() {
+        let table = table();
+        let out = "\
++--+----+----+
+|id|desc|note|
+|  |ript|    |
+|  |ion |    |
++==+====+====+
+|1 |a   |shor|
+|  |rath|t   |
+|  |er  |    |
+|  |long|    |
+|  |desc|    |
+|  |ript|    |
+|  |ion |    |
++--+----+----+
+";
+        assert_eq!(print(&table, 14), out);
+        assert_eq!(print(&table, 10), table.to_string().replace("\r\n", "\n"));
+    }
+
+    #[test]
+    fn fit_width_frozen() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("name"), Cell::new("value")]));
+        table.add_row(Row::new(vec![
+            Cell::new("a long key name"),
+            Cell::new("some longer value text"),
+        ]));
+        table.set_frozen_columns(1);
+        let out = "\
++-----------------+--------+
+| name            | value  |
++=================+========+
+| a long key name | some   |
+|                 | longer |
+|                 | value  |
+|                 | text   |
++-----------------+--------+
+";
+        assert_eq!(print(&table, 30), out);
+        assert_eq!(table.fitted(30, 4).column_widths(), vec![15, 6]);
+
+        // Frozen columns are shrunk only when the table cannot fit otherwise
+        let out = print(&table, 20);
+        assert!(out.lines().all(|l| l.chars().count() <= 20));
+        assert!(out.lines().nth(1).unwrap().starts_with("| name "));
+        assert!(!out.contains("a long key name"));
+    }
+}
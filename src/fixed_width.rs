@@ -0,0 +1,80 @@
+//! Import of fixed-width text reports
+
+use std::io::{self, BufRead};
+
+use unicode_width::UnicodeWidthChar;
+
+use super::{Cell, Row, Table};
+
+/// Split `line` into fields of the given display `widths`. A character spanning the
+/// boundary between two fields belongs to the field it starts in. Any text past
+/// the last field is returned as an extra field
+fn split_line(line: &str, widths: &[usize]) -> Vec<String> {
+    let mut fields = vec![String::new(); widths.len() + 1];
+    let mut field = 0;
+    let mut end = widths.first().copied().unwrap_or(0);
+    let mut column = 0;
+    for c in line.chars() {
+        while field < widths.len() && column >= end {
+            field += 1;
+            end += widths.get(field).copied().unwrap_or(0);
+        }
+        fields[field].push(c);
+        column += c.width().unwrap_or(0);
+    }
+    let extra = fields.pop().unwrap_or_default();
+    if !extra.trim().is_empty() {
+        fields.push(extra);
+    }
+    fields.iter().map(|f| f.trim_end().to_string()).collect()
+}
+
+impl Table {
+    /// Create a table from a fixed-width text report read from `reader`, with one row
+    /// per line.
+    ///
+    /// Lines are sliced into cells at the given display `widths`, so wide characters
+    /// are measured as they would be displayed and are never split. Trailing spaces
+    /// are trimmed from cells. Text past the last column, if any, is kept in an extra cell.
+    pub fn from_fixed_width<R: BufRead>(reader: R, widths: &[usize]) -> io::Result<Table> {
+        let mut table = Table::new();
+        for line in reader.lines() {
+            let fields = split_line(&line?, widths);
+            table.add_row(Row::new(fields.iter().map(|f| Cell::new(f)).collect()));
+        }
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_line;
+    use crate::Table;
+
+    #[test]
+    fn from_fixed_width() {
+        let report = "\
+ID  NAME      CITY
+1   Alice     Paris
+22  由系统    Lyon
+333 Bob
+";
+        let table = Table::from_fixed_width(report.as_bytes(), &[4, 10, 6]).unwrap();
+        assert_eq!(table.len(), 4);
+        let row = table.get_row(2).unwrap();
+        assert_eq!(row.get_cell(0).unwrap().get_content(), "22");
+        assert_eq!(row.get_cell(1).unwrap().get_content(), "由系统");
+        assert_eq!(row.get_cell(2).unwrap().get_content(), "Lyon");
+        let row = table.get_row(3).unwrap();
+        assert_eq!(row.len(), 3);
+        assert_eq!(row.get_cell(1).unwrap().get_content(), "Bob");
+        assert_eq!(row.get_cell(2).unwrap().get_content(), "");
+    }
+
+    #[test]
+    fn split_fields() {
+        assert_eq!(split_line("ab由cd", &[3, 2]), vec!["ab由", "c", "d"]);
+        assert_eq!(split_line("abc  ", &[2, 2]), vec!["ab", "c"]);
+        assert_eq!(split_line("", &[2]), vec![""]);
+    }
+}
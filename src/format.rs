@@ -4,7 +4,7 @@ use std::io::{Error, Write};
 
 use encode_unicode::Utf8Char;
 
-use super::utils::NEWLINE;
+use super::utils::{display_width, grapheme_width, strip_ansi, NEWLINE};
 
 /// Alignment for cell's content
 #[derive(Clone, Debug, PartialEq, Copy, Hash, Eq)]
@@ -17,6 +17,18 @@ pub enum Alignment {
     RIGHT,
 }
 
+/// Vertical alignment for the content of cells in multi-line rows
+#[derive(Clone, Debug, PartialEq, Copy, Hash, Eq, Default)]
+pub enum VerticalAlignment {
+    /// Align at the top
+    #[default]
+    TOP,
+    /// Align in the middle
+    MIDDLE,
+    /// Align at the bottom
+    BOTTOM,
+}
+
 /// Position of a line separator in a table
 #[derive(Clone, Debug, PartialEq, Copy, Hash, Eq)]
 pub enum LinePosition {
@@ -67,12 +79,20 @@ impl LineSeparator {
         }
     }
 
+    /// Return the `line`, `junc`, `ljunc` and `rjunc` characters of this separator
+    pub(crate) fn get_chars(&self) -> (char, char, char, char) {
+        (self.line, self.junc, self.ljunc, self.rjunc)
+    }
+
     /// Print a full line separator to `out`. `col_width` is a slice containing the width of each column.
+    /// Columns listed in `hidden` are left blank.
     /// Returns the number of printed lines
+    #[allow(clippy::too_many_arguments)]
     fn print<T: Write + ?Sized>(
         &self,
         out: &mut T,
         col_width: &[usize],
+        hidden: &[usize],
         padding: (usize, usize),
         colsep: bool,
         lborder: bool,
@@ -81,10 +101,15 @@ impl LineSeparator {
         if lborder {
             out.write_all(Utf8Char::from(self.ljunc).as_bytes())?;
         }
-        let mut iter = col_width.iter().peekable();
-        while let Some(width) = iter.next() {
+        let mut iter = col_width.iter().enumerate().peekable();
+        while let Some((column, width)) = iter.next() {
+            let line = if hidden.contains(&column) {
+                ' '
+            } else {
+                self.line
+            };
             for _ in 0..width + padding.0 + padding.1 {
-                out.write_all(Utf8Char::from(self.line).as_bytes())?;
+                out.write_all(Utf8Char::from(line).as_bytes())?;
             }
             if colsep && iter.peek().is_some() {
                 out.write_all(Utf8Char::from(self.junc).as_bytes())?;
@@ -104,6 +129,66 @@ impl Default for LineSeparator {
     }
 }
 
+/// Constraint on the width of a column, as set with `Table::set_column_width()`.
+/// Widths are display widths of the text, excluding padding.
+#[derive(Clone, Debug, Copy, Hash, PartialEq, Eq)]
+pub enum ColumnWidth {
+    /// Exactly this width, whatever the content of the column
+    Fixed(usize),
+    /// At least this width, the column being padded if needed
+    Min(usize),
+    /// At most this width
+    Max(usize),
+    /// At least the first width, and at most the second one
+    Range(usize, usize),
+}
+
+impl ColumnWidth {
+    /// Return the minimum width of the column
+    pub(crate) fn min(&self) -> usize {
+        match *self {
+            ColumnWidth::Fixed(w) | ColumnWidth::Min(w) | ColumnWidth::Range(w, _) => w,
+            ColumnWidth::Max(_) => 0,
+        }
+    }
+
+    /// Return the maximum width of the column, if any. It is never lower than
+    /// the minimum width, nor than 1.
+    pub(crate) fn max(&self) -> Option<usize> {
+        match *self {
+            ColumnWidth::Fixed(w) | ColumnWidth::Max(w) => Some(w.max(1)),
+            ColumnWidth::Range(min, max) => Some(max.max(min).max(1)),
+            ColumnWidth::Min(_) => None,
+        }
+    }
+}
+
+/// How the width of the text in cells is measured
+// Custom modes are compared by function address, which is good enough to tell formats apart
+#[allow(unpredictable_function_pointer_comparisons)]
+#[derive(Clone, Debug, Copy, Hash, PartialEq, Eq, Default)]
+pub enum WidthMode {
+    /// Sum of the widths of the chars, as defined by Unicode
+    #[default]
+    Unicode,
+    /// Sum of the widths of the character clusters, each one taking the width of its
+    /// widest char. Combined emoji sequences are measured as a single glyph.
+    Graphemes,
+    /// Width computed by a user function, receiving the text without escape sequences
+    Custom(fn(&str) -> usize),
+}
+
+impl WidthMode {
+    /// Return the width of `text` on screen, ignoring ANSI escape sequences
+    pub fn width(&self, text: &str) -> usize {
+        match *self {
+            WidthMode::Unicode => display_width(text),
+            WidthMode::Graphemes => grapheme_width(text),
+            WidthMode::Custom(f) => f(&strip_ansi(text)),
+        }
+    }
+}
+
 /// Contains the table formatting rules
 #[derive(Clone, Debug, Copy, Hash, PartialEq, Eq)]
 pub struct TableFormat {
@@ -127,6 +212,10 @@ pub struct TableFormat {
     pad_right: usize,
     /// Global indentation when rendering the table
     indent: usize,
+    /// Measure of the text width
+    width_mode: WidthMode,
+    /// Distance between tab stops
+    tab_width: usize,
 }
 
 impl TableFormat {
@@ -143,6 +232,8 @@ impl TableFormat {
             pad_left: 0,
             pad_right: 0,
             indent: 0,
+            width_mode: WidthMode::Unicode,
+            tab_width: 4,
         }
     }
 
@@ -195,7 +286,17 @@ impl TableFormat {
         }
     }
 
-    fn get_sep_for_line(&self, pos: LinePosition) -> &Option<LineSeparator> {
+    /// Return the separator set for line `pos`, without falling back to the internal
+    /// separator for the title line
+    #[cfg(feature = "serde")]
+    pub(crate) fn get_own_separator(&self, pos: LinePosition) -> Option<LineSeparator> {
+        match pos {
+            LinePosition::Title => self.tsep,
+            pos => *self.get_sep_for_line(pos),
+        }
+    }
+
+    pub(crate) fn get_sep_for_line(&self, pos: LinePosition) -> &Option<LineSeparator> {
         match pos {
             LinePosition::Intern => &self.lsep,
             LinePosition::Top => &self.top_sep,
@@ -217,6 +318,29 @@ impl TableFormat {
         self.indent
     }
 
+    /// Set how the width of the text in cells is measured
+    pub fn width_mode(&mut self, mode: WidthMode) {
+        self.width_mode = mode;
+    }
+
+    /// Get how the width of the text in cells is measured
+    pub fn get_width_mode(&self) -> WidthMode {
+        self.width_mode
+    }
+
+    /// Set the distance between tab stops, 4 by default.
+    ///
+    /// When printing, tabs in cells are expanded with spaces up to the next tab stop,
+    /// counted from the start of the cell content. A width of 0 shows tabs as `\t`.
+    pub fn set_tab_width(&mut self, width: usize) {
+        self.tab_width = width;
+    }
+
+    /// Get the distance between tab stops
+    pub fn get_tab_width(&self) -> usize {
+        self.tab_width
+    }
+
     /// Print a full line separator to `out`. `col_width` is a slice containing the width of each column.
     /// Returns the number of printed lines
     // #[deprecated(since="0.8.0", note="Will become private in future release. See [issue #87](https://github.com/phsym/prettytable-rs/issues/87)")]
@@ -225,6 +349,17 @@ impl TableFormat {
         out: &mut T,
         col_width: &[usize],
         pos: LinePosition,
+    ) -> Result<usize, Error> {
+        self.print_line_separator_except(out, col_width, &[], pos)
+    }
+
+    /// Print a line separator, leaving blank the columns listed in `hidden`
+    pub(crate) fn print_line_separator_except<T: Write + ?Sized>(
+        &self,
+        out: &mut T,
+        col_width: &[usize],
+        hidden: &[usize],
+        pos: LinePosition,
     ) -> Result<usize, Error> {
         match *self.get_sep_for_line(pos) {
             Some(ref l) => {
@@ -233,6 +368,7 @@ impl TableFormat {
                 l.print(
                     out,
                     col_width,
+                    hidden,
                     self.get_padding(),
                     self.csep.is_some(),
                     self.lborder.is_some(),
@@ -335,6 +471,18 @@ impl FormatBuilder {
         self
     }
 
+    /// Set how the width of the text in cells is measured
+    pub fn width_mode(mut self, mode: WidthMode) -> Self {
+        self.format.width_mode(mode);
+        self
+    }
+
+    /// Set the distance between tab stops
+    pub fn tab_width(mut self, width: usize) -> Self {
+        self.format.set_tab_width(width);
+        self
+    }
+
     /// Return the generated `TableFormat`
     pub fn build(&self) -> TableFormat {
         *self.format
@@ -349,6 +497,12 @@ impl From<TableFormat> for FormatBuilder {
     }
 }
 
+impl From<FormatBuilder> for TableFormat {
+    fn from(builder: FormatBuilder) -> Self {
+        builder.build()
+    }
+}
+
 /// Predifined formats. Those constants are lazily evaluated when
 /// the corresponding struct is dereferenced
 pub mod consts {
@@ -0,0 +1,126 @@
+//! Graphviz HTML-like label output for tables
+
+use super::format::Alignment;
+use super::utils::{strip_ansi, HtmlEscape};
+use super::{AsTableSlice, Cell, Row, Table, TableSlice};
+
+/// Format `row` as a label `<TR>` element, with bold content if `bold` is set
+fn format_row(row: &Row, bold: bool) -> String {
+    let cells: String = row.iter().map(|c| format_cell(c, bold)).collect();
+    format!("<TR>{}</TR>", cells)
+}
+
+/// Format `cell` as a label `<TD>` element
+fn format_cell(cell: &Cell, bold: bool) -> String {
+    let align = match cell.get_align() {
+        Alignment::LEFT => "LEFT",
+        Alignment::CENTER => "CENTER",
+        Alignment::RIGHT => "RIGHT",
+    };
+    let mut attrs = format!(" ALIGN=\"{}\"", align);
+    if cell.get_hspan() > 1 {
+        attrs += &format!(" COLSPAN=\"{}\"", cell.get_hspan());
+    }
+    let line_break = format!("<BR ALIGN=\"{}\"/>", align);
+    let lines: Vec<String> = strip_ansi(&cell.get_content())
+        .lines()
+        .map(|l| HtmlEscape(l).to_string())
+        .collect();
+    let mut content = lines.join(&line_break);
+    if bold && !content.is_empty() {
+        content = format!("<B>{}</B>", content);
+    }
+    format!("<TD{}>{}</TD>", attrs, content)
+}
+
+impl<'a> TableSlice<'a> {
+    /// Return the table as a Graphviz HTML-like label `<TABLE>` element, with
+    /// `BORDER="0"` and `CELLBORDER="1"`.
+    ///
+    /// The result is meant to be enclosed in angle brackets in a dot file,
+    /// as in `label=<...>`. Titles are rendered as a bold first row, and the footer
+    /// as the last row. There is no caption in a label, so it is left out.
+    pub fn to_graphviz_label(&self) -> String {
+        self.to_graphviz_label_with_borders(0, 1)
+    }
+
+    /// Return the table as a Graphviz HTML-like label `<TABLE>` element, with
+    /// the given `BORDER` and `CELLBORDER` values.
+    ///
+    /// See `to_graphviz_label()` for details.
+    pub fn to_graphviz_label_with_borders(&self, border: u32, cell_border: u32) -> String {
+        let mut label = format!(
+            "<TABLE BORDER=\"{}\" CELLBORDER=\"{}\" CELLSPACING=\"0\">",
+            border, cell_border
+        );
+        let table = self.exported();
+        let mut rows = 0;
+        if let Some(ref t) = *table.titles {
+            if !t.is_empty() {
+                label += &format_row(t, true);
+                rows += 1;
+            }
+        }
+        for r in table.rows.iter().filter(|r| !r.is_empty()) {
+            label += &format_row(r, false);
+            rows += 1;
+        }
+        // Graphviz rejects tables without any cell
+        if rows == 0 {
+            label += "<TR><TD></TD></TR>";
+        }
+        label += "</TABLE>";
+        label
+    }
+}
+
+impl Table {
+    /// Return the table as a Graphviz HTML-like label `<TABLE>` element.
+    ///
+    /// See `TableSlice::to_graphviz_label()` for details.
+    pub fn to_graphviz_label(&self) -> String {
+        self.as_slice().to_graphviz_label()
+    }
+
+    /// Return the table as a Graphviz HTML-like label `<TABLE>` element, with
+    /// the given `BORDER` and `CELLBORDER` values.
+    pub fn to_graphviz_label_with_borders(&self, border: u32, cell_border: u32) -> String {
+        self.as_slice()
+            .to_graphviz_label_with_borders(border, cell_border)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::Alignment;
+    use crate::{Cell, Row, Table};
+
+    #[test]
+    fn graphviz_label() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2")]));
+        table.add_row(Row::new(vec![
+            Cell::new("a<b"),
+            Cell::new_align("c\nd", Alignment::RIGHT),
+        ]));
+        table.add_row(Row::new(vec![Cell::new("e & f").with_hspan(2)]));
+        let out = "\
+<TABLE BORDER=\"0\" CELLBORDER=\"1\" CELLSPACING=\"0\">\
+<TR><TD ALIGN=\"LEFT\"><B>t1</B></TD><TD ALIGN=\"LEFT\"><B>t2</B></TD></TR>\
+<TR><TD ALIGN=\"LEFT\">a&lt;b</TD><TD ALIGN=\"RIGHT\">c<BR ALIGN=\"RIGHT\"/>d</TD></TR>\
+<TR><TD ALIGN=\"LEFT\" COLSPAN=\"2\">e &amp; f</TD></TR>\
+</TABLE>";
+        assert_eq!(table.to_graphviz_label(), out);
+        assert!(table
+            .to_graphviz_label_with_borders(1, 0)
+            .starts_with("<TABLE BORDER=\"1\" CELLBORDER=\"0\" CELLSPACING=\"0\">"));
+    }
+
+    #[test]
+    fn empty_graphviz_label() {
+        assert_eq!(
+            Table::new().to_graphviz_label(),
+            "<TABLE BORDER=\"0\" CELLBORDER=\"1\" CELLSPACING=\"0\"><TR><TD></TD></TR></TABLE>"
+        );
+    }
+}
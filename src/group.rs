@@ -0,0 +1,198 @@
+//! Grouping of table rows under header rows
+
+use super::utils::strip_ansi;
+use super::{Row, SortOrder, Table};
+
+/// How group header rows are written when exporting a table to CSV
+#[derive(Clone, Debug, Copy, Hash, PartialEq, Eq, Default)]
+pub enum GroupHeaders {
+    /// Group headers are not written
+    #[default]
+    Skip,
+    /// Group headers are not written as records, but each record starts with a field
+    /// holding the header of its group
+    Column,
+}
+
+impl Table {
+    /// Group the rows by the content of column `column`, without the ANSI escape
+    /// sequences. Each group is preceded by a group header row holding that content,
+    /// and spanning all the columns. If `remove_column` is `true`, the column is removed
+    /// from the titles and the rows, since the headers hold its content.
+    ///
+    /// Groups are ordered by first appearance, and the rows keep their order in each group.
+    /// Rows can thus be sorted before grouping, or afterwards with `sort_by_column()`, which
+    /// sorts the rows within their group.
+    /// Existing group headers are replaced, and rows without a cell in `column`
+    /// are put in a group with an empty header.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut table = table![["Alice", "Pen"], ["Bob", "Ink"], ["Alice", "Paper"]];
+    /// table.group_by(0, true);
+    /// let lines: Vec<_> = table.row_iter().map(|r| r[0].get_content()).collect();
+    /// assert_eq!(lines, vec!["Alice", "Pen", "Paper", "Bob", "Ink"]);
+    /// assert!(table[0].is_group_header());
+    /// # }
+    /// ```
+    pub fn group_by(&mut self, column: usize, remove_column: bool) {
+        let mut groups: Vec<(String, Vec<Row>)> = Vec::new();
+        for row in self.rows.drain(..).filter(|r| !r.is_group_header()) {
+            let key = row
+                .get_cell(column)
+                .map_or(String::new(), |cell| strip_ansi(&cell.get_content()));
+            match groups.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, rows)) => rows.push(row),
+                None => groups.push((key, vec![row])),
+            }
+        }
+        for (key, mut rows) in groups {
+            if remove_column {
+                for row in &mut rows {
+                    row.remove_cell(column);
+                }
+            }
+            self.rows.push(Row::group_header(&key));
+            self.rows.extend(rows);
+        }
+        if remove_column {
+            if let Some(titles) = self.titles.as_mut() {
+                titles.remove_cell(column);
+            }
+        }
+        self.span_group_headers();
+    }
+
+    /// Append a group of `rows`, preceded by a group header row holding `header`.
+    /// See `group_by()` for details.
+    pub fn add_group(&mut self, header: &str, rows: Vec<Row>) {
+        self.rows.push(Row::group_header(header));
+        self.rows.extend(rows);
+        self.span_group_headers();
+    }
+
+    /// Sort the rows of each group by the content of column `column`. Groups and their
+    /// headers are not moved. This is what `sort_by_column()` does as well.
+    pub fn sort_within_groups(&mut self, column: usize, order: SortOrder) {
+        self.sort_by_column(column, order);
+    }
+
+    /// Make the group headers span all the columns of the other rows and titles
+    pub(crate) fn span_group_headers(&mut self) {
+        let columns = self
+            .titles
+            .iter()
+            .chain(self.rows.iter().filter(|r| !r.is_group_header()))
+            .map(Row::column_count)
+            .max()
+            .unwrap_or(0);
+        for row in self.rows.iter_mut().filter(|r| r.is_group_header()) {
+            if let Some(cell) = row.get_mut_cell(0) {
+                cell.set_hspan(columns);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cell, Row, SortOrder, Table};
+
+    fn orders() -> Table {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![
+            Cell::new("Customer"),
+            Cell::new("Item"),
+            Cell::new("Qty"),
+        ]));
+        for (customer, item, qty) in [
+            ("Bob", "Ink", 3),
+            ("Alice", "Pen", 1),
+            ("Bob", "Paper", 12),
+            ("Alice", "Eraser", 2),
+        ] {
+            table.add_row(Row::new(vec![
+                Cell::new(customer),
+                Cell::new(item),
+                Cell::from(qty),
+            ]));
+        }
+        table
+    }
+
+    #[test]
+    fn group_by() {
+        let mut table = orders();
+        table.group_by(0, true);
+        table.sort_within_groups(0, SortOrder::Ascending);
+        let out = "\
++--------+-----+
+| Item   | Qty |
++========+=====+
+| Bob          |
++--------+-----+
+| Ink    | 3   |
++--------+-----+
+| Paper  | 12  |
++--------+-----+
+| Alice        |
++--------+-----+
+| Eraser | 2   |
++--------+-----+
+| Pen    | 1   |
++--------+-----+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(table[0][0].get_hspan(), 2);
+
+        // Grouping again replaces the headers
+        table.group_by(1, false);
+        assert_eq!(table.len(), 8);
+        assert_eq!(table[0], ["3"]);
+        assert_eq!(table[0][0].get_hspan(), 2);
+    }
+
+    #[test]
+    fn sort_groups() {
+        let items = |table: &Table| -> Vec<String> {
+            table.row_iter().map(|r| r[0].get_content()).collect()
+        };
+        let mut table = orders();
+        table.group_by(0, true);
+        table.sort_by_column(1, SortOrder::Descending);
+        assert_eq!(
+            items(&table),
+            ["Bob", "Paper", "Ink", "Alice", "Eraser", "Pen"]
+        );
+        table.sort_by(|a, b| a[0].get_content().cmp(&b[0].get_content()));
+        assert_eq!(
+            items(&table),
+            ["Bob", "Ink", "Paper", "Alice", "Eraser", "Pen"]
+        );
+        let view = table
+            .view()
+            .sort_by_column(1, SortOrder::Ascending)
+            .to_table();
+        assert_eq!(
+            items(&view),
+            ["Bob", "Ink", "Paper", "Alice", "Pen", "Eraser"]
+        );
+        assert!(view[0].is_group_header() && view[3].is_group_header());
+    }
+
+    #[test]
+    fn add_group() {
+        let mut table = Table::new();
+        table.add_group("first", vec![Row::new(vec![Cell::new("a")])]);
+        table.add_group(
+            "second",
+            vec![Row::new(vec![Cell::new("b"), Cell::new("c")])],
+        );
+        assert!(table[0].is_group_header());
+        assert!(!table[1].is_group_header());
+        assert_eq!(table[0][0].get_hspan(), 2);
+        assert_eq!(table[2][0].get_hspan(), 2);
+    }
+}
@@ -0,0 +1,317 @@
+//! HTML output customization
+
+use std::fs::File;
+use std::io::{BufWriter, Error, Write};
+use std::path::Path;
+
+use super::cell::html_style;
+use super::utils::{HtmlEscape, StringWriter};
+use super::{AsTableSlice, Row, Table, TableSlice};
+
+/// Options controlling the HTML output of a table.
+///
+/// # Examples
+/// ```rust
+/// # #[macro_use] extern crate prettytable;
+/// use prettytable::HtmlOptions;
+/// # fn main() {
+/// let table = table![[1, 2], [3, 4]];
+/// let options = HtmlOptions {
+///     table_class: Some("data".to_string()),
+///     row_class_fn: Some(Box::new(|i| Some(if i % 2 == 0 { "even" } else { "odd" }.to_string()))),
+///     ..HtmlOptions::default()
+/// };
+/// let mut out = Vec::new();
+/// table.print_html_with_options(&mut out, &options).unwrap();
+/// # }
+/// ```
+#[derive(Default)]
+pub struct HtmlOptions {
+    /// CSS class of the `<table>` element
+    pub table_class: Option<String>,
+    /// Id of the `<table>` element
+    pub table_id: Option<String>,
+    /// CSS class of the titles row element
+    pub header_class: Option<String>,
+    /// Function returning the optional CSS class of a `<tr>` element,
+    /// given the index of the row in the table
+    #[allow(clippy::type_complexity)]
+    pub row_class_fn: Option<Box<dyn Fn(usize) -> Option<String>>>,
+    /// Emit cell colors and attributes as CSS class names (eg. `fg-red`, `bg-bright-blue`,
+    /// `bold`, `italic`, `underline`) instead of inline styles
+    pub style_classes: bool,
+}
+
+/// Style sheet giving HTML documents borders and padding resembling the default format
+const DOCUMENT_STYLE: &str = "\
+table { border-collapse: collapse; }
+td { border: 1px solid; padding: 0 1ch; }
+thead td, th > td { font-weight: bold; }";
+
+/// Format the `class` and `id` attributes of an element
+fn attributes(class: Option<&str>, id: Option<&str>) -> String {
+    let mut attrs = String::new();
+    if let Some(id) = id {
+        attrs += &format!(" id=\"{}\"", HtmlEscape(id));
+    }
+    if let Some(class) = class {
+        attrs += &format!(" class=\"{}\"", HtmlEscape(class));
+    }
+    attrs
+}
+
+/// Format the attributes of the element holding `row`, with the CSS class `class`
+/// and the style of the row
+fn row_attributes(row: &Row, class: Option<String>, options: &HtmlOptions) -> String {
+    let (styles, classes) = match row.get_style() {
+        Some(style) => html_style(&style.attrs(), options),
+        None => (String::new(), Vec::new()),
+    };
+    let class = class
+        .into_iter()
+        .chain(classes)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut attrs = attributes(
+        Some(&class).filter(|c| !c.is_empty()).map(String::as_str),
+        None,
+    );
+    if !styles.is_empty() {
+        attrs += &format!(" style=\"{}\"", styles);
+    }
+    attrs
+}
+
+impl<'a> TableSlice<'a> {
+    /// Print table in HTML format to `out`, using `options` to add
+    /// classes and ids to the generated elements.
+    pub fn print_html_with_options<T: Write + ?Sized>(
+        &self,
+        out: &mut T,
+        options: &HtmlOptions,
+    ) -> Result<(), Error> {
+        // Rows are neither clipped to their maximum height nor striped,
+        // HTML has no screen to fill and stripes are set as classes
+        if !self.render.is_identity() {
+            let table = self.rendered_with(false);
+            return table
+                .as_slice()
+                .print_html_rows(out, options, self.render.is_striped());
+        }
+        self.print_html_rows(out, options, false)
+    }
+
+    /// Print table in HTML format to `out`, as it is, adding the `even` and `odd`
+    /// classes to the rows if `striped` is `true`
+    fn print_html_rows<T: Write + ?Sized>(
+        &self,
+        out: &mut T,
+        options: &HtmlOptions,
+        striped: bool,
+    ) -> Result<(), Error> {
+        // Compute column width
+        let column_num = self.get_column_num();
+        let table_attrs = attributes(options.table_class.as_deref(), options.table_id.as_deref());
+        out.write_all(format!("<table{}>", table_attrs).as_bytes())?;
+        self.caption.print_html(out)?;
+        // Print titles / table header
+        for t in self.header_rows.iter().chain(self.titles.iter()) {
+            let header_attrs = row_attributes(t, options.header_class.clone(), options);
+            out.write_all(format!("<th{}>", header_attrs).as_bytes())?;
+            t.print_html_with_options(out, column_num, options)?;
+            out.write_all(b"</th>")?;
+        }
+        // Print rows
+        let mut stripes = ["even", "odd"].iter().cycle();
+        for (i, r) in self.rows.iter().enumerate() {
+            let mut class = options.row_class_fn.as_ref().and_then(|f| f(i));
+            if striped && !r.is_group_header() {
+                let stripe = stripes.next().unwrap();
+                class = Some(match class {
+                    Some(class) => format!("{} {}", class, stripe),
+                    None => stripe.to_string(),
+                });
+            }
+            out.write_all(format!("<tr{}>", row_attributes(r, class, options)).as_bytes())?;
+            r.print_html_with_options(out, column_num, options)?;
+            out.write_all(b"</tr>")?;
+        }
+        if let Some(ref f) = *self.footer {
+            out.write_all(format!("<tfoot><tr{}>", row_attributes(f, None, options)).as_bytes())?;
+            f.print_html_with_options(out, column_num, options)?;
+            out.write_all(b"</tr></tfoot>")?;
+        }
+        out.write_all(b"</table>")?;
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Return the table as a complete HTML5 document titled `title`, with a style sheet
+    /// giving the table borders and padding.
+    ///
+    /// The table itself is printed the same way as with `print_html()`.
+    pub fn html_document(&self, title: &str) -> String {
+        let mut writer = StringWriter::new();
+        // Writing into a `StringWriter` cannot fail
+        let _ = self.print_html(&mut writer);
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{}</title>\n<style>\n{}\n</style>\n</head>\n<body>\n{}\n</body>\n</html>\n",
+            HtmlEscape(title),
+            DOCUMENT_STYLE,
+            writer.as_string()
+        )
+    }
+
+    /// Save the table as a complete HTML5 document to the file at `path`,
+    /// titled after the file name.
+    ///
+    /// See `html_document()` for details.
+    pub fn save_html<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        let title = path
+            .file_stem()
+            .map(|s| s.to_string_lossy())
+            .unwrap_or_else(|| "Table".into());
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(self.html_document(&title).as_bytes())?;
+        file.flush()
+    }
+}
+
+impl Table {
+    /// Print table in HTML format to `out`, using `options` to add
+    /// classes and ids to the generated elements.
+    pub fn print_html_with_options<T: Write + ?Sized>(
+        &self,
+        out: &mut T,
+        options: &HtmlOptions,
+    ) -> Result<(), Error> {
+        self.as_slice().print_html_with_options(out, options)
+    }
+
+    /// Return the table as a complete HTML5 document titled `title`.
+    ///
+    /// See `TableSlice::html_document()` for details.
+    pub fn html_document(&self, title: &str) -> String {
+        self.as_slice().html_document(title)
+    }
+
+    /// Save the table as a complete HTML5 document to the file at `path`.
+    ///
+    /// See `TableSlice::save_html()` for details.
+    pub fn save_html<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        self.as_slice().save_html(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HtmlOptions;
+    use crate::utils::StringWriter;
+    use crate::{Cell, Row, Table};
+
+    #[test]
+    fn html_title_rows_and_footer() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("1")]));
+        table.add_title_row(Row::new(vec![Cell::new("Group").with_hspan(2)]));
+        table.add_title_row(Row::new(vec![Cell::new("t1"), Cell::new("t2")]));
+        table.set_footer(Row::new(vec![Cell::new("Total").with_hspan(2)]));
+        let mut writer = StringWriter::new();
+        table.print_html(&mut writer).unwrap();
+        assert!(writer.as_string().starts_with(
+            "<table><th><td colspan=\"2\" style=\"text-align: left;\">Group</td></th><th><td"
+        ));
+        assert!(writer.as_string().ends_with(
+            "</tr><tfoot><tr><td colspan=\"2\" style=\"text-align: left;\">Total</td></tr></tfoot></table>"
+        ));
+    }
+
+    #[test]
+    fn html_with_options() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("t1")]));
+        table.add_row(Row::new(vec![Cell::new("a")]));
+        table.add_row(Row::new(vec![Cell::new("b")]));
+        let options = HtmlOptions {
+            table_class: Some("data".to_string()),
+            table_id: Some("my\"id".to_string()),
+            header_class: Some("head".to_string()),
+            row_class_fn: Some(Box::new(|i| {
+                if i == 1 {
+                    Some("odd".to_string())
+                } else {
+                    None
+                }
+            })),
+            ..HtmlOptions::default()
+        };
+        let out = "\
+<table id=\"my&quot;id\" class=\"data\">\
+<th class=\"head\"><td style=\"text-align: left;\">t1</td></th>\
+<tr><td style=\"text-align: left;\">a</td></tr>\
+<tr class=\"odd\"><td style=\"text-align: left;\">b</td></tr>\
+</table>";
+        let mut writer = StringWriter::new();
+        assert!(table.print_html_with_options(&mut writer, &options).is_ok());
+        assert_eq!(writer.as_string(), out);
+
+        let mut default = StringWriter::new();
+        let mut plain = StringWriter::new();
+        table
+            .print_html_with_options(&mut default, &HtmlOptions::default())
+            .unwrap();
+        table.print_html(&mut plain).unwrap();
+        assert_eq!(default.as_string(), plain.as_string());
+    }
+
+    #[test]
+    fn html_row_style() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("t1")]));
+        table.add_row(Row::new(vec![
+            Cell::new("a"),
+            Cell::new("b").style_spec("Fg"),
+        ]));
+        table.set_row_style(0, "bc").unwrap();
+        let mut titles = Row::new(vec![Cell::new("t1")]);
+        titles.set_style_spec("i");
+        table.set_titles(titles);
+        assert!(table.set_row_style(1, "b").is_err());
+        let out = "\
+<table>\
+<th style=\"font-style: italic;\"><td style=\"text-align: left;\">t1</td><td style=\"text-align: left;\"></td></th>\
+<tr style=\"font-weight: bold;\"><td style=\"text-align: center;\">a</td>\
+<td style=\"color: #00aa00;text-align: center;\">b</td></tr>\
+</table>";
+        let mut writer = StringWriter::new();
+        table.print_html(&mut writer).unwrap();
+        assert_eq!(writer.as_string(), out);
+
+        let options = HtmlOptions {
+            style_classes: true,
+            row_class_fn: Some(Box::new(|_| Some("row".to_string()))),
+            ..HtmlOptions::default()
+        };
+        let mut writer = StringWriter::new();
+        table
+            .print_html_with_options(&mut writer, &options)
+            .unwrap();
+        assert!(writer.as_string().contains("<tr class=\"row bold\">"));
+    }
+
+    #[test]
+    fn html_document() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a")]));
+        let doc = table.html_document("<T>");
+        assert!(doc.starts_with("<!DOCTYPE html>\n<html>\n"));
+        assert!(doc.contains("<title>&lt;T&gt;</title>"));
+        assert!(doc.contains("<style>\ntable { border-collapse: collapse; }"));
+        let mut fragment = StringWriter::new();
+        table.print_html(&mut fragment).unwrap();
+        assert!(doc.contains(&format!("<body>\n{}\n</body>", fragment.as_string())));
+        assert!(doc.ends_with("</html>\n"));
+    }
+}
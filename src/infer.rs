@@ -0,0 +1,208 @@
+//! Inference of the kind of values held by table columns
+
+use super::format::Alignment;
+use super::summary::parse_value;
+use super::Table;
+
+/// Share of the non-empty cells of a column which must hold values of a kind
+/// for the column to be of that kind
+const INFERENCE_THRESHOLD: f64 = 0.9;
+
+/// Kind of the values of a column, as found by `Table::infer_column_kinds()`
+#[derive(Clone, Debug, Copy, Hash, PartialEq, Eq)]
+pub enum ColumnKind {
+    /// Any text, or an empty column
+    Text,
+    /// Numbers, possibly with separators between groups of thousands
+    Number,
+    /// Dates like `2024-03-01` or `01/03/2024`, possibly followed by a time
+    Date,
+    /// Booleans, written `true`, `false`, `yes` or `no` in any case
+    Boolean,
+}
+
+impl ColumnKind {
+    /// Return the kind of the values `texts`, ignoring the empty ones
+    fn infer<I: Iterator<Item = String>>(texts: I) -> ColumnKind {
+        let texts = texts
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect::<Vec<_>>();
+        let matching = |f: fn(&str) -> bool| {
+            let count = texts.iter().filter(|t| f(t)).count();
+            !texts.is_empty() && count as f64 >= texts.len() as f64 * INFERENCE_THRESHOLD
+        };
+        if matching(is_boolean) {
+            ColumnKind::Boolean
+        } else if matching(|t| parse_value(t).is_some()) {
+            ColumnKind::Number
+        } else if matching(is_date) {
+            ColumnKind::Date
+        } else {
+            ColumnKind::Text
+        }
+    }
+}
+
+/// Tell if `text` is a boolean
+fn is_boolean(text: &str) -> bool {
+    ["true", "false", "yes", "no"]
+        .iter()
+        .any(|b| text.eq_ignore_ascii_case(b))
+}
+
+/// Tell if `text` is a date, written year first with `-` or `/` separators, or year last
+/// with `/` separators, optionally followed by a time after a space or a `T`
+fn is_date(text: &str) -> bool {
+    let (date, time) = match text.find([' ', 'T']) {
+        Some(i) => (&text[..i], Some(&text[i + 1..])),
+        None => (text, None),
+    };
+    let fields = date
+        .split(['-', '/'])
+        .map(|f| match f.len() {
+            1..=4 if f.bytes().all(|b| b.is_ascii_digit()) => f.parse().ok().map(|v| (f.len(), v)),
+            _ => None,
+        })
+        .collect::<Option<Vec<(usize, u32)>>>();
+    let day = |month: u32, day: u32| (1..=12).contains(&month) && (1..=31).contains(&day);
+    let valid = match fields.as_deref() {
+        Some(&[(4, _), (_, m), (_, d)]) => day(m, d),
+        Some(&[(_, a), (_, b), (4, _)]) if !date.contains('-') => day(a, b) || day(b, a),
+        _ => false,
+    };
+    valid && time.is_none_or(is_time)
+}
+
+/// Tell if `text` is a time starting with hours and minutes, like `12:30`, `12:30:05.250`
+/// or `12:30:05+02:00`
+fn is_time(text: &str) -> bool {
+    let number = |range: std::ops::Range<usize>, max| {
+        text.get(range)
+            .filter(|t| t.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|t| t.parse::<u32>().ok())
+            .is_some_and(|v| v < max)
+    };
+    number(0..2, 24)
+        && text.get(2..3) == Some(":")
+        && number(3..5, 60)
+        && text[5..]
+            .chars()
+            .all(|c| c.is_ascii_digit() || ":.+-Z".contains(c))
+}
+
+impl Table {
+    /// Return the kind of the values of each column, from the text of their cells without
+    /// ANSI escape sequences. Titles, header rows, footer and group headers are ignored.
+    ///
+    /// A column is of a kind when at least 90% of its non-empty cells hold values of that
+    /// kind, so a few cells like `n/a` do not make a column of numbers a column of text.
+    /// Columns without any value are `ColumnKind::Text`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// use prettytable::ColumnKind;
+    /// # fn main() {
+    /// let table = table![["db", "1,500", "2024-03-01", "yes"], ["web", "n/a", "", "no"]];
+    /// assert_eq!(
+    ///     table.infer_column_kinds(),
+    ///     [ColumnKind::Text, ColumnKind::Text, ColumnKind::Date, ColumnKind::Boolean]
+    /// );
+    /// # }
+    /// ```
+    pub fn infer_column_kinds(&self) -> Vec<ColumnKind> {
+        (0..self.column_count())
+            .map(|column| ColumnKind::infer(self.column_texts(column)))
+            .collect()
+    }
+
+    /// Infer the kind of the values of each column, like `infer_column_kinds()`, and align
+    /// the columns of numbers to `RIGHT` and of booleans to `CENTER`, as with
+    /// `set_column_alignment()`. The alignment of the other columns is unchanged.
+    /// Return the kind of each column.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut table = table![["Bolts", 1500, "yes"], ["Nuts", 25, "no"]];
+    /// table.infer_column_formats();
+    /// assert_eq!(table.to_string().replace("\r\n", "\n"), "\
+    /// +-------+------+-----+
+    /// | Bolts | 1500 | yes |
+    /// +-------+------+-----+
+    /// | Nuts  |   25 | no  |
+    /// +-------+------+-----+
+    /// ");
+    /// # }
+    /// ```
+    pub fn infer_column_formats(&mut self) -> Vec<ColumnKind> {
+        let kinds = self.infer_column_kinds();
+        for (column, kind) in kinds.iter().enumerate() {
+            match kind {
+                ColumnKind::Number => self.set_column_alignment(column, Alignment::RIGHT),
+                ColumnKind::Boolean => self.set_column_alignment(column, Alignment::CENTER),
+                ColumnKind::Text | ColumnKind::Date => {}
+            }
+        }
+        kinds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_date, ColumnKind};
+    use crate::{Cell, Row, Table};
+
+    #[test]
+    fn dates() {
+        for date in ["2024-03-01", "2024/3/1", "01/03/2024", "12/31/1999"] {
+            assert!(is_date(date), "{}", date);
+        }
+        for date in [
+            "2024-03-01T12:30:05Z",
+            "2024-03-01 08:00",
+            "2024-03-01T12:30+02:00",
+        ] {
+            assert!(is_date(date), "{}", date);
+        }
+        for text in [
+            "2024-13-01",
+            "01-03-2024",
+            "13/13/2024",
+            "2024-03",
+            "2024-03-01 noon",
+        ] {
+            assert!(!is_date(text), "{}", text);
+        }
+        assert!(!is_date("2024-03-01T25:00"));
+    }
+
+    #[test]
+    fn infer_column_kinds() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("name"), Cell::new("count")]));
+        for i in 0..9 {
+            table.add_row(Row::new(vec![
+                Cell::new(&format!("item {}", i)),
+                Cell::new("\u{1b}[1m1,250.5\u{1b}[0m"),
+                Cell::new("TRUE"),
+            ]));
+        }
+        table.add_row(Row::new(vec![Cell::new(""), Cell::new("n/a")]));
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new(""), Cell::new("")]));
+        assert_eq!(
+            table.infer_column_formats(),
+            [ColumnKind::Text, ColumnKind::Number, ColumnKind::Boolean]
+        );
+        assert!(table.to_string().contains("|        |     n/a |      |"));
+
+        table.add_row(Row::new(vec![Cell::new(""), Cell::new("-")]));
+        assert_eq!(table.infer_column_kinds()[1], ColumnKind::Text);
+        assert_eq!(Table::new().infer_column_kinds(), []);
+        let mut empty = Table::new();
+        empty.add_row(Row::new(vec![Cell::new(" ")]));
+        assert_eq!(empty.infer_column_kinds(), [ColumnKind::Text]);
+    }
+}
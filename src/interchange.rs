@@ -0,0 +1,1250 @@
+//! Serialization of tables, rows, cells and formats with `serde`
+//!
+//! Values are written as structs with named fields, and enumerations as lowercase
+//! strings, so that they can be read by any `serde` format, like JSON or YAML.
+//! The names and representations of the fields are stable across patch versions.
+//! Unknown fields are ignored and missing fields take their default value when
+//! deserializing, so that data written by other versions can still be read.
+//! The fields of each type are documented on its `Serialize` implementation.
+
+use std::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::ser::{self, Serialize, SerializeStruct, Serializer};
+
+use std::collections::BTreeSet;
+
+use super::caption::{Caption, CaptionPosition};
+use super::format::{
+    consts, Alignment, ColumnPosition, ColumnWidth, LinePosition, LineSeparator, TableFormat,
+    VerticalAlignment, WidthMode,
+};
+use super::render::RenderOptions;
+use super::{Attr, Cell, CellStyle, NumberFormat, Row, RowNumbers, Rule, Table};
+
+/// Define a struct written with all its fields by `Serialize`, and read back by
+/// `Deserialize` from a map or from a sequence of the fields in order. Missing fields
+/// take the given default values, and unknown fields are ignored.
+macro_rules! serde_struct {
+    ($name:ident as $public:literal { $($field:ident: $ty:ty = $default:expr),* $(,)? }) => {
+        struct $name {
+            $($field: $ty),*
+        }
+
+        impl Default for $name {
+            fn default() -> $name {
+                $name {
+                    $($field: $default),*
+                }
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                const FIELDS: &[&str] = &[$(stringify!($field)),*];
+                let mut s = serializer.serialize_struct($public, FIELDS.len())?;
+                $(s.serialize_field(stringify!($field), &self.$field)?;)*
+                s.end()
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<$name, D::Error> {
+                struct FieldsVisitor;
+
+                impl<'de> Visitor<'de> for FieldsVisitor {
+                    type Value = $name;
+
+                    fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                        write!(fmt, "a {}", $public)
+                    }
+
+                    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<$name, A::Error> {
+                        let mut value = $name::default();
+                        while let Some(key) = map.next_key::<String>()? {
+                            match key.as_str() {
+                                $(stringify!($field) => value.$field = map.next_value()?,)*
+                                _ => {
+                                    map.next_value::<IgnoredAny>()?;
+                                }
+                            }
+                        }
+                        Ok(value)
+                    }
+
+                    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<$name, A::Error> {
+                        let mut value = $name::default();
+                        $(match seq.next_element()? {
+                            Some(field) => value.$field = field,
+                            None => return Ok(value),
+                        })*
+                        Ok(value)
+                    }
+                }
+
+                const FIELDS: &[&str] = &[$(stringify!($field)),*];
+                deserializer.deserialize_struct($public, FIELDS, FieldsVisitor)
+            }
+        }
+    };
+}
+
+/// Return the name of alignment `align`
+fn align_name(align: Alignment) -> String {
+    match align {
+        Alignment::LEFT => "left",
+        Alignment::CENTER => "center",
+        Alignment::RIGHT => "right",
+    }
+    .to_string()
+}
+
+/// Return the alignment named `name`
+fn parse_align(name: &str) -> Result<Alignment, String> {
+    match name {
+        "left" => Ok(Alignment::LEFT),
+        "center" => Ok(Alignment::CENTER),
+        "right" => Ok(Alignment::RIGHT),
+        _ => Err(format!("Unknown alignment {:?}", name)),
+    }
+}
+
+/// Return the name of the terminal attribute `attr`
+fn attr_name(attr: Attr) -> String {
+    let flag = |set, name: &str| match set {
+        true => name.to_string(),
+        false => format!("no-{}", name),
+    };
+    match attr {
+        Attr::Bold => "bold".to_string(),
+        Attr::Dim => "dim".to_string(),
+        Attr::Italic(set) => flag(set, "italic"),
+        Attr::Underline(set) => flag(set, "underline"),
+        Attr::Blink => "blink".to_string(),
+        Attr::Standout(set) => flag(set, "standout"),
+        Attr::Reverse => "reverse".to_string(),
+        Attr::Secure => "secure".to_string(),
+        Attr::ForegroundColor(color) => format!("fg:{}", color),
+        Attr::BackgroundColor(color) => format!("bg:{}", color),
+    }
+}
+
+/// Return the name of the width constraint `width`
+fn width_name(width: ColumnWidth) -> String {
+    match width {
+        ColumnWidth::Fixed(w) => format!("fixed:{}", w),
+        ColumnWidth::Min(w) => format!("min:{}", w),
+        ColumnWidth::Max(w) => format!("max:{}", w),
+        ColumnWidth::Range(min, max) => format!("range:{}:{}", min, max),
+    }
+}
+
+/// Return the width constraint named `name`
+fn parse_width(name: &str) -> Result<ColumnWidth, String> {
+    let error = || format!("Invalid column width {:?}", name);
+    let mut parts = name.split(':');
+    let kind = parts.next().unwrap_or_default();
+    let widths = parts
+        .map(|w| w.parse().map_err(|_| error()))
+        .collect::<Result<Vec<usize>, _>>()?;
+    match (kind, widths.as_slice()) {
+        ("fixed", &[w]) => Ok(ColumnWidth::Fixed(w)),
+        ("min", &[w]) => Ok(ColumnWidth::Min(w)),
+        ("max", &[w]) => Ok(ColumnWidth::Max(w)),
+        ("range", &[min, max]) => Ok(ColumnWidth::Range(min, max)),
+        _ => Err(error()),
+    }
+}
+
+/// Parse the style spec `spec`
+fn parse_style(spec: &str) -> Result<CellStyle, String> {
+    CellStyle::try_from_spec(spec).map_err(|e| e.to_string())
+}
+
+/// Return the terminal attribute named `name`
+fn parse_attr(name: &str) -> Result<Attr, String> {
+    let color = |number: &str| {
+        number
+            .parse()
+            .map_err(|_| format!("Invalid color in style {:?}", name))
+    };
+    match name {
+        "bold" => Ok(Attr::Bold),
+        "dim" => Ok(Attr::Dim),
+        "italic" => Ok(Attr::Italic(true)),
+        "no-italic" => Ok(Attr::Italic(false)),
+        "underline" => Ok(Attr::Underline(true)),
+        "no-underline" => Ok(Attr::Underline(false)),
+        "blink" => Ok(Attr::Blink),
+        "standout" => Ok(Attr::Standout(true)),
+        "no-standout" => Ok(Attr::Standout(false)),
+        "reverse" => Ok(Attr::Reverse),
+        "secure" => Ok(Attr::Secure),
+        _ if name.starts_with("fg:") => color(&name[3..]).map(Attr::ForegroundColor),
+        _ if name.starts_with("bg:") => color(&name[3..]).map(Attr::BackgroundColor),
+        _ => Err(format!("Unknown style {:?}", name)),
+    }
+}
+
+serde_struct!(CellData as "Cell" {
+    content: String = String::new(),
+    align: Option<String> = None,
+    valign: String = "top".to_string(),
+    style: Vec<String> = Vec::new(),
+    hspan: usize = 1,
+    max_width: Option<usize> = None,
+    truncate: Option<(usize, String)> = None,
+    url: Option<String> = None,
+    padding: Option<(usize, usize)> = None,
+    fill: char = ' ',
+    html_attrs: Vec<(String, String)> = Vec::new(),
+});
+
+impl CellData {
+    fn from_cell(cell: &Cell) -> CellData {
+        let valign = match cell.get_vertical_align() {
+            VerticalAlignment::TOP => "top",
+            VerticalAlignment::MIDDLE => "middle",
+            VerticalAlignment::BOTTOM => "bottom",
+        };
+        CellData {
+            content: cell.get_content(),
+            align: cell.get_explicit_align().map(align_name),
+            valign: valign.to_string(),
+            style: cell.get_style().iter().map(|&a| attr_name(a)).collect(),
+            hspan: cell.get_hspan(),
+            max_width: cell.get_max_width(),
+            truncate: cell.get_truncate().map(|(w, m)| (w, m.to_string())),
+            url: cell.get_hyperlink().map(str::to_string),
+            padding: cell.get_padding(),
+            fill: cell.get_fill(),
+            html_attrs: cell.get_html_attrs().to_vec(),
+        }
+    }
+
+    fn into_cell(self) -> Result<Cell, String> {
+        let mut cell = match self.align {
+            Some(ref align) => Cell::new_align(&self.content, parse_align(align)?),
+            None => Cell::new(&self.content),
+        };
+        cell.set_vertical_align(match self.valign.as_str() {
+            "top" => VerticalAlignment::TOP,
+            "middle" => VerticalAlignment::MIDDLE,
+            "bottom" => VerticalAlignment::BOTTOM,
+            valign => return Err(format!("Unknown vertical alignment {:?}", valign)),
+        });
+        for attr in &self.style {
+            cell.style(parse_attr(attr)?);
+        }
+        cell.set_hspan(self.hspan);
+        if let Some(width) = self.max_width {
+            cell.set_max_width(width);
+        }
+        if let Some((width, ref marker)) = self.truncate {
+            cell.set_truncate(width, marker);
+        }
+        if let Some(ref url) = self.url {
+            cell = cell.with_hyperlink(url);
+        }
+        if let Some((left, right)) = self.padding {
+            cell.set_padding(left, right);
+        }
+        cell.set_fill(self.fill);
+        cell.set_html_attrs(self.html_attrs);
+        Ok(cell)
+    }
+}
+
+serde_struct!(RowData as "Row" {
+    cells: Vec<CellData> = Vec::new(),
+    style: Option<String> = None,
+    separator_below: bool = false,
+    group_header: bool = false,
+    max_height: Option<usize> = None,
+});
+
+impl RowData {
+    fn from_row(row: &Row) -> RowData {
+        RowData {
+            cells: row.iter().map(CellData::from_cell).collect(),
+            style: row.get_style().map(|s| s.to_spec()),
+            separator_below: row.has_separator_below(),
+            group_header: row.is_group_header(),
+            max_height: row.get_max_height(),
+        }
+    }
+
+    fn into_row(self) -> Result<Row, String> {
+        let cells = self
+            .cells
+            .into_iter()
+            .map(CellData::into_cell)
+            .collect::<Result<_, _>>()?;
+        let mut row = match self.group_header {
+            true => Row::group_header("").with_cells(cells),
+            false => Row::new(cells),
+        };
+        if let Some(ref spec) = self.style {
+            row.set_style(parse_style(spec)?);
+        }
+        row.set_separator_below(self.separator_below);
+        if let Some(lines) = self.max_height {
+            row.set_max_height(lines);
+        }
+        Ok(row)
+    }
+}
+
+serde_struct!(SeparatorData as "LineSeparator" {
+    line: char = '-',
+    junction: char = '+',
+    left_junction: char = '+',
+    right_junction: char = '+',
+});
+
+impl SeparatorData {
+    fn from_separator(separator: LineSeparator) -> SeparatorData {
+        let (line, junction, left_junction, right_junction) = separator.get_chars();
+        SeparatorData {
+            line,
+            junction,
+            left_junction,
+            right_junction,
+        }
+    }
+
+    fn to_separator(&self) -> LineSeparator {
+        LineSeparator::new(
+            self.line,
+            self.junction,
+            self.left_junction,
+            self.right_junction,
+        )
+    }
+}
+
+serde_struct!(FormatData as "TableFormat" {
+    column_separator: Option<char> = None,
+    left_border: Option<char> = None,
+    right_border: Option<char> = None,
+    top_separator: Option<SeparatorData> = None,
+    title_separator: Option<SeparatorData> = None,
+    inner_separator: Option<SeparatorData> = None,
+    bottom_separator: Option<SeparatorData> = None,
+    padding: (usize, usize) = TableFormat::new().get_padding(),
+    indent: usize = 0,
+    width_mode: String = "unicode".to_string(),
+    tab_width: usize = TableFormat::new().get_tab_width(),
+});
+
+impl FormatData {
+    fn from_format(format: &TableFormat) -> Result<FormatData, String> {
+        let separator = |pos| {
+            format
+                .get_own_separator(pos)
+                .map(SeparatorData::from_separator)
+        };
+        let width_mode = match format.get_width_mode() {
+            WidthMode::Unicode => "unicode",
+            WidthMode::Graphemes => "graphemes",
+            WidthMode::Custom(_) => return Err("Custom width modes cannot be serialized".into()),
+        };
+        Ok(FormatData {
+            column_separator: format.get_column_separator(ColumnPosition::Intern),
+            left_border: format.get_column_separator(ColumnPosition::Left),
+            right_border: format.get_column_separator(ColumnPosition::Right),
+            top_separator: separator(LinePosition::Top),
+            title_separator: separator(LinePosition::Title),
+            inner_separator: separator(LinePosition::Intern),
+            bottom_separator: separator(LinePosition::Bottom),
+            padding: format.get_padding(),
+            indent: format.get_indent(),
+            width_mode: width_mode.to_string(),
+            tab_width: format.get_tab_width(),
+        })
+    }
+
+    fn to_format(&self) -> Result<TableFormat, String> {
+        let mut format = TableFormat::new();
+        if let Some(c) = self.column_separator {
+            format.column_separator(c);
+        }
+        if let Some(c) = self.left_border {
+            format.left_border(c);
+        }
+        if let Some(c) = self.right_border {
+            format.right_border(c);
+        }
+        let separators = [
+            (LinePosition::Top, &self.top_separator),
+            (LinePosition::Title, &self.title_separator),
+            (LinePosition::Intern, &self.inner_separator),
+            (LinePosition::Bottom, &self.bottom_separator),
+        ];
+        for (pos, separator) in separators {
+            if let Some(separator) = separator {
+                format.separator(pos, separator.to_separator());
+            }
+        }
+        format.padding(self.padding.0, self.padding.1);
+        format.indent(self.indent);
+        format.width_mode(match self.width_mode.as_str() {
+            "unicode" => WidthMode::Unicode,
+            "graphemes" => WidthMode::Graphemes,
+            mode => return Err(format!("Unknown width mode {:?}", mode)),
+        });
+        format.set_tab_width(self.tab_width);
+        Ok(format)
+    }
+}
+
+serde_struct!(CaptionData as "Caption" {
+    text: Option<String> = None,
+    align: String = "center".to_string(),
+    position: String = "above".to_string(),
+});
+
+serde_struct!(NumberFormatData as "NumberFormat" {
+    decimals: Option<usize> = None,
+    thousands: Option<char> = None,
+});
+
+serde_struct!(ColumnData as "ColumnSettings" {
+    column: usize = 0,
+    placeholder: Option<String> = None,
+    merged: bool = false,
+    hide_separators: bool = false,
+    align: Option<String> = None,
+    width: Option<String> = None,
+    priority: Option<u8> = None,
+    number_format: Option<NumberFormatData> = None,
+});
+
+serde_struct!(RuleData as "FormatRule" {
+    column: usize = 0,
+    condition: String = "matches".to_string(),
+    numbers: Vec<f64> = Vec::new(),
+    pattern: Option<String> = None,
+    style: String = String::new(),
+});
+
+impl RuleData {
+    fn from_rule(column: usize, rule: &Rule, style: &CellStyle) -> Result<RuleData, String> {
+        let (condition, numbers, pattern) = match *rule {
+            Rule::GreaterThan(n) => ("greater_than", vec![n], None),
+            Rule::LessThan(n) => ("less_than", vec![n], None),
+            Rule::Between(min, max) => ("between", vec![min, max], None),
+            Rule::Matches(ref pattern) => ("matches", Vec::new(), Some(pattern.clone())),
+            Rule::Custom(_) => return Err("Custom format rules cannot be serialized".into()),
+        };
+        Ok(RuleData {
+            column,
+            condition: condition.to_string(),
+            numbers,
+            pattern,
+            style: style.to_spec(),
+        })
+    }
+
+    fn into_rule(self) -> Result<(usize, Rule, CellStyle), String> {
+        let rule = match (self.condition.as_str(), self.numbers.as_slice()) {
+            ("greater_than", &[n]) => Rule::GreaterThan(n),
+            ("less_than", &[n]) => Rule::LessThan(n),
+            ("between", &[min, max]) => Rule::Between(min, max),
+            ("matches", []) => Rule::Matches(self.pattern.unwrap_or_default()),
+            (condition, _) => return Err(format!("Invalid format rule {:?}", condition)),
+        };
+        Ok((self.column, rule, parse_style(&self.style)?))
+    }
+}
+
+serde_struct!(RowNumbersData as "RowNumbers" {
+    header: String = RowNumbers::default().header,
+    start: usize = RowNumbers::default().start,
+    original: bool = false,
+    exported: bool = false,
+});
+
+serde_struct!(DisplayData as "DisplaySettings" {
+    placeholder: Option<String> = None,
+    max_row_height: Option<usize> = None,
+    height_marker: Option<String> = None,
+    striping: Option<(String, String)> = None,
+    frozen_columns: usize = 0,
+    row_numbers: Option<RowNumbersData> = None,
+    max_width: Option<usize> = None,
+    columns: Vec<ColumnData> = Vec::new(),
+    format_rules: Vec<RuleData> = Vec::new(),
+});
+
+impl DisplayData {
+    fn from_options(options: &RenderOptions) -> Result<DisplayData, String> {
+        let columns = options
+            .column_placeholders
+            .keys()
+            .chain(options.merged_columns.keys())
+            .chain(options.column_alignments.keys())
+            .chain(options.column_widths.keys())
+            .chain(options.column_priorities.keys())
+            .chain(options.number_formats.keys())
+            .collect::<BTreeSet<_>>();
+        let columns = columns
+            .into_iter()
+            .map(|column| {
+                let merged = options.merged_columns.get(column);
+                ColumnData {
+                    column: *column,
+                    placeholder: options.column_placeholders.get(column).cloned(),
+                    merged: merged.is_some(),
+                    hide_separators: merged == Some(&true),
+                    align: options
+                        .column_alignments
+                        .get(column)
+                        .map(|&a| align_name(a)),
+                    width: options.column_widths.get(column).map(|&w| width_name(w)),
+                    priority: options.column_priorities.get(column).copied(),
+                    number_format: options.number_formats.get(column).map(|format| {
+                        NumberFormatData {
+                            decimals: format.decimals,
+                            thousands: format.thousands,
+                        }
+                    }),
+                }
+            })
+            .collect();
+        let format_rules = options
+            .format_rules
+            .iter()
+            .map(|(column, rule, style)| RuleData::from_rule(*column, rule, style))
+            .collect::<Result<_, _>>()?;
+        Ok(DisplayData {
+            placeholder: options.placeholder.clone(),
+            max_row_height: options.max_row_height,
+            height_marker: options.height_marker.clone(),
+            striping: options
+                .striping
+                .map(|(even, odd)| (even.to_spec(), odd.to_spec())),
+            frozen_columns: options.frozen_columns,
+            row_numbers: options.row_numbers.as_ref().map(|numbers| RowNumbersData {
+                header: numbers.header.clone(),
+                start: numbers.start,
+                original: numbers.original,
+                exported: numbers.exported,
+            }),
+            max_width: options.max_width,
+            columns,
+            format_rules,
+        })
+    }
+
+    fn into_options(self) -> Result<RenderOptions, String> {
+        let mut options = RenderOptions {
+            placeholder: self.placeholder,
+            max_row_height: self.max_row_height,
+            height_marker: self.height_marker,
+            frozen_columns: self.frozen_columns,
+            row_numbers: self.row_numbers.map(|numbers| RowNumbers {
+                header: numbers.header,
+                start: numbers.start,
+                original: numbers.original,
+                exported: numbers.exported,
+            }),
+            max_width: self.max_width,
+            ..RenderOptions::default()
+        };
+        if let Some((ref even, ref odd)) = self.striping {
+            options.striping = Some((parse_style(even)?, parse_style(odd)?));
+        }
+        for data in self.columns {
+            let column = data.column;
+            if let Some(placeholder) = data.placeholder {
+                options.column_placeholders.insert(column, placeholder);
+            }
+            if data.merged {
+                options.merged_columns.insert(column, data.hide_separators);
+            }
+            if let Some(ref align) = data.align {
+                options
+                    .column_alignments
+                    .insert(column, parse_align(align)?);
+            }
+            if let Some(ref width) = data.width {
+                options.column_widths.insert(column, parse_width(width)?);
+            }
+            if let Some(priority) = data.priority {
+                options.column_priorities.insert(column, priority);
+            }
+            if let Some(format) = data.number_format {
+                let format = NumberFormat {
+                    decimals: format.decimals,
+                    thousands: format.thousands,
+                };
+                options.number_formats.insert(column, format);
+            }
+        }
+        options.format_rules = self
+            .format_rules
+            .into_iter()
+            .map(RuleData::into_rule)
+            .collect::<Result<_, _>>()?;
+        Ok(options)
+    }
+}
+
+serde_struct!(TableData as "Table" {
+    format: FormatData = FormatData::from_format(&consts::FORMAT_DEFAULT).unwrap_or_default(),
+    titles: Option<RowData> = None,
+    header_rows: Vec<RowData> = Vec::new(),
+    rows: Vec<RowData> = Vec::new(),
+    footer: Option<RowData> = None,
+    caption: CaptionData = CaptionData::default(),
+    display: DisplayData = DisplayData::default(),
+});
+
+impl TableData {
+    fn from_table(table: &Table) -> Result<TableData, String> {
+        let caption = &table.caption;
+        let position = match caption.position {
+            CaptionPosition::Above => "above",
+            CaptionPosition::Below => "below",
+        };
+        Ok(TableData {
+            format: FormatData::from_format(&table.format)?,
+            titles: (*table.titles).as_ref().map(RowData::from_row),
+            header_rows: table.header_rows.iter().map(RowData::from_row).collect(),
+            rows: table.rows.iter().map(RowData::from_row).collect(),
+            footer: (*table.footer).as_ref().map(RowData::from_row),
+            caption: CaptionData {
+                text: caption.text.clone(),
+                align: align_name(caption.align),
+                position: position.to_string(),
+            },
+            display: DisplayData::from_options(&table.render)?,
+        })
+    }
+
+    fn into_table(self) -> Result<Table, String> {
+        let rows = |rows: Vec<RowData>| {
+            rows.into_iter()
+                .map(RowData::into_row)
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let mut table = Table::init(rows(self.rows)?);
+        table.set_format(self.format.to_format()?);
+        *table.titles = self.titles.map(RowData::into_row).transpose()?;
+        table.header_rows = rows(self.header_rows)?;
+        *table.footer = self.footer.map(RowData::into_row).transpose()?;
+        table.caption = Caption {
+            text: self.caption.text,
+            align: parse_align(&self.caption.align)?,
+            position: match self.caption.position.as_str() {
+                "above" => CaptionPosition::Above,
+                "below" => CaptionPosition::Below,
+                position => return Err(format!("Unknown caption position {:?}", position)),
+            },
+        };
+        table.render = self.display.into_options()?;
+        Ok(table)
+    }
+}
+
+/// Cells are written as structs with the fields:
+/// * `content`: the text of the cell. Cells produced on demand or holding a nested
+///   table are written with their text.
+/// * `align`: `"left"`, `"center"`, `"right"`, or `null` to follow the column
+/// * `valign`: `"top"`, `"middle"` or `"bottom"`
+/// * `style`: the list of attributes, among `"bold"`, `"dim"`, `"italic"`, `"no-italic"`,
+///   `"underline"`, `"no-underline"`, `"blink"`, `"standout"`, `"no-standout"`,
+///   `"reverse"`, `"secure"`, and colors as `"fg:N"` or `"bg:N"` with a terminal color number
+/// * `hspan`, `max_width`, `truncate` as `[width, marker]`, `url`, `padding`
+///   as `[left, right]`, `fill` and `html_attrs` as a list of `[name, value]`
+impl Serialize for Cell {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        CellData::from_cell(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Cell {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Cell, D::Error> {
+        CellData::deserialize(deserializer)?
+            .into_cell()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Rows are written as structs with their `cells`, their `style` as a style spec
+/// (see `Cell::style_spec()`), `separator_below`, `group_header` and `max_height`
+impl Serialize for Row {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RowData::from_row(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Row {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Row, D::Error> {
+        RowData::deserialize(deserializer)?
+            .into_row()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Formats are written as structs with the `column_separator`, `left_border` and
+/// `right_border` characters, the `top_separator`, `title_separator`, `inner_separator`
+/// and `bottom_separator` lines as structs of `line`, `junction`, `left_junction` and
+/// `right_junction` characters, `padding` as `[left, right]`, `indent`, `width_mode`
+/// (`"unicode"` or `"graphemes"`) and `tab_width`. Formats with a `WidthMode::Custom`
+/// width mode fail to serialize.
+impl Serialize for TableFormat {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        FormatData::from_format(self)
+            .map_err(ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TableFormat {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<TableFormat, D::Error> {
+        FormatData::deserialize(deserializer)?
+            .to_format()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Tables are written as structs with their `format`, `titles`, `header_rows`, `rows`,
+/// `footer`, `caption`, as a struct of `text`, `align` and `position` (`"above"` or
+/// `"below"`), and `display`, the display settings set on the table, as a struct of:
+/// * `placeholder`, `max_row_height`, `height_marker`, `striping` as `[even, odd]` style
+///   specs, `frozen_columns` and `max_width`
+/// * `row_numbers`: `null`, or a struct of `header`, `start`, `original` and `exported`
+/// * `columns`: the settings of each column, as structs of `column`, the index of the
+///   column, `placeholder`, `merged`, `hide_separators`, `align`, `width` (`"fixed:N"`,
+///   `"min:N"`, `"max:N"` or `"range:MIN:MAX"`), `priority` and `number_format`, as a struct
+///   of `decimals` and `thousands`
+/// * `format_rules`: the rules in the order they were added, as structs of `column`,
+///   `condition` (`"greater_than"`, `"less_than"`, `"between"` or `"matches"`), `numbers`,
+///   `pattern` and `style` as a style spec
+///
+/// Tables with a `Rule::Custom` format rule or a `WidthMode::Custom` width mode fail to
+/// serialize.
+///
+/// The fields and their representations are stable across patch versions. Unknown fields
+/// are ignored and missing fields take their default value when deserializing.
+impl Serialize for Table {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        TableData::from_table(self)
+            .map_err(ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Table {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Table, D::Error> {
+        TableData::deserialize(deserializer)?
+            .into_table()
+            .map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt;
+
+    use serde::de::value::{Error, MapDeserializer, SeqDeserializer};
+    use serde::de::{self, Deserialize, IntoDeserializer};
+    use serde::ser::{self, Impossible, Serialize};
+
+    use crate::format::consts::{FORMAT_BOX_CHARS, FORMAT_CLEAN};
+    use crate::format::{Alignment, ColumnWidth, VerticalAlignment, WidthMode};
+    use crate::{
+        Attr, CaptionPosition, Cell, CellStyle, Color, NumberFormat, Row, RowNumbers, Rule, Table,
+    };
+
+    /// Serialized value, written like JSON
+    #[derive(Clone, Debug, PartialEq)]
+    enum Value {
+        Null,
+        Bool(bool),
+        Int(u64),
+        Float(f64),
+        Str(String),
+        Seq(Vec<Value>),
+        Map(Vec<(String, Value)>),
+    }
+
+    impl Value {
+        fn write(&self, fmt: &mut fmt::Formatter, indent: usize) -> fmt::Result {
+            let pad = "  ".repeat(indent + 1);
+            match self {
+                Value::Null => fmt.write_str("null"),
+                Value::Bool(b) => write!(fmt, "{}", b),
+                Value::Int(i) => write!(fmt, "{}", i),
+                Value::Float(n) => write!(fmt, "{:?}", n),
+                Value::Str(s) => write!(fmt, "{:?}", s),
+                Value::Seq(values) if values.iter().all(|v| !matches!(v, Value::Map(_))) => {
+                    fmt.write_str("[")?;
+                    for (i, value) in values.iter().enumerate() {
+                        fmt.write_str(if i > 0 { ", " } else { "" })?;
+                        value.write(fmt, indent)?;
+                    }
+                    fmt.write_str("]")
+                }
+                Value::Seq(values) => {
+                    fmt.write_str("[\n")?;
+                    for value in values {
+                        fmt.write_str(&pad)?;
+                        value.write(fmt, indent + 1)?;
+                        fmt.write_str(",\n")?;
+                    }
+                    write!(fmt, "{}]", "  ".repeat(indent))
+                }
+                Value::Map(fields) => {
+                    fmt.write_str("{\n")?;
+                    for (key, value) in fields {
+                        write!(fmt, "{}{:?}: ", pad, key)?;
+                        value.write(fmt, indent + 1)?;
+                        fmt.write_str(",\n")?;
+                    }
+                    write!(fmt, "{}}}", "  ".repeat(indent))
+                }
+            }
+        }
+    }
+
+    impl fmt::Display for Value {
+        fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            self.write(fmt, 0)
+        }
+    }
+
+    /// Serializer producing a `Value`
+    struct ValueSerializer;
+
+    /// Items of a sequence or fields of a struct being serialized
+    struct Compound<T>(Vec<T>);
+
+    macro_rules! serialize_int {
+        ($($method:ident($ty:ty)),*) => {
+            $(fn $method(self, v: $ty) -> Result<Value, Error> {
+                Ok(Value::Int(v as u64))
+            })*
+        };
+    }
+
+    macro_rules! unsupported {
+        ($($method:ident($($arg:ty),*) -> $ret:ty),*) => {
+            $(fn $method(self, $(_: $arg),*) -> Result<$ret, Error> {
+                Err(ser::Error::custom(stringify!($method)))
+            })*
+        };
+    }
+
+    impl ser::Serializer for ValueSerializer {
+        type Ok = Value;
+        type Error = Error;
+        type SerializeSeq = Compound<Value>;
+        type SerializeTuple = Compound<Value>;
+        type SerializeTupleStruct = Impossible<Value, Error>;
+        type SerializeTupleVariant = Impossible<Value, Error>;
+        type SerializeMap = Impossible<Value, Error>;
+        type SerializeStruct = Compound<(String, Value)>;
+        type SerializeStructVariant = Impossible<Value, Error>;
+
+        serialize_int!(
+            serialize_i8(i8),
+            serialize_i16(i16),
+            serialize_i32(i32),
+            serialize_i64(i64),
+            serialize_u8(u8),
+            serialize_u16(u16),
+            serialize_u32(u32),
+            serialize_u64(u64)
+        );
+
+        unsupported!(
+            serialize_f32(f32) -> Value,
+            serialize_bytes(&[u8]) -> Value,
+            serialize_unit_struct(&'static str) -> Value,
+            serialize_unit_variant(&'static str, u32, &'static str) -> Value,
+            serialize_tuple_struct(&'static str, usize) -> Impossible<Value, Error>,
+            serialize_tuple_variant(&'static str, u32, &'static str, usize)
+                -> Impossible<Value, Error>,
+            serialize_map(Option<usize>) -> Impossible<Value, Error>,
+            serialize_struct_variant(&'static str, u32, &'static str, usize)
+                -> Impossible<Value, Error>
+        );
+
+        fn serialize_bool(self, v: bool) -> Result<Value, Error> {
+            Ok(Value::Bool(v))
+        }
+
+        fn serialize_f64(self, v: f64) -> Result<Value, Error> {
+            Ok(Value::Float(v))
+        }
+
+        fn serialize_char(self, v: char) -> Result<Value, Error> {
+            Ok(Value::Str(v.to_string()))
+        }
+
+        fn serialize_str(self, v: &str) -> Result<Value, Error> {
+            Ok(Value::Str(v.to_string()))
+        }
+
+        fn serialize_none(self) -> Result<Value, Error> {
+            Ok(Value::Null)
+        }
+
+        fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Value, Error> {
+            value.serialize(self)
+        }
+
+        fn serialize_unit(self) -> Result<Value, Error> {
+            Ok(Value::Null)
+        }
+
+        fn serialize_newtype_struct<T: ?Sized + Serialize>(
+            self,
+            _: &'static str,
+            value: &T,
+        ) -> Result<Value, Error> {
+            value.serialize(self)
+        }
+
+        fn serialize_newtype_variant<T: ?Sized + Serialize>(
+            self,
+            _: &'static str,
+            _: u32,
+            variant: &'static str,
+            _: &T,
+        ) -> Result<Value, Error> {
+            Err(ser::Error::custom(variant))
+        }
+
+        fn serialize_seq(self, _: Option<usize>) -> Result<Compound<Value>, Error> {
+            Ok(Compound(Vec::new()))
+        }
+
+        fn serialize_tuple(self, _: usize) -> Result<Compound<Value>, Error> {
+            Ok(Compound(Vec::new()))
+        }
+
+        fn serialize_struct(
+            self,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Compound<(String, Value)>, Error> {
+            Ok(Compound(Vec::new()))
+        }
+    }
+
+    impl ser::SerializeSeq for Compound<Value> {
+        type Ok = Value;
+        type Error = Error;
+
+        fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+            self.0.push(value.serialize(ValueSerializer)?);
+            Ok(())
+        }
+
+        fn end(self) -> Result<Value, Error> {
+            Ok(Value::Seq(self.0))
+        }
+    }
+
+    impl ser::SerializeTuple for Compound<Value> {
+        type Ok = Value;
+        type Error = Error;
+
+        fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+            ser::SerializeSeq::serialize_element(self, value)
+        }
+
+        fn end(self) -> Result<Value, Error> {
+            ser::SerializeSeq::end(self)
+        }
+    }
+
+    impl ser::SerializeStruct for Compound<(String, Value)> {
+        type Ok = Value;
+        type Error = Error;
+
+        fn serialize_field<T: ?Sized + Serialize>(
+            &mut self,
+            key: &'static str,
+            value: &T,
+        ) -> Result<(), Error> {
+            self.0
+                .push((key.to_string(), value.serialize(ValueSerializer)?));
+            Ok(())
+        }
+
+        fn end(self) -> Result<Value, Error> {
+            Ok(Value::Map(self.0))
+        }
+    }
+
+    impl<'de> de::Deserializer<'de> for Value {
+        type Error = Error;
+
+        fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            match self {
+                Value::Null => visitor.visit_unit(),
+                Value::Bool(b) => visitor.visit_bool(b),
+                Value::Int(i) => visitor.visit_u64(i),
+                Value::Float(n) => visitor.visit_f64(n),
+                Value::Str(s) => visitor.visit_string(s),
+                Value::Seq(values) => visitor.visit_seq(SeqDeserializer::new(values.into_iter())),
+                Value::Map(fields) => visitor.visit_map(MapDeserializer::new(fields.into_iter())),
+            }
+        }
+
+        fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            match self {
+                Value::Null => visitor.visit_none(),
+                value => visitor.visit_some(value),
+            }
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf unit unit_struct newtype_struct seq tuple
+            tuple_struct map struct enum identifier ignored_any
+        }
+    }
+
+    impl<'de> IntoDeserializer<'de, Error> for Value {
+        type Deserializer = Value;
+
+        fn into_deserializer(self) -> Value {
+            self
+        }
+    }
+
+    fn to_value<T: Serialize>(value: &T) -> Value {
+        value.serialize(ValueSerializer).unwrap()
+    }
+
+    fn from_value<T: for<'de> Deserialize<'de>>(value: Value) -> Result<T, Error> {
+        T::deserialize(value)
+    }
+
+    fn styled_cell() -> Cell {
+        let mut cell = Cell::new_align("a\nb", Alignment::RIGHT)
+            .with_style(Attr::Bold)
+            .with_style(Attr::Italic(false))
+            .with_style(Attr::ForegroundColor(3))
+            .with_hspan(2)
+            .with_truncate(5, "…")
+            .with_hyperlink("https://example.com")
+            .with_padding(0, 2)
+            .with_html_attr("title", "A");
+        cell.set_vertical_align(VerticalAlignment::BOTTOM);
+        cell.set_fill('.');
+        cell
+    }
+
+    #[test]
+    fn cell_snapshot() {
+        let out = r#"{
+  "content": "a\nb",
+  "align": "right",
+  "valign": "bottom",
+  "style": ["bold", "no-italic", "fg:3"],
+  "hspan": 2,
+  "max_width": null,
+  "truncate": [5, "…"],
+  "url": "https://example.com",
+  "padding": [0, 2],
+  "fill": ".",
+  "html_attrs": [["title", "A"]],
+}"#;
+        assert_eq!(to_value(&styled_cell()).to_string(), out);
+        let plain = to_value(&Cell::new("x")).to_string();
+        assert!(plain.contains("\"align\": null,\n  \"valign\": \"top\",\n"));
+        assert!(plain.contains("\"hspan\": 1,"));
+    }
+
+    #[test]
+    fn format_snapshot() {
+        let out = r#"{
+  "column_separator": "│",
+  "left_border": "│",
+  "right_border": "│",
+  "top_separator": {
+    "line": "─",
+    "junction": "┬",
+    "left_junction": "┌",
+    "right_junction": "┐",
+  },
+  "title_separator": null,
+  "inner_separator": {
+    "line": "─",
+    "junction": "┼",
+    "left_junction": "├",
+    "right_junction": "┤",
+  },
+  "bottom_separator": {
+    "line": "─",
+    "junction": "┴",
+    "left_junction": "└",
+    "right_junction": "┘",
+  },
+  "padding": [1, 1],
+  "indent": 0,
+  "width_mode": "unicode",
+  "tab_width": 4,
+}"#;
+        assert_eq!(to_value(&*FORMAT_BOX_CHARS).to_string(), out);
+        let mut format = *FORMAT_CLEAN;
+        format.width_mode(WidthMode::Custom(|s| s.len()));
+        assert!(format.serialize(ValueSerializer).is_err());
+    }
+
+    #[test]
+    fn table_snapshot() {
+        let mut table = Table::new();
+        table.set_format(*FORMAT_CLEAN);
+        table.set_titles(Row::new(vec![Cell::new("name")]));
+        let mut row = Row::new(vec![Cell::new("db")]);
+        row.set_style(CellStyle::new().fg(Color::Red).bold());
+        row.set_separator_below(true);
+        table.add_row(row);
+        table.set_caption("Services", Alignment::LEFT);
+        table.set_caption_position(CaptionPosition::Below);
+        table.set_column_alignment(0, Alignment::RIGHT);
+        let value = to_value(&table).to_string();
+        let out = r#"  "rows": [
+    {
+      "cells": [
+        {
+          "content": "db",
+          "align": null,
+          "valign": "top",
+          "style": [],
+          "hspan": 1,
+          "max_width": null,
+          "truncate": null,
+          "url": null,
+          "padding": null,
+          "fill": " ",
+          "html_attrs": [],
+        },
+      ],
+      "style": "Frb",
+      "separator_below": true,
+      "group_header": false,
+      "max_height": null,
+    },
+  ],
+  "footer": null,
+  "caption": {
+    "text": "Services",
+    "align": "left",
+    "position": "below",
+  },
+  "display": {
+    "placeholder": null,
+    "max_row_height": null,
+    "height_marker": null,
+    "striping": null,
+    "frozen_columns": 0,
+    "row_numbers": null,
+    "max_width": null,
+    "columns": [
+      {
+        "column": 0,
+        "placeholder": null,
+        "merged": false,
+        "hide_separators": false,
+        "align": "right",
+        "width": null,
+        "priority": null,
+        "number_format": null,
+      },
+    ],
+    "format_rules": [],
+  },
+}"#;
+        assert!(value.ends_with(out), "{}", value);
+        assert!(value.starts_with("{\n  \"format\": {\n    \"column_separator\": null,\n"));
+        assert!(value.contains("\n  \"header_rows\": [],\n"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut table = Table::new();
+        table.set_format(*FORMAT_BOX_CHARS);
+        table.get_format().indent(2);
+        table.set_titles(Row::new(vec![
+            Cell::new("a"),
+            Cell::new("b").with_max_width(3),
+        ]));
+        table.add_row(Row::new(vec![styled_cell()]));
+        let mut row = Row::group_header("group");
+        row.set_max_height(2);
+        table.add_row(row);
+        table.set_footer(Row::new(vec![Cell::new("total")]));
+        table.set_caption("Caption", Alignment::RIGHT);
+
+        let read = from_value::<Table>(to_value(&table)).unwrap();
+        assert!(read.strict_eq(&table));
+        assert_eq!(read.to_string(), table.to_string());
+        let cell = from_value::<Cell>(to_value(&styled_cell())).unwrap();
+        assert_eq!(cell, styled_cell());
+        assert_eq!(from_value::<Row>(to_value(&table[1])).unwrap(), table[1]);
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("1234.5"), Cell::new("")]));
+        table.add_row(Row::new(vec![Cell::new("1234.5"), Cell::new("x")]));
+        table.set_empty_cell_placeholder("-");
+        table.set_column_placeholder(1, "?");
+        table.merge_column_duplicates_with(0, true);
+        table.set_column_alignment(1, Alignment::CENTER);
+        table.set_column_width(1, ColumnWidth::Range(2, 6));
+        table.set_column_priority(1, 3);
+        table.set_column_number_format(0, NumberFormat::new().thousands(','));
+        table.set_max_row_height(2);
+        table.set_row_striping("b", "");
+        table.add_format_rule(0, Rule::Between(1.0, 2000.5), "Fr");
+        table.add_format_rule(1, Rule::Matches("x*".to_string()), "i");
+        table.set_row_numbers(RowNumbers::default());
+
+        let value = to_value(&table);
+        let out = "\"width\": \"range:2:6\",\n        \"priority\": 3,\n";
+        assert!(value.to_string().contains(out));
+        let read = from_value::<Table>(value).unwrap();
+        assert!(read.strict_eq(&table));
+        assert_eq!(read.to_string(), table.to_string());
+
+        table.add_format_rule(0, Rule::Custom(|cell| cell.get_content().is_empty()), "b");
+        let err = table.serialize(ValueSerializer).unwrap_err();
+        assert_eq!(err.to_string(), "Custom format rules cannot be serialized");
+        let mut table = Table::new();
+        table
+            .get_format()
+            .width_mode(WidthMode::Custom(|s| s.len()));
+        assert!(table.serialize(ValueSerializer).is_err());
+    }
+
+    #[test]
+    fn missing_and_unknown_fields() {
+        let value = Value::Map(vec![
+            ("content".to_string(), Value::Str("x".to_string())),
+            ("since".to_string(), Value::Seq(vec![Value::Int(2)])),
+        ]);
+        assert_eq!(from_value::<Cell>(value).unwrap(), Cell::new("x"));
+        let table = from_value::<Table>(Value::Map(Vec::new())).unwrap();
+        assert!(table.strict_eq(&Table::new()));
+
+        let value = Value::Map(vec![("align".to_string(), Value::Str("up".to_string()))]);
+        let err = from_value::<Cell>(value).unwrap_err();
+        assert_eq!(err.to_string(), "Unknown alignment \"up\"");
+        let value = Value::Map(vec![(
+            "style".to_string(),
+            Value::Seq(vec![Value::Str("fg:red".to_string())]),
+        )]);
+        assert!(from_value::<Cell>(value).is_err());
+    }
+}
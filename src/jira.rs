@@ -0,0 +1,89 @@
+//! Jira / Confluence wiki markup output for tables
+
+use std::io::{Error, Write};
+
+use super::utils::NEWLINE;
+use super::{AsTableSlice, Row, Table, TableSlice};
+
+/// Print a single row, where `sep` is the cell delimiter. Horizontally spanning cells are
+/// followed by empty cells, and the row is padded with empty cells up to `col_num` columns
+fn print_row<T: Write + ?Sized>(
+    out: &mut T,
+    row: &Row,
+    col_num: usize,
+    sep: &str,
+) -> Result<(), Error> {
+    let mut cells = Vec::new();
+    for cell in row {
+        cells.push(
+            cell.get_content()
+                .replace('|', "\\|")
+                .replace('\n', " \\\\ "),
+        );
+        for _ in 1..cell.get_hspan() {
+            cells.push(String::new());
+        }
+    }
+    cells.resize(col_num.max(cells.len()), String::new());
+    let mut line = String::from(sep);
+    for cell in cells {
+        // Jira collapses truly empty cells
+        line += if cell.is_empty() { " " } else { &cell };
+        line += sep;
+    }
+    out.write_all(line.as_bytes())?;
+    out.write_all(NEWLINE)
+}
+
+impl<'a> TableSlice<'a> {
+    /// Print table in Jira / Confluence wiki markup to `out`.
+    ///
+    /// The footer is printed as the last row. Jira tables have no caption, so it is left out.
+    pub fn print_jira<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        let table = self.exported();
+        let slice = table.as_slice();
+        let column_num = slice.get_column_num();
+        if let Some(ref t) = *slice.titles {
+            print_row(out, t, column_num, "||")?;
+        }
+        for r in slice.rows {
+            print_row(out, r, column_num, "|")?;
+        }
+        out.flush()
+    }
+}
+
+impl Table {
+    /// Print table in Jira / Confluence wiki markup to `out`.
+    pub fn print_jira<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        self.as_slice().print_jira(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::StringWriter;
+    use crate::{Cell, Row, Table};
+
+    #[test]
+    fn jira() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2")]));
+        table.add_row(Row::new(vec![Cell::new("a|b"), Cell::new("")]));
+        table.add_row(Row::new(vec![Cell::new("c\nd")]));
+        table.add_row(Row::new(vec![
+            Cell::new("e"),
+            Cell::new("f"),
+            Cell::new("g"),
+        ]));
+        let out = "\
+||t1||t2|| ||
+|a\\|b| | |
+|c \\\\ d| | |
+|e|f|g|
+";
+        let mut writer = StringWriter::new();
+        assert!(table.print_jira(&mut writer).is_ok());
+        assert_eq!(writer.as_string().replace("\r\n", "\n"), out);
+    }
+}
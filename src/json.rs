@@ -0,0 +1,545 @@
+//! JSON import and export of tables
+
+use std::io::{Error, Write};
+
+use super::utils::{strip_ansi, StringWriter};
+use super::{AsTableSlice, Cell, ParseError, Row, Table, TableSlice};
+
+/// Write `s` as a JSON string literal
+fn write_string<T: Write + ?Sized>(out: &mut T, s: &str) -> Result<(), Error> {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    out.write_all(escaped.as_bytes())
+}
+
+/// Get the plain text content of the cell at `idx` in `row`, or an empty string
+fn cell_text(row: &Row, idx: usize) -> String {
+    row.get_cell(idx)
+        .map(|c| strip_ansi(&c.get_content()))
+        .unwrap_or_default()
+}
+
+/// A parsed JSON value. Numbers keep their original text
+enum Value {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+/// Maximum nesting of arrays and objects accepted by the parser
+const MAX_DEPTH: usize = 128;
+
+/// Minimal JSON parser, reporting errors with their line and column
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+    /// Current line, starting from 1
+    line: usize,
+    /// Byte offset of the start of the current line
+    line_start: usize,
+    /// Number of arrays and objects being parsed
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str) -> Parser<'a> {
+        Parser {
+            text,
+            pos: 0,
+            line: 1,
+            line_start: 0,
+            depth: 0,
+        }
+    }
+
+    fn column(&self) -> usize {
+        self.text[self.line_start..self.pos].chars().count() + 1
+    }
+
+    fn error<T>(&self, message: &str) -> Result<T, ParseError> {
+        Err(ParseError::new(
+            self.line,
+            format!("{} at column {}", message, self.column()),
+        ))
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespaces(&mut self) {
+        while let Some(c @ b' ') | Some(c @ b'\t') | Some(c @ b'\n') | Some(c @ b'\r') = self.peek()
+        {
+            self.pos += 1;
+            if c == b'\n' {
+                self.line += 1;
+                self.line_start = self.pos;
+            }
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), ParseError> {
+        self.skip_whitespaces();
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            self.error(&format!("Expected '{}'", byte as char))
+        }
+    }
+
+    /// Parse the comma separated items of an array or object until `end`,
+    /// the opening character being already consumed
+    fn parse_items<F>(&mut self, end: u8, mut item: F) -> Result<(), ParseError>
+    where
+        F: FnMut(&mut Self) -> Result<(), ParseError>,
+    {
+        self.skip_whitespaces();
+        if self.peek() == Some(end) {
+            self.pos += 1;
+            return Ok(());
+        }
+        loop {
+            item(self)?;
+            self.skip_whitespaces();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(c) if c == end => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                _ => return self.error(&format!("Expected ',' or '{}'", end as char)),
+            }
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Value, ParseError> {
+        self.skip_whitespaces();
+        if let Some(b'[') | Some(b'{') = self.peek() {
+            if self.depth == MAX_DEPTH {
+                return self.error("Too deeply nested");
+            }
+            self.depth += 1;
+            let value = self.parse_nested();
+            self.depth -= 1;
+            return value;
+        }
+        match self.peek() {
+            Some(b'n') => self.parse_literal("null", Value::Null),
+            Some(b't') => self.parse_literal("true", Value::Bool(true)),
+            Some(b'f') => self.parse_literal("false", Value::Bool(false)),
+            Some(b'"') => self.parse_string().map(Value::String),
+            Some(b'-') | Some(b'0'..=b'9') => self.parse_number(),
+            Some(_) => self.error("Unexpected character"),
+            None => self.error("Unexpected end of input"),
+        }
+    }
+
+    /// Parse the array or object starting at the current position
+    fn parse_nested(&mut self) -> Result<Value, ParseError> {
+        match self.peek() {
+            Some(b'[') => {
+                self.pos += 1;
+                let mut values = Vec::new();
+                self.parse_items(b']', |p| {
+                    values.push(p.parse_value()?);
+                    Ok(())
+                })?;
+                Ok(Value::Array(values))
+            }
+            Some(b'{') => {
+                self.pos += 1;
+                let mut entries = Vec::new();
+                self.parse_items(b'}', |p| {
+                    p.skip_whitespaces();
+                    let key = p.parse_string()?;
+                    p.expect(b':')?;
+                    entries.push((key, p.parse_value()?));
+                    Ok(())
+                })?;
+                Ok(Value::Object(entries))
+            }
+            _ => self.error("Expected an array or an object"),
+        }
+    }
+
+    fn parse_literal(&mut self, word: &str, value: Value) -> Result<Value, ParseError> {
+        if self.text[self.pos..].starts_with(word) {
+            self.pos += word.len();
+            Ok(value)
+        } else {
+            self.error("Invalid literal")
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<Value, ParseError> {
+        let start = self.pos;
+        let digits = |p: &mut Self| {
+            let from = p.pos;
+            while let Some(b'0'..=b'9') = p.peek() {
+                p.pos += 1;
+            }
+            p.pos > from
+        };
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        let mut valid = digits(self);
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            valid &= digits(self);
+        }
+        if let Some(b'e') | Some(b'E') = self.peek() {
+            self.pos += 1;
+            if let Some(b'+') | Some(b'-') = self.peek() {
+                self.pos += 1;
+            }
+            valid &= digits(self);
+        }
+        if !valid {
+            return self.error("Invalid number");
+        }
+        Ok(Value::Number(self.text[start..self.pos].to_string()))
+    }
+
+    fn parse_hex(&mut self) -> Result<u32, ParseError> {
+        let hex = self.text.get(self.pos..self.pos + 4).unwrap_or("");
+        match u32::from_str_radix(hex, 16) {
+            Ok(n) if hex.len() == 4 => {
+                self.pos += 4;
+                Ok(n)
+            }
+            _ => self.error("Invalid unicode escape"),
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, ParseError> {
+        if self.peek() != Some(b'"') {
+            return self.error("Expected a string");
+        }
+        self.pos += 1;
+        let mut string = String::new();
+        loop {
+            let c = match self.text[self.pos..].chars().next() {
+                Some(c) => c,
+                None => return self.error("Unterminated string"),
+            };
+            self.pos += c.len_utf8();
+            match c {
+                '"' => return Ok(string),
+                '\\' => {
+                    let escape = self.peek();
+                    self.pos += 1;
+                    let unescaped = match escape {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => {
+                            let mut code = self.parse_hex()?;
+                            if (0xD800..0xDC00).contains(&code)
+                                && self.text[self.pos..].starts_with("\\u")
+                            {
+                                self.pos += 2;
+                                let low = self.parse_hex()?;
+                                code =
+                                    0x10000 + ((code - 0xD800) << 10) + (low.wrapping_sub(0xDC00));
+                            }
+                            match std::char::from_u32(code) {
+                                Some(c) => c,
+                                None => return self.error("Invalid unicode escape"),
+                            }
+                        }
+                        _ => return self.error("Invalid escape sequence"),
+                    };
+                    string.push(unescaped);
+                }
+                c if (c as u32) < 0x20 => return self.error("Control character in string"),
+                c => string.push(c),
+            }
+        }
+    }
+
+    /// Parse a top level array, returning its items with the line they start on
+    fn parse_rows(&mut self) -> Result<Vec<(usize, Value)>, ParseError> {
+        self.expect(b'[')?;
+        let mut rows = Vec::new();
+        self.parse_items(b']', |p| {
+            p.skip_whitespaces();
+            let line = p.line;
+            rows.push((line, p.parse_value()?));
+            Ok(())
+        })?;
+        self.skip_whitespaces();
+        if self.pos < self.text.len() {
+            return self.error("Unexpected content after the array");
+        }
+        Ok(rows)
+    }
+}
+
+/// Convert a plain JSON value to a cell. Nested arrays and objects are an error
+fn value_cell(
+    value: Option<&Value>,
+    line: usize,
+    row: usize,
+    column: &str,
+) -> Result<Cell, ParseError> {
+    Ok(match value {
+        None | Some(Value::Null) => Cell::default(),
+        Some(Value::Bool(b)) => Cell::new(&b.to_string()),
+        Some(Value::Number(n)) | Some(Value::String(n)) => Cell::new(n),
+        Some(Value::Array(_)) | Some(Value::Object(_)) => {
+            return Err(ParseError::new(
+                line,
+                format!("Nested value in row {}, column {}", row, column),
+            ))
+        }
+    })
+}
+
+impl<'a> TableSlice<'a> {
+    /// Print the table content in JSON format to `out`.
+    ///
+    /// When titles are set, the table is printed as an array of objects keyed by the titles.
+    /// Every object has all the keys, rows missing a cell get an empty string, and cells
+    /// beyond the titles are keyed by their column index.
+    /// Without titles, the table is printed as an array of arrays of strings.
+    ///
+    /// ANSI escape sequences are stripped from the content.
+    pub fn print_json<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        out.write_all(b"[")?;
+        for (i, r) in self.rows.iter().enumerate() {
+            if i > 0 {
+                out.write_all(b",")?;
+            }
+            match *self.titles {
+                Some(ref t) => {
+                    out.write_all(b"{")?;
+                    for j in 0..t.len().max(r.len()) {
+                        if j > 0 {
+                            out.write_all(b",")?;
+                        }
+                        let key = match t.get_cell(j) {
+                            Some(c) => strip_ansi(&c.get_content()),
+                            None => j.to_string(),
+                        };
+                        write_string(out, &key)?;
+                        out.write_all(b":")?;
+                        write_string(out, &cell_text(r, j))?;
+                    }
+                    out.write_all(b"}")?;
+                }
+                None => {
+                    out.write_all(b"[")?;
+                    for j in 0..r.len() {
+                        if j > 0 {
+                            out.write_all(b",")?;
+                        }
+                        write_string(out, &cell_text(r, j))?;
+                    }
+                    out.write_all(b"]")?;
+                }
+            }
+        }
+        out.write_all(b"]")?;
+        out.flush()
+    }
+
+    /// Return the table content as a JSON string.
+    ///
+    /// See `print_json()` for details.
+    pub fn to_json(&self) -> String {
+        let mut writer = StringWriter::new();
+        // Writing into a `StringWriter` cannot fail
+        let _ = self.print_json(&mut writer);
+        writer.as_string().to_string()
+    }
+}
+
+impl Table {
+    /// Print the table content in JSON format to `out`.
+    ///
+    /// See `TableSlice::print_json()` for details.
+    pub fn print_json<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        self.as_slice().print_json(out)
+    }
+
+    /// Return the table content as a JSON string.
+    pub fn to_json(&self) -> String {
+        self.as_slice().to_json()
+    }
+
+    /// Create a table from a JSON array.
+    ///
+    /// An array of objects produces a table titled with the union of the keys, in the order
+    /// they are first seen, where missing keys are empty cells. An array of arrays produces
+    /// an untitled table. Numbers and booleans are stringified and nulls are empty cells.
+    /// Nested objects and arrays are an error naming the offending row index and column.
+    pub fn from_json(text: &str) -> Result<Table, ParseError> {
+        let rows = Parser::new(text).parse_rows()?;
+        let mut table = Table::new();
+        match rows.first() {
+            Some((_, Value::Object(_))) => {
+                let mut titles: Vec<&str> = Vec::new();
+                for (line, row) in &rows {
+                    match row {
+                        Value::Object(entries) => {
+                            for (key, _) in entries {
+                                if !titles.contains(&key.as_str()) {
+                                    titles.push(key);
+                                }
+                            }
+                        }
+                        _ => return Err(ParseError::new(*line, "Expected an object")),
+                    }
+                }
+                for (i, (line, row)) in rows.iter().enumerate() {
+                    if let Value::Object(entries) = row {
+                        let mut cells = Vec::with_capacity(titles.len());
+                        for title in &titles {
+                            let value = entries.iter().rev().find(|(k, _)| k == title);
+                            let column = format!("{:?}", title);
+                            cells.push(value_cell(value.map(|(_, v)| v), *line, i, &column)?);
+                        }
+                        table.add_row(Row::new(cells));
+                    }
+                }
+                table.set_titles(Row::new(titles.iter().map(|t| Cell::new(t)).collect()));
+            }
+            Some(_) => {
+                for (i, (line, row)) in rows.iter().enumerate() {
+                    match row {
+                        Value::Array(values) => {
+                            let mut cells = Vec::with_capacity(values.len());
+                            for (j, value) in values.iter().enumerate() {
+                                cells.push(value_cell(Some(value), *line, i, &j.to_string())?);
+                            }
+                            table.add_row(Row::new(cells));
+                        }
+                        _ => return Err(ParseError::new(*line, "Expected an array")),
+                    }
+                }
+            }
+            None => {}
+        }
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cell, Row, Table};
+
+    #[test]
+    fn to_json() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![
+            Cell::new("\x1B[31ma\x1B[0m"),
+            Cell::new("b\"c"),
+        ]));
+        table.add_row(Row::new(vec![Cell::new("d\ne")]));
+        assert_eq!(table.to_json(), r#"[["a","b\"c"],["d\ne"]]"#);
+
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2")]));
+        assert_eq!(
+            table.to_json(),
+            r#"[{"t1":"a","t2":"b\"c"},{"t1":"d\ne","t2":""}]"#
+        );
+        table.add_row(Row::new(vec![
+            Cell::new("1"),
+            Cell::new("2"),
+            Cell::new("3"),
+        ]));
+        assert!(table.to_json().ends_with(r#"{"t1":"1","t2":"2","2":"3"}]"#));
+    }
+
+    #[test]
+    fn from_json() {
+        let text = r#"[
+            {"name": "Alice", "age": 30, "admin": true},
+            {"name": "Bob \u00e9\ud83d\ude00", "city": null, "age": -1.5e2}
+        ]"#;
+        let table = Table::from_json(text).unwrap();
+        let titles = table.titles.as_ref().as_ref().unwrap();
+        let titles: Vec<String> = titles.iter().map(|c| c.get_content()).collect();
+        assert_eq!(titles, vec!["name", "age", "admin", "city"]);
+        let row = table.get_row(1).unwrap();
+        assert_eq!(row.get_cell(0).unwrap().get_content(), "Bob é😀");
+        assert_eq!(row.get_cell(1).unwrap().get_content(), "-1.5e2");
+        assert_eq!(row.get_cell(2).unwrap().get_content(), "");
+        assert_eq!(row.get_cell(3).unwrap().get_content(), "");
+        assert_eq!(
+            table.get_row(0).unwrap().get_cell(2).unwrap().get_content(),
+            "true"
+        );
+
+        let table = Table::from_json(r#"[["a", 1], ["b"]]"#).unwrap();
+        assert!(table.titles.is_none());
+        assert_eq!(table.to_json(), r#"[["a","1"],["b"]]"#);
+        assert!(Table::from_json("[]").unwrap().is_empty());
+    }
+
+    #[test]
+    fn from_json_errors() {
+        let err = Table::from_json("[\n{\"a\": 1},\n{\"a\": [1]}\n]").unwrap_err();
+        assert_eq!(err.line(), 3);
+        assert_eq!(err.message(), "Nested value in row 1, column \"a\"");
+        assert!(Table::from_json("{}").is_err());
+        assert!(Table::from_json("[[1], {}]").is_err());
+        assert!(Table::from_json("[\"a\"]").is_err());
+        assert!(Table::from_json("[1,]").is_err());
+        assert!(Table::from_json("[] x").is_err());
+        assert_eq!(Table::from_json("[\n\"a").unwrap_err().line(), 2);
+        let err = Table::from_json("[\n  [1,, 2]]").unwrap_err();
+        assert_eq!(err.to_string(), "line 2: Unexpected character at column 6");
+
+        let err = Table::from_json(&"[".repeat(200_000)).unwrap_err();
+        assert_eq!(err.message(), "Too deeply nested at column 130");
+        let nested = format!("[{}{}]", "[".repeat(127), "]".repeat(127));
+        let err = Table::from_json(&nested).unwrap_err();
+        assert_eq!(err.message(), "Nested value in row 0, column 0");
+    }
+
+    #[test]
+    fn from_json_large() {
+        let text = format!("[{}[0]]", "[1, \"a\"],\n".repeat(50_000));
+        let table = Table::from_json(&text).unwrap();
+        assert_eq!(table.len(), 50_001);
+        let err = Table::from_json(&format!("{}}}", &text[..text.len() - 1])).unwrap_err();
+        assert_eq!(err.line(), 50_001);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t\"2")]));
+        table.add_row(Row::new(vec![Cell::new("a\\b"), Cell::new("c\nd")]));
+        let parsed = Table::from_json(&table.to_json()).unwrap();
+        assert_eq!(parsed.to_string(), table.to_string());
+    }
+
+    #[test]
+    fn empty_json() {
+        assert_eq!(Table::new().to_json(), "[]");
+    }
+}
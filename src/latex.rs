@@ -0,0 +1,161 @@
+//! LaTeX output for tables
+
+use std::io::{Error, Write};
+
+use super::format::Alignment;
+use super::utils::NEWLINE;
+use super::{AsTableSlice, Cell, Row, Table, TableSlice};
+
+/// Return the LaTeX column specifier for `align`
+fn align_spec(align: Alignment) -> char {
+    match align {
+        Alignment::LEFT => 'l',
+        Alignment::CENTER => 'c',
+        Alignment::RIGHT => 'r',
+    }
+}
+
+/// Escape characters having a special meaning in LaTeX
+fn escape(content: &str) -> String {
+    let mut escaped = String::with_capacity(content.len());
+    for c in content.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Format the content of `cell`. Multi-line cells are wrapped into a `\makecell`
+/// and horizontally spanning cells into a `\multicolumn`
+fn format_cell(cell: &Cell) -> String {
+    let spec = align_spec(cell.get_align());
+    let lines: Vec<String> = cell.get_content().lines().map(escape).collect();
+    let content = if lines.len() > 1 {
+        format!("\\makecell[{}]{{{}}}", spec, lines.join(" \\\\ "))
+    } else {
+        lines.join("")
+    };
+    if cell.get_hspan() > 1 {
+        format!(
+            "\\multicolumn{{{}}}{{{}}}{{{}}}",
+            cell.get_hspan(),
+            spec,
+            content
+        )
+    } else {
+        content
+    }
+}
+
+/// Print a single row, padded with empty cells up to `col_num` columns
+fn print_row<T: Write + ?Sized>(out: &mut T, row: &Row, col_num: usize) -> Result<(), Error> {
+    let mut cells: Vec<String> = row.iter().map(format_cell).collect();
+    let printed_columns: usize = row.iter().map(Cell::get_hspan).sum();
+    for _ in printed_columns..col_num {
+        cells.push(String::new());
+    }
+    out.write_all(cells.join(" & ").as_bytes())?;
+    out.write_all(b" \\\\")?;
+    out.write_all(NEWLINE)
+}
+
+impl<'a> TableSlice<'a> {
+    /// Print table as a LaTeX `tabular` environment to `out`.
+    ///
+    /// Column specifiers are derived from the alignment of the titles, or of the first row
+    /// when there are no titles. Multi-line cells require the `makecell` package.
+    /// The footer is printed as the last row, below a `\hline`. The caption is left out,
+    /// since it can only be set on the `table` float enclosing the `tabular`.
+    pub fn print_latex<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        let table = self.exported();
+        let slice = table.as_slice();
+        let column_num = slice.get_column_num();
+        let specs: String = slice
+            .get_column_alignments()
+            .into_iter()
+            .map(align_spec)
+            .collect();
+        out.write_all(format!("\\begin{{tabular}}{{{}}}", specs).as_bytes())?;
+        out.write_all(NEWLINE)?;
+        out.write_all(b"\\hline")?;
+        out.write_all(NEWLINE)?;
+        if let Some(ref t) = *slice.titles {
+            print_row(out, t, column_num)?;
+            out.write_all(b"\\hline")?;
+            out.write_all(NEWLINE)?;
+        }
+        for (i, r) in slice.rows.iter().enumerate() {
+            if self.footer.is_some() && i + 1 == slice.rows.len() {
+                out.write_all(b"\\hline")?;
+                out.write_all(NEWLINE)?;
+            }
+            print_row(out, r, column_num)?;
+        }
+        out.write_all(b"\\hline")?;
+        out.write_all(NEWLINE)?;
+        out.write_all(b"\\end{tabular}")?;
+        out.write_all(NEWLINE)?;
+        out.flush()
+    }
+}
+
+impl Table {
+    /// Print table as a LaTeX `tabular` environment to `out`.
+    ///
+    /// See `TableSlice::print_latex()` for details.
+    pub fn print_latex<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        self.as_slice().print_latex(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::Alignment;
+    use crate::utils::StringWriter;
+    use crate::{Cell, Row, Table};
+
+    #[test]
+    fn latex() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![
+            Cell::new("t1"),
+            Cell::new_align("t2", Alignment::CENTER),
+            Cell::new_align("t3", Alignment::RIGHT),
+        ]));
+        table.add_row(Row::new(vec![
+            Cell::new("50%"),
+            Cell::new("a_b & c"),
+            Cell::new_align("d\ne", Alignment::RIGHT),
+        ]));
+        table.add_row(Row::new(vec![Cell::new("$#").with_hspan(2)]));
+        let out = "\
+\\begin{tabular}{lcr}
+\\hline
+t1 & t2 & t3 \\\\
+\\hline
+50\\% & a\\_b \\& c & \\makecell[r]{d \\\\ e} \\\\
+\\multicolumn{2}{l}{\\$\\#} &  \\\\
+\\hline
+\\end{tabular}
+";
+        let mut writer = StringWriter::new();
+        assert!(table.print_latex(&mut writer).is_ok());
+        assert_eq!(writer.as_string().replace("\r\n", "\n"), out);
+
+        table.set_footer(Row::new(vec![Cell::new("f")]));
+        let mut writer = StringWriter::new();
+        assert!(table.print_latex(&mut writer).is_ok());
+        assert!(writer
+            .as_string()
+            .replace("\r\n", "\n")
+            .ends_with("&  \\\\\n\\hline\nf &  &  \\\\\n\\hline\n\\end{tabular}\n"));
+    }
+}
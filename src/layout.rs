@@ -0,0 +1,281 @@
+//! Column layouts kept across prints of a table
+
+use std::fmt;
+use std::io::{Error, Write};
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use super::format::ColumnWidth;
+use super::max_width::KeptColumns;
+use super::render::prepare_row;
+use super::{column_widths, AsTableSlice, Table, TableSlice};
+
+/// Widths of the columns of a table, as computed by `Table::compute_layout()`.
+///
+/// A layout can be used to print a table again with the same column widths, even
+/// after its content has changed, for instance when refreshing a live dashboard.
+/// It is written as comma separated widths by `Display`, and read back with `FromStr`,
+/// so that it can be carried across processes. With the `serde` feature, it is also
+/// serialized as a sequence of widths.
+///
+/// # Examples
+/// ```rust
+/// # #[macro_use] extern crate prettytable;
+/// use prettytable::TableLayout;
+/// # fn main() {
+/// let mut layout = table![["a", "bbb"]].compute_layout();
+/// layout.merge(&table![["cc", "d"]].compute_layout());
+/// assert_eq!(layout.widths(), &[2, 3]);
+/// assert_eq!(layout.to_string(), "2,3");
+/// assert_eq!("2,3".parse::<TableLayout>().unwrap(), layout);
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct TableLayout {
+    widths: Vec<usize>,
+}
+
+impl TableLayout {
+    /// Create a layout with the given column widths, excluding padding
+    pub fn new(widths: Vec<usize>) -> TableLayout {
+        TableLayout { widths }
+    }
+
+    /// Get the width of each column, excluding padding
+    pub fn widths(&self) -> &[usize] {
+        &self.widths
+    }
+
+    /// Widen the columns of this layout to the ones of `other`, keeping the widest
+    /// of both for each column. Columns only in `other` are added.
+    pub fn merge(&mut self, other: &TableLayout) {
+        if other.widths.len() > self.widths.len() {
+            self.widths.resize(other.widths.len(), 0);
+        }
+        for (width, &other) in self.widths.iter_mut().zip(&other.widths) {
+            *width = (*width).max(other);
+        }
+    }
+}
+
+impl fmt::Display for TableLayout {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        for (i, width) in self.widths.iter().enumerate() {
+            if i > 0 {
+                fmt.write_str(",")?;
+            }
+            write!(fmt, "{}", width)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for TableLayout {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<TableLayout, ParseIntError> {
+        if s.trim().is_empty() {
+            return Ok(TableLayout::default());
+        }
+        let widths = s
+            .split(',')
+            .map(|w| w.trim().parse())
+            .collect::<Result<_, _>>()?;
+        Ok(TableLayout { widths })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for TableLayout {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.widths.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for TableLayout {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<TableLayout, D::Error> {
+        Vec::deserialize(deserializer).map(TableLayout::new)
+    }
+}
+
+impl Table {
+    /// Return the widths the columns of this table are printed with. The column of row
+    /// numbers is left out, so that the layout holds a width for each column of the table.
+    /// Columns dropped to fit the width set with `set_max_width()` have a width of 0.
+    pub fn compute_layout(&self) -> TableLayout {
+        TableLayout {
+            widths: self.printed_widths().1,
+        }
+    }
+
+    /// Return the widths of all the printed columns, including the column of row numbers
+    /// and the marker of dropped columns, along with the widths of the columns of the
+    /// table only, 0 for the dropped ones
+    pub(crate) fn printed_widths(&self) -> (Vec<usize>, Vec<usize>) {
+        let table = self.rendered();
+        let titles = (*table.titles)
+            .as_ref()
+            .map(|t| prepare_row(t, &table.format));
+        let rows = table
+            .header_rows
+            .iter()
+            .chain(table.rows.iter())
+            .chain(table.footer.iter())
+            .map(|r| prepare_row(r, &table.format))
+            .collect::<Vec<_>>();
+        let min_width = table.render.min_column_widths();
+        let mut widths = column_widths(&table.format, &titles, &rows);
+        for (width, &min) in widths.iter_mut().zip(&min_width) {
+            *width = (*width).max(min);
+        }
+        let added = self.render.added_columns(true).min(widths.len());
+        // Columns are dropped the same way as when printing
+        let all_rows = titles.iter().chain(rows.iter());
+        let kept = match KeptColumns::new(&table.format, &table.render, all_rows, &min_width) {
+            Some(kept) => kept,
+            None => {
+                let columns = widths[added..].to_vec();
+                return (widths, columns);
+            }
+        };
+        let titles = titles.as_ref().map(|t| kept.apply(t));
+        let rows = rows.iter().map(|r| kept.apply(r)).collect::<Vec<_>>();
+        let mut printed = column_widths(&table.format, &titles, &rows);
+        for (width, min) in printed.iter_mut().zip(kept.widths(&min_width)) {
+            *width = (*width).max(min);
+        }
+        let columns = (added..widths.len())
+            .map(|c| kept.columns(&[c]).first().map_or(0, |&i| printed[i]))
+            .collect();
+        (printed, columns)
+    }
+
+    /// Print the table to `out` with the column widths of `layout`, and return the number
+    /// of lines printed, or an error.
+    ///
+    /// Narrower columns are padded up to the width of the layout, and the cells of wider
+    /// columns are wrapped, or truncated if they are set to with `Cell::set_truncate()`.
+    /// The layout overrides the widths set with `set_column_width()` for its columns,
+    /// apart from the ones of width 0, like the columns dropped to fit the maximum width.
+    /// Cells spanning several columns may still widen them.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// use prettytable::TableLayout;
+    /// # fn main() {
+    /// let table = table![["a", "Some text"]];
+    /// let mut out = Vec::new();
+    /// table.print_with_layout(&mut out, &TableLayout::new(vec![3, 4])).unwrap();
+    /// assert_eq!(String::from_utf8(out).unwrap().replace("\r\n", "\n"), "\
+    /// +-----+------+
+    /// | a   | Some |
+    /// |     | text |
+    /// +-----+------+
+    /// ");
+    /// # }
+    /// ```
+    pub fn print_with_layout<W: Write + ?Sized>(
+        &self,
+        out: &mut W,
+        layout: &TableLayout,
+    ) -> Result<usize, Error> {
+        let mut render = self.render.clone();
+        for (column, &width) in layout.widths.iter().enumerate() {
+            if width > 0 {
+                render.set_column_width(column, ColumnWidth::Fixed(width));
+            }
+        }
+        TableSlice {
+            render: &render,
+            ..self.as_slice()
+        }
+        .print(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TableLayout;
+    use crate::format::ColumnWidth;
+    use crate::{Cell, Row, Table};
+
+    fn print(table: &Table, layout: &TableLayout) -> String {
+        let mut out = Vec::new();
+        table.print_with_layout(&mut out, layout).unwrap();
+        String::from_utf8(out).unwrap().replace("\r\n", "\n")
+    }
+
+    #[test]
+    fn compute_layout() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("Name"), Cell::new("n")]));
+        table.add_row(Row::new(vec![Cell::new("a\tb"), Cell::new("123")]));
+        table.set_footer(Row::new(vec![Cell::new("total"), Cell::new("1234")]));
+        table.set_column_width(2, ColumnWidth::Min(3));
+        assert_eq!(table.compute_layout().widths(), &[5, 4]);
+        table.set_column_width(0, ColumnWidth::Max(3));
+        table.set_column_width(1, ColumnWidth::Min(6));
+        assert_eq!(table.compute_layout().widths(), &[3, 6]);
+        assert_eq!(Table::new().compute_layout(), TableLayout::default());
+    }
+
+    #[test]
+    fn merge() {
+        let mut layout = TableLayout::new(vec![3, 1]);
+        layout.merge(&TableLayout::new(vec![2, 4, 5]));
+        assert_eq!(layout.widths(), &[3, 4, 5]);
+        layout.merge(&TableLayout::new(vec![1]));
+        assert_eq!(layout.widths(), &[3, 4, 5]);
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            " 3, 0,12 ".parse::<TableLayout>().unwrap().widths(),
+            &[3, 0, 12]
+        );
+        assert_eq!("".parse::<TableLayout>().unwrap(), TableLayout::default());
+        assert!("3,,4".parse::<TableLayout>().is_err());
+        assert_eq!(TableLayout::default().to_string(), "");
+    }
+
+    #[test]
+    fn print_with_layout() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("id"), Cell::new("status")]));
+        table.add_row(Row::new(vec![Cell::new("1"), Cell::new("ok")]));
+        let layout = table.compute_layout();
+        table[0][1] = Cell::new("running");
+        let mut cell = Cell::new("failed");
+        cell.set_truncate(6, "…");
+        table.add_row(Row::new(vec![Cell::new("22"), cell]));
+        let out = "\
++----+--------+
+| id | status |
++====+========+
+| 1  | runnin |
+|    | g      |
++----+--------+
+| 22 | failed |
++----+--------+
+";
+        assert_eq!(print(&table, &layout), out);
+
+        let mut grown = layout.clone();
+        grown.merge(&table.compute_layout());
+        assert_eq!(grown.widths(), &[2, 7]);
+        table.set_titles(Row::new(vec![Cell::new("id")]));
+        let out = "\
++------+---------+
+| id   |         |
++======+=========+
+| 1    | running |
++------+---------+
+| 22   | failed  |
++------+---------+
+";
+        assert_eq!(print(&table, &TableLayout::new(vec![4, 7])), out);
+    }
+}
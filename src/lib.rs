@@ -5,23 +5,86 @@
     unused_qualifications
 )]
 //! A formatted and aligned table printer written in rust
+//!
+//! # Exports
+//!
+//! Besides being printed as text, tables can be written in markup languages, like HTML,
+//! Markdown or reStructuredText, and in data formats, like CSV or JSON. Markup exports
+//! all write a table the same way:
+//!
+//! * the cells are rendered like with `Table::rendered()`, except that rows are not
+//!   clipped to their maximum height nor striped with styles, and row numbers are only
+//!   written when `RowNumbers::exported` is set
+//! * the footer is written as the last row, marked as the table foot when the markup
+//!   has one, like the HTML `<tfoot>`
+//! * the caption is written with the markup for it, like the `.. table::` directive of
+//!   reStructuredText or the `Table:` paragraph of pandoc Markdown, and left out by the
+//!   markups without one: BBCode, Jira, LaTeX `tabular` and Graphviz labels
+//!
+//! Data exports write the cells unchanged, without the caption. CSV also writes the
+//! exported row numbers, and the footer on request.
 
 #[macro_use]
 extern crate lazy_static;
 
+use std::borrow::Borrow;
 use std::fmt;
 use std::io::{self, Error, Write};
-use std::iter::{FromIterator, IntoIterator};
-use std::ops::{Index, IndexMut};
+use std::iter::{self, FromIterator, IntoIterator};
+use std::ops::{Bound, Index, IndexMut, Range, RangeBounds};
 use std::slice::{Iter, IterMut};
 
 pub use term::{color, Attr};
 pub(crate) use term::{stdout, Terminal};
 
+mod append;
+mod ascii;
+mod asciidoc;
+mod bbcode;
+mod builder;
+mod caption;
 mod cell;
+mod checkpoint;
+mod columns;
+mod dedup;
+mod diff;
+mod equality;
+mod filter;
+mod fit;
+mod fixed_width;
 pub mod format;
+mod graphviz;
+mod group;
+mod html;
+mod infer;
+mod jira;
+mod latex;
+mod layout;
+mod limit;
+mod markdown;
+mod max_width;
+mod mediawiki;
+mod number;
+mod numbering;
+mod org;
+mod page;
+mod pandoc;
+mod parse;
+mod plain;
+mod refresh;
+mod render;
 mod row;
+mod rst;
+mod rule;
+mod search;
+mod sort;
+mod sql;
+mod style;
+mod summary;
+mod textile;
 mod utils;
+mod vertical;
+mod view;
 
 #[cfg(feature = "csv")]
 pub mod csv;
@@ -29,17 +92,65 @@ pub mod csv;
 #[cfg(feature = "evcxr")]
 pub mod evcxr;
 
+#[cfg(feature = "serde")]
+mod interchange;
+
+#[cfg(feature = "json")]
+mod json;
+
+#[cfg(feature = "serde")]
+mod ser;
+
+#[cfg(feature = "xlsx")]
+pub mod xlsx;
+
+#[cfg(feature = "yaml")]
+mod yaml;
+
+pub use builder::TableBuilder;
+use caption::Caption;
+pub use caption::CaptionPosition;
 pub use cell::Cell;
-use format::{consts, LinePosition, TableFormat};
+pub use checkpoint::TableCheckpoint;
+use columns::pad_row;
+pub use diff::DiffStats;
+pub use filter::FilteredTable;
+use format::{consts, Alignment, LinePosition, TableFormat};
+pub use group::GroupHeaders;
+pub use html::HtmlOptions;
+pub use infer::ColumnKind;
+pub use layout::TableLayout;
+pub use limit::RowLimit;
+use max_width::KeptColumns;
+pub use number::NumberFormat;
+pub use numbering::RowNumbers;
+pub use parse::ParseError;
+use render::{needs_preparing, prepare_row, RenderOptions};
 pub use row::Row;
-use utils::StringWriter;
+pub use rule::Rule;
+pub use search::SearchOptions;
+#[cfg(feature = "serde")]
+pub use ser::SerdeError;
+pub use sort::SortOrder;
+pub use style::{CellStyle, Color, StyleParseError};
+pub use summary::Aggregation;
+use utils::{strip_ansi, StringWriter};
+pub use view::TableView;
 
 /// An owned printable table
-#[derive(Default, Clone, Debug, Hash, PartialEq, Eq)]
+///
+/// Tables are compared and hashed on their plain text, see `Table::strict_eq()` and
+/// `Table::content_hash()`.
+#[derive(Default, Clone, Debug)]
 pub struct Table {
     format: Box<TableFormat>,
     titles: Box<Option<Row>>,
+    /// Header rows printed above the titles
+    header_rows: Vec<Row>,
     rows: Vec<Row>,
+    footer: Box<Option<Row>>,
+    caption: Caption,
+    render: RenderOptions,
 }
 
 /// A borrowed immutable `Table` slice
@@ -61,28 +172,26 @@ pub struct Table {
 /// # }
 /// ```
 ///
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug)]
 pub struct TableSlice<'a> {
     format: &'a TableFormat,
     titles: &'a Option<Row>,
+    header_rows: &'a [Row],
     rows: &'a [Row],
+    footer: &'a Option<Row>,
+    caption: &'a Caption,
+    render: &'a RenderOptions,
+    /// Rows and footer the columns are sized on, instead of the printed ones
+    sizing: Option<(&'a [Row], &'a Option<Row>)>,
+    /// Position of the first row of the slice in its table
+    offset: usize,
 }
 
 impl<'a> TableSlice<'a> {
     /// Compute and return the number of column
     // #[deprecated(since="0.8.0", note="Will become private in future release. See [issue #87](https://github.com/phsym/prettytable-rs/issues/87)")]
     fn get_column_num(&self) -> usize {
-        let mut cnum = match *self.titles {
-            Some(ref t) => t.column_count(),
-            None => 0,
-        };
-        for r in self.rows {
-            let l = r.column_count();
-            if l > cnum {
-                cnum = l;
-            }
-        }
-        cnum
+        column_count(self.titles, self.rows)
     }
 
     /// Get the number of rows
@@ -100,42 +209,84 @@ impl<'a> TableSlice<'a> {
         self.rows.get(row)
     }
 
-    /// Get the width of the column at position `col_idx`.
-    /// Return 0 if the column does not exists;
-    fn get_column_width(&self, col_idx: usize) -> usize {
-        let mut width = match *self.titles {
-            Some(ref t) => t.get_column_width(col_idx, self.format),
-            None => 0,
-        };
-        for r in self.rows {
-            let l = r.get_column_width(col_idx, self.format);
-            if l > width {
-                width = l;
-            }
+    /// Return a slice of the rows of this slice in `range`, like `Slice::slice()`.
+    /// Rows out of this slice are silently skipped.
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> TableSlice<'a> {
+        let range = clamp_range(&range, self.rows.len());
+        TableSlice {
+            offset: self.offset + range.start,
+            rows: &self.rows[range],
+            ..*self
+        }
+    }
+
+    /// Return the position in their table of each of the rows of this slice, when rows are
+    /// numbered after it, or nothing otherwise
+    fn row_positions(&self) -> Vec<usize> {
+        if !self.render.row_numbers().is_some_and(|n| n.original) {
+            return Vec::new();
+        }
+        (self.offset..self.offset + self.rows.len()).collect()
+    }
+
+    /// Return a new table holding a copy of the rows of this slice, with the titles,
+    /// header rows, footer, caption, format and display settings of its table
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// use prettytable::Slice;
+    /// # fn main() {
+    /// let table = table![["a"], ["b"], ["c"], ["d"]];
+    /// let copy = table.slice(1..).slice(..=1).to_table();
+    /// assert_eq!(copy, table![["b"], ["c"]]);
+    /// assert!(table.slice(3..10).slice(5..).to_table().is_empty());
+    /// # }
+    /// ```
+    pub fn to_table(&self) -> Table {
+        Table {
+            format: Box::new(*self.format),
+            titles: Box::new(self.titles.clone()),
+            header_rows: self.header_rows.to_vec(),
+            rows: self.rows.to_vec(),
+            footer: Box::new(self.footer.clone()),
+            caption: self.caption.clone(),
+            render: self.render.clone(),
         }
-        width
     }
 
     /// Get the width of all columns, and return a slice
-    /// with the result for each column
+    /// with the result for each column.
+    ///
+    /// Columns are first sized after the cells spanning a single column. Then, when a cell
+    /// spanning several columns needs more room, the missing width is shared evenly
+    /// between the spanned columns, narrower spans being handled first.
     fn get_all_column_width(&self) -> Vec<usize> {
+        column_widths(self.format, self.titles, self.rows)
+    }
+
+    /// Get the alignment of each column, as found in the titles row,
+    /// or in the first row if there are no titles. Columns not covered default to `LEFT`
+    fn get_column_alignments(&self) -> Vec<Alignment> {
         let colnum = self.get_column_num();
-        let mut col_width = vec![0usize; colnum];
-        #[allow(clippy::needless_range_loop)]
-        for i in 0..colnum {
-            // TODO: calling "get_column_width()" in a loop is inefficient
-            col_width[i] = self.get_column_width(i);
+        let mut aligns = Vec::with_capacity(colnum);
+        let row = self.titles.as_ref().or_else(|| self.rows.first());
+        for cell in row.into_iter().flat_map(Row::iter) {
+            for _ in 0..cell.get_hspan() {
+                aligns.push(cell.get_align());
+            }
         }
-        col_width
+        aligns.resize(colnum, Alignment::LEFT);
+        aligns
     }
 
     /// Returns an iterator over the immutable cells of the column specified by `column`
-    pub fn column_iter(&self, column: usize) -> ColumnIter {
+    pub fn column_iter(&self, column: usize) -> ColumnIter<'_> {
         ColumnIter(self.rows.iter(), column)
     }
 
     /// Returns an iterator over immutable rows
-    pub fn row_iter(&self) -> Iter<Row> {
+    pub fn row_iter(&self) -> Iter<'_, Row> {
         self.rows.iter()
     }
 
@@ -144,33 +295,7 @@ impl<'a> TableSlice<'a> {
     where
         F: Fn(&Row, &mut T, &TableFormat, &[usize]) -> Result<usize, Error>,
     {
-        let mut height = 0;
-        // Compute columns width
-        let col_width = self.get_all_column_width();
-        height += self
-            .format
-            .print_line_separator(out, &col_width, LinePosition::Top)?;
-        if let Some(ref t) = *self.titles {
-            height += f(t, out, self.format, &col_width)?;
-            height += self
-                .format
-                .print_line_separator(out, &col_width, LinePosition::Title)?;
-        }
-        // Print rows
-        let mut iter = self.rows.iter().peekable();
-        while let Some(r) = iter.next() {
-            height += f(r, out, self.format, &col_width)?;
-            if iter.peek().is_some() {
-                height +=
-                    self.format
-                        .print_line_separator(out, &col_width, LinePosition::Intern)?;
-            }
-        }
-        height += self
-            .format
-            .print_line_separator(out, &col_width, LinePosition::Bottom)?;
-        out.flush()?;
-        Ok(height)
+        print_rows(out, self, self.rows, &self.row_positions(), None, f)
     }
 
     /// Print the table to `out` and returns the number of
@@ -211,27 +336,374 @@ impl<'a> TableSlice<'a> {
         let _ = self.print_tty(false); // Ignore result
     }
 
+    /// Return the lines printed by `print()`, without line endings
+    pub fn render_lines(&self) -> Vec<String> {
+        self.to_string().lines().map(str::to_string).collect()
+    }
+
+    /// Return, for each row, the range of the lines printed for it among the lines
+    /// returned by `render_lines()`. Rows printed on several lines, like rows holding
+    /// multi-line cells, span several lines, and the separators between rows are left
+    /// out of the ranges.
+    pub fn row_line_ranges(&self) -> Vec<Range<usize>> {
+        let mut row_lines = Vec::new();
+        // Printing to a sink cannot fail
+        let _ = print_rows_lines(
+            &mut io::sink(),
+            self,
+            self.rows,
+            &self.row_positions(),
+            None,
+            Row::print,
+            &mut row_lines,
+        );
+        row_lines
+    }
+
     /// Print table in HTML format to `out`.
     pub fn print_html<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
-        // Compute column width
-        let column_num = self.get_column_num();
-        out.write_all(b"<table>")?;
-        // Print titles / table header
-        if let Some(ref t) = *self.titles {
-            out.write_all(b"<th>")?;
-            t.print_html(out, column_num)?;
-            out.write_all(b"</th>")?;
+        self.print_html_with_options(out, &HtmlOptions::default())
+    }
+}
+
+/// Return the positions of `range` within `0..len`. Positions past `len` are dropped,
+/// and a range ending before its start is empty
+fn clamp_range<R: RangeBounds<usize>>(range: &R, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&s) => s,
+        Bound::Excluded(&s) => s.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&e) => e.saturating_add(1),
+        Bound::Excluded(&e) => e,
+        Bound::Unbounded => len,
+    };
+    let end = end.min(len);
+    start.min(end)..end
+}
+
+/// Compute the number of columns of `titles` and `rows`
+fn column_count<R: Borrow<Row>>(titles: &Option<Row>, rows: &[R]) -> usize {
+    let mut cnum = match *titles {
+        Some(ref t) => t.column_count(),
+        None => 0,
+    };
+    for r in rows {
+        let l = r.borrow().column_count();
+        if l > cnum {
+            cnum = l;
         }
-        // Print rows
-        for r in self.rows {
-            out.write_all(b"<tr>")?;
-            r.print_html(out, column_num)?;
-            out.write_all(b"</tr>")?;
+    }
+    cnum
+}
+
+/// Get the width of all the columns of `titles` and `rows`.
+///
+/// Columns are first sized after the cells spanning a single column. Then, when a cell
+/// spanning several columns needs more room, the missing width is shared evenly
+/// between the spanned columns, narrower spans being handled first.
+fn column_widths<R: Borrow<Row>>(
+    format: &TableFormat,
+    titles: &Option<Row>,
+    rows: &[R],
+) -> Vec<usize> {
+    let colnum = column_count(titles, rows);
+    let mut col_width = vec![0usize; colnum];
+    let mut spanning = Vec::new();
+    for row in titles.iter().chain(rows.iter().map(Borrow::borrow)) {
+        for (column, hspan, width) in row.get_cell_widths(format) {
+            if hspan == 1 {
+                col_width[column] = col_width[column].max(width);
+            } else {
+                spanning.push((column, hspan, width));
+            }
         }
-        out.write_all(b"</table>")?;
+    }
+    spanning.sort_by_key(|&(_, hspan, _)| hspan);
+    for (column, hspan, width) in spanning {
+        let spanned = &mut col_width[column..column + hspan];
+        let current: usize = spanned.iter().sum();
+        if width > current {
+            let missing = width - current;
+            for (k, w) in spanned.iter_mut().enumerate() {
+                *w += missing / hspan + usize::from(k < missing % hspan);
+            }
+        }
+    }
+    col_width
+}
+
+/// Print `rows` to `out` with the format, titles and footer of `table`, once transformed
+/// by its render options, and only the rows kept by `limit` if any.
+/// `positions` are the positions of `rows` in their table, used to number them like
+/// `positions` of `RenderOptions::render()`.
+/// `f` prints a single row and returns the number of lines printed.
+fn print_rows<T: Write + ?Sized, F, R: Borrow<Row>>(
+    out: &mut T,
+    table: &TableSlice,
+    rows: &[R],
+    positions: &[usize],
+    limit: Option<&RowLimit>,
+    f: F,
+) -> Result<usize, Error>
+where
+    F: Fn(&Row, &mut T, &TableFormat, &[usize]) -> Result<usize, Error>,
+{
+    print_rows_lines(out, table, rows, positions, limit, f, &mut Vec::new())
+}
+
+/// Print `rows` to `out` like `print_rows()`, and set `row_lines` to the range of lines
+/// printed for each of `rows`
+fn print_rows_lines<T: Write + ?Sized, F, R: Borrow<Row>>(
+    out: &mut T,
+    table: &TableSlice,
+    rows: &[R],
+    positions: &[usize],
+    limit: Option<&RowLimit>,
+    f: F,
+    row_lines: &mut Vec<Range<usize>>,
+) -> Result<usize, Error>
+where
+    F: Fn(&Row, &mut T, &TableFormat, &[usize]) -> Result<usize, Error>,
+{
+    let (format, titles, render) = (table.format, table.titles, table.render);
+    let footer = table.footer.as_ref();
+    let clipped = |r: &Row| r.get_max_height().is_some();
+    if !render.is_identity_for(titles, rows)
+        || footer.is_some_and(clipped)
+        || table.header_rows.iter().any(clipped)
+        || table.sizing.is_some()
+    {
+        let hidden = (0..rows.len())
+            .map(|i| render.hidden_separators(rows, i))
+            .collect::<Vec<_>>();
+        let min_width = render.min_column_widths();
+        let (titles, rows) = render.render(titles, rows, positions, true);
+        let footer = footer.map(|f| render.render_fixed_row(f, true));
+        let header_rows = table
+            .header_rows
+            .iter()
+            .map(|t| render.render_fixed_row(t, true))
+            .collect::<Vec<_>>();
+        let sizing_rows = table.sizing.map(|(rows, footer)| {
+            let (_, mut rows) = render.render(&None, rows, &[], true);
+            rows.extend(footer.iter().map(|f| render.render_fixed_row(f, true)));
+            rows
+        });
+        let rendered = Rendered {
+            sizing_rows: sizing_rows.as_deref(),
+            header_rows: &header_rows,
+            footer: footer.as_ref(),
+            caption: Some(table.caption),
+            hidden: &hidden,
+            min_width: &min_width,
+            capped: Some(render).filter(|r| r.max_width().is_some()),
+        };
+        return print_rendered_rows(out, format, &titles, &rows, rendered, limit, f, row_lines);
+    }
+    let rendered = Rendered {
+        header_rows: table.header_rows,
+        footer,
+        caption: Some(table.caption),
+        ..Rendered::default()
+    };
+    print_rendered_rows(out, format, titles, rows, rendered, limit, f, row_lines)
+}
+
+/// Header rows, footer and layout settings of rendered rows, used when printing them
+#[derive(Clone, Copy, Default)]
+struct Rendered<'a> {
+    /// Rows the columns are sized on, instead of the printed ones
+    sizing_rows: Option<&'a [Row]>,
+    /// Header rows printed above the titles
+    header_rows: &'a [Row],
+    /// Row printed below the other ones
+    footer: Option<&'a Row>,
+    /// Caption printed above or below the table
+    caption: Option<&'a Caption>,
+    /// Columns left blank in the separator above each row
+    hidden: &'a [Vec<usize>],
+    /// Minimum width of each column
+    min_width: &'a [usize],
+    /// Render options setting a maximum width to the table, if any
+    capped: Option<&'a RenderOptions>,
+}
+
+/// Print `rendered.header_rows`, `titles`, `rows` and `rendered.footer` to `out` with
+/// `format`, leaving blank the columns listed in `rendered.hidden[i]` in the separator
+/// above row `i`, and only the rows kept by `limit` if any. `row_lines` is set to the range
+/// of lines printed for each of `rows`, which is empty for the rows omitted by `limit`.
+#[allow(clippy::too_many_arguments)]
+fn print_rendered_rows<T: Write + ?Sized, F, R: Borrow<Row>>(
+    out: &mut T,
+    format: &TableFormat,
+    titles: &Option<Row>,
+    rows: &[R],
+    rendered: Rendered,
+    limit: Option<&RowLimit>,
+    f: F,
+    row_lines: &mut Vec<Range<usize>>,
+) -> Result<usize, Error>
+where
+    F: Fn(&Row, &mut T, &TableFormat, &[usize]) -> Result<usize, Error>,
+{
+    let rows_iter = || rows.iter().map(Borrow::borrow);
+    let (header_rows, footer) = (rendered.header_rows, rendered.footer);
+    let sizing_rows = rendered.sizing_rows;
+    let all_rows = || {
+        header_rows
+            .iter()
+            .chain(titles)
+            .chain(rows_iter())
+            .chain(footer)
+            .chain(sizing_rows.into_iter().flatten())
+    };
+    if all_rows().any(|r| needs_preparing(r, format)) {
+        let titles = titles.as_ref().map(|t| prepare_row(t, format));
+        let rows = rows_iter()
+            .map(|r| prepare_row(r, format))
+            .collect::<Vec<_>>();
+        let footer = footer.map(|r| prepare_row(r, format));
+        let header_rows = header_rows
+            .iter()
+            .map(|r| prepare_row(r, format))
+            .collect::<Vec<_>>();
+        let sizing_rows = sizing_rows.map(|rows| {
+            rows.iter()
+                .map(|r| prepare_row(r, format))
+                .collect::<Vec<_>>()
+        });
+        let rendered = Rendered {
+            sizing_rows: sizing_rows.as_deref(),
+            header_rows: &header_rows,
+            footer: footer.as_ref(),
+            ..rendered
+        };
+        return print_rendered_rows(out, format, &titles, &rows, rendered, limit, f, row_lines);
+    }
+    let capped = rendered
+        .capped
+        .and_then(|render| KeptColumns::new(format, render, all_rows(), rendered.min_width));
+    if let Some(kept) = capped {
+        let titles = titles.as_ref().map(|t| kept.apply(t));
+        let rows = rows_iter().map(|r| kept.apply(r)).collect::<Vec<_>>();
+        let footer = footer.map(|r| kept.apply(r));
+        let header_rows = header_rows
+            .iter()
+            .map(|r| kept.apply(r))
+            .collect::<Vec<_>>();
+        let sizing_rows =
+            sizing_rows.map(|rows| rows.iter().map(|r| kept.apply(r)).collect::<Vec<_>>());
+        let hidden = rendered
+            .hidden
+            .iter()
+            .map(|h| kept.columns(h))
+            .collect::<Vec<_>>();
+        let min_width = kept.widths(rendered.min_width);
+        let rendered = Rendered {
+            sizing_rows: sizing_rows.as_deref(),
+            header_rows: &header_rows,
+            footer: footer.as_ref(),
+            hidden: &hidden,
+            min_width: &min_width,
+            capped: None,
+            ..rendered
+        };
+        return print_rendered_rows(out, format, &titles, &rows, rendered, limit, f, row_lines);
+    }
+    row_lines.clear();
+    row_lines.resize(rows.len(), 0..0);
+    // Nothing to print, not even borders
+    if titles.is_none() && rows.is_empty() && footer.is_none() {
         out.flush()?;
-        Ok(())
+        return Ok(0);
+    }
+    // Rows to print, with their index. Omitted rows are replaced by a marker row
+    let marker;
+    let mut printed: Vec<(Option<usize>, &Row)> =
+        rows_iter().enumerate().map(|(i, r)| (Some(i), r)).collect();
+    let mut sized: Vec<&Row> = Vec::new();
+    let mut omitted_rows = 0..0;
+    if let Some(limit) = limit {
+        if let Some(omitted) = limit.omitted(rows.len()) {
+            marker = limit.marker(omitted.len(), column_count(titles, rows));
+            omitted_rows = omitted.clone();
+            printed.splice(omitted, iter::once((None, &marker)));
+            if limit.sizes_on_all_rows() {
+                sized = rows_iter().chain(iter::once(&marker)).collect();
+            }
+        }
+    }
+    if sized.is_empty() {
+        sized = match sizing_rows {
+            Some(rows) => rows.iter().collect(),
+            None => printed.iter().map(|&(_, r)| r).collect(),
+        };
     }
+    sized.extend(header_rows.iter().chain(footer));
+    let caption = rendered.caption;
+    let mut height = 0;
+    // Compute columns width
+    let mut col_width = column_widths(format, titles, &sized);
+    for (width, &min) in col_width.iter_mut().zip(rendered.min_width) {
+        *width = (*width).max(min);
+    }
+    if let Some(caption) = caption.filter(|c| c.is_at(CaptionPosition::Above)) {
+        height += caption.print(out, format, &col_width)?;
+    }
+    height += format.print_line_separator(out, &col_width, LinePosition::Top)?;
+    if let Some(ref t) = *titles {
+        for r in header_rows {
+            height += f(r, out, format, &col_width)?;
+        }
+        height += f(t, out, format, &col_width)?;
+        height += format.print_line_separator(out, &col_width, LinePosition::Title)?;
+    }
+    // Print rows
+    let mut above: Option<(Option<usize>, &Row)> = None;
+    for &(index, r) in &printed {
+        if let Some((above_index, above)) = above {
+            let hidden = match (above_index, index) {
+                (Some(a), Some(i)) if a + 1 == i => {
+                    rendered.hidden.get(i).map_or(&[][..], |h| &h[..])
+                }
+                _ => &[],
+            };
+            let pos = match *format.get_sep_for_line(LinePosition::Intern) {
+                // Without separator between rows, the title, bottom or top one is used
+                // below flagged rows, whichever the format has first
+                None if above.has_separator_below() => {
+                    [LinePosition::Title, LinePosition::Bottom, LinePosition::Top]
+                        .iter()
+                        .copied()
+                        .find(|&pos| format.get_sep_for_line(pos).is_some())
+                        .unwrap_or(LinePosition::Intern)
+                }
+                _ => LinePosition::Intern,
+            };
+            height += format.print_line_separator_except(out, &col_width, hidden, pos)?;
+        }
+        let start = height;
+        height += f(r, out, format, &col_width)?;
+        match index {
+            Some(i) => row_lines[i] = start..height,
+            // Omitted rows are located before the marker
+            None => row_lines[omitted_rows.clone()].fill(start..start),
+        }
+        above = Some((index, r));
+    }
+    // The footer is separated from the rows like the titles are
+    if let Some(footer) = footer {
+        height += format.print_line_separator(out, &col_width, LinePosition::Title)?;
+        height += f(footer, out, format, &col_width)?;
+    }
+    height += format.print_line_separator(out, &col_width, LinePosition::Bottom)?;
+    if let Some(caption) = caption.filter(|c| c.is_at(CaptionPosition::Below)) {
+        height += caption.print(out, format, &col_width)?;
+    }
+    out.flush()?;
+    Ok(height)
 }
 
 impl<'a> IntoIterator for &'a TableSlice<'a> {
@@ -248,12 +720,74 @@ impl Table {
         Self::init(Vec::new())
     }
 
+    /// Create a table from `rows`, where each row is an iterator of cell values.
+    ///
+    /// Rows may have different lengths.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use prettytable::Table;
+    /// let table = Table::from_rows(vec![vec!["a", "b"], vec!["c"]]);
+    /// assert_eq!(table.len(), 2);
+    /// ```
+    pub fn from_rows<I, J, T>(rows: I) -> Table
+    where
+        I: IntoIterator<Item = J>,
+        J: IntoIterator<Item = T>,
+        T: ToString,
+    {
+        rows.into_iter().collect()
+    }
+
+    /// Create a two-column table from key-value pairs, one row per entry.
+    ///
+    /// Entries keep the iteration order of `map`, so a `BTreeMap` gives sorted
+    /// rows while a `HashMap` gives them in arbitrary order.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use prettytable::Table;
+    /// # use std::collections::BTreeMap;
+    /// let mut map = BTreeMap::new();
+    /// map.insert("name", "prettytable");
+    /// map.insert("edition", "2018");
+    /// let table = Table::from_map(map);
+    /// assert_eq!(table[0][0].get_content(), "edition");
+    /// assert_eq!(table[1][1].get_content(), "prettytable");
+    /// ```
+    pub fn from_map<I, K, V>(map: I) -> Table
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: ToString,
+        V: ToString,
+    {
+        map.into_iter()
+            .map(|(k, v)| Row::new(vec![Cell::new(&k.to_string()), Cell::new(&v.to_string())]))
+            .collect()
+    }
+
+    /// Same as `from_map`, with `key` and `value` as the title row.
+    pub fn from_map_with_titles<I, K, V>(map: I, key: &str, value: &str) -> Table
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: ToString,
+        V: ToString,
+    {
+        let mut table = Self::from_map(map);
+        table.set_titles(Row::new(vec![Cell::new(key), Cell::new(value)]));
+        table
+    }
+
     /// Create a table initialized with `rows`
     pub fn init(rows: Vec<Row>) -> Table {
         Table {
             rows,
             titles: Box::new(None),
+            header_rows: Vec::new(),
+            footer: Box::new(None),
+            caption: Caption::default(),
             format: Box::new(*consts::FORMAT_DEFAULT),
+            render: RenderOptions::default(),
         }
     }
 
@@ -284,14 +818,139 @@ impl Table {
         self.rows.is_empty()
     }
 
-    /// Set the optional title lines
+    /// Get the number of columns, which is the largest count of columns
+    /// spanned by the titles, the header rows, the footer or any row
+    pub fn column_count(&self) -> usize {
+        let rows = self
+            .header_rows
+            .iter()
+            .chain(self.rows.iter())
+            .chain(self.footer.iter())
+            .collect::<Vec<_>>();
+        column_count(&self.titles, &rows)
+    }
+
+    /// Get the display width of each column, as it is printed. Widths exclude the
+    /// padding of the format, and take into account the cells spanning several columns
+    /// or overriding the padding, as well as the widths set with `set_column_width()`.
+    /// The column of row numbers is left out, so that there is a width for each of the
+    /// `column_count()` columns, and the columns dropped to fit the width set with
+    /// `set_max_width()` have a width of 0.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let table = table![["Name", "Age"], ["Alice", 34]];
+    /// assert_eq!(table.column_widths(), vec![5, 3]);
+    /// assert_eq!(table.total_width(), 15);
+    /// # }
+    /// ```
+    pub fn column_widths(&self) -> Vec<usize> {
+        self.printed_widths().1
+    }
+
+    /// Get the display width of the lines of the printed table, including the
+    /// indentation, the borders, the column separators and the padding of the format,
+    /// as well as the column of row numbers and the marker of dropped columns
+    pub fn total_width(&self) -> usize {
+        fit::table_width(&self.format, &self.printed_widths().0)
+    }
+
+    /// Set the optional title lines, replacing all the header rows with `titles`
     pub fn set_titles(&mut self, titles: Row) {
         *self.titles = Some(titles);
+        self.header_rows.clear();
     }
 
-    /// Unset the title line
+    /// Unset the title line, and all the header rows
     pub fn unset_titles(&mut self) {
         *self.titles = None;
+        self.header_rows.clear();
+    }
+
+    /// Add a header row below the current ones, which are all printed above the
+    /// title separator. Like titles, header rows are not among the rows of the table.
+    ///
+    /// The last header row added is the one used as titles, for instance by exports
+    /// having a single header row like CSV. The ones above it are only printed, as text
+    /// or HTML. Cells spanning several columns give grouped headings.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// use prettytable::{Cell, Row};
+    /// # fn main() {
+    /// let mut table = table![[1, 2, 3, 4]];
+    /// table.add_title_row(Row::new(vec![
+    ///     Cell::new("2023").with_hspan(2),
+    ///     Cell::new("2024").with_hspan(2),
+    /// ]));
+    /// table.add_title_row(row!["H1", "H2", "H1", "H2"]);
+    /// assert_eq!(table.to_string().replace("\r\n", "\n"), "\
+    /// +----+----+----+----+
+    /// | 2023    | 2024    |
+    /// | H1 | H2 | H1 | H2 |
+    /// +====+====+====+====+
+    /// | 1  | 2  | 3  | 4  |
+    /// +----+----+----+----+
+    /// ");
+    /// # }
+    /// ```
+    pub fn add_title_row(&mut self, row: Row) {
+        if let Some(titles) = self.titles.replace(row) {
+            self.header_rows.push(titles);
+        }
+    }
+
+    /// Return an iterator over all the header rows, from top to bottom,
+    /// the titles being the last one
+    pub fn title_rows(&self) -> impl Iterator<Item = &Row> {
+        self.header_rows.iter().chain(self.titles.iter())
+    }
+
+    /// Set the footer, a row printed below the other ones, and separated from them
+    /// like the titles are.
+    ///
+    /// The footer is not one of the rows of the table, so that it stays at the bottom
+    /// when sorting, filtering or slicing the table, and is not counted by `len()`.
+    /// Markup exports write it as their last row, and CSV on request, see the crate
+    /// documentation.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut table = table![["apples", 3], ["pears", 12]];
+    /// table.set_footer(row!["Total", 15]);
+    /// assert_eq!(table.to_string().replace("\r\n", "\n"), "\
+    /// +--------+----+
+    /// | apples | 3  |
+    /// +--------+----+
+    /// | pears  | 12 |
+    /// +========+====+
+    /// | Total  | 15 |
+    /// +--------+----+
+    /// ");
+    /// # }
+    /// ```
+    pub fn set_footer(&mut self, footer: Row) {
+        *self.footer = Some(footer);
+    }
+
+    /// Unset the footer
+    pub fn unset_footer(&mut self) {
+        *self.footer = None;
+    }
+
+    /// Get a reference to the footer, if any
+    pub fn get_footer(&self) -> Option<&Row> {
+        self.footer.as_ref().as_ref()
+    }
+
+    /// Get a mutable reference to the footer, if any
+    pub fn get_mut_footer(&mut self) -> Option<&mut Row> {
+        self.footer.as_mut().as_mut()
     }
 
     /// Get a mutable reference to a row
@@ -304,6 +963,57 @@ impl Table {
         self.rows.get(row)
     }
 
+    /// Get an immutable reference to the cell at index `column` of row `row`
+    pub fn get_cell(&self, row: usize, column: usize) -> Option<&Cell> {
+        self.rows.get(row).and_then(|r| r.get_cell(column))
+    }
+
+    /// Get a mutable reference to the cell at index `column` of row `row`
+    pub fn get_mut_cell(&mut self, row: usize, column: usize) -> Option<&mut Cell> {
+        self.rows.get_mut(row).and_then(|r| r.get_mut_cell(column))
+    }
+
+    /// Set `cell` at index `column` of row `row`. A row shorter than `column` is first
+    /// padded with empty cells. An error is returned if the table has no row `row`.
+    pub fn set_cell(&mut self, row: usize, column: usize, cell: Cell) -> Result<(), &str> {
+        let row = self.rows.get_mut(row).ok_or("Cannot find row")?;
+        pad_row(row, column + 1);
+        row[column] = cell;
+        Ok(())
+    }
+
+    /// Set each cell of `updates` at its `(row, column)` coordinates, like `set_cell()`, and
+    /// return the coordinates of the updates which were skipped because the table has no
+    /// such row, in order.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// use prettytable::Cell;
+    /// # fn main() {
+    /// let mut table = table![["build", "running"], ["test"]];
+    /// let skipped = table.apply(vec![
+    ///     ((0, 1), Cell::new("done")),
+    ///     ((1, 1), Cell::new("running")),
+    ///     ((2, 1), Cell::new("queued")),
+    /// ]);
+    /// assert_eq!(skipped, vec![(2, 1)]);
+    /// assert_eq!(table, table![["build", "done"], ["test", "running"]]);
+    /// # }
+    /// ```
+    pub fn apply<I: IntoIterator<Item = ((usize, usize), Cell)>>(
+        &mut self,
+        updates: I,
+    ) -> Vec<(usize, usize)> {
+        let mut skipped = Vec::new();
+        for ((row, column), cell) in updates {
+            if self.set_cell(row, column, cell).is_err() {
+                skipped.push((row, column));
+            }
+        }
+        skipped
+    }
+
     /// Append a row in the table, transferring ownership of this row to the table
     /// and returning a mutable reference to the row
     pub fn add_row(&mut self, row: Row) -> &mut Row {
@@ -312,6 +1022,12 @@ impl Table {
         &mut self.rows[l]
     }
 
+    /// Append a row made of the fields of `line` separated by `delimiter`.
+    /// Return a mutable reference to this new row. See `Row::from_delimited()` for details.
+    pub fn add_row_split(&mut self, line: &str, delimiter: char) -> &mut Row {
+        self.add_row(Row::from_delimited(line, delimiter))
+    }
+
     /// Append an empty row in the table. Return a mutable reference to this new row.
     pub fn add_empty_row(&mut self) -> &mut Row {
         self.add_row(Row::default())
@@ -335,6 +1051,22 @@ impl Table {
         rowline.set_cell(Cell::new(element), column)
     }
 
+    /// Set the style of the row at position `index` from the specifier `spec`.
+    /// See `Row::set_style()` for details.
+    pub fn set_row_style(&mut self, index: usize, spec: &str) -> Result<(), &str> {
+        let row = self.get_mut_row(index).ok_or("Cannot find row")?;
+        row.set_style_spec(spec);
+        Ok(())
+    }
+
+    /// Print a separator line after the row at position `index`.
+    /// See `Row::set_separator_below()` for details.
+    pub fn add_separator_after(&mut self, index: usize) -> Result<(), &str> {
+        let row = self.get_mut_row(index).ok_or("Cannot find row")?;
+        row.set_separator_below(true);
+        Ok(())
+    }
+
     /// Remove the row at position `index`. Silently skip if the row does not exist
     pub fn remove_row(&mut self, index: usize) {
         if index < self.rows.len() {
@@ -342,26 +1074,195 @@ impl Table {
         }
     }
 
-    /// Return an iterator over the immutable cells of the column specified by `column`
-    pub fn column_iter(&self, column: usize) -> ColumnIter {
+    /// Remove the rows in `range`. Rows out of the table are silently skipped.
+    ///
+    /// Slices borrow the table, so none can be alive while rows are removed:
+    /// ```rust,compile_fail
+    /// # #[macro_use] extern crate prettytable;
+    /// # use prettytable::Slice;
+    /// # fn main() {
+    /// let mut table = table![["a"], ["b"], ["c"]];
+    /// let slice = table.slice(1..);
+    /// table.remove_rows(0..2);
+    /// slice.printstd();
+    /// # }
+    /// ```
+    pub fn remove_rows(&mut self, range: Range<usize>) {
+        self.drain(range);
+    }
+
+    /// Remove the rows in `range` from the table, and return them in an iterator.
+    /// Rows out of the table are silently skipped.
+    ///
+    /// The rows are removed even if the iterator is not fully consumed.
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> std::vec::Drain<'_, Row> {
+        let range = clamp_range(&range, self.rows.len());
+        self.rows.drain(range)
+    }
+
+    /// Swap the rows at positions `a` and `b`
+    pub fn swap_rows(&mut self, a: usize, b: usize) -> Result<(), &str> {
+        if a >= self.rows.len() || b >= self.rows.len() {
+            return Err("Cannot find row");
+        }
+        self.rows.swap(a, b);
+        Ok(())
+    }
+
+    /// Keep the first `len` rows of the table and remove the others.
+    /// Nothing is removed if the table has `len` rows or less
+    pub fn truncate(&mut self, len: usize) {
+        self.rows.truncate(len);
+    }
+
+    /// Return an iterator over the immutable cells of the column specified by `column`.
+    /// The iteration stops at the first row too short to have a cell in the column,
+    /// use `column()` to go through all the rows.
+    pub fn column_iter(&self, column: usize) -> ColumnIter<'_> {
         ColumnIter(self.rows.iter(), column)
     }
 
-    /// Return an iterator over the mutable cells of the column specified by `column`
-    pub fn column_iter_mut(&mut self, column: usize) -> ColumnIterMut {
+    /// Return an iterator over the mutable cells of the column specified by `column`.
+    /// The iteration stops at the first row too short to have a cell in the column,
+    /// use `column_mut()` to go through all the rows.
+    pub fn column_iter_mut(&mut self, column: usize) -> ColumnIterMut<'_> {
         ColumnIterMut(self.rows.iter_mut(), column)
     }
 
+    /// Return an iterator yielding the cell at index `column` of each row, or `None`
+    /// for the rows too short to have one
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let table = table![["a", "1"], ["b"], ["c", "\x1b[1m3\x1b[0m"]];
+    /// let cells: Vec<_> = table.column(1).map(|c| c.map(|c| c.get_content())).collect();
+    /// assert_eq!(cells, vec![Some("1".to_string()), None, Some("\x1b[1m3\x1b[0m".to_string())]);
+    /// assert_eq!(table.column_values(1), vec!["1", "", "3"]);
+    /// # }
+    /// ```
+    pub fn column(&self, column: usize) -> ColumnCells<'_> {
+        ColumnCells(self.rows.iter(), column)
+    }
+
+    /// Return an iterator yielding the mutable cell at index `column` of each row,
+    /// or `None` for the rows too short to have one
+    pub fn column_mut(&mut self, column: usize) -> ColumnCellsMut<'_> {
+        ColumnCellsMut(self.rows.iter_mut(), column)
+    }
+
+    /// Return the plain text of the cell at index `column` of each row, without its ANSI
+    /// escape sequences, or an empty string for the rows too short to have one
+    pub fn column_values(&self, column: usize) -> Vec<String> {
+        self.column(column)
+            .map(|cell| cell.map_or(String::new(), |c| strip_ansi(&c.get_content())))
+            .collect()
+    }
+
+    /// Return an iterator over the columns of the table, each one being an iterator over
+    /// its cells as returned by `column()`, up to the length of the longest row.
+    /// The table is not transposed.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let table = table![["a", "b"], ["c"]];
+    /// let filled: Vec<usize> = table.columns().map(|c| c.flatten().count()).collect();
+    /// assert_eq!(filled, vec![2, 1]);
+    /// # }
+    /// ```
+    pub fn columns(&self) -> Columns<'_> {
+        let count = self.rows.iter().map(Row::len).max().unwrap_or(0);
+        Columns(&self.rows, 0..count)
+    }
+
+    /// Wrap the content of the cells of column `column`, titles included, at `width`
+    /// display columns when printed.
+    ///
+    /// Only the cells currently in the table are affected. See `Cell::set_max_width()`
+    /// for details.
+    pub fn set_max_column_width(&mut self, column: usize, width: usize) {
+        if let Some(cell) = self
+            .titles
+            .as_mut()
+            .as_mut()
+            .and_then(|t| t.get_mut_cell(column))
+        {
+            cell.set_max_width(width);
+        }
+        for cell in self.column_mut(column).flatten() {
+            cell.set_max_width(width);
+        }
+    }
+
+    /// Set the left and right padding of the cells of column `column`, titles included,
+    /// overriding the padding of the table format.
+    ///
+    /// Only the cells currently in the table are affected.
+    pub fn set_column_padding(&mut self, column: usize, left: usize, right: usize) {
+        if let Some(cell) = self
+            .titles
+            .as_mut()
+            .as_mut()
+            .and_then(|t| t.get_mut_cell(column))
+        {
+            cell.set_padding(left, right);
+        }
+        for cell in self.column_mut(column).flatten() {
+            cell.set_padding(left, right);
+        }
+    }
+
     /// Returns an iterator over immutable rows
-    pub fn row_iter(&self) -> Iter<Row> {
+    pub fn row_iter(&self) -> Iter<'_, Row> {
         self.rows.iter()
     }
 
     /// Returns an iterator over mutable rows
-    pub fn row_iter_mut(&mut self) -> IterMut<Row> {
+    pub fn row_iter_mut(&mut self) -> IterMut<'_, Row> {
         self.rows.iter_mut()
     }
 
+    /// Returns an iterator over mutable rows. Same as `row_iter_mut()`
+    pub fn rows_mut(&mut self) -> IterMut<'_, Row> {
+        self.row_iter_mut()
+    }
+
+    /// Call `f` on every cell of the table, titles excluded, with the index of its row
+    /// and its index in the row.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut table = table![["Name", "City"], ["ada", "london"]];
+    /// table.for_each_cell_mut(|row, _, cell| {
+    ///     if row > 0 {
+    ///         *cell = prettytable::Cell::new(&cell.get_content().to_uppercase());
+    ///     }
+    /// });
+    /// assert_eq!(table[1][1].get_content(), "LONDON");
+    /// # }
+    /// ```
+    pub fn for_each_cell_mut<F: FnMut(usize, usize, &mut Cell)>(&mut self, mut f: F) {
+        for (i, row) in self.rows.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                f(i, j, cell);
+            }
+        }
+    }
+
+    /// Call `f` on every cell of the titles, if any, with its index in the titles row
+    pub fn for_each_title_cell_mut<F: FnMut(usize, &mut Cell)>(&mut self, mut f: F) {
+        if let Some(ref mut titles) = *self.titles {
+            for (j, cell) in titles.iter_mut().enumerate() {
+                f(j, cell);
+            }
+        }
+    }
+
     /// Print the table to `out` and returns the number
     /// of lines printed, or an error
     pub fn print<T: Write + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
@@ -396,6 +1297,30 @@ impl Table {
         self.as_slice().printstd()
     }
 
+    /// Return the lines printed by `print()`, without line endings, to embed the table
+    /// in a larger display
+    pub fn render_lines(&self) -> Vec<String> {
+        self.as_slice().render_lines()
+    }
+
+    /// Return, for each row, the range of the lines printed for it among the lines
+    /// returned by `render_lines()`, for instance to find the row under the mouse or
+    /// to scroll to a row. See `TableSlice::row_line_ranges()` for details.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let table = table![["a"], ["multi\nline"], ["b"]];
+    /// let lines = table.render_lines();
+    /// assert_eq!(lines[3], "| multi |");
+    /// assert_eq!(table.row_line_ranges(), [1..2, 3..5, 6..7]);
+    /// # }
+    /// ```
+    pub fn row_line_ranges(&self) -> Vec<Range<usize>> {
+        self.as_slice().row_line_ranges()
+    }
+
     /// Print table in HTML format to `out`.
     pub fn print_html<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
         self.as_slice().print_html(out)
@@ -413,7 +1338,13 @@ impl AsTableSlice for Table {
         TableSlice {
             format: &self.format,
             titles: &self.titles,
+            header_rows: &self.header_rows,
             rows: &self.rows,
+            footer: &self.footer,
+            caption: &self.caption,
+            render: &self.render,
+            sizing: None,
+            offset: 0,
         }
     }
 }
@@ -542,6 +1473,60 @@ impl<'a> Iterator for ColumnIterMut<'a> {
     }
 }
 
+/// Iterator over the immutable cells of a column, one per row, as returned by `Table::column()`
+#[derive(Clone, Debug)]
+pub struct ColumnCells<'a>(Iter<'a, Row>, usize);
+
+impl<'a> Iterator for ColumnCells<'a> {
+    type Item = Option<&'a Cell>;
+    fn next(&mut self) -> Option<Option<&'a Cell>> {
+        self.0.next().map(|row| row.get_cell(self.1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for ColumnCells<'_> {}
+
+/// Iterator over the mutable cells of a column, one per row, as returned by
+/// `Table::column_mut()`
+#[derive(Debug)]
+pub struct ColumnCellsMut<'a>(IterMut<'a, Row>, usize);
+
+impl<'a> Iterator for ColumnCellsMut<'a> {
+    type Item = Option<&'a mut Cell>;
+    fn next(&mut self) -> Option<Option<&'a mut Cell>> {
+        self.0.next().map(|row| row.get_mut_cell(self.1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for ColumnCellsMut<'_> {}
+
+/// Iterator over the columns of a table, as returned by `Table::columns()`
+#[derive(Clone, Debug)]
+pub struct Columns<'a>(&'a [Row], Range<usize>);
+
+impl<'a> Iterator for Columns<'a> {
+    type Item = ColumnCells<'a>;
+    fn next(&mut self) -> Option<ColumnCells<'a>> {
+        self.1
+            .next()
+            .map(|column| ColumnCells(self.0.iter(), column))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.1.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Columns<'_> {}
+
 impl<'a> AsTableSlice for TableSlice<'a> {
     fn as_slice(&self) -> TableSlice<'_> {
         *self
@@ -562,16 +1547,15 @@ pub trait Slice<'a, E> {
     fn slice(&'a self, arg: E) -> Self::Output;
 }
 
+/// Rows out of the table are silently skipped, so that slicing never panics
 impl<'a, T, E> Slice<'a, E> for T
 where
     T: AsTableSlice,
-    [Row]: Index<E, Output = [Row]>,
+    E: RangeBounds<usize>,
 {
     type Output = TableSlice<'a>;
     fn slice(&'a self, arg: E) -> Self::Output {
-        let mut sl = self.as_slice();
-        sl.rows = sl.rows.index(arg);
-        sl
+        self.as_slice().slice(arg)
     }
 }
 
@@ -634,7 +1618,7 @@ macro_rules! ptable {
 #[cfg(test)]
 mod tests {
     use crate::utils::StringWriter;
-    use crate::{format, AsTableSlice, Cell, Row, Slice, Table};
+    use crate::{format, AsTableSlice, Cell, Row, Slice, SortOrder, Table};
     use format::consts::{
         FORMAT_BOX_CHARS, FORMAT_CLEAN, FORMAT_DEFAULT, FORMAT_NO_COLSEP, FORMAT_NO_LINESEP,
     };
@@ -759,6 +1743,71 @@ mod tests {
         assert_eq!(table.get_row(1).unwrap()[3].get_content(), "z");
     }
 
+    #[test]
+    fn set_cell() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("b")]));
+        table.add_row(Row::new(vec![Cell::new("c")]));
+        assert_eq!(table.get_cell(0, 1).unwrap().get_content(), "b");
+        assert!(table.get_cell(1, 1).is_none());
+        assert!(table.get_cell(2, 0).is_none());
+        table.get_mut_cell(1, 0).unwrap().style(crate::Attr::Bold);
+        assert_eq!(table[1][0].get_style(), &[crate::Attr::Bold]);
+
+        assert!(table.set_cell(0, 0, Cell::new("x")).is_ok());
+        assert!(table.set_cell(1, 2, Cell::new("y")).is_ok());
+        assert!(table.set_cell(2, 0, Cell::new("z")).is_err());
+        assert_eq!(table[0], ["x", "b"]);
+        assert_eq!(table[1], ["c", "", "y"]);
+
+        let skipped = table.apply(vec![
+            ((3, 0), Cell::new("w")),
+            ((1, 1), Cell::new("v")),
+            ((0, 3), Cell::new("u")),
+            ((2, 5), Cell::new("t")),
+        ]);
+        assert_eq!(skipped, vec![(3, 0), (2, 5)]);
+        assert_eq!(table[0], ["x", "b", "", "u"]);
+        assert_eq!(table[1], ["c", "v", "y"]);
+        assert_eq!(table.len(), 2);
+    }
+
+    #[test]
+    fn columns() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("1")]));
+        table.add_row(Row::new(vec![Cell::new("b")]));
+        table.add_row(Row::new(vec![
+            Cell::new("c"),
+            Cell::new("\u{1b}[1m3\u{1b}[0m"),
+            Cell::new("x"),
+        ]));
+        assert_eq!(
+            table
+                .column_iter(1)
+                .map(Cell::get_content)
+                .collect::<Vec<_>>(),
+            ["1"]
+        );
+        let present = table.column(1).map(|c| c.is_some()).collect::<Vec<_>>();
+        assert_eq!(present, [true, false, true]);
+        assert_eq!(table.column(5).len(), 3);
+        assert_eq!(table.column_values(1), ["1", "", "3"]);
+        assert_eq!(table.column_values(2), ["", "", "x"]);
+
+        for cell in table.column_mut(1).flatten() {
+            *cell = Cell::new("n");
+        }
+        assert_eq!(table.column_values(1), ["n", "", "n"]);
+        let columns = table
+            .columns()
+            .map(|c| c.map(|c| c.map(Cell::get_content)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(columns.len(), 3);
+        assert_eq!(columns[2], [None, None, Some("x".to_string())]);
+        assert_eq!(Table::new().columns().count(), 0);
+    }
+
     #[test]
     fn add_empty_row() {
         let mut table = Table::new();
@@ -1077,6 +2126,29 @@ mod tests {
             9,
             table.slice(1..4).print(&mut StringWriter::new()).unwrap()
         );
+        assert_eq!(out, table.slice(1..=3).to_string().replace("\r\n", "\n"));
+        assert_eq!(
+            out,
+            table
+                .slice((std::ops::Bound::Excluded(0), std::ops::Bound::Included(3)))
+                .to_string()
+                .replace("\r\n", "\n")
+        );
+
+        // Out of bounds ranges are clamped
+        assert_eq!(table.slice(4..100).len(), 2);
+        assert!(table.slice(10..).is_empty());
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = table.slice(4..2);
+        assert!(reversed.is_empty());
+        assert_eq!(table.slice(2..).slice(..=10).len(), 4);
+
+        let mut owned = table.slice(3..).to_table();
+        owned.set_footer(Row::new(vec![Cell::new("f")]));
+        assert_eq!(owned.len(), 3);
+        assert_eq!(owned.titles, table.titles);
+        assert_eq!(owned[0][0].get_content(), "3");
+        assert!(table.get_footer().is_none());
     }
 
     #[test]
@@ -1179,6 +2251,54 @@ mod tests {
         assert_eq!(6, table.print(&mut StringWriter::new()).unwrap());
     }
 
+    #[test]
+    fn from_rows() {
+        let table = Table::from_rows(vec![vec![1, 2, 3], vec![4]]);
+        let out = "\
++---+---+---+
+| 1 | 2 | 3 |
++---+---+---+
+| 4 |   |   |
++---+---+---+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        assert_eq!(
+            table.to_string(),
+            Table::from(vec![vec![1, 2, 3], vec![4]]).to_string()
+        );
+        let collected: Table = vec![vec!["a"], vec!["b", "c"]]
+            .into_iter()
+            .map(|r| r.into_iter().collect::<Row>())
+            .collect();
+        assert_eq!(collected.get_row(1).unwrap().len(), 2);
+
+        let empty = Table::from_rows(Vec::<Vec<String>>::new());
+        assert!(empty.is_empty());
+        assert_eq!(empty.to_string(), "");
+        assert_eq!(0, empty.print(&mut StringWriter::new()).unwrap());
+    }
+
+    #[test]
+    fn from_map() {
+        let map: std::collections::BTreeMap<_, _> = vec![("b", 2), ("a", 10)].into_iter().collect();
+        let table = Table::from_map_with_titles(&map, "Key", "Value");
+        let out = "\
++-----+-------+
+| Key | Value |
++=====+=======+
+| a   | 10    |
++-----+-------+
+| b   | 2     |
++-----+-------+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+
+        let table = Table::from_map(vec![("z", "last"), ("y", "first")]);
+        assert_eq!(table[0][0].get_content(), "z");
+        assert_eq!(table[1][1].get_content(), "first");
+        assert!(Table::from_map(Vec::<(String, String)>::new()).is_empty());
+    }
+
     #[test]
     fn test_empty_table_with_title() {
         let mut table = Table::new();
@@ -1322,4 +2442,478 @@ mod tests {
         assert_eq!(table.to_string().replace("\r\n", "\n"), out);
         assert_eq!(3, table.print(&mut StringWriter::new()).unwrap());
     }
+
+    #[test]
+    fn max_column_width() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("Key"), Cell::new("Description")]));
+        table.add_row(Row::new(vec![
+            Cell::new("a"),
+            Cell::new("The quick brown fox jumps"),
+        ]));
+        table.set_max_column_width(1, 11);
+        let out = "\
++-----+-------------+
+| Key | Description |
++=====+=============+
+| a   | The quick   |
+|     | brown fox   |
+|     | jumps       |
++-----+-------------+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(
+            table.get_row(0).unwrap().get_cell(1).unwrap().get_content(),
+            "The quick brown fox jumps"
+        );
+    }
+
+    #[test]
+    fn column_padding() {
+        let mut table = table!(["Flags", "Name"], ["rw", "x"], ["r", "longer"]);
+        table.set_column_padding(0, 0, 0);
+        table.get_mut_row(2).unwrap()[1].set_padding(3, 2);
+        let out = "\
++-----+-----------+
+|Flags| Name      |
++-----+-----------+
+|rw   | x         |
++-----+-----------+
+|r    |   longer  |
++-----+-----------+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+    }
+
+    #[test]
+    fn vertical_align() {
+        use crate::format::VerticalAlignment;
+        let mut table = table!(["1\n2\n3", "top", "middle", "bottom"]);
+        table[0][2].set_vertical_align(VerticalAlignment::MIDDLE);
+        table[0][3].set_vertical_align(VerticalAlignment::BOTTOM);
+        let out = "\
++---+-----+--------+--------+
+| 1 | top |        |        |
+| 2 |     | middle |        |
+| 3 |     |        | bottom |
++---+-----+--------+--------+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+    }
+
+    #[test]
+    fn spanning_width_distribution() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("Q1 results").with_hspan(3)]));
+        table.add_row(Row::new(vec![
+            Cell::new("a"),
+            Cell::new("b"),
+            Cell::new("c"),
+        ]));
+        table.add_row(Row::new(vec![
+            Cell::new("x"),
+            Cell::new("wide span").with_hspan(2),
+        ]));
+        let out = "\
++---+-----+-----+
+| Q1 results    |
++===+=====+=====+
+| a | b   | c   |
++---+-----+-----+
+| x | wide span |
++---+-----+-----+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+
+        let slice = table.slice(..1);
+        let out = "\
++----+---+---+
+| Q1 results |
++====+===+===+
+| a  | b | c |
++----+---+---+
+";
+        assert_eq!(slice.to_string().replace("\r\n", "\n"), out);
+    }
+
+    #[test]
+    fn cell_fill() {
+        let mut table = table!(["Name", "Value"], ["first", "1"], ["second", "22"]);
+        table.set_format(*FORMAT_CLEAN);
+        for row in table.row_iter_mut() {
+            row[0].set_fill('.');
+            row[1].set_fill('·');
+            row[1].align(format::Alignment::RIGHT);
+        }
+        let out = " Name..  Value \n first.  ····1 \n second  ···22 \n";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+    }
+
+    #[test]
+    fn width_mode() {
+        let family = "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}";
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new(family), Cell::new("x")]));
+        table.add_row(Row::new(vec![
+            Cell::new("ab"),
+            Cell::new("\x1b[1my\x1b[0m"),
+        ]));
+        let mut format = *FORMAT_NO_LINESEP;
+        format.width_mode(format::WidthMode::Graphemes);
+        table.set_format(format);
+        let out = format!(
+            "+----+---+\n| {} | x |\n| ab | \x1b[1my\x1b[0m |\n+----+---+\n",
+            family
+        );
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+
+        fn chars(text: &str) -> usize {
+            text.chars().count()
+        }
+        format.width_mode(format::WidthMode::Custom(chars));
+        table.set_format(format);
+        let out = format!(
+            "+-------+---+\n| {} | x |\n| ab    | \x1b[1my\x1b[0m |\n+-------+---+\n",
+            family
+        );
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+    }
+
+    #[test]
+    fn width_mode_wrapping() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("ab cd").with_max_width(3)]));
+        table.set_caption("x y z", format::Alignment::CENTER);
+        let mut format = *FORMAT_CLEAN;
+        format.width_mode(format::WidthMode::Custom(|s| 2 * s.chars().count()));
+        table.set_format(format);
+        // Each char is two columns wide, so the cell and the caption wrap at every char
+        let out = " x\n y\n z\n a \n b \n c \n d \n";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(table.compute_layout().widths(), [2]);
+        assert_eq!(table[0][0].get_content(), "ab cd");
+    }
+
+    #[test]
+    fn tab_expansion() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("k\tv"), Cell::new("n")]));
+        table.add_row(Row::new(vec![
+            Cell::new("\ta"),
+            Cell::new_align("1\t2", format::Alignment::RIGHT),
+        ]));
+        table.add_row(Row::new(vec![
+            Cell::new("abc\td\nx\ty"),
+            Cell::new_align("\t", format::Alignment::CENTER),
+        ]));
+        let out = "\
++-------+-------+
+| k   v | n     |
++=======+=======+
+|     a | 1   2 |
++-------+-------+
+| abc d |       |
+| x   y |       |
++-------+-------+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(table[1][0].get_content(), "abc\td\nx\ty");
+
+        let mut format = *FORMAT_DEFAULT;
+        format.set_tab_width(2);
+        format.padding(2, 0);
+        table.set_format(format);
+        let out = "\
++-------+-----+
+|  k v  |  n  |
++=======+=====+
+|    a  |  1 2|
++-------+-----+
+|  abc d|     |
+|  x y  |     |
++-------+-----+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        table.get_format().set_tab_width(0);
+        assert!(table.to_string().contains("|  \\ta "));
+    }
+
+    #[test]
+    fn remove_and_reorder_rows() {
+        let mut table = table!(["a"], ["b"], ["c"], ["d"], ["e"]);
+        let contents = |table: &Table| {
+            table
+                .row_iter()
+                .map(|r| r[0].get_content())
+                .collect::<Vec<_>>()
+                .join("")
+        };
+        let before = table.slice(1..3).to_string();
+        table.remove_rows(1..3);
+        assert_eq!(contents(&table), "ade");
+        assert_ne!(table.slice(1..3).to_string(), before);
+        table.remove_rows(2..10);
+        table.remove_rows(5..8);
+        assert_eq!(contents(&table), "ad");
+
+        let mut table = table!(["a"], ["b"], ["c"], ["d"], ["e"]);
+        let drained = table
+            .drain(3..)
+            .map(|r| r[0].get_content())
+            .collect::<Vec<_>>();
+        assert_eq!(drained, vec!["d", "e"]);
+        assert_eq!(table.drain(..=0).count(), 1);
+        assert_eq!(table.drain(7..9).count(), 0);
+        assert_eq!(contents(&table), "bc");
+
+        assert!(table.swap_rows(0, 1).is_ok());
+        assert_eq!(contents(&table), "cb");
+        assert!(table.swap_rows(0, 2).is_err());
+        table.insert_row(10, Row::new(vec![Cell::new("z")]));
+        assert_eq!(contents(&table), "cbz");
+        table.truncate(5);
+        assert_eq!(table.len(), 3);
+        table.truncate(1);
+        assert_eq!(contents(&table), "c");
+    }
+
+    #[test]
+    fn mutable_iteration() {
+        let mut table = table!(["a", "b"], ["c"]);
+        table.set_titles(Row::new(vec![Cell::new("t")]));
+        let mut seen = Vec::new();
+        table.for_each_cell_mut(|i, j, cell| {
+            seen.push(format!("{}{}{}", i, j, cell.get_content()));
+            cell.style(crate::Attr::Bold);
+        });
+        assert_eq!(seen, vec!["00a", "01b", "10c"]);
+        assert!(table[1][0].get_style().contains(&crate::Attr::Bold));
+        table.for_each_title_cell_mut(|_, cell| *cell = Cell::new("T"));
+        assert_eq!(
+            table.titles.as_ref().as_ref().unwrap()[0].get_content(),
+            "T"
+        );
+
+        for row in &mut table {
+            row.add_cell(Cell::new("x"));
+        }
+        for row in table.rows_mut() {
+            for cell in row.cells_mut() {
+                cell.align(format::Alignment::RIGHT);
+            }
+        }
+        assert_eq!(table[1].len(), 2);
+        assert_eq!(table[1][1].get_align(), format::Alignment::RIGHT);
+    }
+
+    #[test]
+    fn add_row_split() {
+        let mut table = Table::new();
+        table.add_row_split("name | age", '|');
+        table
+            .add_row_split("Alice | 34 |", '|')
+            .get_mut_cell(0)
+            .unwrap()
+            .style(crate::Attr::Bold);
+        assert_eq!(table.get_column_num(), 3);
+        assert_eq!(table[1][1].get_content(), "34");
+        assert_eq!(table[1][2].get_content(), "");
+    }
+
+    #[test]
+    fn title_rows() {
+        let mut table = table!(["b", "2", "x"], ["a", "10", "y"]);
+        table.add_title_row(Row::new(vec![Cell::new("Group").with_hspan(2)]));
+        table.add_title_row(Row::new(vec![
+            Cell::new("name"),
+            Cell::new("n"),
+            Cell::new("code\t!"),
+        ]));
+        table.sort_by_column(0, SortOrder::Ascending);
+        let out = "\
++------+----+-----------+
+| Group     |           |
+| name | n  | code    ! |
++======+====+===========+
+| a    | 10 | y         |
++------+----+-----------+
+| b    | 2  | x         |
++------+----+-----------+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(table.len(), 2);
+        let titles = table.title_rows().map(|r| r.len()).collect::<Vec<_>>();
+        assert_eq!(titles, vec![1, 3]);
+
+        #[cfg(feature = "csv")]
+        {
+            let csv = table.to_csv(Vec::new()).unwrap().into_inner().unwrap();
+            assert!(String::from_utf8(csv)
+                .unwrap()
+                .starts_with("name,n,code\t!\n"));
+        }
+        table.remove_column(2);
+        assert_eq!(table.title_rows().count(), 2);
+        table.set_titles(Row::new(vec![Cell::new("t")]));
+        assert_eq!(table.title_rows().count(), 1);
+        table.unset_titles();
+        assert_eq!(table.title_rows().count(), 0);
+    }
+
+    #[test]
+    fn footer() {
+        let mut table = table!(["b", "2"], ["a", "10"]);
+        table.set_titles(Row::new(vec![Cell::new("t"), Cell::new("n")]));
+        table.set_footer(Row::new(vec![Cell::new("Total"), Cell::new("12")]));
+        table.sort_by_column(0, SortOrder::Ascending);
+        let out = "\
++-------+----+
+| t     | n  |
++=======+====+
+| a     | 10 |
++-------+----+
+| b     | 2  |
++=======+====+
+| Total | 12 |
++-------+----+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.get_footer().unwrap(), &["Total", "12"]);
+
+        table.set_format(*FORMAT_CLEAN);
+        table.set_column_alignment(1, format::Alignment::RIGHT);
+        let out = " t       n \n a      10 \n b       2 \n Total  12 \n";
+        assert_eq!(table.slice(..).to_string().replace("\r\n", "\n"), out);
+        let out = " t       n \n b       2 \n Total  12 \n";
+        let filtered = table.filtered(|r| r[0].get_content() == "b");
+        assert_eq!(filtered.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(
+            table.rendered().get_footer().unwrap()[1].get_align(),
+            format::Alignment::RIGHT
+        );
+
+        table.get_mut_footer().unwrap()[0] = Cell::new("Sum");
+        assert!(table.to_string().contains("Sum"));
+        table.unset_footer();
+        assert!(table.get_footer().is_none());
+        assert!(!table.to_string().contains("Sum"));
+    }
+
+    #[test]
+    fn column_statistics() {
+        let mut table = Table::new();
+        assert!(table.is_empty());
+        assert_eq!(table.column_count(), 0);
+        assert_eq!(table.column_widths(), Vec::<usize>::new());
+        table.set_titles(Row::new(vec![Cell::new("a"), Cell::new("b")]));
+        table.add_row(Row::new(vec![Cell::new("wide cell").with_hspan(2)]));
+        table.add_row(Row::new(vec![Cell::new("x").with_padding(3, 3)]));
+        table.set_footer(Row::new(vec![
+            Cell::new("1"),
+            Cell::new("2"),
+            Cell::new("3"),
+        ]));
+        assert!(!table.is_empty());
+        assert_eq!(table.column_count(), 3);
+        assert_eq!(table.column_widths(), vec![5, 1, 1]);
+        table.get_format().indent(2);
+        let width = table.total_width();
+        assert_eq!(width, 19);
+        let out = table.to_string().replace("\r\n", "\n");
+        assert!(out.lines().all(|l| l.chars().count() == width));
+    }
+
+    #[test]
+    fn separator_below() {
+        let mut table = table!(["a"], ["b"], ["c"], ["d"]);
+        table.set_titles(Row::new(vec![Cell::new("t")]));
+        table.set_format(*format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+        table.add_separator_after(0).unwrap();
+        table.add_separator_after(1).unwrap();
+        table.add_separator_after(3).unwrap();
+        assert!(table.add_separator_after(4).is_err());
+        let out = "\
++---+
+| t |
++---+
+| a |
++---+
+| b |
++---+
+| c |
+| d |
++---+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+
+        // The flag follows its row
+        table.remove_row(0);
+        table.insert_row(0, Row::new(vec![Cell::new("z")]));
+        assert!(!table[0].has_separator_below());
+        assert!(table[1].has_separator_below());
+        assert!(!table[2].has_separator_below());
+
+        // No double lines when the format already separates every row
+        table.set_format(*FORMAT_DEFAULT);
+        let out = "\
++---+
+| t |
++===+
+| z |
++---+
+| b |
++---+
+| c |
++---+
+| d |
++---+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        assert!(table.get_row(1).unwrap().has_separator_below());
+
+        // Formats without title separator use the bottom one
+        table.set_format(*FORMAT_NO_LINESEP);
+        let out = "\
++---+
+| t |
+| z |
+| b |
++---+
+| c |
+| d |
++---+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+
+        // Formats without any separator have nothing to print
+        table.set_format(*FORMAT_CLEAN);
+        assert_eq!(table.to_string().lines().count(), 5);
+    }
+
+    #[test]
+    fn row_line_ranges() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("name")]));
+        table.add_row(Row::new(vec![Cell::new("a\tb")]));
+        table.add_row(Row::new(vec![Cell::new("c\nd")]));
+        table.set_footer(Row::new(vec![Cell::new("total")]));
+        table.set_caption("Caption", format::Alignment::LEFT);
+        let lines = table.render_lines();
+        assert_eq!(lines.len(), 11);
+        assert_eq!(
+            lines.join("\n") + "\n",
+            table.to_string().replace("\r\n", "\n")
+        );
+        let ranges = table.row_line_ranges();
+        assert_eq!(ranges, [4..5, 6..8]);
+        assert_eq!(lines[ranges[1].start], "| c     |");
+
+        table.set_format(*FORMAT_CLEAN);
+        table.set_show_row_numbers(true);
+        assert_eq!(table.row_line_ranges(), [2..3, 3..5]);
+        assert_eq!(table.slice(1..).row_line_ranges()[0], 2..4);
+        assert!(Table::new().row_line_ranges().is_empty());
+        assert!(Table::new().render_lines().is_empty());
+    }
 }
@@ -0,0 +1,182 @@
+//! Printing of the first and last rows of long tables
+
+use std::io::{Error, Write};
+use std::ops::Range;
+
+use super::format::Alignment;
+use super::number::group_thousands;
+use super::{print_rows, AsTableSlice, Cell, Row, Table, TableSlice};
+
+/// Limit on the rows printed: only the first `head` and last `tail` rows are printed,
+/// with a line telling how many rows were omitted in between.
+///
+/// By default, columns are as wide as needed by the printed rows only.
+///
+/// # Examples
+/// ```rust
+/// # #[macro_use] extern crate prettytable;
+/// use prettytable::RowLimit;
+/// # fn main() {
+/// let table = table![[1], [2], [3], [4], [5]];
+/// let mut out = Vec::new();
+/// table.print_limited(&mut out, &RowLimit::new(1, 1)).unwrap();
+/// assert!(String::from_utf8(out).unwrap().contains("… 3 rows omitted …"));
+/// # }
+/// ```
+#[derive(Clone, Debug, Copy, Hash, PartialEq, Eq)]
+pub struct RowLimit {
+    head: usize,
+    tail: usize,
+    all_rows_width: bool,
+}
+
+impl RowLimit {
+    /// Create a limit printing the first `head` and the last `tail` rows
+    pub fn new(head: usize, tail: usize) -> RowLimit {
+        RowLimit {
+            head,
+            tail,
+            all_rows_width: false,
+        }
+    }
+
+    /// Compute the width of the columns from all the rows, including omitted ones,
+    /// rather than only from the printed rows. This keeps the columns as wide as
+    /// when printing the whole table.
+    pub fn widths_from_all_rows(mut self, yes: bool) -> RowLimit {
+        self.all_rows_width = yes;
+        self
+    }
+
+    pub(crate) fn sizes_on_all_rows(&self) -> bool {
+        self.all_rows_width
+    }
+
+    /// Return the range of the rows omitted among `len` rows, if any
+    pub(crate) fn omitted(&self, len: usize) -> Option<Range<usize>> {
+        match self.head.checked_add(self.tail) {
+            Some(printed) if printed < len => Some(self.head..len - self.tail),
+            _ => None,
+        }
+    }
+
+    /// Create the row printed in place of `omitted` rows, spanning `columns` columns
+    pub(crate) fn marker(&self, omitted: usize, columns: usize) -> Row {
+        let rows = if omitted == 1 { "row" } else { "rows" };
+        let count = group_thousands(&omitted.to_string(), ',');
+        let text = format!("… {} {} omitted …", count, rows);
+        Row::new(vec![
+            Cell::new_align(&text, Alignment::CENTER).with_hspan(columns)
+        ])
+    }
+}
+
+impl TableSlice<'_> {
+    /// Print the rows kept by `limit` to `out` and returns the number of
+    /// line printed, or an error. See `RowLimit` for details.
+    pub fn print_limited<T: Write + ?Sized>(
+        &self,
+        out: &mut T,
+        limit: &RowLimit,
+    ) -> Result<usize, Error> {
+        print_rows(
+            out,
+            self,
+            self.rows,
+            &self.row_positions(),
+            Some(limit),
+            Row::print,
+        )
+    }
+}
+
+impl Table {
+    /// Print the rows kept by `limit` to `out` and returns the number of
+    /// line printed, or an error. See `RowLimit` for details.
+    pub fn print_limited<T: Write + ?Sized>(
+        &self,
+        out: &mut T,
+        limit: &RowLimit,
+    ) -> Result<usize, Error> {
+        self.as_slice().print_limited(out, limit)
+    }
+
+    /// Print the first `head` and last `tail` rows to `out`, with a line telling how many
+    /// rows were omitted in between. Returns the number of line printed, or an error.
+    pub fn print_head_tail<T: Write + ?Sized>(
+        &self,
+        out: &mut T,
+        head: usize,
+        tail: usize,
+    ) -> Result<usize, Error> {
+        self.print_limited(out, &RowLimit::new(head, tail))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RowLimit;
+    use crate::utils::StringWriter;
+    use crate::{Cell, Row, Table};
+
+    fn numbers(count: usize) -> Table {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("n"), Cell::new("square")]));
+        for n in 1..=count {
+            table.add_row(Row::new(vec![Cell::from(n), Cell::from(n * n)]));
+        }
+        table
+    }
+
+    fn print(table: &Table, limit: &RowLimit) -> String {
+        let mut out = StringWriter::new();
+        table.print_limited(&mut out, limit).unwrap();
+        out.as_string().replace("\r\n", "\n")
+    }
+
+    #[test]
+    fn head_tail() {
+        let table = numbers(1003);
+        let out = "\
++----------+-------------+
+| n        | square      |
++==========+=============+
+| 1        | 1           |
++----------+-------------+
+| … 1,000 rows omitted … |
++----------+-------------+
+| 1002     | 1004004     |
++----------+-------------+
+| 1003     | 1006009     |
++----------+-------------+
+";
+        assert_eq!(print(&table, &RowLimit::new(1, 2)), out);
+        let mut head = StringWriter::new();
+        assert_eq!(table.print_head_tail(&mut head, 1, 2).unwrap(), 11);
+        assert_eq!(head.as_string().replace("\r\n", "\n"), out);
+
+        // Nothing to omit
+        let table = numbers(3);
+        assert_eq!(print(&table, &RowLimit::new(2, 1)), table.to_string());
+        assert_eq!(
+            print(&table, &RowLimit::new(usize::MAX, 1)),
+            table.to_string()
+        );
+    }
+
+    #[test]
+    fn widths_from_all_rows() {
+        let mut table = numbers(3);
+        table.set_format(*crate::format::consts::FORMAT_CLEAN);
+        table[1][1] = Cell::new("a wide omitted cell");
+        let limit = RowLimit::new(1, 1);
+        assert_eq!(
+            print(&table, &limit),
+            " n      square \n 1      1 \n … 1 row omitted … \n 3      9 \n"
+        );
+        assert_eq!(
+            print(&table, &limit.widths_from_all_rows(true)),
+            " n  square \n 1  1 \n   … 1 row omitted …    \n 3  9 \n"
+        );
+    }
+}
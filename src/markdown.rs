@@ -0,0 +1,363 @@
+//! Markdown import and export of tables
+
+use std::io::{Error, Write};
+
+use super::format::Alignment;
+use super::utils::{StringWriter, NEWLINE};
+use super::{AsTableSlice, CaptionPosition, Cell, ParseError, Row, Table, TableSlice};
+
+/// Escape characters having a special meaning in a Markdown table cell,
+/// and replace newlines with `<br>` so a cell stays on a single line
+fn escape(content: &str) -> String {
+    let mut escaped = String::with_capacity(content.len());
+    for c in content.chars() {
+        match c {
+            '\\' | '|' | '`' | '*' | '_' | '[' | ']' | '<' | '>' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("<br>"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Print a single row as a Markdown table line. `hspan` cells are followed by
+/// empty cells, and the row is padded with empty cells up to `col_num` columns
+fn print_row<T: Write + ?Sized>(out: &mut T, row: &Row, col_num: usize) -> Result<(), Error> {
+    let mut printed_columns = 0;
+    out.write_all(b"|")?;
+    for cell in row {
+        out.write_all(format!(" {} |", escape(&cell.get_content())).as_bytes())?;
+        for _ in 1..cell.get_hspan() {
+            out.write_all(b"  |")?;
+        }
+        printed_columns += cell.get_hspan();
+    }
+    for _ in printed_columns..col_num {
+        out.write_all(b"  |")?;
+    }
+    out.write_all(NEWLINE)
+}
+
+/// Split a Markdown table line into its unescaped cells, turning `<br>` tags into newlines
+fn split_row(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut closed = false;
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        closed = false;
+        match c {
+            '\\' => match chars.peek() {
+                Some(&(_, next)) if next.is_ascii_punctuation() => {
+                    cell.push(next);
+                    chars.next();
+                }
+                _ => cell.push(c),
+            },
+            '|' => {
+                cells.push(cell.trim().to_string());
+                cell.clear();
+                closed = true;
+            }
+            '<' => match ["<br>", "<br/>", "<br />"]
+                .iter()
+                .find(|tag| line[i..].starts_with(*tag))
+            {
+                Some(tag) => {
+                    cell.push('\n');
+                    for _ in 1..tag.len() {
+                        chars.next();
+                    }
+                }
+                None => cell.push(c),
+            },
+            _ => cell.push(c),
+        }
+    }
+    if !closed {
+        cells.push(cell.trim().to_string());
+    }
+    cells
+}
+
+/// Parse the alignments of a delimiter row, or return `None` if `cells` is not one
+fn parse_alignments(cells: &[String]) -> Option<Vec<Alignment>> {
+    cells
+        .iter()
+        .map(|c| {
+            let left = c.starts_with(':');
+            let right = c.len() > 1 && c.ends_with(':');
+            let dashes = c.trim_start_matches(':').trim_end_matches(':');
+            if dashes.is_empty() || !dashes.chars().all(|d| d == '-') {
+                return None;
+            }
+            Some(match (left, right) {
+                (true, true) => Alignment::CENTER,
+                (false, true) => Alignment::RIGHT,
+                _ => Alignment::LEFT,
+            })
+        })
+        .collect()
+}
+
+/// Build a row from `cells`, truncated or padded to the number of `aligns`
+fn make_row(cells: Vec<String>, aligns: &[Alignment]) -> Row {
+    Row::new(
+        aligns
+            .iter()
+            .enumerate()
+            .map(|(i, align)| Cell::new_align(cells.get(i).map_or("", |c| c), *align))
+            .collect(),
+    )
+}
+
+impl<'a> TableSlice<'a> {
+    /// Print table in GitHub-flavored Markdown format to `out`.
+    ///
+    /// The header line is made of the titles row, or of empty cells if the table has no titles.
+    /// Column alignment is taken from the title cells, or from the cells of the first row
+    /// when there are no titles. The footer is printed as the last row, and the caption
+    /// in a pandoc `Table:` paragraph above or below the table.
+    pub fn print_markdown<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        let table = self.exported();
+        table.caption.print_pandoc(out, CaptionPosition::Above)?;
+        table.as_slice().print_markdown_rows(out)?;
+        table.caption.print_pandoc(out, CaptionPosition::Below)?;
+        out.flush()
+    }
+
+    /// Print the titles and rows of the table as a Markdown table to `out`
+    fn print_markdown_rows<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        let column_num = self.get_column_num();
+        let header = match *self.titles {
+            Some(ref t) => t.clone(),
+            None => Row::new(vec![Cell::default(); column_num]),
+        };
+        print_row(out, &header, column_num)?;
+        out.write_all(b"|")?;
+        for align in self.get_column_alignments() {
+            let sep: &[u8] = match align {
+                Alignment::LEFT => b" :--- |",
+                Alignment::CENTER => b" :---: |",
+                Alignment::RIGHT => b" ---: |",
+            };
+            out.write_all(sep)?;
+        }
+        out.write_all(NEWLINE)?;
+        for r in self.rows {
+            print_row(out, r, column_num)?;
+        }
+        Ok(())
+    }
+
+    /// Return the table formatted as a GitHub-flavored Markdown table.
+    ///
+    /// See `print_markdown()` for details.
+    pub fn to_markdown(&self) -> String {
+        let mut writer = StringWriter::new();
+        // Writing into a `StringWriter` cannot fail
+        let _ = self.print_markdown(&mut writer);
+        writer.as_string().to_string()
+    }
+}
+
+impl Table {
+    /// Print table in GitHub-flavored Markdown format to `out`.
+    ///
+    /// See `TableSlice::print_markdown()` for details.
+    pub fn print_markdown<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        self.as_slice().print_markdown(out)
+    }
+
+    /// Return the table formatted as a GitHub-flavored Markdown table.
+    pub fn to_markdown(&self) -> String {
+        self.as_slice().to_markdown()
+    }
+
+    /// Parse the first GitHub-flavored Markdown pipe table found in `text`,
+    /// ignoring any line before or after it.
+    ///
+    /// The header line becomes the titles, unless all of its cells are empty, and the
+    /// delimiter row sets the alignment of every cell in its column. Rows are truncated or
+    /// padded with empty cells to the number of columns of the header. Escaped characters
+    /// such as `\|` are unescaped, `<br>` tags become newlines and cells are trimmed.
+    pub fn from_markdown(text: &str) -> Result<Table, ParseError> {
+        Self::parse_markdown(text, false)
+    }
+
+    /// Parse a GitHub-flavored Markdown pipe table from `text`, which must not contain
+    /// anything but the table and blank lines.
+    ///
+    /// See `from_markdown()` for details.
+    pub fn from_markdown_strict(text: &str) -> Result<Table, ParseError> {
+        Self::parse_markdown(text, true)
+    }
+
+    fn parse_markdown(text: &str, strict: bool) -> Result<Table, ParseError> {
+        let lines: Vec<&str> = text.lines().collect();
+        let mut header = None;
+        for (i, line) in lines.iter().enumerate() {
+            if strict && line.trim().is_empty() {
+                continue;
+            }
+            let cells = split_row(line);
+            let aligns = lines
+                .get(i + 1)
+                .filter(|_| line.contains('|'))
+                .and_then(|l| parse_alignments(&split_row(l)))
+                .filter(|a| a.len() == cells.len());
+            match aligns {
+                Some(aligns) => {
+                    header = Some((i, cells, aligns));
+                    break;
+                }
+                None if strict => {
+                    return Err(ParseError::new(i + 1, "Expected a table header"));
+                }
+                None => {}
+            }
+        }
+        let (start, titles, aligns) =
+            header.ok_or_else(|| ParseError::new(lines.len().max(1), "No Markdown table found"))?;
+        let mut table = Table::new();
+        if titles.iter().any(|t| !t.is_empty()) {
+            table.set_titles(make_row(titles, &aligns));
+        }
+        let mut end = start + 2;
+        while end < lines.len() && !lines[end].trim().is_empty() && lines[end].contains('|') {
+            table.add_row(make_row(split_row(lines[end]), &aligns));
+            end += 1;
+        }
+        if strict {
+            if let Some(i) = (end..lines.len()).find(|&i| !lines[i].trim().is_empty()) {
+                return Err(ParseError::new(i + 1, "Unexpected content after the table"));
+            }
+        }
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::Alignment;
+    use crate::{CaptionPosition, Cell, Row, Slice, Table};
+
+    #[test]
+    fn markdown() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![
+            Cell::new("t1"),
+            Cell::new_align("t2", Alignment::CENTER),
+            Cell::new_align("t3", Alignment::RIGHT),
+        ]));
+        table.add_row(Row::new(vec![
+            Cell::new("a"),
+            Cell::new("b|c"),
+            Cell::new("d\ne"),
+        ]));
+        table.add_row(Row::new(vec![Cell::new("f")]));
+        let out = "\
+| t1 | t2 | t3 |
+| :--- | :---: | ---: |
+| a | b\\|c | d<br>e |
+| f |  |  |
+";
+        assert_eq!(table.to_markdown().replace("\r\n", "\n"), out);
+        let out = "\
+| t1 | t2 | t3 |
+| :--- | :---: | ---: |
+| f |  |  |
+";
+        assert_eq!(table.slice(1..).to_markdown().replace("\r\n", "\n"), out);
+
+        table.set_footer(Row::new(vec![Cell::new("g")]));
+        table.set_caption("Letters", Alignment::CENTER);
+        let out = "\
+Table: Letters
+
+| t1 | t2 | t3 |
+| :--- | :---: | ---: |
+| f |  |  |
+| g |  |  |
+";
+        assert_eq!(table.slice(1..).to_markdown().replace("\r\n", "\n"), out);
+        table.set_caption_position(CaptionPosition::Below);
+        assert!(table
+            .to_markdown()
+            .replace("\r\n", "\n")
+            .ends_with("| g |  |  |\n\nTable: Letters\n"));
+    }
+
+    #[test]
+    fn markdown_no_titles() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![
+            Cell::new_align("1", Alignment::RIGHT),
+            Cell::new("*2*").with_hspan(2),
+        ]));
+        let out = "\
+|  |  |  |
+| ---: | :--- | :--- |
+| 1 | \\*2\\* |  |
+";
+        assert_eq!(table.to_markdown().replace("\r\n", "\n"), out);
+    }
+
+    #[test]
+    fn from_markdown() {
+        let text = "\
+Some text
+
+| t1 | t2 | t3 |
+|----|:--:|---:|
+| a \\| b | `c` | d<br>e |
+| f |
+more text
+";
+        let table = Table::from_markdown(text).unwrap();
+        assert_eq!(table.len(), 2);
+        let titles = table.titles.as_ref().as_ref().unwrap();
+        assert_eq!(titles.get_cell(1).unwrap().get_content(), "t2");
+        assert_eq!(titles.get_cell(1).unwrap().get_align(), Alignment::CENTER);
+        let row = table.get_row(0).unwrap();
+        assert_eq!(row.get_cell(0).unwrap().get_content(), "a | b");
+        assert_eq!(row.get_cell(0).unwrap().get_align(), Alignment::LEFT);
+        assert_eq!(row.get_cell(2).unwrap().get_content(), "d\ne");
+        assert_eq!(row.get_cell(2).unwrap().get_align(), Alignment::RIGHT);
+        assert_eq!(table.get_row(1).unwrap().len(), 3);
+
+        let err = Table::from_markdown_strict(text).unwrap_err();
+        assert_eq!(err.line(), 1);
+        let err = Table::from_markdown_strict(&text[11..]).unwrap_err();
+        assert_eq!(err.line(), 5);
+        assert!(Table::from_markdown("no table").is_err());
+    }
+
+    #[test]
+    fn markdown_round_trip() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![
+            Cell::new("t1"),
+            Cell::new_align("t2", Alignment::RIGHT),
+        ]));
+        table.add_row(Row::new(vec![
+            Cell::new("*a* | <br>"),
+            Cell::new_align("b\nc", Alignment::RIGHT),
+        ]));
+        let md = table.to_markdown();
+        let parsed = Table::from_markdown_strict(&md).unwrap();
+        assert_eq!(parsed.to_markdown(), md);
+        assert_eq!(parsed.to_string(), table.to_string());
+
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("b")]));
+        let parsed = Table::from_markdown_strict(&table.to_markdown()).unwrap();
+        assert!(parsed.titles.is_none());
+        assert_eq!(parsed.to_string(), table.to_string());
+    }
+}
@@ -0,0 +1,266 @@
+//! Maximum width of printed tables, reached by dropping columns
+
+use std::cmp::Reverse;
+
+use super::fit::table_width;
+use super::format::TableFormat;
+use super::render::RenderOptions;
+use super::{column_widths, Cell, Row, Table};
+
+/// Priority of the columns without one set with `Table::set_column_priority()`
+pub(crate) const DEFAULT_COLUMN_PRIORITY: u8 = 128;
+
+/// Content of the column printed in place of the dropped ones
+const DROPPED_MARKER: &str = "…";
+
+/// Columns kept when printing rows wider than the maximum width of their table
+pub(crate) struct KeptColumns {
+    kept: Vec<bool>,
+}
+
+impl KeptColumns {
+    /// Return the columns of `rows` to print with `format` to keep the table within the
+    /// maximum width set in `render`, given the minimum width of each column, or `None`
+    /// if the table fits or has no maximum width
+    pub(crate) fn new<'a, I>(
+        format: &TableFormat,
+        render: &RenderOptions,
+        rows: I,
+        min_width: &[usize],
+    ) -> Option<KeptColumns>
+    where
+        I: IntoIterator<Item = &'a Row>,
+    {
+        let max_width = render.max_width()?;
+        let rows = rows.into_iter().collect::<Vec<_>>();
+        let mut widths = column_widths(format, &None, &rows);
+        for (width, &min) in widths.iter_mut().zip(min_width) {
+            *width = (*width).max(min);
+        }
+        if table_width(format, &widths) <= max_width {
+            return None;
+        }
+        // Columns with the lowest priority are dropped first, from the right for equal ones
+        let mut droppable =
+            (render.printed_frozen_columns().min(widths.len())..widths.len()).collect::<Vec<_>>();
+        droppable.sort_by_key(|&c| (render.column_priority(c), Reverse(c)));
+        let mut columns = KeptColumns {
+            kept: vec![true; widths.len()],
+        };
+        for column in droppable {
+            columns.kept[column] = false;
+            let mut kept_widths = columns.widths(&widths);
+            if let Some(marker) = kept_widths.last_mut() {
+                *marker = DROPPED_MARKER.chars().count();
+            }
+            if table_width(format, &kept_widths) <= max_width {
+                break;
+            }
+        }
+        Some(columns)
+    }
+
+    /// Return a copy of `row` holding the cells of the kept columns, and the marker
+    /// of the dropped columns. Cells spanning dropped columns only span the kept ones.
+    pub(crate) fn apply(&self, row: &Row) -> Row {
+        let mut cells = Vec::with_capacity(self.kept.len() + 1);
+        let mut column = 0;
+        let mut printed = 0;
+        for cell in row.iter() {
+            let end = (column + cell.get_hspan()).min(self.kept.len());
+            let kept = self.kept[column.min(end)..end]
+                .iter()
+                .filter(|&&k| k)
+                .count();
+            if kept > 0 {
+                let mut cell = cell.clone();
+                cell.set_hspan(kept);
+                cells.push(cell);
+                printed += kept;
+            }
+            column += cell.get_hspan();
+        }
+        // Short rows are padded, so that markers line up
+        let count = self.kept.iter().filter(|&&k| k).count();
+        cells.extend((printed..count).map(|_| Cell::default()));
+        cells.push(Cell::new(DROPPED_MARKER));
+        row.with_cells(cells)
+    }
+
+    /// Return the new index of each of `columns` which is kept
+    pub(crate) fn columns(&self, columns: &[usize]) -> Vec<usize> {
+        columns
+            .iter()
+            .filter(|&&c| self.kept.get(c) == Some(&true))
+            .map(|&c| self.kept[..c].iter().filter(|&&k| k).count())
+            .collect()
+    }
+
+    /// Return the widths of the kept columns among `widths`, followed by a null width
+    /// for the marker of the dropped columns
+    pub(crate) fn widths(&self, widths: &[usize]) -> Vec<usize> {
+        let mut kept = widths
+            .iter()
+            .zip(&self.kept)
+            .filter(|&(_, &k)| k)
+            .map(|(&w, _)| w)
+            .collect::<Vec<_>>();
+        kept.push(0);
+        kept
+    }
+}
+
+impl Table {
+    /// Print the table at most `width` display columns wide, to any output, by dropping
+    /// columns when it is wider. A column holding `…` is printed after the kept columns
+    /// to tell that some are missing.
+    ///
+    /// Columns with the lowest priority, as set with `set_column_priority()`, are dropped
+    /// first, and columns of equal priority from the right. The columns frozen with
+    /// `set_frozen_columns()` and the column of row numbers are never dropped. The table is
+    /// printed as wide as needed if dropping all the other columns is not enough.
+    /// The table is not modified, and its exports are not affected.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut table = table![["id", "name", "description"], [1, "db", "main database"]];
+    /// table.set_max_width(22);
+    /// assert_eq!(table.to_string().replace("\r\n", "\n"), "\
+    /// +----+------+---+
+    /// | id | name | … |
+    /// +----+------+---+
+    /// | 1  | db   | … |
+    /// +----+------+---+
+    /// ");
+    /// # }
+    /// ```
+    pub fn set_max_width(&mut self, width: usize) {
+        self.render.set_max_width(Some(width));
+    }
+
+    /// Print the table as wide as needed, undoing `set_max_width()`
+    pub fn unset_max_width(&mut self) {
+        self.render.set_max_width(None);
+    }
+
+    /// Set the priority of column `column` when columns are dropped to keep the table within
+    /// its maximum width, set with `set_max_width()`. Columns with a lower priority are
+    /// dropped first. Columns have a priority of 128 by default.
+    pub fn set_column_priority(&mut self, column: usize, priority: u8) {
+        self.render.set_column_priority(column, priority);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::ColumnWidth;
+    use crate::{Cell, Row, Table};
+
+    fn table() -> Table {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![
+            Cell::new("id"),
+            Cell::new("name"),
+            Cell::new("region"),
+            Cell::new("errors"),
+        ]));
+        table.add_row(Row::new(vec![
+            Cell::new("1"),
+            Cell::new("db"),
+            Cell::new("eu-west"),
+            Cell::new("12"),
+        ]));
+        table.add_row(Row::new(vec![
+            Cell::new("2"),
+            Cell::new("web").with_hspan(3),
+        ]));
+        table.add_row(Row::new(vec![Cell::new("3")]));
+        table
+    }
+
+    fn print(table: &Table) -> String {
+        table.to_string().replace("\r\n", "\n")
+    }
+
+    #[test]
+    fn max_width() {
+        let mut table = table();
+        let natural = print(&table);
+        table.set_max_width(40);
+        assert_eq!(print(&table), natural);
+
+        table.set_max_width(30);
+        let out = "\
++----+------+---------+---+
+| id | name | region  | … |
++====+======+=========+===+
+| 1  | db   | eu-west | … |
++----+------+---------+---+
+| 2  | web            | … |
++----+------+---------+---+
+| 3  |      |         | … |
++----+------+---------+---+
+";
+        assert_eq!(print(&table), out);
+        assert_eq!(table[0].len(), 4);
+
+        table.set_column_priority(2, 0);
+        table.set_column_priority(3, 200);
+        let out = "\
++----+------+--------+---+
+| id | name | errors | … |
++====+======+========+===+
+| 1  | db   | 12     | … |
++----+------+--------+---+
+| 2  | web           | … |
++----+------+--------+---+
+| 3  |      |        | … |
++----+------+--------+---+
+";
+        assert_eq!(print(&table), out);
+        assert_eq!(table.column_widths(), vec![2, 4, 0, 6]);
+        assert_eq!(table.total_width(), 26);
+        assert_eq!(table.compute_layout().to_string(), "2,4,0,6");
+        let mut printed = Vec::new();
+        table
+            .print_with_layout(&mut printed, &table.compute_layout())
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(printed).unwrap().replace("\r\n", "\n"),
+            out
+        );
+
+        table.unset_max_width();
+        assert_eq!(print(&table), natural);
+    }
+
+    #[test]
+    fn max_width_frozen() {
+        let mut table = table();
+        table.set_frozen_columns(3);
+        table.set_column_width(1, ColumnWidth::Min(6));
+        table.set_max_width(10);
+        let out = "\
++----+--------+---------+---+
+| id | name   | region  | … |
++====+========+=========+===+
+| 1  | db     | eu-west | … |
++----+--------+---------+---+
+| 2  | web              | … |
++----+--------+---------+---+
+| 3  |        |         | … |
++----+--------+---------+---+
+";
+        assert_eq!(print(&table), out);
+        assert_eq!(print(&table.fitted(10, 4)), out);
+
+        table.set_frozen_columns(0);
+        table.set_show_row_numbers(true);
+        let out = table.to_string().replace("\r\n", "\n");
+        assert!(out.starts_with("+---+---+\n| # | … |\n"));
+        assert_eq!(table.column_widths(), vec![0; 4]);
+        assert_eq!(table.total_width(), 9);
+    }
+}
@@ -0,0 +1,132 @@
+//! MediaWiki markup output for tables
+
+use std::io::{Error, Write};
+
+use super::format::Alignment;
+use super::utils::NEWLINE;
+use super::{AsTableSlice, Cell, Row, Table, TableSlice};
+
+/// Print every cell of `row` on its own line, prefixed with `marker`
+fn print_row<T: Write + ?Sized>(out: &mut T, row: &Row, marker: &str) -> Result<(), Error> {
+    for cell in row {
+        let mut attrs = Vec::new();
+        if cell.get_hspan() > 1 {
+            attrs.push(format!("colspan=\"{}\"", cell.get_hspan()));
+        }
+        match cell.get_align() {
+            Alignment::LEFT => {}
+            Alignment::CENTER => attrs.push("style=\"text-align:center\"".to_string()),
+            Alignment::RIGHT => attrs.push("style=\"text-align:right\"".to_string()),
+        }
+        let mut line = format!("{} ", marker);
+        if !attrs.is_empty() {
+            line += &attrs.join(" ");
+            line += " | ";
+        }
+        line += &format_content(cell);
+        out.write_all(line.as_bytes())?;
+        out.write_all(NEWLINE)?;
+    }
+    Ok(())
+}
+
+/// Format the content of `cell`, so that it cannot be mistaken for table markup
+fn format_content(cell: &Cell) -> String {
+    escape(&cell.get_content())
+}
+
+/// Escape `content`, so that it cannot be mistaken for table markup
+fn escape(content: &str) -> String {
+    content
+        .replace('|', "&#124;")
+        .replace('!', "&#33;")
+        .replace('\n', "<br />")
+}
+
+impl<'a> TableSlice<'a> {
+    /// Print table in MediaWiki markup to `out`, as a `wikitable`.
+    ///
+    /// The footer is printed as the last row, and the caption with the `|+` markup.
+    pub fn print_mediawiki<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        let table = self.exported();
+        out.write_all(b"{| class=\"wikitable\"")?;
+        out.write_all(NEWLINE)?;
+        if let Some(caption) = table.caption.line() {
+            out.write_all(format!("|+ {}", escape(&caption)).as_bytes())?;
+            out.write_all(NEWLINE)?;
+        }
+        if let Some(ref t) = *table.titles {
+            print_row(out, t, "!")?;
+        }
+        for (i, r) in table.rows.iter().enumerate() {
+            if i > 0 || table.titles.is_some() {
+                out.write_all(b"|-")?;
+                out.write_all(NEWLINE)?;
+            }
+            print_row(out, r, "|")?;
+        }
+        out.write_all(b"|}")?;
+        out.write_all(NEWLINE)?;
+        out.flush()
+    }
+}
+
+impl Table {
+    /// Print table in MediaWiki markup to `out`, as a `wikitable`.
+    pub fn print_mediawiki<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        self.as_slice().print_mediawiki(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::Alignment;
+    use crate::utils::StringWriter;
+    use crate::{Cell, Row, Table};
+
+    #[test]
+    fn mediawiki() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![
+            Cell::new("t1"),
+            Cell::new_align("t2", Alignment::RIGHT),
+        ]));
+        table.add_row(Row::new(vec![Cell::new("a|b"), Cell::new("c\nd")]));
+        table.add_row(Row::new(vec![
+            Cell::new_align("e", Alignment::CENTER).with_hspan(2)
+        ]));
+        let out = "\
+{| class=\"wikitable\"
+! t1
+! style=\"text-align:right\" | t2
+|-
+| a&#124;b
+| c<br />d
+|-
+| colspan=\"2\" style=\"text-align:center\" | e
+|}
+";
+        let mut writer = StringWriter::new();
+        assert!(table.print_mediawiki(&mut writer).is_ok());
+        assert_eq!(writer.as_string().replace("\r\n", "\n"), out);
+    }
+
+    #[test]
+    fn mediawiki_caption_footer() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a")]));
+        table.set_footer(Row::new(vec![Cell::new("b")]));
+        table.set_caption("x|y", Alignment::CENTER);
+        let out = "\
+{| class=\"wikitable\"
+|+ x&#124;y
+| a
+|-
+| b
+|}
+";
+        let mut writer = StringWriter::new();
+        assert!(table.print_mediawiki(&mut writer).is_ok());
+        assert_eq!(writer.as_string().replace("\r\n", "\n"), out);
+    }
+}
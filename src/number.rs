@@ -0,0 +1,194 @@
+//! Formatting of numeric cells
+
+use super::format::Alignment;
+use super::{Cell, Table};
+use std::fmt::Display;
+
+/// Formatting applied to numbers, with a fixed count of decimal places
+/// and a separator between groups of thousands, both optional.
+///
+/// # Examples
+/// ```rust
+/// use prettytable::NumberFormat;
+/// let format = NumberFormat::new().decimals(2).thousands(',');
+/// assert_eq!(format.format("1234567.891").unwrap(), "1,234,567.89");
+/// assert_eq!(format.format("n/a"), None);
+/// ```
+#[derive(Clone, Debug, Copy, Hash, PartialEq, Eq, Default)]
+pub struct NumberFormat {
+    pub(crate) decimals: Option<usize>,
+    pub(crate) thousands: Option<char>,
+}
+
+impl NumberFormat {
+    /// Create a format leaving numbers unchanged
+    pub fn new() -> NumberFormat {
+        NumberFormat::default()
+    }
+
+    /// Round numbers to `decimals` decimal places
+    pub fn decimals(mut self, decimals: usize) -> NumberFormat {
+        self.decimals = Some(decimals);
+        self
+    }
+
+    /// Separate groups of thousands in the integer part with `separator`
+    pub fn thousands(mut self, separator: char) -> NumberFormat {
+        self.thousands = Some(separator);
+        self
+    }
+
+    /// Format the number written in `text`, or return `None` if `text` is not a number
+    pub fn format(&self, text: &str) -> Option<String> {
+        let text = text.trim();
+        let value = parse_number(text)?;
+        let number = match self.decimals {
+            Some(decimals) => format!("{:.*}", decimals, value),
+            None => text.to_string(),
+        };
+        Some(match self.thousands {
+            Some(separator) => group_thousands(&number, separator),
+            None => number,
+        })
+    }
+}
+
+/// Parse the number written in `text`, surrounded by optional whitespace.
+/// Unlike `f64::from_str`, "inf" and "NaN" are not considered numbers.
+pub(crate) fn parse_number(text: &str) -> Option<f64> {
+    let text = text.trim();
+    if !text.bytes().any(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    text.parse::<f64>().ok()
+}
+
+/// Insert `separator` between groups of 3 digits in the integer part of `number`
+pub(crate) fn group_thousands(number: &str, separator: char) -> String {
+    let digits_start = number.find(|c: char| c.is_ascii_digit()).unwrap_or(0);
+    let digits_end = number[digits_start..]
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or(number.len(), |i| digits_start + i);
+    let digits = &number[digits_start..digits_end];
+    let mut grouped = String::from(&number[..digits_start]);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(separator);
+        }
+        grouped.push(c);
+    }
+    grouped.push_str(&number[digits_end..]);
+    grouped
+}
+
+impl Cell {
+    /// Create a new `Cell` holding a number, aligned to `RIGHT`
+    pub fn new_number<T: Display>(value: T) -> Cell {
+        Cell::new_align(&value.to_string(), Alignment::RIGHT)
+    }
+
+    /// Create a new `Cell` holding `value` with `decimals` decimal places, aligned to `RIGHT`
+    pub fn from_f64(value: f64, decimals: usize) -> Cell {
+        Cell::new_number(format!("{:.*}", decimals, value))
+    }
+
+    /// Create a new `Cell` holding `value` with groups of thousands separated by
+    /// `separator`, aligned to `RIGHT`
+    pub fn from_int_with_thousands(value: i64, separator: char) -> Cell {
+        Cell::new_number(group_thousands(&value.to_string(), separator))
+    }
+}
+
+impl Table {
+    /// Display the numbers in column `column` with `format` when printing, aligned
+    /// to `RIGHT`.
+    ///
+    /// Cells which do not hold a number, as well as titles and the footer, are displayed
+    /// unchanged. The cells keep their content, so that exports like CSV or JSON write
+    /// the raw values. Use `rendered()` to get a copy of the table with the formatted
+    /// numbers.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// use prettytable::NumberFormat;
+    /// # fn main() {
+    /// let mut table = table![["Item", "Price"], ["Car", 12500], ["Bike", 320.5]];
+    /// table.set_column_number_format(1, NumberFormat::new().decimals(2).thousands(','));
+    /// assert_eq!(table[1][1].get_content(), "12500");
+    /// let rendered = table.rendered();
+    /// assert_eq!(rendered[1][1].get_content(), "12,500.00");
+    /// assert_eq!(rendered[2][1].get_content(), "320.50");
+    /// # }
+    /// ```
+    pub fn set_column_number_format(&mut self, column: usize, format: NumberFormat) {
+        self.render.set_number_format(column, format);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NumberFormat;
+    use crate::format::Alignment;
+    use crate::{Cell, Row, Table};
+
+    #[test]
+    fn format() {
+        let format = NumberFormat::new();
+        assert_eq!(format.format(" 42 ").unwrap(), "42");
+        assert_eq!(format.format("inf"), None);
+        assert_eq!(format.format(""), None);
+        let format = NumberFormat::new().thousands('\'');
+        assert_eq!(format.format("-1234567").unwrap(), "-1'234'567");
+        assert_eq!(format.format("123").unwrap(), "123");
+        assert_eq!(format.format("1234.5678").unwrap(), "1'234.5678");
+        let format = NumberFormat::new().decimals(1);
+        assert_eq!(format.format("2.25e3").unwrap(), "2250.0");
+    }
+
+    #[test]
+    fn number_cells() {
+        let cell = Cell::new_number(42);
+        assert_eq!(cell.get_content(), "42");
+        assert_eq!(cell.get_align(), Alignment::RIGHT);
+        assert_eq!(Cell::from_f64(1.23456, 2).get_content(), "1.23");
+        assert_eq!(Cell::from_f64(-0.5, 0).get_content(), "-0");
+        assert_eq!(
+            Cell::from_int_with_thousands(-9876543, ',').get_content(),
+            "-9,876,543"
+        );
+        assert_eq!(Cell::from_int_with_thousands(999, ',').get_content(), "999");
+    }
+
+    #[test]
+    fn column_number_format() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("Name"), Cell::new("1000")]));
+        table.add_row(Row::new(vec![
+            Cell::new("a"),
+            Cell::new("1234.5").style_spec("bl"),
+        ]));
+        table.add_row(Row::new(vec![Cell::new("b"), Cell::new("-")]));
+        table.set_column_number_format(1, NumberFormat::new().decimals(2).thousands(' '));
+        let out = "\
++------+----------+
+| Name | 1000     |
++======+==========+
+| a    | 1 234.50 |
++------+----------+
+| b    | -        |
++------+----------+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        assert!(table.rendered()[0][1]
+            .get_style()
+            .contains(&crate::Attr::Bold));
+        assert_eq!(table[0][1].get_content(), "1234.5");
+
+        table.add_row(Row::new(vec![Cell::new("c"), Cell::new("12")]));
+        table.insert_column(0, None, Vec::new());
+        let rendered = table.rendered();
+        assert_eq!(rendered[2][2].get_content(), "12.00");
+        assert_eq!(rendered[2][2].get_align(), Alignment::RIGHT);
+    }
+}
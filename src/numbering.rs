@@ -0,0 +1,253 @@
+//! Numbering of the rows of a table, in a column printed before them
+
+use super::format::Alignment;
+use super::{Cell, Row, Table};
+
+/// Settings of the column of row numbers printed before the cells of a table, as set
+/// with `Table::set_row_numbers()`.
+///
+/// # Examples
+/// ```rust
+/// # #[macro_use] extern crate prettytable;
+/// use prettytable::{RowNumbers, Slice};
+/// # fn main() {
+/// let mut table = table![["a"], ["b"], ["c"]];
+/// table.set_row_numbers(RowNumbers {
+///     header: "n".to_string(),
+///     original: true,
+///     ..RowNumbers::default()
+/// });
+/// assert_eq!(table.slice(2..).to_string().replace("\r\n", "\n"), "\
+/// +---+---+
+/// | 3 | c |
+/// +---+---+
+/// ");
+/// # }
+/// ```
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct RowNumbers {
+    /// Title of the column, printed if the table has titles. `#` by default
+    pub header: String,
+    /// Number of the first row, 1 by default
+    pub start: usize,
+    /// Number the rows after their position in the table, instead of the order they are
+    /// printed in, so that slices and filtered views keep the numbers of the whole table
+    pub original: bool,
+    /// Also write the column to CSV and to markup exports like HTML
+    pub exported: bool,
+}
+
+impl Default for RowNumbers {
+    fn default() -> RowNumbers {
+        RowNumbers {
+            header: "#".to_string(),
+            start: 1,
+            original: false,
+            exported: false,
+        }
+    }
+}
+
+impl RowNumbers {
+    /// Return the number of a row, given its index among the numbered rows printed
+    /// and its position in the table
+    pub(crate) fn number(&self, printed: usize, position: usize) -> usize {
+        self.start + if self.original { position } else { printed }
+    }
+
+    /// Return the cell holding `number`
+    pub(crate) fn number_cell(number: usize) -> Cell {
+        Cell::new_align(&number.to_string(), Alignment::RIGHT)
+    }
+
+    /// Return the cell titling the column
+    pub(crate) fn header_cell(&self) -> Cell {
+        Cell::new_align(&self.header, Alignment::RIGHT)
+    }
+}
+
+/// Return a copy of `row` starting with `cell`
+pub(crate) fn prepend_cell(row: Row, cell: Cell) -> Row {
+    let mut cells = Vec::with_capacity(row.len() + 1);
+    cells.push(cell);
+    cells.extend(row.iter().cloned());
+    row.with_cells(cells)
+}
+
+impl Table {
+    /// Print a column of row numbers before the cells of the table, titled `#`, when
+    /// `show` is `true`, or stop printing it otherwise. See `RowNumbers` for the settings
+    /// of the column.
+    ///
+    /// Rows are numbered from 1 in the order they are printed, when printing
+    /// the table, a slice or a filtered view of it. The rows are not modified, and
+    /// settings referring to columns by index, like `set_column_alignment()`, still
+    /// refer to the columns of the table. Group headers are not numbered. The column
+    /// keeps its width when the table is fitted, like the columns frozen with
+    /// `set_frozen_columns()`. Exports only include the column if `RowNumbers::exported`
+    /// is set.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut table = table![["Alice"], ["Bob"]];
+    /// table.set_titles(row!["Name"]);
+    /// table.set_show_row_numbers(true);
+    /// assert_eq!(table.to_string().replace("\r\n", "\n"), "\
+    /// +---+-------+
+    /// | # | Name  |
+    /// +===+=======+
+    /// | 1 | Alice |
+    /// +---+-------+
+    /// | 2 | Bob   |
+    /// +---+-------+
+    /// ");
+    /// # }
+    /// ```
+    pub fn set_show_row_numbers(&mut self, show: bool) {
+        let numbers = match show {
+            true => Some(self.render.row_numbers().cloned().unwrap_or_default()),
+            false => None,
+        };
+        self.render.set_row_numbers(numbers);
+    }
+
+    /// Print a column of row numbers before the cells of the table, with the
+    /// settings `numbers`, like `set_show_row_numbers()`
+    pub fn set_row_numbers(&mut self, numbers: RowNumbers) {
+        self.render.set_row_numbers(Some(numbers));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RowNumbers;
+    use crate::format::{Alignment, ColumnWidth};
+    use crate::{Cell, Row, Slice, SortOrder, Table};
+
+    fn table() -> Table {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("service"), Cell::new("errors")]));
+        for (service, errors) in [("db", 12), ("web", 250), ("cache", 40)] {
+            table.add_row(Row::new(vec![Cell::new(service), Cell::from(errors)]));
+        }
+        table.set_footer(Row::new(vec![Cell::new("total"), Cell::new("302")]));
+        table
+    }
+
+    #[test]
+    fn row_numbers() {
+        let mut table = table();
+        table.set_show_row_numbers(true);
+        table.set_column_alignment(1, Alignment::RIGHT);
+        table.sort_by_column(1, SortOrder::Descending);
+        let out = "\
++---+---------+--------+
+| # | service | errors |
++===+=========+========+
+| 1 | web     |    250 |
++---+---------+--------+
+| 2 | cache   |     40 |
++---+---------+--------+
+| 3 | db      |     12 |
++===+=========+========+
+|   | total   |    302 |
++---+---------+--------+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(table.len(), 3);
+        assert_eq!(table[0].len(), 2);
+        let sliced = table.slice(1..).to_string().replace("\r\n", "\n");
+        assert!(sliced.contains("| 1 | cache   |     40 |"));
+        assert!(sliced.contains("| 2 | db      |     12 |"));
+
+        table.set_row_numbers(RowNumbers {
+            start: 0,
+            original: true,
+            ..RowNumbers::default()
+        });
+        let sliced = table.slice(1..).slice(1..).to_string();
+        assert!(sliced.contains("| 2 | db      |     12 |"));
+        let filtered = table
+            .filtered(|r| r[0].get_content() != "cache")
+            .to_string();
+        assert!(filtered.contains("| 0 | web     |    250 |"));
+        assert!(filtered.contains("| 2 | db      |     12 |"));
+        let pages = table.pages(2).map(|p| p.to_string()).collect::<Vec<_>>();
+        assert!(pages[1].contains("| 2 | db      |     12 |"));
+
+        table.set_show_row_numbers(false);
+        assert!(!table.to_string().contains('#'));
+    }
+
+    #[test]
+    fn row_numbers_groups() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("eu"), Cell::new("db")]));
+        table.add_row(Row::new(vec![Cell::new("us"), Cell::new("web")]));
+        table.add_row(Row::new(vec![Cell::new("eu"), Cell::new("api")]));
+        table.group_by(0, false);
+        table.set_show_row_numbers(true);
+        let rendered = table.rendered();
+        assert_eq!(rendered[0][0].get_hspan(), 3);
+        let numbers = rendered
+            .row_iter()
+            .map(|r| r[0].get_content())
+            .collect::<Vec<_>>();
+        assert_eq!(numbers, ["eu", "1", "2", "us", "3"]);
+    }
+
+    #[test]
+    fn row_numbers_fit() {
+        let mut table = table();
+        table.set_show_row_numbers(true);
+        table.set_column_width(0, ColumnWidth::Min(8));
+        let layout = table.compute_layout();
+        assert_eq!(layout.widths(), &[8, 6]);
+        assert_eq!(table.column_widths().len(), table.column_count());
+        let out = table.to_string();
+        assert_eq!(table.total_width(), out.lines().next().unwrap().len());
+        let mut printed = Vec::new();
+        table.print_with_layout(&mut printed, &layout).unwrap();
+        assert_eq!(String::from_utf8(printed).unwrap(), out);
+        let fitted = table.fitted(18, 2);
+        assert_eq!(fitted.compute_layout().widths()[0], 1);
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn row_numbers_exports() {
+        let mut table = table();
+        table.set_show_row_numbers(true);
+        let csv = |table: &Table| {
+            let writer =
+                table.to_csv_writer_with_footer(csv::Writer::from_writer(Vec::new()), true);
+            String::from_utf8(writer.unwrap().into_inner().unwrap()).unwrap()
+        };
+        let html = |table: &Table| {
+            let mut out = Vec::new();
+            table.print_html(&mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(
+            csv(&table),
+            "service,errors\ndb,12\nweb,250\ncache,40\ntotal,302\n"
+        );
+        assert!(!html(&table).contains(">1</td>"));
+        assert!(table.to_markdown().starts_with("| service | errors |"));
+        table.set_row_numbers(RowNumbers {
+            header: "n".to_string(),
+            exported: true,
+            ..RowNumbers::default()
+        });
+        assert_eq!(
+            csv(&table),
+            "n,service,errors\n1,db,12\n2,web,250\n3,cache,40\n,total,302\n"
+        );
+        assert!(html(&table).contains(">1</td>"));
+        let markdown = table.to_markdown().replace("\r\n", "\n");
+        assert!(markdown.starts_with("| n | service | errors |"));
+        assert!(markdown.ends_with("| 3 | cache | 40 |\n|  | total | 302 |\n"));
+    }
+}
@@ -0,0 +1,110 @@
+//! Org-mode output for tables
+
+use std::io::{Error, Write};
+
+use super::format::{FormatBuilder, LinePosition, LineSeparator, TableFormat};
+use super::utils::NEWLINE;
+use super::{AsTableSlice, Cell, Row, Table, TableSlice};
+
+lazy_static! {
+    /// Format of an Org-mode table
+    static ref FORMAT_ORG: TableFormat = FormatBuilder::new()
+        .column_separator('|')
+        .borders('|')
+        .separator(LinePosition::Title, LineSeparator::new('-', '+', '|', '|'))
+        .padding(1, 1)
+        .build();
+}
+
+/// Convert `row` into a row suitable for Org-mode: `|` are escaped, newlines are collapsed
+/// into spaces, and horizontally spanning cells are followed by empty cells
+fn org_row(row: &Row) -> Row {
+    let mut cells = Vec::new();
+    for cell in row {
+        let content = cell
+            .get_content()
+            .replace('|', "\\vert{}")
+            .replace('\n', " ");
+        cells.push(cell.with_content(&content).with_hspan(1));
+        for _ in 1..cell.get_hspan() {
+            cells.push(Cell::default());
+        }
+    }
+    Row::new(cells)
+}
+
+impl<'a> TableSlice<'a> {
+    /// Print table as an Emacs Org-mode table to `out`, and return the number of
+    /// lines printed, or an error.
+    ///
+    /// The table's own format is ignored, and columns are padded so the raw text is aligned.
+    /// The footer is printed as the last row, and the caption in a `#+CAPTION:` line
+    /// above the table.
+    pub fn print_org<T: Write + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
+        let mut table = self.exported();
+        *table.titles = (*table.titles).as_ref().map(org_row);
+        table.rows = table.rows.iter().map(org_row).collect();
+        table.header_rows = table.header_rows.iter().map(org_row).collect();
+        let mut height = 0;
+        if let Some(caption) = table.caption.line() {
+            out.write_all(format!("#+CAPTION: {}", caption).as_bytes())?;
+            out.write_all(NEWLINE)?;
+            height += 1;
+            table.unset_caption();
+        }
+        let slice = TableSlice {
+            format: &FORMAT_ORG,
+            ..table.as_slice()
+        };
+        Ok(height + slice.print(out)?)
+    }
+}
+
+impl Table {
+    /// Print table as an Emacs Org-mode table to `out`, and return the number of
+    /// lines printed, or an error.
+    ///
+    /// See `TableSlice::print_org()` for details.
+    pub fn print_org<T: Write + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
+        self.as_slice().print_org(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::Alignment;
+    use crate::utils::StringWriter;
+    use crate::{CaptionPosition, Cell, Row, Table};
+
+    #[test]
+    fn org() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2")]));
+        table.add_row(Row::new(vec![Cell::new("a|b"), Cell::new("c\nd")]));
+        table.add_row(Row::new(vec![Cell::new("e").with_hspan(2)]));
+        let out = "\
+| t1        | t2  |
+|-----------+-----|
+| a\\vert{}b | c d |
+| e         |     |
+";
+        let mut writer = StringWriter::new();
+        assert_eq!(table.print_org(&mut writer).unwrap(), 4);
+        assert_eq!(writer.as_string().replace("\r\n", "\n"), out);
+
+        table.set_footer(Row::new(vec![Cell::new("f")]));
+        table.set_caption("Letters", Alignment::CENTER);
+        table.set_caption_position(CaptionPosition::Below);
+        let out = "\
+#+CAPTION: Letters
+| t1        | t2  |
+|-----------+-----|
+| a\\vert{}b | c d |
+| e         |     |
+| f         |     |
+";
+        let mut writer = StringWriter::new();
+        assert_eq!(table.print_org(&mut writer).unwrap(), 6);
+        assert_eq!(writer.as_string().replace("\r\n", "\n"), out);
+    }
+}
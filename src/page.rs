@@ -0,0 +1,172 @@
+//! Pagination of tables, for interactive programs printing one page at a time
+
+use std::io::{self, BufRead, Error, Write};
+
+use super::{AsTableSlice, Row, Table, TableSlice};
+
+/// Footer of the pages before the last one
+static NO_FOOTER: Option<Row> = None;
+
+/// Prompt printed between pages by `print_paged()`
+const PAGE_PROMPT: &[u8] = b"-- More: press Enter to continue, q to quit --";
+
+impl Table {
+    /// Return an iterator over the pages of the table, each one holding `rows_per_page`
+    /// rows, except the last one. A count of 0 is handled as 1.
+    ///
+    /// Every page is printed with the titles and the caption of the table. The footer is only
+    /// printed with the last page. Columns are sized on all the rows of the table, so that
+    /// they keep the same width from a page to the next.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let table = table![["a"], ["bbb"], ["c"]];
+    /// let pages = table.pages(2).map(|page| page.to_string()).collect::<Vec<_>>();
+    /// assert_eq!(pages.len(), 2);
+    /// assert_eq!(pages[1].replace("\r\n", "\n"), "+-----+\n| c   |\n+-----+\n");
+    /// # }
+    /// ```
+    pub fn pages(&self, rows_per_page: usize) -> impl Iterator<Item = TableSlice<'_>> {
+        let table = self.as_slice();
+        let count = self.rows.len().div_ceil(rows_per_page.max(1));
+        self.rows
+            .chunks(rows_per_page.max(1))
+            .enumerate()
+            .map(move |(i, rows)| TableSlice {
+                rows,
+                footer: if i + 1 == count {
+                    table.footer
+                } else {
+                    &NO_FOOTER
+                },
+                sizing: Some((table.rows, table.footer)),
+                offset: table.offset + i * rows_per_page.max(1),
+                ..table
+            })
+    }
+
+    /// Print the table to standard output one page of `rows_per_page` rows at a time,
+    /// waiting for the Enter key between pages, or `q` and Enter to stop.
+    /// See `pages()` for details.
+    ///
+    /// If standard output is not a terminal, the whole table is printed at once.
+    /// Returns the number of lines of the table printed, or an error.
+    pub fn print_paged(&self, rows_per_page: usize) -> Result<usize, Error> {
+        use is_terminal::IsTerminal;
+        if !io::stdout().is_terminal() {
+            return self.print_tty(false);
+        }
+        let stdin = io::stdin();
+        self.page_through(
+            rows_per_page,
+            &mut stdin.lock(),
+            &mut io::stdout(),
+            |page| page.print_tty(false),
+        )
+    }
+
+    /// Print the pages of the table with `print_page`, printing a prompt to `out` and
+    /// reading a line from `input` between pages
+    fn page_through<I, T, F>(
+        &self,
+        rows_per_page: usize,
+        input: &mut I,
+        out: &mut T,
+        mut print_page: F,
+    ) -> Result<usize, Error>
+    where
+        I: BufRead,
+        T: Write + ?Sized,
+        F: FnMut(TableSlice) -> Result<usize, Error>,
+    {
+        let mut height = 0;
+        let mut pages = self.pages(rows_per_page).peekable();
+        while let Some(page) = pages.next() {
+            height += print_page(page)?;
+            if pages.peek().is_none() {
+                break;
+            }
+            out.write_all(PAGE_PROMPT)?;
+            out.flush()?;
+            let mut answer = String::new();
+            let quit = input.read_line(&mut answer)? == 0 || answer.trim() == "q";
+            out.write_all(b"\n")?;
+            if quit {
+                break;
+            }
+        }
+        Ok(height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PAGE_PROMPT;
+    use crate::utils::StringWriter;
+    use crate::{Cell, Row, Table};
+
+    fn sample() -> Table {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("n"), Cell::new("\tv")]));
+        for (n, v) in [("1", "a"), ("2", "a"), ("3", "long value"), ("4", "b")] {
+            table.add_row(Row::new(vec![Cell::new(n), Cell::new(v)]));
+        }
+        table.set_footer(Row::new(vec![Cell::new("end")]));
+        table.merge_column_duplicates(1);
+        table.set_max_row_height(1);
+        table
+    }
+
+    #[test]
+    fn pages() {
+        let table = sample();
+        let pages = table
+            .pages(2)
+            .map(|page| page.to_string().replace("\r\n", "\n"))
+            .collect::<Vec<_>>();
+        let first = "\
++-----+------------+
+| n   |     v      |
++=====+============+
+| 1   | a          |
++-----+------------+
+| 2   |            |
++-----+------------+
+";
+        let last = "\
++-----+------------+
+| n   |     v      |
++=====+============+
+| 3   | long value |
++-----+------------+
+| 4   | b          |
++=====+============+
+| end |            |
++-----+------------+
+";
+        assert_eq!(pages, vec![first, last]);
+        assert_eq!(table.pages(0).count(), 4);
+        assert_eq!(table.pages(10).count(), 1);
+        assert_eq!(Table::new().pages(10).count(), 0);
+    }
+
+    #[test]
+    fn page_through() {
+        let table = sample();
+        let mut printed = Vec::new();
+        let mut out = StringWriter::new();
+        let mut input = "\nq\n".as_bytes();
+        let height = table
+            .page_through(1, &mut input, &mut out, |page| {
+                printed.push(page.len());
+                Ok(1)
+            })
+            .unwrap();
+        assert_eq!(height, 2);
+        assert_eq!(printed, vec![1, 1]);
+        let prompt = String::from_utf8(PAGE_PROMPT.to_vec()).unwrap();
+        assert_eq!(out.as_string(), format!("{}\n{}\n", prompt, prompt));
+    }
+}
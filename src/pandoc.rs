@@ -0,0 +1,158 @@
+//! Pandoc grid table output
+
+use std::io::{Error, Write};
+
+use super::format::consts::FORMAT_DEFAULT;
+use super::format::{Alignment, LinePosition};
+use super::utils::NEWLINE;
+use super::{AsTableSlice, CaptionPosition, Table, TableSlice};
+
+/// Print a separator line made of `fill` characters, with pandoc alignment markers
+/// at the boundaries of each column
+fn print_aligned_separator<T: Write + ?Sized>(
+    out: &mut T,
+    col_width: &[usize],
+    aligns: &[Alignment],
+    fill: char,
+) -> Result<usize, Error> {
+    let (lpad, rpad) = FORMAT_DEFAULT.get_padding();
+    let mut line = String::from("+");
+    for (width, align) in col_width.iter().zip(aligns) {
+        let mut segment = vec![fill; width + lpad + rpad];
+        if *align != Alignment::RIGHT {
+            segment[0] = ':';
+        }
+        if *align != Alignment::LEFT {
+            *segment.last_mut().unwrap() = ':';
+        }
+        line.extend(segment);
+        line.push('+');
+    }
+    out.write_all(line.as_bytes())?;
+    out.write_all(NEWLINE)?;
+    Ok(1)
+}
+
+impl<'a> TableSlice<'a> {
+    /// Print table as a pandoc grid table to `out`, and return the number of
+    /// lines printed, or an error.
+    ///
+    /// The table's own format is ignored. Column alignments are taken from the titles,
+    /// or from the first row when there are no titles, and marked with `:` in the
+    /// line below the titles, or in the top line when there are no titles. The footer is
+    /// printed as the last row, and the caption in a `Table:` paragraph above or below
+    /// the table.
+    pub fn print_pandoc_grid<T: Write + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
+        let table = self.exported();
+        let mut height = table.caption.print_pandoc(out, CaptionPosition::Above)?;
+        height += table.as_slice().print_pandoc_grid_rows(out)?;
+        height += table.caption.print_pandoc(out, CaptionPosition::Below)?;
+        out.flush()?;
+        Ok(height)
+    }
+
+    /// Print the titles and rows of the table as a pandoc grid table to `out`, and return
+    /// the number of lines printed
+    fn print_pandoc_grid_rows<T: Write + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
+        let format = &*FORMAT_DEFAULT;
+        let col_width = self.get_all_column_width();
+        let aligns = self.get_column_alignments();
+        let mut height = 0;
+        if let Some(ref t) = *self.titles {
+            height += format.print_line_separator(out, &col_width, LinePosition::Top)?;
+            height += t.print(out, format, &col_width)?;
+            height += print_aligned_separator(out, &col_width, &aligns, '=')?;
+        } else {
+            height += print_aligned_separator(out, &col_width, &aligns, '-')?;
+        }
+        for r in self.rows {
+            height += r.print(out, format, &col_width)?;
+            height += format.print_line_separator(out, &col_width, LinePosition::Intern)?;
+        }
+        Ok(height)
+    }
+}
+
+impl Table {
+    /// Print table as a pandoc grid table to `out`, and return the number of
+    /// lines printed, or an error.
+    ///
+    /// See `TableSlice::print_pandoc_grid()` for details.
+    pub fn print_pandoc_grid<T: Write + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
+        self.as_slice().print_pandoc_grid(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::Alignment;
+    use crate::utils::StringWriter;
+    use crate::{CaptionPosition, Cell, Row, Table};
+
+    #[test]
+    fn pandoc_grid() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![
+            Cell::new("t1"),
+            Cell::new_align("t2", Alignment::CENTER),
+            Cell::new_align("t3", Alignment::RIGHT),
+        ]));
+        table.add_row(Row::new(vec![
+            Cell::new("由系统"),
+            Cell::new("a\nbc"),
+            Cell::new("1"),
+        ]));
+        table.add_row(Row::new(vec![Cell::new("d")]));
+        let out = "\
++--------+----+----+
+| t1     | t2 | t3 |
++:=======+:==:+===:+
+| 由系统 | a  | 1  |
+|        | bc |    |
++--------+----+----+
+| d      |    |    |
++--------+----+----+
+";
+        let mut writer = StringWriter::new();
+        assert_eq!(table.print_pandoc_grid(&mut writer).unwrap(), 8);
+        assert_eq!(writer.as_string().replace("\r\n", "\n"), out);
+    }
+
+    #[test]
+    fn pandoc_grid_no_titles() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![
+            Cell::new("a"),
+            Cell::new_align("b", Alignment::RIGHT),
+        ]));
+        let out = "\
++:--+--:+
+| a | b |
++---+---+
+";
+        let mut writer = StringWriter::new();
+        assert_eq!(table.print_pandoc_grid(&mut writer).unwrap(), 3);
+        assert_eq!(writer.as_string().replace("\r\n", "\n"), out);
+    }
+
+    #[test]
+    fn pandoc_grid_caption() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("b")]));
+        table.set_footer(Row::new(vec![Cell::new("c"), Cell::new("d")]));
+        table.set_caption("Letters", Alignment::LEFT);
+        table.set_caption_position(CaptionPosition::Below);
+        let out = "\
++:--+:--+
+| a | b |
++---+---+
+| c | d |
++---+---+
+
+Table: Letters
+";
+        let mut writer = StringWriter::new();
+        assert_eq!(table.print_pandoc_grid(&mut writer).unwrap(), 7);
+        assert_eq!(writer.as_string().replace("\r\n", "\n"), out);
+    }
+}
@@ -0,0 +1,39 @@
+//! Errors reported when parsing tables from text
+
+use std::error;
+use std::fmt;
+
+/// Error returned when a table cannot be parsed from its text representation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    message: String,
+}
+
+impl ParseError {
+    /// Create a new error at `line`, starting from 1
+    pub(crate) fn new<S: Into<String>>(line: usize, message: S) -> ParseError {
+        ParseError {
+            line,
+            message: message.into(),
+        }
+    }
+
+    /// Get the line the error was found at, starting from 1
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Get the description of the error
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl error::Error for ParseError {}
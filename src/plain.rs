@@ -0,0 +1,120 @@
+//! Plain text output, without ANSI escape sequences
+
+use std::io::{Error, Write};
+
+use super::utils::{strip_ansi, StringWriter};
+use super::{AsTableSlice, Row, Table, TableSlice};
+
+/// Return a copy of `row` with ANSI escape sequences stripped from its cells content
+fn strip_row(row: &Row) -> Row {
+    row.with_cells(
+        row.iter()
+            .map(|c| c.with_content(&strip_ansi(&c.get_content())))
+            .collect(),
+    )
+}
+
+impl<'a> TableSlice<'a> {
+    /// Print the table to `out` using its format, with all ANSI CSI and OSC escape
+    /// sequences stripped from the cells content, and return the number of lines printed,
+    /// or an error.
+    ///
+    /// Hyperlinks only keep their visible text.
+    pub fn print_plain<T: Write + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
+        let titles = self.titles.as_ref().map(strip_row);
+        let rows: Vec<Row> = self.rows.iter().map(strip_row).collect();
+        let header_rows: Vec<Row> = self.header_rows.iter().map(strip_row).collect();
+        let footer = self.footer.as_ref().map(strip_row);
+        TableSlice {
+            format: self.format,
+            titles: &titles,
+            header_rows: &header_rows,
+            rows: &rows,
+            footer: &footer,
+            ..*self
+        }
+        .print(out)
+    }
+
+    /// Return the table rendered with its format as a string, with all ANSI escape
+    /// sequences stripped from the cells content.
+    ///
+    /// See `print_plain()` for details.
+    pub fn to_plain_string(&self) -> String {
+        let mut writer = StringWriter::new();
+        // Writing into a `StringWriter` cannot fail
+        let _ = self.print_plain(&mut writer);
+        writer.as_string().to_string()
+    }
+}
+
+impl Table {
+    /// Print the table to `out` using its format, with all ANSI escape sequences
+    /// stripped from the cells content, and return the number of lines printed, or an error.
+    ///
+    /// See `TableSlice::print_plain()` for details.
+    pub fn print_plain<T: Write + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
+        self.as_slice().print_plain(out)
+    }
+
+    /// Return the table rendered with its format as a string, with all ANSI escape
+    /// sequences stripped from the cells content.
+    pub fn to_plain_string(&self) -> String {
+        self.as_slice().to_plain_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::consts::FORMAT_NO_LINESEP_WITH_TITLE;
+    use crate::{Cell, Row, Table};
+
+    #[test]
+    fn plain() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![
+            Cell::new("\x1B[1mt1\x1B[0m"),
+            Cell::new("t2"),
+        ]));
+        table.add_row(Row::new(vec![
+            Cell::new("\x1B[31mred\x1B[0m"),
+            Cell::new("\x1B]8;;https://example.com\x1B\\link\x1B]8;;\x1B\\"),
+        ]));
+        table.add_row(Row::new(vec![Cell::new(
+            "\x1B]8;;http://a.b\x07a\x1B]8;;\x07",
+        )]));
+        let out = "\
++-----+------+
+| t1  | t2   |
++=====+======+
+| red | link |
++-----+------+
+| a   |      |
++-----+------+
+";
+        assert_eq!(table.to_plain_string().replace("\r\n", "\n"), out);
+        assert!(!table.to_plain_string().contains('\x1B'));
+        let mut out = Vec::new();
+        assert_eq!(table.print_plain(&mut out).unwrap(), 7);
+    }
+
+    #[test]
+    fn plain_row_settings() {
+        let mut table = Table::new();
+        table.set_format(*FORMAT_NO_LINESEP_WITH_TITLE);
+        let mut row = Row::new(vec![Cell::new("\x1B[1ma\nb\nc\x1B[0m")]);
+        row.set_max_height(2);
+        row.set_separator_below(true);
+        table.add_row(row);
+        table.add_row(Row::new(vec![Cell::new("d")]));
+        let out = "\
++----------------+
+| a              |
+| b … (+1 lines) |
++----------------+
+| d              |
++----------------+
+";
+        assert_eq!(table.to_plain_string().replace("\r\n", "\n"), out);
+    }
+}
@@ -0,0 +1,176 @@
+//! Refreshing a table printed to a terminal by rewriting its changed lines
+
+use std::io::{self, Error, Write};
+
+use super::utils::NEWLINE;
+use super::{Table, Terminal};
+
+/// Write to `out` the escape sequences moving the cursor `lines` lines up, or down
+/// when `lines` is negative, to the first column
+fn move_cursor<T: Write + ?Sized>(out: &mut T, lines: isize) -> Result<(), Error> {
+    match lines {
+        0 => write!(out, "\r"),
+        l if l > 0 => write!(out, "\x1b[{}A\r", l),
+        l => write!(out, "\x1b[{}B\r", -l),
+    }
+}
+
+/// Rewrite the lines of `previous`, printed just above the cursor, which differ in
+/// `current`, leaving the cursor below them. When `reprint` is `true` or the texts do not
+/// have the same number of lines, `previous` is erased and `current` printed in full.
+/// Returns the number of lines written.
+fn rewrite_lines<T: Write + ?Sized>(
+    out: &mut T,
+    previous: &str,
+    current: &str,
+    reprint: bool,
+) -> Result<usize, Error> {
+    let (old, new) = (
+        previous.lines().collect::<Vec<_>>(),
+        current.lines().collect::<Vec<_>>(),
+    );
+    if reprint || old.len() != new.len() {
+        if !old.is_empty() {
+            move_cursor(out, old.len() as isize)?;
+            out.write_all(b"\x1b[J")?;
+        }
+        for line in &new {
+            out.write_all(line.as_bytes())?;
+            out.write_all(NEWLINE)?;
+        }
+        return Ok(new.len());
+    }
+    // Index of the line the cursor is on, from the first line of the table
+    let mut cursor = old.len();
+    let mut rewritten = 0;
+    for (i, line) in new.iter().enumerate().filter(|&(i, line)| old[i] != *line) {
+        move_cursor(out, cursor as isize - i as isize)?;
+        out.write_all(b"\x1b[2K")?;
+        out.write_all(line.as_bytes())?;
+        out.write_all(NEWLINE)?;
+        cursor = i + 1;
+        rewritten += 1;
+    }
+    if cursor < old.len() {
+        move_cursor(out, cursor as isize - old.len() as isize)?;
+    }
+    Ok(rewritten)
+}
+
+/// Return what `table` prints to a terminal writing into a buffer, with `term`
+/// capabilities
+fn print_to_buffer<F>(table: &Table, term: F) -> Result<String, Error>
+where
+    F: Fn() -> Option<term::TerminfoTerminal<Vec<u8>>>,
+{
+    let mut terminal = term().ok_or_else(|| Error::other("No terminal info"))?;
+    table.print_term(&mut terminal)?;
+    Ok(String::from_utf8_lossy(&terminal.into_inner()).into_owned())
+}
+
+impl Table {
+    /// Refresh this table on `out`, where `previous` was printed with `print()` just
+    /// above the cursor, by rewriting only the lines which changed, with ANSI escape
+    /// sequences moving the cursor. This avoids the flicker of clearing the screen
+    /// when a table is printed again on every tick of a live display.
+    ///
+    /// When the column widths or the number of lines differ, as computed by
+    /// `compute_layout()`, the previous table is erased and this one printed in full.
+    /// To keep the same layout across refreshes, set the widths of the columns with
+    /// `set_column_width()`. In both cases the cursor ends below the table, ready for
+    /// the next refresh. Returns the number of lines written, 0 if the tables print the same.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let previous = table![["db", "up"], ["web", "up"]];
+    /// let current = table![["db", "up"], ["web", "no"]];
+    /// let mut out = Vec::new();
+    /// assert_eq!(current.print_diff(&mut out, &previous).unwrap(), 1);
+    /// assert_eq!(
+    ///     String::from_utf8(out).unwrap().replace("\r\n", "\n"),
+    ///     "\x1b[2A\r\x1b[2K| web | no |\n\x1b[1B\r"
+    /// );
+    /// # }
+    /// ```
+    pub fn print_diff<T: Write + ?Sized>(
+        &self,
+        out: &mut T,
+        previous: &Table,
+    ) -> Result<usize, Error> {
+        let reprint = self.compute_layout() != previous.compute_layout();
+        rewrite_lines(out, &previous.to_string(), &self.to_string(), reprint)
+    }
+
+    /// Refresh this table on standard output, where `previous` was printed with
+    /// `print_tty()` or `printstd()` just above the cursor, like `print_diff()`. Styles are
+    /// applied when standard output is a terminal. When it is not, the table is printed
+    /// in full, without moving the cursor.
+    pub fn print_diff_tty(&self, previous: &Table) -> Result<usize, Error> {
+        use is_terminal::IsTerminal;
+        if !io::stdout().is_terminal() {
+            return self.print(&mut io::stdout());
+        }
+        let term = || term::TerminfoTerminal::new(Vec::new());
+        let (old, new) = match (print_to_buffer(previous, term), print_to_buffer(self, term)) {
+            (Ok(old), Ok(new)) => (old, new),
+            _ => (previous.to_string(), self.to_string()),
+        };
+        let reprint = self.compute_layout() != previous.compute_layout();
+        let mut out = io::stdout().lock();
+        let lines = rewrite_lines(&mut out, &old, &new, reprint)?;
+        out.flush()?;
+        Ok(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::ColumnWidth;
+    use crate::{Cell, Row, Table};
+
+    fn refresh(current: &Table, previous: &Table) -> (usize, String) {
+        let mut out = Vec::new();
+        let lines = current.print_diff(&mut out, previous).unwrap();
+        (lines, String::from_utf8(out).unwrap().replace("\r\n", "\n"))
+    }
+
+    #[test]
+    fn print_diff() {
+        let previous = Table::from_rows(vec![vec!["a", "1"], vec!["b", "2"], vec!["c", "3"]]);
+        assert_eq!(refresh(&previous, &previous), (0, String::new()));
+
+        let current = Table::from_rows(vec![vec!["x", "1"], vec!["b", "2"], vec!["c", "4"]]);
+        let out = "\x1b[6A\r\x1b[2K| x | 1 |\n\x1b[3B\r\x1b[2K| c | 4 |\n\x1b[1B\r";
+        assert_eq!(refresh(&current, &previous), (2, out.to_string()));
+
+        let mut last = Table::from_rows(vec![vec!["a", "1"], vec!["b", "2"], vec!["c", "3"]]);
+        last.set_footer(Row::new(vec![Cell::new("t"), Cell::new("6")]));
+        let mut previous = previous.clone();
+        previous.set_footer(Row::new(vec![Cell::new("t"), Cell::new("5")]));
+        let (lines, out) = refresh(&last, &previous);
+        assert_eq!(
+            (lines, out.as_str()),
+            (1, "\x1b[2A\r\x1b[2K| t | 6 |\n\x1b[1B\r")
+        );
+    }
+
+    #[test]
+    fn print_diff_layout() {
+        let previous = Table::from_rows(vec![vec!["a", "1"]]);
+        let current = Table::from_rows(vec![vec!["a", "10"]]);
+        let (lines, out) = refresh(&current, &previous);
+        assert_eq!(lines, 3);
+        assert_eq!(out, format!("\x1b[3A\r\x1b[J{}", current));
+
+        let mut previous = Table::from_rows(vec![vec!["a", "1"]]);
+        let mut current = Table::from_rows(vec![vec!["a", "10"], vec!["b", "2"]]);
+        previous.set_column_width(1, ColumnWidth::Fixed(2));
+        current.set_column_width(1, ColumnWidth::Fixed(2));
+        let (lines, out) = refresh(&current, &previous);
+        assert_eq!(lines, 5);
+        assert!(out.starts_with("\x1b[3A\r\x1b[J+---+----+\n"));
+        assert_eq!(refresh(&current, &Table::new()).0, 5);
+    }
+}
@@ -0,0 +1,1057 @@
+//! Transformations applied to the cells of a table when it is printed,
+//! without modifying the table's data
+
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+
+use super::format::{Alignment, ColumnWidth, TableFormat};
+use super::max_width::DEFAULT_COLUMN_PRIORITY;
+use super::number::NumberFormat;
+use super::numbering::{prepend_cell, RowNumbers};
+use super::rule::Rule;
+use super::utils::{expand_tabs, strip_ansi};
+use super::{AsTableSlice, Cell, CellStyle, Row, Table, TableSlice};
+
+/// Settings changing how the cells of a table are displayed
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub(crate) struct RenderOptions {
+    pub(crate) placeholder: Option<String>,
+    pub(crate) column_placeholders: BTreeMap<usize, String>,
+    /// Columns where adjacent duplicates are merged, and whether the
+    /// separators between them are hidden
+    pub(crate) merged_columns: BTreeMap<usize, bool>,
+    /// Alignment of the cells without an explicit alignment, per column
+    pub(crate) column_alignments: BTreeMap<usize, Alignment>,
+    /// Maximum number of lines printed per row
+    pub(crate) max_row_height: Option<usize>,
+    /// Marker ending clipped cells, the default one if `None`
+    pub(crate) height_marker: Option<String>,
+    /// Styles of the even and odd rows
+    pub(crate) striping: Option<(CellStyle, CellStyle)>,
+    /// Constraints on the width of the columns
+    pub(crate) column_widths: BTreeMap<usize, ColumnWidth>,
+    /// Count of leading columns keeping their width when the table is fitted
+    pub(crate) frozen_columns: usize,
+    /// Conditional formatting rules, with their column and the style they apply
+    pub(crate) format_rules: Vec<(usize, Rule, CellStyle)>,
+    /// Column of row numbers printed before the cells
+    pub(crate) row_numbers: Option<RowNumbers>,
+    /// Maximum width of the printed table
+    pub(crate) max_width: Option<usize>,
+    /// Priority of the columns when dropping columns to keep the maximum width
+    pub(crate) column_priorities: BTreeMap<usize, u8>,
+    /// Formatting of the numbers, per column
+    pub(crate) number_formats: BTreeMap<usize, NumberFormat>,
+}
+
+/// Marker ending the cells clipped by a maximum row height
+const DEFAULT_HEIGHT_MARKER: &str = " … (+{} lines)";
+
+impl RenderOptions {
+    /// Tell if rows are printed as they are
+    pub(crate) fn is_identity(&self) -> bool {
+        *self == RenderOptions::default()
+    }
+
+    /// Return the placeholder for empty cells of column `column`, if any
+    fn get_placeholder(&self, column: usize) -> Option<&str> {
+        self.column_placeholders
+            .get(&column)
+            .or(self.placeholder.as_ref())
+            .map(String::as_str)
+    }
+
+    /// Return `cell` of column `column`, aligned with the default alignment
+    /// of the column if it has no explicit alignment
+    fn align_cell(&self, mut cell: Cell, column: usize) -> Cell {
+        if cell.get_explicit_align().is_none() {
+            if let Some(&align) = self.column_alignments.get(&column) {
+                cell.align(align);
+            }
+        }
+        cell
+    }
+
+    /// Return `cell` of column `column` formatted and aligned to `RIGHT` if it holds
+    /// a number and the column has a number format
+    fn format_number(&self, cell: Cell, column: usize) -> Cell {
+        let number = self
+            .number_formats
+            .get(&column)
+            .and_then(|format| format.format(&cell.get_content()));
+        match number {
+            Some(number) => {
+                let mut cell = cell.with_content(&number);
+                cell.align(Alignment::RIGHT);
+                cell
+            }
+            None => cell,
+        }
+    }
+
+    /// Return a copy of `row` clipped to its maximum height, or the one set by
+    /// `max_row_height` if any
+    fn clip_row(&self, row: Row, max_row_height: Option<usize>) -> Row {
+        let lines = match row.get_max_height().or(max_row_height) {
+            Some(lines) => lines,
+            None => return row,
+        };
+        let marker = self
+            .height_marker
+            .as_deref()
+            .unwrap_or(DEFAULT_HEIGHT_MARKER);
+        let cells = row
+            .iter()
+            .map(|cell| cell.clipped(lines, marker).unwrap_or_else(|| cell.clone()))
+            .collect();
+        row.with_cells(cells)
+    }
+
+    /// Return a copy of `row` with its cells wrapped or truncated to the maximum width
+    /// of their column. Cells spanning several columns are left unchanged.
+    fn cap_row(&self, row: Row) -> Row {
+        if self.column_widths.is_empty() {
+            return row;
+        }
+        let cells = grid_columns(&row)
+            .map(|(column, cell)| {
+                let mut cell = cell.clone();
+                let max = self.column_widths.get(&column).and_then(ColumnWidth::max);
+                if let (Some(max), 1) = (max, cell.get_hspan()) {
+                    cell.fit_width(max);
+                }
+                cell
+            })
+            .collect();
+        row.with_cells(cells)
+    }
+
+    /// Set the constraint on the width of column `column`
+    pub(crate) fn set_column_width(&mut self, column: usize, width: ColumnWidth) {
+        self.column_widths.insert(column, width);
+    }
+
+    /// Get the count of leading columns keeping their width when the table is fitted
+    pub(crate) fn frozen_columns(&self) -> usize {
+        self.frozen_columns
+    }
+
+    /// Set the count of leading columns keeping their width when the table is fitted
+    pub(crate) fn set_frozen_columns(&mut self, columns: usize) {
+        self.frozen_columns = columns;
+    }
+
+    /// Count the leading printed columns keeping their width when the table is fitted, and
+    /// never dropped, including the columns added before the cells
+    pub(crate) fn printed_frozen_columns(&self) -> usize {
+        self.frozen_columns + self.added_columns(true)
+    }
+
+    /// Get the maximum width of the printed table, if any
+    pub(crate) fn max_width(&self) -> Option<usize> {
+        self.max_width
+    }
+
+    /// Set the maximum width of the printed table, or remove it with `None`
+    pub(crate) fn set_max_width(&mut self, width: Option<usize>) {
+        self.max_width = width;
+    }
+
+    /// Set the priority of column `column` when dropping columns to keep the maximum width
+    pub(crate) fn set_column_priority(&mut self, column: usize, priority: u8) {
+        self.column_priorities.insert(column, priority);
+    }
+
+    /// Get the priority of the printed column `column` when dropping columns to keep the
+    /// maximum width. The columns added before the cells have the highest priority.
+    pub(crate) fn column_priority(&self, column: usize) -> u8 {
+        match column.checked_sub(self.added_columns(true)) {
+            Some(column) => self
+                .column_priorities
+                .get(&column)
+                .copied()
+                .unwrap_or(DEFAULT_COLUMN_PRIORITY),
+            None => u8::MAX,
+        }
+    }
+
+    /// Add a formatting rule applying `style` to the cells of column `column`
+    /// for which `rule` holds
+    pub(crate) fn add_format_rule(&mut self, column: usize, rule: Rule, style: CellStyle) {
+        self.format_rules.push((column, rule, style));
+    }
+
+    /// Set the format of the numbers of column `column`
+    pub(crate) fn set_number_format(&mut self, column: usize, format: NumberFormat) {
+        self.number_formats.insert(column, format);
+    }
+
+    /// Remove all the formatting rules
+    pub(crate) fn clear_format_rules(&mut self) {
+        self.format_rules.clear();
+    }
+
+    /// Get the settings of the column of row numbers, if it is printed
+    pub(crate) fn row_numbers(&self) -> Option<&RowNumbers> {
+        self.row_numbers.as_ref()
+    }
+
+    /// Set the settings of the column of row numbers, or stop printing it with `None`
+    pub(crate) fn set_row_numbers(&mut self, numbers: Option<RowNumbers>) {
+        self.row_numbers = numbers;
+    }
+
+    /// Return the settings of the column of row numbers if it is added to the rendered
+    /// rows. See `render()` for the meaning of `text`.
+    pub(crate) fn numbered(&self, text: bool) -> Option<&RowNumbers> {
+        self.row_numbers.as_ref().filter(|n| text || n.exported)
+    }
+
+    /// Return the count of columns added before the cells of the rendered rows.
+    /// See `render()` for the meaning of `text`.
+    pub(crate) fn added_columns(&self, text: bool) -> usize {
+        usize::from(self.numbered(text).is_some())
+    }
+
+    /// Return `cell` of column `column` styled by the formatting rules holding for it
+    fn format_cell(&self, mut cell: Cell, column: usize) -> Cell {
+        for (_, rule, style) in self.format_rules.iter().filter(|r| r.0 == column) {
+            if rule.matches(&cell) {
+                cell.apply_rule_style(style);
+            }
+        }
+        cell
+    }
+
+    /// Return the minimum width of the printed columns, up to the last one with a minimum
+    pub(crate) fn min_column_widths(&self) -> Vec<usize> {
+        let added = self.added_columns(true);
+        let mut widths = Vec::new();
+        for (&column, width) in &self.column_widths {
+            if width.min() > 0 {
+                widths.resize(widths.len().max(column + added + 1), 0);
+                widths[column + added] = width.min();
+            }
+        }
+        widths
+    }
+
+    /// Return a copy of `row` as it should be displayed, `previous` being the row above it
+    fn render_row(&self, row: &Row, previous: Option<&Row>) -> Row {
+        let cells = grid_columns(row)
+            .map(|(column, cell)| (column, self.format_number(cell.clone(), column)))
+            .map(|(column, cell)| (column, self.align_cell(cell, column)))
+            .map(|(column, cell)| (column, self.format_cell(cell, column)))
+            .map(|(column, cell)| {
+                if self.merged_columns.contains_key(&column) && is_duplicate(row, previous, column)
+                {
+                    return cell.with_content("");
+                }
+                match self.get_placeholder(column) {
+                    Some(placeholder) if strip_ansi(&cell.get_content()).trim().is_empty() => {
+                        let mut cell = cell.with_content(placeholder);
+                        cell.align(Alignment::CENTER);
+                        cell
+                    }
+                    _ => cell,
+                }
+            })
+            .collect::<Vec<Cell>>();
+        row.with_cells(cells)
+    }
+
+    /// Tell if `titles` and `rows` are printed as they are
+    pub(crate) fn is_identity_for<R: Borrow<Row>>(&self, titles: &Option<Row>, rows: &[R]) -> bool {
+        self.is_identity()
+            && titles
+                .iter()
+                .chain(rows.iter().map(Borrow::borrow))
+                .all(|row| row.get_max_height().is_none())
+    }
+
+    /// Return these options once the settings of each column `c` are moved to column
+    /// `f(c)`, or dropped if it is `None`. `f` must keep the columns in order.
+    pub(crate) fn move_columns<F: Fn(usize) -> Option<usize>>(&self, f: F) -> RenderOptions {
+        fn moved<T: Clone, F: Fn(usize) -> Option<usize>>(
+            map: &BTreeMap<usize, T>,
+            f: &F,
+        ) -> BTreeMap<usize, T> {
+            map.iter()
+                .filter_map(|(&c, v)| f(c).map(|c| (c, v.clone())))
+                .collect()
+        }
+        RenderOptions {
+            column_placeholders: moved(&self.column_placeholders, &f),
+            merged_columns: moved(&self.merged_columns, &f),
+            column_alignments: moved(&self.column_alignments, &f),
+            column_widths: moved(&self.column_widths, &f),
+            column_priorities: moved(&self.column_priorities, &f),
+            number_formats: moved(&self.number_formats, &f),
+            format_rules: self
+                .format_rules
+                .iter()
+                .filter_map(|(c, rule, style)| f(*c).map(|c| (c, rule.clone(), *style)))
+                .collect(),
+            // Columns moved inside the frozen ones are frozen as well
+            frozen_columns: (0..self.frozen_columns)
+                .filter_map(&f)
+                .max()
+                .map_or(0, |c| c + 1),
+            ..self.clone()
+        }
+    }
+
+    /// Replace the settings of the columns from `offset` on by the ones of the columns
+    /// of `other`, moved `offset` columns to the right
+    pub(crate) fn add_columns_of(&mut self, other: &RenderOptions, offset: usize) {
+        *self = self.move_columns(|c| Some(c).filter(|&c| c < offset));
+        let other = other.move_columns(|c| Some(c + offset));
+        self.column_placeholders.extend(other.column_placeholders);
+        self.merged_columns.extend(other.merged_columns);
+        self.column_alignments.extend(other.column_alignments);
+        self.column_widths.extend(other.column_widths);
+        self.column_priorities.extend(other.column_priorities);
+        self.number_formats.extend(other.number_formats);
+        self.format_rules.extend(other.format_rules);
+    }
+
+    /// Return these options for a table made of the columns `columns` of the
+    /// current one, in that order
+    pub(crate) fn select_columns(&self, columns: &[usize]) -> RenderOptions {
+        fn select<T: Clone>(map: &BTreeMap<usize, T>, columns: &[usize]) -> BTreeMap<usize, T> {
+            columns
+                .iter()
+                .enumerate()
+                .filter_map(|(new, old)| map.get(old).map(|v| (new, v.clone())))
+                .collect()
+        }
+        RenderOptions {
+            column_placeholders: select(&self.column_placeholders, columns),
+            merged_columns: select(&self.merged_columns, columns),
+            column_alignments: select(&self.column_alignments, columns),
+            column_widths: select(&self.column_widths, columns),
+            column_priorities: select(&self.column_priorities, columns),
+            number_formats: select(&self.number_formats, columns),
+            format_rules: columns
+                .iter()
+                .enumerate()
+                .flat_map(|(new, &old)| {
+                    self.format_rules
+                        .iter()
+                        .filter(move |r| r.0 == old)
+                        .map(move |(_, rule, style)| (new, rule.clone(), *style))
+                })
+                .collect(),
+            frozen_columns: columns
+                .iter()
+                .take_while(|&&c| c < self.frozen_columns)
+                .count(),
+            ..self.clone()
+        }
+    }
+
+    /// Tell if rows are striped
+    pub(crate) fn is_striped(&self) -> bool {
+        self.striping.is_some()
+    }
+
+    /// Return a copy of `row` with `stripe` applied to the cells without a background
+    /// color, once the style of the row is applied
+    fn stripe_row(row: Row, stripe: &CellStyle) -> Row {
+        let mut cells = row.iter().cloned().collect::<Vec<_>>();
+        for cell in &mut cells {
+            if let Some(style) = row.get_style() {
+                cell.apply_row_style(&style, true);
+            }
+            cell.apply_stripe_style(stripe);
+        }
+        row.with_cells(cells)
+    }
+
+    /// Return copies of `titles` and `rows` as they should be displayed, `positions` being
+    /// the positions of the rows in their table, or nothing when they are numbered in order,
+    /// like returned by `TableSlice::row_positions()`.
+    /// With `text` set to `false`, transformations only meaningful to text output are not
+    /// applied: rows are neither capped to the maximum width of the columns, clipped to their
+    /// maximum height nor striped, and row numbers are only added if they are exported.
+    pub(crate) fn render<R: Borrow<Row>>(
+        &self,
+        titles: &Option<Row>,
+        rows: &[R],
+        positions: &[usize],
+        text: bool,
+    ) -> (Option<Row>, Vec<Row>) {
+        let clip = text;
+        let striping = self.striping.filter(|_| text);
+        let mut striped = 0;
+        let rows = rows
+            .iter()
+            .enumerate()
+            .map(|(i, r)| self.render_row(r.borrow(), i.checked_sub(1).map(|p| rows[p].borrow())))
+            .map(|r| match clip {
+                true => self.clip_row(self.cap_row(r), self.max_row_height),
+                false => r,
+            })
+            .map(|r| match striping {
+                // Group headers are neither striped nor counted
+                Some((even, odd)) if !r.is_group_header() => {
+                    striped += 1;
+                    Self::stripe_row(r, if striped % 2 == 1 { &even } else { &odd })
+                }
+                _ => r,
+            })
+            .collect::<Vec<_>>();
+        let titles = titles.as_ref().map(|t| self.render_fixed(t, text));
+        match self.numbered(text) {
+            Some(numbers) => {
+                let mut printed = 0;
+                let rows = rows
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, r)| {
+                        // Group headers are neither numbered nor counted, and span
+                        // the column of numbers as well
+                        if r.is_group_header() {
+                            let mut cells = r.iter().cloned().collect::<Vec<_>>();
+                            if let Some(cell) = cells.first_mut() {
+                                cell.set_hspan(cell.get_hspan() + 1);
+                            }
+                            return r.with_cells(cells);
+                        }
+                        let position = positions.get(i).copied().unwrap_or(i);
+                        printed += 1;
+                        let number = numbers.number(printed - 1, position);
+                        prepend_cell(r, RowNumbers::number_cell(number))
+                    })
+                    .collect();
+                let titles = titles.map(|t| prepend_cell(t, numbers.header_cell()));
+                (titles, rows)
+            }
+            None => (titles, rows),
+        }
+    }
+
+    /// Return a copy of `row`, a header row or the footer, as it should be displayed.
+    /// See `render()` for the meaning of `text`.
+    pub(crate) fn render_fixed_row(&self, row: &Row, text: bool) -> Row {
+        let row = self.render_fixed(row, text);
+        match self.numbered(text) {
+            Some(_) => prepend_cell(row, Cell::default()),
+            None => row,
+        }
+    }
+
+    /// Return a copy of `row`, the titles, a header row or the footer, as it should be
+    /// displayed, without the columns added before its cells
+    fn render_fixed(&self, row: &Row, text: bool) -> Row {
+        let cells = grid_columns(row)
+            .map(|(column, cell)| self.align_cell(cell.clone(), column))
+            .collect::<Vec<Cell>>();
+        let row = row.with_cells(cells);
+        // Header rows and footers are only clipped to their own maximum height
+        match text {
+            true => self.clip_row(self.cap_row(row), None),
+            false => row,
+        }
+    }
+
+    /// Return the printed columns where the separator above `rows[index]` is hidden
+    pub(crate) fn hidden_separators<R: Borrow<Row>>(&self, rows: &[R], index: usize) -> Vec<usize> {
+        let added = self.added_columns(true);
+        self.merged_columns
+            .iter()
+            .filter(|&(&column, &hidden)| {
+                hidden
+                    && index > 0
+                    && is_duplicate(rows[index].borrow(), Some(rows[index - 1].borrow()), column)
+            })
+            .map(|(&column, _)| column + added)
+            .collect()
+    }
+}
+
+/// Tell if some cell of `row` holds a tab
+pub(crate) fn has_tabs(row: &Row) -> bool {
+    row.iter().any(|cell| cell.get_content().contains('\t'))
+}
+
+/// Return a copy of `row` with the tabs of its cells expanded to `tab_width` columns
+pub(crate) fn expand_row_tabs(row: &Row, tab_width: usize) -> Row {
+    let cells = row
+        .iter()
+        .map(|cell| {
+            let content = cell.get_content();
+            if content.contains('\t') {
+                cell.with_content(&expand_tabs(&content, tab_width))
+            } else {
+                cell.clone()
+            }
+        })
+        .collect::<Vec<Cell>>();
+    row.with_cells(cells)
+}
+
+/// Tell if some cell of `row` is changed by `prepare_row()` with `format`
+pub(crate) fn needs_preparing(row: &Row, format: &TableFormat) -> bool {
+    let mode = format.get_width_mode();
+    has_tabs(row) || row.iter().any(|cell| cell.depends_on_width_mode(mode))
+}
+
+/// Return a copy of `row` as printed with `format`, with the tabs of its cells expanded,
+/// and the cells with a maximum width wrapped and truncated with text widths measured
+/// with the width mode of `format`
+pub(crate) fn prepare_row(row: &Row, format: &TableFormat) -> Row {
+    let row = expand_row_tabs(row, format.get_tab_width());
+    let mode = format.get_width_mode();
+    let cells = row
+        .iter()
+        .map(|cell| cell.measured_with(mode).unwrap_or_else(|| cell.clone()))
+        .collect();
+    row.with_cells(cells)
+}
+
+/// Return the cells of `row` along with the column each one starts at, counting the
+/// columns spanned by the cells before it
+fn grid_columns(row: &Row) -> impl Iterator<Item = (usize, &Cell)> {
+    row.iter().scan(0, |column, cell| {
+        let start = *column;
+        *column += cell.get_hspan();
+        Some((start, cell))
+    })
+}
+
+/// Return the cell of `row` starting at column `column`, if any
+fn cell_at(row: &Row, column: usize) -> Option<&Cell> {
+    grid_columns(row)
+        .find(|&(start, _)| start == column)
+        .map(|(_, cell)| cell)
+}
+
+/// Tell if the cell starting at `column` in `row` has the same plain content as the one
+/// of `previous`
+fn is_duplicate(row: &Row, previous: Option<&Row>, column: usize) -> bool {
+    match (
+        cell_at(row, column),
+        previous.and_then(|p| cell_at(p, column)),
+    ) {
+        (Some(cell), Some(above)) => {
+            strip_ansi(&cell.get_content()) == strip_ansi(&above.get_content())
+        }
+        _ => false,
+    }
+}
+
+impl<'a> TableSlice<'a> {
+    /// Return a new table holding the cells of this slice as they are displayed,
+    /// with placeholders in empty cells, merged duplicates left blank and column
+    /// alignments applied.
+    ///
+    /// This is useful to export the table the same way as it is printed,
+    /// since exports like CSV use the cells unchanged.
+    pub fn rendered(&self) -> Table {
+        self.rendered_with(true)
+    }
+
+    /// Like `rendered()`, with the transformations only meaningful to
+    /// text output applied only if `text` is `true`
+    pub(crate) fn rendered_with(&self, text: bool) -> Table {
+        let positions = self.row_positions();
+        let (titles, rows) = self.render.render(self.titles, self.rows, &positions, text);
+        let mut table = Table::init(rows);
+        table.set_format(*self.format);
+        // Minimum and maximum widths are only applied when printing. The added columns
+        // are frozen.
+        if text {
+            let added = self.render.added_columns(text);
+            table.render.column_widths = self
+                .render
+                .column_widths
+                .iter()
+                .map(|(&column, &width)| (column + added, width))
+                .collect();
+            table.render.frozen_columns = self.render.printed_frozen_columns();
+            table.render.max_width = self.render.max_width;
+            table.render.column_priorities = self
+                .render
+                .column_priorities
+                .iter()
+                .map(|(&column, &priority)| (column + added, priority))
+                .collect();
+        }
+        if let Some(titles) = titles {
+            table.set_titles(titles);
+        }
+        table.caption = self.caption.clone();
+        table.header_rows = self
+            .header_rows
+            .iter()
+            .map(|r| self.render.render_fixed_row(r, text))
+            .collect();
+        if let Some(footer) = self.footer {
+            table.set_footer(self.render.render_fixed_row(footer, text));
+        }
+        table
+    }
+
+    /// Return a new table holding the cells of this slice as written by markup exports,
+    /// rendered without the transformations only meaningful to text output, and with
+    /// the footer moved after the last row
+    pub(crate) fn exported(&self) -> Table {
+        let mut table = self.rendered_with(false);
+        if let Some(footer) = table.footer.take() {
+            table.rows.push(footer);
+        }
+        table
+    }
+}
+
+impl Table {
+    /// Display `placeholder` instead of empty or whitespace-only cells when printing,
+    /// centered in the column.
+    ///
+    /// The cells keep their content, so that exports like CSV or JSON are unchanged.
+    /// Use `rendered()` to get a copy of the table with the placeholders. Titles and
+    /// missing cells at the end of short rows are not affected.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut table = table![["a", ""], ["", "d"]];
+    /// table.set_empty_cell_placeholder("-");
+    /// assert_eq!(table.to_string().replace("\r\n", "\n"), "\
+    /// +---+---+
+    /// | a | - |
+    /// +---+---+
+    /// | - | d |
+    /// +---+---+
+    /// ");
+    /// # }
+    /// ```
+    pub fn set_empty_cell_placeholder(&mut self, placeholder: &str) {
+        self.render.placeholder = Some(placeholder.to_string());
+    }
+
+    /// Display `placeholder` instead of empty or whitespace-only cells of column `column`
+    /// when printing, overriding the placeholder of the table.
+    ///
+    /// See `set_empty_cell_placeholder()` for details.
+    pub fn set_column_placeholder(&mut self, column: usize, placeholder: &str) {
+        self.render
+            .column_placeholders
+            .insert(column, placeholder.to_string());
+    }
+
+    /// Display only the first of consecutive cells of column `column` with the same
+    /// content, leaving the following ones blank. The separators between them are kept.
+    ///
+    /// Contents are compared without their ANSI escape sequences. The cells are not
+    /// modified, and titles are not affected.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut table = table![["a", "1"], ["a", "2"], ["b", "3"]];
+    /// table.merge_column_duplicates(0);
+    /// assert_eq!(table.to_string().replace("\r\n", "\n"), "\
+    /// +---+---+
+    /// | a | 1 |
+    /// +---+---+
+    /// |   | 2 |
+    /// +---+---+
+    /// | b | 3 |
+    /// +---+---+
+    /// ");
+    /// # }
+    /// ```
+    pub fn merge_column_duplicates(&mut self, column: usize) {
+        self.render.merged_columns.insert(column, false);
+    }
+
+    /// Like `merge_column_duplicates()`, and also leave blank the separators between
+    /// the merged cells of column `column` when `hide_separators` is `true`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut table = table![["a", "1"], ["a", "2"], ["b", "3"]];
+    /// table.merge_column_duplicates_with(0, true);
+    /// assert_eq!(table.to_string().replace("\r\n", "\n"), "\
+    /// +---+---+
+    /// | a | 1 |
+    /// +   +---+
+    /// |   | 2 |
+    /// +---+---+
+    /// | b | 3 |
+    /// +---+---+
+    /// ");
+    /// # }
+    /// ```
+    pub fn merge_column_duplicates_with(&mut self, column: usize, hide_separators: bool) {
+        self.render.merged_columns.insert(column, hide_separators);
+    }
+
+    /// Align the cells of column `column` to `align` when printing, unless their
+    /// alignment has been set explicitly. Titles are also affected.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// use prettytable::format::Alignment;
+    /// # fn main() {
+    /// let mut table = table![["Item", "Qty"], ["Bolts", 150], ["Nuts", c->7]];
+    /// table.set_column_alignment(1, Alignment::RIGHT);
+    /// assert_eq!(table.to_string().replace("\r\n", "\n"), "\
+    /// +-------+-----+
+    /// | Item  | Qty |
+    /// +-------+-----+
+    /// | Bolts | 150 |
+    /// +-------+-----+
+    /// | Nuts  |  7  |
+    /// +-------+-----+
+    /// ");
+    /// # }
+    /// ```
+    pub fn set_column_alignment(&mut self, column: usize, align: Alignment) {
+        self.render.column_alignments.insert(column, align);
+    }
+
+    /// Print at most `lines` lines of each row, for rows without their own limit set with
+    /// `Row::set_max_height()`. Titles are not affected. A limit of 0 is handled as 1.
+    ///
+    /// The last line printed of each clipped cell ends with a marker telling how many
+    /// lines are hidden, `" … (+N lines)"` unless changed with `set_row_height_marker()`.
+    /// HTML output is not affected.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut table = table![["1\n2\n3\n4", "a"]];
+    /// table.set_max_row_height(2);
+    /// assert_eq!(table.to_string().replace("\r\n", "\n"), "\
+    /// +----------------+---+
+    /// | 1              | a |
+    /// | 2 … (+2 lines) |   |
+    /// +----------------+---+
+    /// ");
+    /// # }
+    /// ```
+    pub fn set_max_row_height(&mut self, lines: usize) {
+        self.render.max_row_height = Some(lines.max(1));
+    }
+
+    /// Stripe the rows when printing, applying the style specifiers `style_even` and
+    /// `style_odd` alternately, as with `Cell::style_spec()`. The first printed row is even,
+    /// also when printing a slice or a filtered view of the table.
+    ///
+    /// Cells with a background color of their own are not striped, and the styles of the
+    /// other cells and rows take precedence over the stripes. Titles and group headers are
+    /// not striped. In HTML, rows get the `even` and `odd` classes instead.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut table = table![["a"], ["b"], ["c"]];
+    /// table.set_row_striping("Bw", "Bc");
+    /// table.printstd();
+    /// # }
+    /// ```
+    pub fn set_row_striping(&mut self, style_even: &str, style_odd: &str) {
+        self.render.striping = Some((
+            CellStyle::from_spec(style_even),
+            CellStyle::from_spec(style_odd),
+        ));
+    }
+
+    /// Constrain the width of column `column`, titles included, when printing.
+    ///
+    /// Cells wider than the maximum width are wrapped, or truncated if they are set to
+    /// with `Cell::set_truncate()`. Narrower columns are padded up to the minimum width,
+    /// which keeps the layout steady when the table is printed again with new content.
+    /// Cells spanning several columns may still widen them. HTML output is not affected.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// use prettytable::format::ColumnWidth;
+    /// # fn main() {
+    /// let mut table = table![["a", "Some text"]];
+    /// table.set_column_width(0, ColumnWidth::Min(3));
+    /// table.set_column_width(1, ColumnWidth::Max(4));
+    /// assert_eq!(table.to_string().replace("\r\n", "\n"), "\
+    /// +-----+------+
+    /// | a   | Some |
+    /// |     | text |
+    /// +-----+------+
+    /// ");
+    /// # }
+    /// ```
+    pub fn set_column_width(&mut self, column: usize, width: ColumnWidth) {
+        self.render.set_column_width(column, width);
+    }
+
+    /// Set the marker ending the cells clipped by a maximum row height.
+    /// `{}` in `marker` is replaced by the count of hidden lines.
+    pub fn set_row_height_marker(&mut self, marker: &str) {
+        self.render.height_marker = Some(marker.to_string());
+    }
+
+    /// Return a new table holding the cells as they are displayed.
+    ///
+    /// See `TableSlice::rendered()` for details.
+    pub fn rendered(&self) -> Table {
+        self.as_slice().rendered()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::{Alignment, ColumnWidth};
+    use crate::{Cell, Row, Table};
+
+    #[test]
+    fn placeholders() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new(""), Cell::new("Number")]));
+        table.add_row(Row::new(vec![
+            Cell::new("a"),
+            Cell::new_align(" ", Alignment::RIGHT),
+        ]));
+        table.add_row(Row::new(vec![Cell::new(""), Cell::new("1")]));
+        table.add_row(Row::new(vec![Cell::new("\x1b[31m\x1b[0m")]));
+        table.set_empty_cell_placeholder("-");
+        table.set_column_placeholder(1, "N/A");
+        let out = "\
++---+--------+
+|   | Number |
++===+========+
+| a |  N/A   |
++---+--------+
+| - | 1      |
++---+--------+
+| - |        |
++---+--------+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(table[1][0].get_content(), "");
+
+        let rendered = table.rendered();
+        assert_eq!(rendered[0][1].get_content(), "N/A");
+        assert_eq!(rendered[0][1].get_align(), Alignment::CENTER);
+        assert_eq!(rendered.to_string(), table.to_string());
+    }
+
+    #[test]
+    fn merged_duplicates() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("Group"), Cell::new("Group")]));
+        table.add_row(Row::new(vec![Cell::new("Group"), Cell::new("x")]));
+        table.add_row(Row::new(vec![
+            Cell::new("\x1b[1mGroup\x1b[0m"),
+            Cell::new("x"),
+        ]));
+        table.add_row(Row::new(vec![Cell::new("Group"), Cell::new("y")]));
+        table.add_row(Row::new(vec![Cell::new("Other"), Cell::new("x")]));
+        table.add_row(Row::new(vec![Cell::new("Group")]));
+        table.merge_column_duplicates_with(0, true);
+        table.merge_column_duplicates(1);
+        let out = "\
++-------+-------+
+| Group | Group |
++=======+=======+
+| Group | x     |
++       +-------+
+|       |       |
++       +-------+
+|       | y     |
++-------+-------+
+| Other | x     |
++-------+-------+
+| Group |       |
++-------+-------+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(table[1][0].get_content(), "\x1b[1mGroup\x1b[0m");
+        assert_eq!(table.rendered()[2][0].get_content(), "");
+    }
+
+    #[test]
+    fn column_alignment() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("Name"), Cell::new("Qty")]));
+        table.add_row(Row::new(vec![Cell::new("apples"), Cell::new("3")]));
+        table.add_row(Row::new(vec![
+            Cell::new("pears"),
+            Cell::new_align("12", Alignment::LEFT),
+        ]));
+        table.add_row(Row::new(vec![Cell::new("kiwis"), Cell::new("")]));
+        table.set_column_alignment(1, Alignment::RIGHT);
+        table.set_column_alignment(0, Alignment::CENTER);
+        table.set_column_placeholder(1, "-");
+        let out = "\
++--------+-----+
+|  Name  | Qty |
++========+=====+
+| apples |   3 |
++--------+-----+
+| pears  | 12  |
++--------+-----+
+| kiwis  |  -  |
++--------+-----+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(table[0][1].get_align(), Alignment::LEFT);
+        assert_eq!(table[0][1].get_explicit_align(), None);
+
+        let mut html = Vec::new();
+        table.print_html(&mut html).unwrap();
+        let html = String::from_utf8(html).unwrap();
+        assert!(html.contains(r#"<td style="text-align: right;">3</td>"#));
+        assert!(html.contains(r#"<td style="text-align: left;">12</td>"#));
+    }
+
+    #[test]
+    fn spanning_cells() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![
+            Cell::new("wide").with_hspan(2),
+            Cell::new(""),
+        ]));
+        table.add_row(Row::new(vec![
+            Cell::new("x"),
+            Cell::new(""),
+            Cell::new("z"),
+        ]));
+        table.add_row(Row::new(vec![
+            Cell::new("x"),
+            Cell::new("y"),
+            Cell::new("z"),
+        ]));
+        table.add_row(Row::new(vec![Cell::new("q").with_hspan(2), Cell::new("z")]));
+        table.set_column_alignment(1, Alignment::RIGHT);
+        table.set_column_placeholder(1, "N/A");
+        table.merge_column_duplicates(2);
+        let rendered = table.rendered();
+        assert_eq!(rendered[0][1].get_content(), "");
+        assert_eq!(rendered[0][1].get_align(), Alignment::LEFT);
+        assert_eq!(rendered[1][1].get_content(), "N/A");
+        assert_eq!(rendered[2][1].get_align(), Alignment::RIGHT);
+        assert_eq!(rendered[2][2].get_content(), "");
+        assert_eq!(rendered[3][1].get_content(), "");
+    }
+
+    #[test]
+    fn max_row_height() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("t\nt"), Cell::new("u")]));
+        table.add_row(Row::new(vec![
+            Cell::new("a\nb\nc").style_spec("r"),
+            Cell::new("one two three four").with_max_width(3),
+        ]));
+        table.add_row(Row::new(vec![Cell::new("dddddd\ne"), Cell::new("f")]));
+        table.set_max_row_height(1);
+        table.set_row_height_marker("+{}");
+        table[1].set_max_height(2);
+        let out = "\
++--------+-------+
+| t      | u     |
+| t      |       |
++========+=======+
+|    a+2 | one+5 |
++--------+-------+
+| dddddd | f     |
+| e      |       |
++--------+-------+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        let mut html = Vec::new();
+        table.print_html(&mut html).unwrap();
+        assert!(String::from_utf8(html).unwrap().contains("c</td>"));
+    }
+
+    #[test]
+    fn row_striping() {
+        use crate::{color, Attr, Slice};
+
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("t")]));
+        table.add_row(Row::new(vec![
+            Cell::new("a"),
+            Cell::new("b").style_spec("By"),
+        ]));
+        table.add_row(Row::new(vec![Cell::new("c").style_spec("Fr")]));
+        table.add_row(Row::new(vec![Cell::new("d")]));
+        table[2].set_style_spec("b");
+        table.set_row_striping("Bw", "Bc");
+        let even = Attr::BackgroundColor(color::WHITE);
+        let odd = Attr::BackgroundColor(color::CYAN);
+
+        let rendered = table.rendered();
+        assert_eq!(rendered[0][0].get_style(), &[even]);
+        assert_eq!(
+            rendered[0][1].get_style(),
+            &[Attr::BackgroundColor(color::YELLOW)]
+        );
+        assert_eq!(
+            rendered[1][0].get_style(),
+            &[odd, Attr::ForegroundColor(color::RED)]
+        );
+        assert_eq!(rendered[2][0].get_style(), &[even, Attr::Bold]);
+        assert!(rendered.titles.as_ref().as_ref().unwrap()[0]
+            .get_style()
+            .is_empty());
+        assert!(table[0][0].get_style().is_empty());
+        // Slices start with an even row
+        assert_eq!(table.slice(1..).rendered()[0][0].get_style()[0], even);
+
+        let mut html = Vec::new();
+        table.print_html(&mut html).unwrap();
+        let html = String::from_utf8(html).unwrap();
+        assert!(html.contains(r#"<tr class="even"><td style="text-align: left;">a</td>"#));
+        assert!(html.contains(r#"<tr class="odd"><td style="color: #aa0000;"#));
+        assert!(!html.contains("#00aaaa"));
+    }
+
+    #[test]
+    fn column_width() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![
+            Cell::new("Name"),
+            Cell::new("Description"),
+            Cell::new("n"),
+            Cell::new("Comment"),
+        ]));
+        table.add_row(Row::new(vec![
+            Cell::new("a"),
+            Cell::new("long text here"),
+            Cell::new("1"),
+            Cell::new("truncated comment").with_truncate(20, "~"),
+        ]));
+        table.add_row(Row::new(vec![Cell::new("spanning both").with_hspan(2)]));
+        table.set_column_width(0, ColumnWidth::Fixed(6));
+        table.set_column_width(1, ColumnWidth::Range(3, 9));
+        table.set_column_width(2, ColumnWidth::Min(3));
+        table.set_column_width(3, ColumnWidth::Max(8));
+        let out = "\
++--------+-----------+-----+----------+
+| Name   | Descripti | n   | Comment  |
+|        | on        |     |          |
++========+===========+=====+==========+
+| a      | long text | 1   | truncat~ |
+|        | here      |     |          |
++--------+-----------+-----+----------+
+| spanning both      |     |          |
++--------+-----------+-----+----------+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(table.rendered().to_string(), table.to_string());
+
+        let mut html = Vec::new();
+        table.print_html(&mut html).unwrap();
+        assert!(String::from_utf8(html).unwrap().contains("long text here"));
+    }
+}
@@ -1,31 +1,176 @@
 //! This module contains definition of table rows stuff
+use std::fmt::Display;
 use std::io::{Error, Write};
 use std::iter::FromIterator;
 use std::slice::{Iter, IterMut};
 // use std::vec::IntoIter;
 use std::ops::{Index, IndexMut};
 
-use super::Terminal;
+use super::{HtmlOptions, Terminal};
 
-use super::format::{ColumnPosition, TableFormat};
+use super::format::{ColumnPosition, TableFormat, WidthMode};
 use super::utils::NEWLINE;
-use super::Cell;
+use super::{Cell, CellStyle};
 
 /// Represent a table row made of cells
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct Row {
     cells: Vec<Cell>,
+    style: Option<CellStyle>,
+    separator_below: bool,
+    group_header: bool,
+    max_height: Option<usize>,
 }
 
 impl Row {
     /// Create a new `Row` backed with `cells` vector
     pub fn new(cells: Vec<Cell>) -> Row {
-        Row { cells }
+        Row {
+            cells,
+            style: None,
+            separator_below: false,
+            group_header: false,
+            max_height: None,
+        }
+    }
+
+    /// Return a copy of this row holding `cells`, with the same style
+    pub(crate) fn with_cells(&self, cells: Vec<Cell>) -> Row {
+        Row {
+            cells,
+            style: self.style,
+            separator_below: self.separator_below,
+            group_header: self.group_header,
+            max_height: self.max_height,
+        }
+    }
+
+    /// Set the style applied when printing to the cells without a style of their own.
+    /// The alignment of the style applies to the cells without an explicit alignment.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # use prettytable::{CellStyle, Color, Row};
+    /// # fn main() {
+    /// let mut total = row!["Total", Fg->42];
+    /// total.set_style(CellStyle::new().fg(Color::Red).bold());
+    /// # }
+    /// ```
+    pub fn set_style(&mut self, style: CellStyle) {
+        self.style = Some(style);
+    }
+
+    /// Set the style of the row from the specifier `spec`, with the syntax of
+    /// `Cell::style_spec()`. See `set_style()` for details.
+    pub fn set_style_spec(&mut self, spec: &str) {
+        self.set_style(CellStyle::from_spec(spec));
+    }
+
+    /// Get the style of the row, if any
+    pub fn get_style(&self) -> Option<CellStyle> {
+        self.style
+    }
+
+    /// Print a separator line below this row, even if the table format has
+    /// no separator between rows. If it has one, the flag has no effect.
+    /// Otherwise the title separator of the format is printed, or its bottom or top
+    /// border if it has none, and nothing if it has no line at all, like `FORMAT_CLEAN`.
+    ///
+    /// The flag belongs to the row, so it moves along with it when rows are
+    /// inserted, removed or sorted. It is ignored on the last row, which is
+    /// followed by the bottom border.
+    pub fn set_separator_below(&mut self, separator: bool) {
+        self.separator_below = separator;
+    }
+
+    /// Check if a separator line is printed below this row
+    pub fn has_separator_below(&self) -> bool {
+        self.separator_below
+    }
+
+    /// Print at most `lines` lines of this row, overriding the limit set with
+    /// `Table::set_max_row_height()`. The last line printed of each clipped cell ends
+    /// with a marker telling how many lines are hidden. A limit of 0 is handled as 1.
+    ///
+    /// HTML output is not affected.
+    pub fn set_max_height(&mut self, lines: usize) {
+        self.max_height = Some(lines.max(1));
+    }
+
+    /// Get the maximum number of lines printed for this row, if set on the row
+    pub fn get_max_height(&self) -> Option<usize> {
+        self.max_height
+    }
+
+    /// Return a copy of the row with its style applied to its cells,
+    /// or `None` if the row has no style
+    pub(crate) fn styled(&self, attrs: bool) -> Option<Row> {
+        let style = self.style.as_ref()?;
+        let mut row = Row::new(self.cells.clone());
+        for cell in &mut row.cells {
+            cell.apply_row_style(style, attrs);
+        }
+        Some(row)
+    }
+
+    /// Create a new `Row` with a cell for each value of `values`, as displayed by `Display`
+    ///
+    /// # Example
+    /// ```rust
+    /// # use prettytable::Row;
+    /// let row = Row::from_display_iter(vec![1.5, 2.0]);
+    /// assert_eq!(row.get_cell(1).unwrap().get_content(), "2");
+    /// ```
+    pub fn from_display_iter<I, T>(values: I) -> Row
+    where
+        I: IntoIterator<Item = T>,
+        T: Display,
+    {
+        Self::new(values.into_iter().map(Cell::from).collect())
+    }
+
+    /// Create a new `Row` from the fields of `line` separated by `delimiter`, each one trimmed.
+    ///
+    /// Empty fields, including trailing ones, produce empty cells. When `delimiter`
+    /// is a comma and the `csv` feature is enabled, fields are read like a CSV record,
+    /// so that double-quoted fields may contain commas.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use prettytable::Row;
+    /// let row = Row::from_delimited("a | b |", '|');
+    /// assert_eq!(row.len(), 3);
+    /// assert_eq!(row.get_cell(1).unwrap().get_content(), "b");
+    /// ```
+    pub fn from_delimited(line: &str, delimiter: char) -> Row {
+        #[cfg(feature = "csv")]
+        {
+            if delimiter == ',' {
+                if let Some(fields) = super::csv::split_record(line) {
+                    return Self::new(fields.iter().map(|f| Cell::new(f.trim())).collect());
+                }
+            }
+        }
+        Self::new(line.split(delimiter).map(|f| Cell::new(f.trim())).collect())
+    }
+
+    /// Create a group header row, holding a single cell with the text `header`.
+    /// See `Table::group_by()` for details.
+    pub fn group_header(header: &str) -> Row {
+        let mut row = Row::new(vec![Cell::new(header)]);
+        row.group_header = true;
+        row
+    }
+
+    /// Check if this row is the header of a group of rows
+    pub fn is_group_header(&self) -> bool {
+        self.group_header
     }
 
     /// Create an row of length `size`, with empty strings stored
     pub fn empty() -> Row {
-        Self::new(vec![Cell::default(); 0])
+        Self::new(Vec::new())
     }
 
     /// Count the number of column required in the table grid.
@@ -60,33 +205,28 @@ impl Row {
         height
     }
 
-    /// Get the minimum width required by the cell in the column `column`.
-    /// Return 0 if the cell does not exist in this row
-    // #[deprecated(since="0.8.0", note="Will become private in future release. See [issue #87](https://github.com/phsym/prettytable-rs/issues/87)")]
-    pub(crate) fn get_column_width(&self, column: usize, format: &TableFormat) -> usize {
-        let mut i = 0;
+    /// Get the grid position and the minimum width required by each cell of the row,
+    /// as `(column, hspan, width)`. For cells spanning several columns, `width` is the
+    /// width to share between the spanned columns, once the padding and separators
+    /// between them are taken into account
+    pub(crate) fn get_cell_widths(&self, format: &TableFormat) -> Vec<(usize, usize, usize)> {
+        let (lp, rp) = format.get_padding();
+        let sep = format
+            .get_column_separator(ColumnPosition::Intern)
+            .map(|_| 1)
+            .unwrap_or_default();
+        let mut column = 0;
+        let mut widths = Vec::with_capacity(self.cells.len());
         for c in &self.cells {
-            if i + c.get_hspan() > column {
-                if c.get_hspan() == 1 {
-                    return c.get_width();
-                }
-                let (lp, rp) = format.get_padding();
-                let sep = format
-                    .get_column_separator(ColumnPosition::Intern)
-                    .map(|_| 1)
-                    .unwrap_or_default();
-                let rem = lp + rp + sep;
-                let mut w = c.get_width();
-                if w > rem {
-                    w -= rem;
-                } else {
-                    w = 0;
-                }
-                return (w as f64 / c.get_hspan() as f64).ceil() as usize;
-            }
-            i += c.get_hspan();
+            // A cell overriding the padding needs more or less room for its content
+            let (clp, crp) = c.get_padding().unwrap_or((lp, rp));
+            let width =
+                (c.get_width_with(format.get_width_mode()) + clp + crp).saturating_sub(lp + rp);
+            let inner = (c.get_hspan() - 1) * (lp + rp + sep);
+            widths.push((column, c.get_hspan(), width.saturating_sub(inner)));
+            column += c.get_hspan();
         }
-        0
+        widths
     }
 
     /// Get the cell at index `idx`
@@ -123,23 +263,62 @@ impl Row {
         }
     }
 
-    /// Remove the cell at position `index`. Silently skip if this cell does not exist
-    pub fn remove_cell(&mut self, index: usize) {
+    /// Remove the cell at position `index` and return it,
+    /// or `None` if this cell does not exist
+    pub fn remove_cell(&mut self, index: usize) -> Option<Cell> {
         if index < self.cells.len() {
-            self.cells.remove(index);
+            Some(self.cells.remove(index))
+        } else {
+            None
+        }
+    }
+
+    /// Append `cells` at the end of the row, keeping their style.
+    ///
+    /// Unlike `extend()`, which turns each item into a new cell from its text,
+    /// the cells are added as is.
+    /// `Extend<Cell>` cannot be implemented next to `Extend<S: ToString>`, since `Cell`
+    /// implements `Display`.
+    pub fn extend_cells<I: IntoIterator<Item = Cell>>(&mut self, cells: I) {
+        self.cells.extend(cells);
+    }
+
+    /// Create a new `Row` holding `cells`, keeping their style.
+    /// See `extend_cells()` for why this is not a `FromIterator` implementation.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use prettytable::{Cell, Row};
+    /// let row = Row::from_cells((1..4).map(|i| Cell::new(&i.to_string()).style_spec("b")));
+    /// assert_eq!(row, ["1", "2", "3"]);
+    /// ```
+    pub fn from_cells<I: IntoIterator<Item = Cell>>(cells: I) -> Row {
+        Self::new(cells.into_iter().collect())
+    }
+
+    /// Set the left and right padding of all the cells of the row,
+    /// overriding the padding of the table format
+    pub fn set_padding(&mut self, left: usize, right: usize) {
+        for cell in &mut self.cells {
+            cell.set_padding(left, right);
         }
     }
 
     /// Returns an immutable iterator over cells
-    pub fn iter(&self) -> Iter<Cell> {
+    pub fn iter(&self) -> Iter<'_, Cell> {
         self.cells.iter()
     }
 
     /// Returns an mutable iterator over cells
-    pub fn iter_mut(&mut self) -> IterMut<Cell> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, Cell> {
         self.cells.iter_mut()
     }
 
+    /// Returns an mutable iterator over cells. Same as `iter_mut()`
+    pub fn cells_mut(&mut self) -> IterMut<'_, Cell> {
+        self.iter_mut()
+    }
+
     /// Internal only
     fn __print<T: Write + ?Sized, F>(
         &self,
@@ -149,8 +328,11 @@ impl Row {
         f: F,
     ) -> Result<usize, Error>
     where
-        F: Fn(&Cell, &mut T, usize, usize, bool) -> Result<(), Error>,
+        F: Fn(&Cell, &mut T, usize, usize, (usize, usize), WidthMode, bool) -> Result<(), Error>,
     {
+        if let Some(row) = self.styled(true) {
+            return row.__print(out, format, col_width, f);
+        }
         let height = self.get_height();
         for i in 0..height {
             //TODO: Wrap this into dedicated function one day
@@ -160,15 +342,19 @@ impl Row {
             let mut j = 0;
             let mut hspan = 0; // The additional offset caused by cell's horizontal spanning
             while j + hspan < col_width.len() {
-                out.write_all(&vec![b' '; lp])?; // Left padding
-                                                 // skip_r_fill skip filling the end of the last cell if there's no character
-                                                 // delimiting the end of the table
+                let (clp, crp) = self
+                    .get_cell(j)
+                    .and_then(Cell::get_padding)
+                    .unwrap_or((lp, rp));
+                // skip_r_fill skip filling the end of the last cell if there's no character
+                // delimiting the end of the table
                 let skip_r_fill = (j == col_width.len() - 1)
                     && format.get_column_separator(ColumnPosition::Right).is_none();
                 match self.get_cell(j) {
                     Some(c) => {
                         // In case of horizontal spanning, width is the sum of all spanned columns' width
-                        let mut w = col_width[j + hspan..j + hspan + c.get_hspan()].iter().sum();
+                        let mut w: usize =
+                            col_width[j + hspan..j + hspan + c.get_hspan()].iter().sum();
                         let real_span = c.get_hspan() - 1;
                         w += real_span * (lp + rp)
                             + real_span
@@ -176,13 +362,28 @@ impl Row {
                                     .get_column_separator(ColumnPosition::Intern)
                                     .map(|_| 1)
                                     .unwrap_or_default();
-                        // Print cell content
-                        f(c, out, i, w, skip_r_fill)?;
+                        // Print cell content, in the room left by its own padding
+                        f(
+                            c,
+                            out,
+                            c.get_line_index(i, height),
+                            (w + lp + rp).saturating_sub(clp + crp),
+                            (clp, crp),
+                            format.get_width_mode(),
+                            skip_r_fill,
+                        )?;
                         hspan += real_span; // Add span to offset
                     }
-                    None => f(&Cell::default(), out, i, col_width[j + hspan], skip_r_fill)?,
+                    None => f(
+                        &Cell::default(),
+                        out,
+                        i,
+                        col_width[j + hspan],
+                        (lp, rp),
+                        format.get_width_mode(),
+                        skip_r_fill,
+                    )?,
                 };
-                out.write_all(&vec![b' '; rp])?; // Right padding
                 if j + hspan < col_width.len() - 1 {
                     format.print_column_separator(out, ColumnPosition::Intern)?;
                 }
@@ -203,7 +404,7 @@ impl Row {
         format: &TableFormat,
         col_width: &[usize],
     ) -> Result<usize, Error> {
-        self.__print(out, format, col_width, Cell::print)
+        self.__print(out, format, col_width, Cell::print_padded)
     }
 
     /// Print the row to terminal `out`, with `separator` as column separator, and `col_width`
@@ -222,13 +423,27 @@ impl Row {
     ///
     /// If the row is has fewer columns than `col_num`, the row is padded with empty cells.
     pub fn print_html<T: Write + ?Sized>(&self, out: &mut T, col_num: usize) -> Result<(), Error> {
+        self.print_html_with_options(out, col_num, &HtmlOptions::default())
+    }
+
+    /// Print the row in HTML format to `out`, following `options`.
+    pub(crate) fn print_html_with_options<T: Write + ?Sized>(
+        &self,
+        out: &mut T,
+        col_num: usize,
+        options: &HtmlOptions,
+    ) -> Result<(), Error> {
+        // Colors and attributes are printed on the row element
+        if let Some(row) = self.styled(false) {
+            return row.print_html_with_options(out, col_num, options);
+        }
         let mut printed_columns = 0;
         for cell in self.iter() {
-            printed_columns += cell.print_html(out)?;
+            printed_columns += cell.print_html_with_options(out, options)?;
         }
         // Pad with empty cells, if target width is not reached
-        for _ in 0..col_num - printed_columns {
-            Cell::default().print_html(out)?;
+        for _ in printed_columns..col_num {
+            Cell::default().print_html_with_options(out, options)?;
         }
         Ok(())
     }
@@ -258,7 +473,12 @@ impl<A: ToString> FromIterator<A> for Row {
     where
         T: IntoIterator<Item = A>,
     {
-        Self::new(iterator.into_iter().map(|ref e| Cell::from(e)).collect())
+        Self::new(
+            iterator
+                .into_iter()
+                .map(|e| Cell::new(&e.to_string()))
+                .collect(),
+        )
     }
 }
 
@@ -272,6 +492,30 @@ where
     }
 }
 
+/// Compare the contents of the cells of the row, ignoring their style
+impl<S: AsRef<str>> PartialEq<[S]> for Row {
+    fn eq(&self, other: &[S]) -> bool {
+        self.cells.len() == other.len()
+            && self
+                .cells
+                .iter()
+                .zip(other)
+                .all(|(cell, s)| cell.get_content() == s.as_ref())
+    }
+}
+
+impl<S: AsRef<str>, const N: usize> PartialEq<[S; N]> for Row {
+    fn eq(&self, other: &[S; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<S: AsRef<str>> PartialEq<Vec<S>> for Row {
+    fn eq(&self, other: &Vec<S>) -> bool {
+        *self == other[..]
+    }
+}
+
 impl<'a> IntoIterator for &'a Row {
     type Item = &'a Cell;
     type IntoIter = Iter<'a, Cell>;
@@ -348,7 +592,8 @@ macro_rules! row {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use Cell;
+    use crate::format::Alignment;
+    use crate::{color, Attr};
 
     #[test]
     fn row_default_empty() {
@@ -365,7 +610,7 @@ mod tests {
         let c1 = row.get_mut_cell(0).unwrap().clone();
         assert_eq!(c1.get_content(), "foo");
 
-        let c1 = Cell::from(&"baz");
+        let c1: Cell = (&"baz").into();
         assert!(row.set_cell(c1.clone(), 1000).is_err());
         assert!(row.set_cell(c1.clone(), 0).is_ok());
         assert_eq!(row.get_cell(0).unwrap().get_content(), "baz");
@@ -400,6 +645,95 @@ mod tests {
         assert_eq!(row.get_cell(1).unwrap().get_content(), "foobar");
     }
 
+    #[test]
+    fn row_style() {
+        let mut row = Row::new(vec![
+            Cell::new("foo"),
+            Cell::new("bar").style_spec("Fgc"),
+            Cell::new("baz").style_spec("r"),
+        ]);
+        assert!(row.styled(true).is_none());
+        row.set_style_spec("bFrl");
+        assert_eq!(row.get_style(), Some(CellStyle::from_spec("bFrl")));
+        let styled = row.styled(true).unwrap();
+        assert_eq!(
+            styled[0].get_style(),
+            &[Attr::ForegroundColor(color::RED), Attr::Bold]
+        );
+        assert_eq!(styled[0].get_align(), Alignment::LEFT);
+        assert_eq!(
+            styled[1].get_style(),
+            &[Attr::ForegroundColor(color::GREEN)]
+        );
+        assert_eq!(styled[1].get_align(), Alignment::CENTER);
+        assert_eq!(
+            styled[2].get_style(),
+            &[Attr::ForegroundColor(color::RED), Attr::Bold]
+        );
+        assert_eq!(styled[2].get_align(), Alignment::RIGHT);
+        let aligned = row.styled(false).unwrap();
+        assert!(aligned[0].get_style().is_empty());
+        assert!(row[0].get_style().is_empty());
+
+        let mut row = Row::new(vec![Cell::new("foo"), Cell::new("bar").style_spec("l")]);
+        row.set_style_spec("r");
+        let mut out = crate::utils::StringWriter::new();
+        let format = *crate::format::consts::FORMAT_CLEAN;
+        row.print(&mut out, &format, &[5, 5]).unwrap();
+        assert_eq!(out.as_string().replace("\r\n", "\n"), "   foo  bar \n");
+    }
+
+    #[test]
+    fn from_delimited() {
+        let contents = |row: &Row| row.iter().map(Cell::get_content).collect::<Vec<_>>();
+        assert_eq!(
+            contents(&Row::from_delimited(" a | b|c ", '|')),
+            ["a", "b", "c"]
+        );
+        assert_eq!(
+            contents(&Row::from_delimited("a;;b;", ';')),
+            ["a", "", "b", ""]
+        );
+        assert_eq!(contents(&Row::from_delimited("", ',')), [""]);
+        assert_eq!(contents(&Row::from_delimited("a,,", ',')), ["a", "", ""]);
+        assert_eq!(contents(&Row::from_delimited("a\tb", '\t')), ["a", "b"]);
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn from_delimited_quoted() {
+        let row = Row::from_delimited("1,\"Doe, John\",\"say \"\"hi\"\"\",", ',');
+        assert_eq!(row.len(), 4);
+        assert_eq!(row[1].get_content(), "Doe, John");
+        assert_eq!(row[2].get_content(), "say \"hi\"");
+        assert_eq!(row[3].get_content(), "");
+        let row = Row::from_delimited("a\nb,c", ',');
+        assert_eq!(row[0].get_content(), "a\nb");
+        // Quotes are only meaningful with commas
+        assert_eq!(Row::from_delimited("\"a;b\"", ';').len(), 2);
+    }
+
+    #[test]
+    fn cells_iteration() {
+        let mut row = Row::from_cells(vec![Cell::new("a").style_spec("b"), Cell::new("b")]);
+        row.extend_cells(Some(Cell::new("c").style_spec("Fr")));
+        assert_eq!(row, ["a", "b", "c"]);
+        assert_eq!(row, vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+        assert!(row != ["a", "b"]);
+        assert!(row != ["a", "b", "d"]);
+        assert_eq!(row[0].get_style(), &[Attr::Bold]);
+        assert_eq!(row.iter().len(), 3);
+        assert_eq!(row.iter_mut().rev().len(), 3);
+        let collected: Row = (1..=2).map(|i| i * 10).collect();
+        assert_eq!(collected, ["10", "20"]);
+
+        let removed = row.remove_cell(2).unwrap();
+        assert_eq!(removed.get_style(), &[Attr::ForegroundColor(color::RED)]);
+        assert!(row.remove_cell(2).is_none());
+        row.insert_cell(0, removed);
+        assert_eq!(row, ["c", "a", "b"]);
+    }
+
     #[test]
     fn extend_row() {
         let mut row = Row::from(vec!["foo", "bar", "foobar"]);
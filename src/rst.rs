@@ -0,0 +1,111 @@
+//! reStructuredText output for tables
+
+use std::io::{Error, Write};
+
+use super::format::consts::FORMAT_DEFAULT;
+use super::utils::NEWLINE;
+use super::{AsTableSlice, Table, TableSlice};
+
+impl<'a> TableSlice<'a> {
+    /// Print table as a reStructuredText grid table to `out`, and return the number of
+    /// lines printed, or an error.
+    ///
+    /// The table's own format is ignored, since grid tables require `|` and `+` borders,
+    /// with `=` below the titles. Column widths and multi-line cells are handled
+    /// the same way as with `print()`. Grid tables have no footer, so the footer is
+    /// printed as the last row. A caption makes the table the content of a `table`
+    /// directive titled with it, wherever the caption is set to be printed.
+    pub fn print_rst<T: Write + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
+        let mut table = self.exported();
+        let mut format = *FORMAT_DEFAULT;
+        let mut height = 0;
+        if let Some(caption) = table.caption.line() {
+            out.write_all(format!(".. table:: {}", caption).as_bytes())?;
+            out.write_all(NEWLINE)?;
+            out.write_all(NEWLINE)?;
+            format.indent(3);
+            height += 2;
+            table.unset_caption();
+        }
+        let slice = TableSlice {
+            format: &format,
+            ..table.as_slice()
+        };
+        Ok(height + slice.print(out)?)
+    }
+}
+
+impl Table {
+    /// Print table as a reStructuredText grid table to `out`, and return the number of
+    /// lines printed, or an error.
+    ///
+    /// See `TableSlice::print_rst()` for details.
+    pub fn print_rst<T: Write + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
+        self.as_slice().print_rst(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::consts::FORMAT_BOX_CHARS;
+    use crate::format::Alignment;
+    use crate::utils::StringWriter;
+    use crate::{Cell, Row, Table};
+
+    #[test]
+    fn rst() {
+        let mut table = Table::new();
+        table.set_format(*FORMAT_BOX_CHARS);
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2")]));
+        table.add_row(Row::new(vec![Cell::new("由系统"), Cell::new("a\nbc")]));
+        table.add_row(Row::new(vec![Cell::new("d")]));
+        let out = "\
++--------+----+
+| t1     | t2 |
++========+====+
+| 由系统 | a  |
+|        | bc |
++--------+----+
+| d      |    |
++--------+----+
+";
+        let mut writer = StringWriter::new();
+        assert_eq!(table.print_rst(&mut writer).unwrap(), 8);
+        assert_eq!(writer.as_string().replace("\r\n", "\n"), out);
+
+        table.set_footer(Row::new(vec![Cell::new("f")]));
+        let out = "\
++--------+----+
+| t1     | t2 |
++========+====+
+| 由系统 | a  |
+|        | bc |
++--------+----+
+| d      |    |
++--------+----+
+| f      |    |
++--------+----+
+";
+        let mut writer = StringWriter::new();
+        assert_eq!(table.print_rst(&mut writer).unwrap(), 10);
+        assert_eq!(writer.as_string().replace("\r\n", "\n"), out);
+    }
+
+    #[test]
+    fn rst_caption() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("b")]));
+        table.set_caption("Some\nletters", Alignment::LEFT);
+        table.set_show_row_numbers(true);
+        let out = "\
+.. table:: Some letters
+
+   +---+---+
+   | a | b |
+   +---+---+
+";
+        let mut writer = StringWriter::new();
+        assert_eq!(table.print_rst(&mut writer).unwrap(), 5);
+        assert_eq!(writer.as_string().replace("\r\n", "\n"), out);
+    }
+}
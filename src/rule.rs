@@ -0,0 +1,202 @@
+//! Conditional formatting of cells, applied when printing
+
+use std::hash::{Hash, Hasher};
+use std::mem;
+
+use super::number::parse_number;
+use super::utils::strip_ansi;
+use super::{Cell, CellStyle, Table};
+
+/// Condition on a cell, styling it when it holds, as set with `Table::add_format_rule()`.
+///
+/// Conditions are checked on the content of the cell without its ANSI escape sequences.
+/// Numeric conditions never hold for cells which do not hold a number.
+///
+/// # Examples
+/// ```rust
+/// # #[macro_use] extern crate prettytable;
+/// use prettytable::Rule;
+/// # fn main() {
+/// let mut table = table![["db", 0, 12.5], ["web", 3, 250.0]];
+/// table.add_format_rule(1, Rule::GreaterThan(0.0), "Fr");
+/// table.add_format_rule(2, Rule::GreaterThan(100.0), "Fy");
+/// table.add_format_rule(0, Rule::Matches("w*".to_string()), "b");
+/// table.printstd();
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub enum Rule {
+    /// The cell holds a number greater than this one
+    GreaterThan(f64),
+    /// The cell holds a number lower than this one
+    LessThan(f64),
+    /// The cell holds a number between these ones, both included
+    Between(f64, f64),
+    /// The whole content of the cell matches this pattern, where `*` matches any
+    /// sequence of chars and `?` any single char
+    Matches(String),
+    /// This function returns `true` for the cell
+    Custom(fn(&Cell) -> bool),
+}
+
+impl Rule {
+    /// Tell if the condition holds for `cell`
+    pub(crate) fn matches(&self, cell: &Cell) -> bool {
+        let content = || strip_ansi(&cell.get_content());
+        let number = || parse_number(&content());
+        match *self {
+            Rule::GreaterThan(min) => number().is_some_and(|n| n > min),
+            Rule::LessThan(max) => number().is_some_and(|n| n < max),
+            Rule::Between(min, max) => number().is_some_and(|n| min <= n && n <= max),
+            Rule::Matches(ref pattern) => {
+                let pattern = pattern.chars().collect::<Vec<_>>();
+                wildcard_match(&pattern, &content().chars().collect::<Vec<_>>())
+            }
+            Rule::Custom(f) => f(cell),
+        }
+    }
+}
+
+// Numbers are compared by their bits, so that rules can be compared and hashed
+impl PartialEq for Rule {
+    // Custom rules are compared by function address, which is good enough to tell rules apart
+    #[allow(unpredictable_function_pointer_comparisons)]
+    fn eq(&self, other: &Rule) -> bool {
+        match (self, other) {
+            (Rule::GreaterThan(a), Rule::GreaterThan(b))
+            | (Rule::LessThan(a), Rule::LessThan(b)) => a.to_bits() == b.to_bits(),
+            (Rule::Between(a, b), Rule::Between(c, d)) => {
+                a.to_bits() == c.to_bits() && b.to_bits() == d.to_bits()
+            }
+            (Rule::Matches(a), Rule::Matches(b)) => a == b,
+            (Rule::Custom(a), Rule::Custom(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Rule {}
+
+impl Hash for Rule {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match self {
+            Rule::GreaterThan(n) | Rule::LessThan(n) => n.to_bits().hash(state),
+            Rule::Between(a, b) => (a.to_bits(), b.to_bits()).hash(state),
+            Rule::Matches(pattern) => pattern.hash(state),
+            Rule::Custom(f) => f.hash(state),
+        }
+    }
+}
+
+/// Tell if `text` matches `pattern`, where `*` matches any sequence of chars
+/// and `?` any single char
+fn wildcard_match(pattern: &[char], text: &[char]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some(('*', rest)) => (0..=text.len()).any(|i| wildcard_match(rest, &text[i..])),
+        Some((&p, rest)) => match text.split_first() {
+            Some((&c, text)) if p == '?' || p == c => wildcard_match(rest, text),
+            _ => false,
+        },
+    }
+}
+
+impl Table {
+    /// Style the cells of column `column` for which `rule` holds with the style specifier
+    /// `style_spec`, as with `Cell::style_spec()`, when printing.
+    ///
+    /// The cells are left unchanged, and are checked each time the table is printed, as text
+    /// or HTML. The attributes set by a rule replace the ones of the cell of the same kind,
+    /// so the rules added last take precedence. Titles, header rows and the footer are not
+    /// affected.
+    pub fn add_format_rule(&mut self, column: usize, rule: Rule, style_spec: &str) {
+        self.render
+            .add_format_rule(column, rule, CellStyle::from_spec(style_spec));
+    }
+
+    /// Remove all the rules added with `add_format_rule()`
+    pub fn clear_format_rules(&mut self) {
+        self.render.clear_format_rules();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{wildcard_match, Rule};
+    use crate::{color, Attr, Cell, Row, Table};
+
+    fn matches(pattern: &str, text: &str) -> bool {
+        let pattern = pattern.chars().collect::<Vec<_>>();
+        wildcard_match(&pattern, &text.chars().collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn wildcards() {
+        assert!(matches("err*", "error"));
+        assert!(matches("*", ""));
+        assert!(matches("a?c", "abc"));
+        assert!(matches("*5?s", "2050s"));
+        assert!(!matches("a?c", "ac"));
+        assert!(!matches("err", "error"));
+    }
+
+    #[test]
+    fn rules() {
+        let cell = Cell::new("\u{1b}[1m150\u{1b}[0m");
+        assert!(Rule::GreaterThan(100.0).matches(&cell));
+        assert!(!Rule::LessThan(100.0).matches(&cell));
+        assert!(Rule::Between(150.0, 200.0).matches(&cell));
+        assert!(Rule::Matches("1*".to_string()).matches(&cell));
+        assert!(!Rule::GreaterThan(0.0).matches(&Cell::new("n/a")));
+        assert!(!Rule::LessThan(0.0).matches(&Cell::new("")));
+        assert!(Rule::Custom(|c| c.get_hspan() == 2).matches(&Cell::new("x").with_hspan(2)));
+        assert_eq!(Rule::Between(1.0, 2.0), Rule::Between(1.0, 2.0));
+        assert_ne!(Rule::GreaterThan(1.0), Rule::LessThan(1.0));
+    }
+
+    #[test]
+    fn format_rules() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("service"), Cell::new("errors")]));
+        table.add_row(Row::new(vec![Cell::new("db"), Cell::new("0")]));
+        table.add_row(Row::new(vec![
+            Cell::new("web"),
+            Cell::new("7").style_spec("b"),
+        ]));
+        table.add_row(Row::new(vec![Cell::new("cache"), Cell::new("120")]));
+        table.add_format_rule(1, Rule::GreaterThan(0.0), "Fyc");
+        table.add_format_rule(1, Rule::GreaterThan(100.0), "Fr");
+        let rendered = table.rendered();
+        assert!(rendered[0][1].get_style().is_empty());
+        assert_eq!(
+            rendered[1][1].get_style(),
+            &[Attr::Bold, Attr::ForegroundColor(color::YELLOW)]
+        );
+        assert_eq!(
+            rendered[2][1].get_style(),
+            &[Attr::ForegroundColor(color::RED)]
+        );
+        assert_eq!(table[1][1].get_style(), &[Attr::Bold]);
+        let out = "\
++---------+--------+
+| service | errors |
++=========+========+
+| db      | 0      |
++---------+--------+
+| web     |   7    |
++---------+--------+
+| cache   |  120   |
++---------+--------+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+
+        let mut html = Vec::new();
+        table.print_html(&mut html).unwrap();
+        let html = String::from_utf8(html).unwrap();
+        assert!(html.contains("<td style=\"color: #aa0000;text-align: center;\">120</td>"));
+
+        table.clear_format_rules();
+        assert!(table.rendered()[2][1].get_style().is_empty());
+    }
+}
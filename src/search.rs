@@ -0,0 +1,209 @@
+//! Search of cells by content
+
+use super::utils::strip_ansi;
+use super::{Cell, CellStyle, Row, Table};
+
+/// Options controlling how cells are matched against a searched text.
+///
+/// By default, the whole content of a cell must be equal to the text, letter case included,
+/// and titles are not searched. Contents are compared without their ANSI escape sequences.
+///
+/// # Examples
+/// ```rust
+/// # #[macro_use] extern crate prettytable;
+/// use prettytable::SearchOptions;
+/// # fn main() {
+/// let table = table![["apple", "Banana"], ["cherry", "banana split"]];
+/// let options = SearchOptions::new().ignore_case(true).substring(true);
+/// assert_eq!(table.find_all_with("banana", &options), vec![(0, 1), (1, 1)]);
+/// assert_eq!(table.find("banana"), None);
+/// # }
+/// ```
+#[derive(Clone, Debug, Copy, Hash, PartialEq, Eq, Default)]
+pub struct SearchOptions {
+    ignore_case: bool,
+    substring: bool,
+    titles: bool,
+}
+
+impl SearchOptions {
+    /// Create the default options, matching exact contents
+    pub fn new() -> SearchOptions {
+        SearchOptions::default()
+    }
+
+    /// Ignore the letter case when comparing contents
+    pub fn ignore_case(mut self, yes: bool) -> SearchOptions {
+        self.ignore_case = yes;
+        self
+    }
+
+    /// Match cells containing the text, rather than cells equal to it
+    pub fn substring(mut self, yes: bool) -> SearchOptions {
+        self.substring = yes;
+        self
+    }
+
+    /// Also search the titles, with `find_title()` and `highlight_matches_with()`
+    pub fn titles(mut self, yes: bool) -> SearchOptions {
+        self.titles = yes;
+        self
+    }
+
+    /// Tell if `content` matches `text`
+    fn matches(&self, content: &str, text: &str) -> bool {
+        let (content, text) = match self.ignore_case {
+            true => (content.to_lowercase(), text.to_lowercase()),
+            false => (content.to_string(), text.to_string()),
+        };
+        match self.substring {
+            true => content.contains(&text),
+            false => content == text,
+        }
+    }
+}
+
+/// Return the plain content of `cell`, without the ANSI escape sequences
+fn plain_content(cell: &Cell) -> String {
+    strip_ansi(&cell.get_content())
+}
+
+impl Table {
+    /// Return the `(row, column)` position of the first cell whose content is `text`,
+    /// ignoring ANSI escape sequences. Titles are not searched.
+    pub fn find(&self, text: &str) -> Option<(usize, usize)> {
+        self.find_with(text, &SearchOptions::new())
+    }
+
+    /// Return the `(row, column)` position of the first cell matching `text`
+    /// according to `options`. Titles are not searched, see `find_title()`.
+    pub fn find_with(&self, text: &str, options: &SearchOptions) -> Option<(usize, usize)> {
+        self.find_all_with(text, options).into_iter().next()
+    }
+
+    /// Return the `(row, column)` positions of the cells whose plain content,
+    /// without the ANSI escape sequences, satisfies `predicate`, row by row.
+    /// Titles are not searched.
+    pub fn find_all<F: Fn(&str) -> bool>(&self, predicate: F) -> Vec<(usize, usize)> {
+        let mut positions = Vec::new();
+        for (i, row) in self.rows.iter().enumerate() {
+            for (j, cell) in row.iter().enumerate() {
+                if predicate(&plain_content(cell)) {
+                    positions.push((i, j));
+                }
+            }
+        }
+        positions
+    }
+
+    /// Return the `(row, column)` positions of the cells matching `text` according
+    /// to `options`, row by row. Titles are not searched, see `find_title()`.
+    pub fn find_all_with(&self, text: &str, options: &SearchOptions) -> Vec<(usize, usize)> {
+        self.find_all(|content| options.matches(content, text))
+    }
+
+    /// Return the column of the first title matching `text` according to `options`,
+    /// or `None` if there is no match or `options` does not search titles
+    pub fn find_title(&self, text: &str, options: &SearchOptions) -> Option<usize> {
+        let titles = self.titles.as_ref().as_ref().filter(|_| options.titles)?;
+        titles
+            .iter()
+            .position(|cell| options.matches(&plain_content(cell), text))
+    }
+
+    /// Apply the style specifier `spec` to each cell whose content is `text`, on top of its
+    /// current style. See `Cell::style_spec()` for the syntax, alignments are ignored.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut table = table![["ok", "error"], ["error", "ok"]];
+    /// table.highlight_matches("error", "bFr");
+    /// table.printstd();
+    /// # }
+    /// ```
+    pub fn highlight_matches(&mut self, text: &str, spec: &str) {
+        self.highlight_matches_with(text, spec, &SearchOptions::new());
+    }
+
+    /// Apply the style specifier `spec` to each cell matching `text` according to `options`,
+    /// titles included if `options` searches them. See `highlight_matches()` for details.
+    pub fn highlight_matches_with(&mut self, text: &str, spec: &str, options: &SearchOptions) {
+        let attrs = CellStyle::from_spec(spec).attrs();
+        let highlight = |row: &mut Row| {
+            for cell in row.iter_mut() {
+                if options.matches(&plain_content(cell), text) {
+                    for &attr in &attrs {
+                        cell.style(attr);
+                    }
+                }
+            }
+        };
+        if options.titles {
+            if let Some(titles) = self.titles.as_mut() {
+                highlight(titles);
+            }
+        }
+        self.rows.iter_mut().for_each(highlight);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SearchOptions;
+    use crate::{Attr, Cell, Row, Table};
+
+    fn table() -> Table {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("Name"), Cell::new("State")]));
+        table.add_row(Row::new(vec![
+            Cell::new("a"),
+            Cell::new("\x1B[31mFailed\x1B[0m"),
+        ]));
+        table.add_row(Row::new(vec![Cell::new("b"), Cell::new("ok")]));
+        table.add_row(Row::new(vec![
+            Cell::new("failed"),
+            Cell::new("failed twice"),
+        ]));
+        table
+    }
+
+    #[test]
+    fn find() {
+        let table = table();
+        assert_eq!(table.find("failed"), Some((2, 0)));
+        assert_eq!(table.find("Failed"), Some((0, 1)));
+        assert_eq!(table.find("missing"), None);
+        let options = SearchOptions::new().ignore_case(true);
+        assert_eq!(
+            table.find_all_with("FAILED", &options),
+            vec![(0, 1), (2, 0)]
+        );
+        let options = options.substring(true);
+        assert_eq!(
+            table.find_all_with("fail", &options),
+            vec![(0, 1), (2, 0), (2, 1)]
+        );
+        assert_eq!(table.find_with("o", &options), Some((1, 1)));
+        assert_eq!(table.find_all(|c| c.len() == 1), vec![(0, 0), (1, 0)]);
+
+        assert_eq!(table.find_title("state", &options), None);
+        assert_eq!(table.find_title("state", &options.titles(true)), Some(1));
+    }
+
+    #[test]
+    fn highlight_matches() {
+        let mut table = table();
+        table.highlight_matches("ok", "bc");
+        assert_eq!(table[1][1].get_style(), &[Attr::Bold]);
+        assert!(table[0][1].get_style().is_empty());
+
+        let options = SearchOptions::new().substring(true).titles(true);
+        table.highlight_matches_with("a", "u", &options);
+        assert_eq!(table[0][0].get_style(), &[Attr::Underline(true)]);
+        assert!(table[1][0].get_style().is_empty());
+        let titles = table.titles.as_ref().as_ref().unwrap();
+        assert_eq!(titles[0].get_style(), &[Attr::Underline(true)]);
+    }
+}
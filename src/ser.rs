@@ -0,0 +1,446 @@
+//! Building tables from serializable values
+
+use std::error;
+use std::fmt;
+
+use serde::ser::{self, Impossible, Serialize, Serializer};
+
+use super::{Cell, Row, Table};
+
+/// Error returned when a value cannot be turned into a table row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerdeError(String);
+
+impl fmt::Display for SerdeError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(&self.0)
+    }
+}
+
+impl error::Error for SerdeError {}
+
+impl ser::Error for SerdeError {
+    fn custom<T: fmt::Display>(msg: T) -> SerdeError {
+        SerdeError(msg.to_string())
+    }
+}
+
+/// Fields of a flattened value, as pairs of dotted keys and stringified values
+type Fields = Vec<(String, String)>;
+
+/// Join a key `prefix` and a `key` with a dot
+fn join(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+/// Serializer flattening a value into `fields`, under `key`. Structs and maps are
+/// flattened with dotted keys, sequences are rejected
+struct FieldSerializer<'a> {
+    fields: &'a mut Fields,
+    key: String,
+}
+
+impl<'a> FieldSerializer<'a> {
+    fn push<T: ToString>(self, value: T) -> Result<(), SerdeError> {
+        self.fields.push((self.key, value.to_string()));
+        Ok(())
+    }
+
+    fn compound(self) -> Compound<'a> {
+        Compound {
+            fields: self.fields,
+            prefix: self.key,
+            next_key: None,
+        }
+    }
+
+    fn sequence<T>(self) -> Result<T, SerdeError> {
+        Err(SerdeError(format!(
+            "Sequence at key {:?} cannot be rendered as a table cell",
+            self.key
+        )))
+    }
+}
+
+macro_rules! serialize_display {
+    ($($method:ident($ty:ty)),*) => {
+        $(fn $method(self, v: $ty) -> Result<(), SerdeError> {
+            self.push(v)
+        })*
+    };
+}
+
+impl<'a> Serializer for FieldSerializer<'a> {
+    type Ok = ();
+    type Error = SerdeError;
+    type SerializeSeq = Impossible<(), SerdeError>;
+    type SerializeTuple = Impossible<(), SerdeError>;
+    type SerializeTupleStruct = Impossible<(), SerdeError>;
+    type SerializeTupleVariant = Impossible<(), SerdeError>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    serialize_display!(
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_u128(u128),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str)
+    );
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), SerdeError> {
+        self.push(String::from_utf8_lossy(v))
+    }
+
+    fn serialize_none(self) -> Result<(), SerdeError> {
+        self.push("")
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), SerdeError> {
+        self.push("")
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), SerdeError> {
+        self.push("")
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<(), SerdeError> {
+        self.push(variant)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, SerdeError> {
+        self.sequence()
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, SerdeError> {
+        self.sequence()
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, SerdeError> {
+        self.sequence()
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerdeError> {
+        self.sequence()
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Compound<'a>, SerdeError> {
+        Ok(self.compound())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, SerdeError> {
+        Ok(self.compound())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, SerdeError> {
+        Ok(self.compound())
+    }
+}
+
+/// Serializer for the fields of a struct or the entries of a map, under `prefix`
+struct Compound<'a> {
+    fields: &'a mut Fields,
+    prefix: String,
+    next_key: Option<String>,
+}
+
+impl<'a> Compound<'a> {
+    fn field<T: ?Sized + Serialize>(&mut self, key: &str, value: &T) -> Result<(), SerdeError> {
+        value.serialize(FieldSerializer {
+            fields: self.fields,
+            key: join(&self.prefix, key),
+        })
+    }
+}
+
+impl<'a> ser::SerializeMap for Compound<'a> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), SerdeError> {
+        let mut fields = Vec::new();
+        key.serialize(FieldSerializer {
+            fields: &mut fields,
+            key: String::new(),
+        })?;
+        match fields.pop() {
+            Some((ref k, v)) if k.is_empty() && fields.is_empty() => {
+                self.next_key = Some(v);
+                Ok(())
+            }
+            _ => Err(SerdeError("Map keys must be plain values".to_string())),
+        }
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), SerdeError> {
+        let key = self
+            .next_key
+            .take()
+            .ok_or_else(|| SerdeError("Map value serialized before its key".to_string()))?;
+        self.field(&key, value)
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeStruct for Compound<'a> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeStructVariant for Compound<'a> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        Ok(())
+    }
+}
+
+impl Table {
+    /// Create a table with one row per item of `iter`, which must serialize
+    /// as structs or maps.
+    ///
+    /// Field names are used as titles, in the order they are first seen, and values are
+    /// stringified into cells. Nested structs and maps are flattened with dotted keys,
+    /// such as `address.city`. `None` values and fields missing from an item are
+    /// rendered as empty cells. Sequences are rejected with an error.
+    pub fn from_serde<T: Serialize, I: IntoIterator<Item = T>>(
+        iter: I,
+    ) -> Result<Table, SerdeError> {
+        let mut titles: Vec<String> = Vec::new();
+        let mut records = Vec::new();
+        for item in iter {
+            let mut fields = Vec::new();
+            item.serialize(FieldSerializer {
+                fields: &mut fields,
+                key: String::new(),
+            })?;
+            if fields.iter().any(|(k, _)| k.is_empty()) {
+                return Err(SerdeError(
+                    "Only structs and maps can be turned into table rows".to_string(),
+                ));
+            }
+            for (key, _) in &fields {
+                if !titles.contains(key) {
+                    titles.push(key.clone());
+                }
+            }
+            records.push(fields);
+        }
+        let rows = records
+            .into_iter()
+            .map(|fields| {
+                Row::new(
+                    titles
+                        .iter()
+                        .map(|t| match fields.iter().find(|(k, _)| k == t) {
+                            Some((_, v)) => Cell::new(v),
+                            None => Cell::default(),
+                        })
+                        .collect(),
+                )
+            })
+            .collect();
+        let mut table = Table::init(rows);
+        if !titles.is_empty() {
+            table.set_titles(Row::new(titles.iter().map(|t| Cell::new(t)).collect()));
+        }
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SerdeError;
+    use crate::Table;
+    use serde::ser::{Serialize, SerializeMap, SerializeStruct, Serializer};
+    use std::collections::BTreeMap;
+
+    struct Address {
+        city: &'static str,
+    }
+
+    struct Person {
+        name: &'static str,
+        age: Option<u32>,
+        address: Address,
+    }
+
+    impl Serialize for Address {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut s = serializer.serialize_struct("Address", 1)?;
+            s.serialize_field("city", self.city)?;
+            s.end()
+        }
+    }
+
+    impl Serialize for Person {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut s = serializer.serialize_struct("Person", 3)?;
+            s.serialize_field("name", self.name)?;
+            s.serialize_field("age", &self.age)?;
+            s.serialize_field("address", &self.address)?;
+            s.end()
+        }
+    }
+
+    #[test]
+    fn from_serde() {
+        let people = vec![
+            Person {
+                name: "Alice",
+                age: Some(30),
+                address: Address { city: "Paris" },
+            },
+            Person {
+                name: "Bob",
+                age: None,
+                address: Address { city: "Lyon" },
+            },
+        ];
+        let table = Table::from_serde(&people).unwrap();
+        let out = "\
++-------+-----+--------------+
+| name  | age | address.city |
++=======+=====+==============+
+| Alice | 30  | Paris        |
++-------+-----+--------------+
+| Bob   |     | Lyon         |
++-------+-----+--------------+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+    }
+
+    #[test]
+    fn from_serde_maps() {
+        struct Map(BTreeMap<&'static str, u8>);
+        impl Serialize for Map {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut m = serializer.serialize_map(Some(self.0.len()))?;
+                for (k, v) in &self.0 {
+                    m.serialize_entry(k, v)?;
+                }
+                m.end()
+            }
+        }
+        let a = Map(vec![("a", 1)].into_iter().collect());
+        let b = Map(vec![("b", 2)].into_iter().collect());
+        let table = Table::from_serde(vec![a, b]).unwrap();
+        assert_eq!(table.get_row(0).unwrap().len(), 2);
+        assert_eq!(
+            table.get_row(1).unwrap().get_cell(0).unwrap().get_content(),
+            ""
+        );
+        assert_eq!(
+            table.get_row(1).unwrap().get_cell(1).unwrap().get_content(),
+            "2"
+        );
+
+        assert!(Table::from_serde(Vec::<Map>::new()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn from_serde_errors() {
+        assert_eq!(
+            Table::from_serde(vec![1, 2]).unwrap_err(),
+            SerdeError("Only structs and maps can be turned into table rows".to_string())
+        );
+        struct WithSeq;
+        impl Serialize for WithSeq {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut s = serializer.serialize_struct("WithSeq", 1)?;
+                s.serialize_field("items", &[1, 2][..])?;
+                s.end()
+            }
+        }
+        assert!(Table::from_serde(vec![WithSeq])
+            .unwrap_err()
+            .to_string()
+            .contains("\"items\""));
+    }
+}
@@ -0,0 +1,263 @@
+//! Sorting of table rows
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+
+use super::number::parse_number;
+use super::utils::strip_ansi;
+use super::{Row, Table};
+
+/// Order in which rows are sorted
+#[derive(Clone, Debug, Copy, Hash, PartialEq, Eq, Default)]
+pub enum SortOrder {
+    /// Smallest values first
+    #[default]
+    Ascending,
+    /// Largest values first
+    Descending,
+}
+
+/// Compare the plain text of cells. Numbers are compared by value and come
+/// before any other text, which is compared as strings.
+fn compare_text(a: &str, b: &str) -> Ordering {
+    match (parse_number(a), parse_number(b)) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => a.cmp(b),
+    }
+}
+
+/// Sort `rows` by the content of column `column`, like `Table::sort_by_column()`.
+/// Group headers are not moved, and the rows are sorted within their group.
+pub(crate) fn sort_rows<R: Borrow<Row>>(rows: Vec<R>, column: usize, order: SortOrder) -> Vec<R> {
+    let mut sorted = Vec::with_capacity(rows.len());
+    let mut group = Vec::new();
+    for row in rows {
+        if row.borrow().is_group_header() {
+            sorted.extend(sort_group(std::mem::take(&mut group), column, order));
+            sorted.push(row);
+        } else {
+            group.push(row);
+        }
+    }
+    sorted.extend(sort_group(group, column, order));
+    sorted
+}
+
+/// Sort `rows`, holding no group header, by the content of column `column`
+fn sort_group<R: Borrow<Row>>(rows: Vec<R>, column: usize, order: SortOrder) -> Vec<R> {
+    // Contents are extracted once, rather than at each comparison
+    let mut keyed: Vec<(Option<String>, R)> = rows
+        .into_iter()
+        .map(|row| {
+            let key = row
+                .borrow()
+                .get_cell(column)
+                .map(|cell| strip_ansi(&cell.get_content()));
+            (key, row)
+        })
+        .collect();
+    keyed.sort_by(|(a, _), (b, _)| match (a, b) {
+        (Some(a), Some(b)) => match order {
+            SortOrder::Ascending => compare_text(a, b),
+            SortOrder::Descending => compare_text(b, a),
+        },
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    });
+    keyed.into_iter().map(|(_, row)| row).collect()
+}
+
+impl Table {
+    /// Sort the rows by the content of column `column`, without the ANSI escape
+    /// sequences. Numbers are sorted by value, before other contents.
+    ///
+    /// Rows without a cell in `column` come last, whatever the order. The sort is stable,
+    /// so rows can be sorted on several columns by sorting on the least significant one
+    /// first. Titles are not moved.
+    ///
+    /// In a table grouped with `group_by()`, the group headers are not moved either,
+    /// and the rows are sorted within their group.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// use prettytable::SortOrder;
+    /// # fn main() {
+    /// let mut table = table![["b", 10], ["a", 9], ["c", 10]];
+    /// table.sort_by_column(0, SortOrder::Ascending);
+    /// table.sort_by_column(1, SortOrder::Descending);
+    /// let order: Vec<_> = table.row_iter().map(|r| r[0].get_content()).collect();
+    /// assert_eq!(order, vec!["b", "c", "a"]);
+    /// # }
+    /// ```
+    pub fn sort_by_column(&mut self, column: usize, order: SortOrder) {
+        let rows = std::mem::take(&mut self.rows);
+        self.rows = sort_rows(rows, column, order);
+    }
+
+    /// Sort the rows with the comparator `compare`. The sort is stable, and titles are not moved.
+    /// Like with `sort_by_column()`, group headers are not moved, and the rows are sorted
+    /// within their group.
+    pub fn sort_by<F: Fn(&Row, &Row) -> Ordering>(&mut self, compare: F) {
+        for group in self.rows.split_mut(Row::is_group_header) {
+            group.sort_by(&compare);
+        }
+    }
+
+    /// Reverse the order of the rows. Titles are not moved.
+    pub fn reverse_rows(&mut self) {
+        self.rows.reverse();
+    }
+
+    /// Rotate the rows by `n` positions, so that the last `n` rows come first.
+    /// Rotating by more than the number of rows wraps around. Titles are not moved.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut table = table![["a"], ["b"], ["c"]];
+    /// table.rotate_rows(1);
+    /// let order: Vec<_> = table.row_iter().map(|r| r[0].get_content()).collect();
+    /// assert_eq!(order, vec!["c", "a", "b"]);
+    /// # }
+    /// ```
+    pub fn rotate_rows(&mut self, n: usize) {
+        if !self.rows.is_empty() {
+            let n = n % self.rows.len();
+            self.rows.rotate_right(n);
+        }
+    }
+
+    /// Shuffle the rows in place. Titles are not moved.
+    ///
+    /// `random(n)` must return a random index lower than `n`, for example
+    /// `|n| rng.gen_range(0..n)` with the `rand` crate, which is not a dependency
+    /// of this crate.
+    pub fn shuffle_rows_with<F: FnMut(usize) -> usize>(&mut self, mut random: F) {
+        // Fisher-Yates shuffle
+        for i in (1..self.rows.len()).rev() {
+            let j = random(i + 1);
+            assert!(j <= i, "random index {} out of range 0..{}", j, i + 1);
+            self.rows.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SortOrder;
+    use crate::{Cell, Row, Table};
+
+    fn column(table: &Table, column: usize) -> Vec<String> {
+        table
+            .row_iter()
+            .map(|r| {
+                r.get_cell(column)
+                    .map(Cell::get_content)
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sort_by_column() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("z")]));
+        for text in &["10", "b", "\x1b[31m9\x1b[0m", "", "-1.5", "a", "inf", "1a"] {
+            table.add_row(Row::new(vec![Cell::new("x"), Cell::new(text)]));
+        }
+        table.add_row(Row::new(vec![Cell::new("short")]));
+        table.sort_by_column(1, SortOrder::Ascending);
+        assert_eq!(
+            column(&table, 1),
+            vec![
+                "-1.5",
+                "\x1b[31m9\x1b[0m",
+                "10",
+                "",
+                "1a",
+                "a",
+                "b",
+                "inf",
+                ""
+            ]
+        );
+        assert_eq!(table[8][0].get_content(), "short");
+        table.sort_by_column(1, SortOrder::Descending);
+        assert_eq!(
+            column(&table, 1),
+            vec![
+                "inf",
+                "b",
+                "a",
+                "1a",
+                "",
+                "10",
+                "\x1b[31m9\x1b[0m",
+                "-1.5",
+                ""
+            ]
+        );
+        assert_eq!(
+            table.titles.as_ref().as_ref().unwrap()[0].get_content(),
+            "z"
+        );
+    }
+
+    #[test]
+    fn reorder_rows() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("t")]));
+        for n in 0..5 {
+            table.add_row(Row::new(vec![Cell::from(n)]));
+        }
+        table[1].set_separator_below(true);
+        table.reverse_rows();
+        assert_eq!(column(&table, 0), vec!["4", "3", "2", "1", "0"]);
+        assert!(table[3].has_separator_below());
+        table.rotate_rows(7);
+        assert_eq!(column(&table, 0), vec!["1", "0", "4", "3", "2"]);
+        table.rotate_rows(0);
+        assert_eq!(column(&table, 0), vec!["1", "0", "4", "3", "2"]);
+
+        // A "random" source always picking the first row
+        table.shuffle_rows_with(|_| 0);
+        assert_eq!(column(&table, 0), vec!["0", "4", "3", "2", "1"]);
+        let mut bounds = Vec::new();
+        table.shuffle_rows_with(|n| {
+            bounds.push(n);
+            n - 1
+        });
+        assert_eq!(bounds, vec![5, 4, 3, 2]);
+        assert_eq!(column(&table, 0), vec!["0", "4", "3", "2", "1"]);
+        assert_eq!(
+            table.titles.as_ref().as_ref().unwrap()[0].get_content(),
+            "t"
+        );
+
+        let mut empty = Table::new();
+        empty.rotate_rows(3);
+        empty.shuffle_rows_with(|_| unreachable!());
+    }
+
+    #[test]
+    fn stable_sort() {
+        let mut table = Table::from_rows(vec![
+            vec!["b", "2"],
+            vec!["a", "1"],
+            vec!["b", "1"],
+            vec!["a", "2"],
+        ]);
+        table.sort_by_column(1, SortOrder::Ascending);
+        table.sort_by_column(0, SortOrder::Ascending);
+        assert_eq!(column(&table, 0), vec!["a", "a", "b", "b"]);
+        assert_eq!(column(&table, 1), vec!["1", "2", "1", "2"]);
+
+        table.sort_by(|a, b| b[1].get_content().cmp(&a[1].get_content()));
+        assert_eq!(column(&table, 0), vec!["a", "b", "a", "b"]);
+    }
+}
@@ -0,0 +1,134 @@
+//! SQL statements generation from tables
+
+use super::utils::strip_ansi;
+use super::{AsTableSlice, Row, Table, TableSlice};
+
+/// Quote `name` as an SQL identifier, unless it is a plain identifier
+fn quote_identifier(name: &str) -> String {
+    let plain = name
+        .chars()
+        .enumerate()
+        .all(|(i, c)| c == '_' || c.is_ascii_alphabetic() || (i > 0 && c.is_ascii_digit()));
+    if plain && !name.is_empty() {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+}
+
+/// Format the first `col_num` cells of `row` as an SQL values tuple. Missing cells are `NULL`
+fn values(row: &Row, col_num: usize) -> String {
+    let values: Vec<String> = (0..col_num)
+        .map(|i| match row.get_cell(i) {
+            Some(c) => format!("'{}'", strip_ansi(&c.get_content()).replace('\'', "''")),
+            None => "NULL".to_string(),
+        })
+        .collect();
+    format!("({})", values.join(", "))
+}
+
+impl<'a> TableSlice<'a> {
+    /// Return the header of an `INSERT` statement into `table_name`, with the titles as columns
+    fn sql_insert_header(&self, table_name: &str) -> Result<(String, usize), &'static str> {
+        let titles = self
+            .titles
+            .as_ref()
+            .ok_or("Titles are required to name SQL columns")?;
+        let columns: Vec<String> = titles
+            .iter()
+            .map(|c| quote_identifier(&strip_ansi(&c.get_content())))
+            .collect();
+        Ok((
+            format!(
+                "INSERT INTO {} ({}) VALUES",
+                quote_identifier(table_name),
+                columns.join(", ")
+            ),
+            columns.len(),
+        ))
+    }
+
+    /// Generate one SQL `INSERT` statement per row into `table_name`, using the titles
+    /// as column names. All values are inserted as strings, values missing from a row are
+    /// inserted as `NULL`, and cells beyond the titles are ignored.
+    ///
+    /// `table_name` and the titles are quoted when they are not plain identifiers. The table
+    /// name is quoted as a single identifier, so a schema-qualified name like `"app.people"`
+    /// names a table called `app.people`.
+    ///
+    /// Return an error if the table has no titles.
+    pub fn to_sql_inserts(&self, table_name: &str) -> Result<String, &'static str> {
+        let (header, col_num) = self.sql_insert_header(table_name)?;
+        Ok(self
+            .rows
+            .iter()
+            .map(|r| format!("{} {};\n", header, values(r, col_num)))
+            .collect())
+    }
+
+    /// Generate a single SQL `INSERT` statement into `table_name` with one `VALUES`
+    /// tuple per row. See `to_sql_inserts()` for details.
+    ///
+    /// Return an error if the table has no titles.
+    pub fn to_sql_inserts_multi_row(&self, table_name: &str) -> Result<String, &'static str> {
+        let (header, col_num) = self.sql_insert_header(table_name)?;
+        if self.rows.is_empty() {
+            return Ok(String::new());
+        }
+        let tuples: Vec<String> = self.rows.iter().map(|r| values(r, col_num)).collect();
+        Ok(format!("{}\n{};\n", header, tuples.join(",\n")))
+    }
+}
+
+impl Table {
+    /// Generate one SQL `INSERT` statement per row into `table_name`, using the titles
+    /// as column names.
+    ///
+    /// See `TableSlice::to_sql_inserts()` for details.
+    pub fn to_sql_inserts(&self, table_name: &str) -> Result<String, &'static str> {
+        self.as_slice().to_sql_inserts(table_name)
+    }
+
+    /// Generate a single SQL `INSERT` statement into `table_name` with one `VALUES`
+    /// tuple per row.
+    ///
+    /// See `TableSlice::to_sql_inserts()` for details.
+    pub fn to_sql_inserts_multi_row(&self, table_name: &str) -> Result<String, &'static str> {
+        self.as_slice().to_sql_inserts_multi_row(table_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cell, Row, Slice, Table};
+
+    #[test]
+    fn sql_inserts() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("1"), Cell::new("O'Brien")]));
+        table.add_row(Row::new(vec![Cell::new("2")]));
+        assert!(table.to_sql_inserts("people").is_err());
+        assert!(table.to_sql_inserts_multi_row("people").is_err());
+
+        table.set_titles(Row::new(vec![Cell::new("id"), Cell::new("last name")]));
+        assert_eq!(
+            table.to_sql_inserts("people").unwrap(),
+            "INSERT INTO people (id, \"last name\") VALUES ('1', 'O''Brien');\n\
+             INSERT INTO people (id, \"last name\") VALUES ('2', NULL);\n"
+        );
+        assert_eq!(
+            table.to_sql_inserts_multi_row("people").unwrap(),
+            "INSERT INTO people (id, \"last name\") VALUES\n\
+             ('1', 'O''Brien'),\n\
+             ('2', NULL);\n"
+        );
+        assert_eq!(
+            table.slice(1..).to_sql_inserts("t; drop").unwrap(),
+            "INSERT INTO \"t; drop\" (id, \"last name\") VALUES ('2', NULL);\n"
+        );
+        assert!(table
+            .to_sql_inserts_multi_row("app.people")
+            .unwrap()
+            .starts_with("INSERT INTO \"app.people\" (id"));
+    }
+}
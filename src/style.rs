@@ -0,0 +1,477 @@
+//! Typed builder for cell styles
+
+use super::format::Alignment;
+use super::{color, Attr, Cell};
+use std::error;
+use std::fmt;
+
+/// Error returned when a style spec string contains an invalid specifier
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyleParseError {
+    position: usize,
+    character: Option<char>,
+    expected: &'static str,
+}
+
+impl StyleParseError {
+    /// Get the position of the offending character in the spec, counted in chars from 0
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Get the offending character, or `None` if the spec ended too early
+    pub fn character(&self) -> Option<char> {
+        self.character
+    }
+}
+
+impl fmt::Display for StyleParseError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.character {
+            Some(c) => write!(
+                fmt,
+                "invalid {} {:?} at position {} in style spec",
+                self.expected, c, self.position
+            ),
+            None => write!(
+                fmt,
+                "missing {} at position {} in style spec",
+                self.expected, self.position
+            ),
+        }
+    }
+}
+
+impl error::Error for StyleParseError {}
+
+/// Colors which can be used in a `CellStyle`
+#[derive(Clone, Debug, Copy, Hash, PartialEq, Eq)]
+pub enum Color {
+    /// Black
+    Black,
+    /// Red
+    Red,
+    /// Green
+    Green,
+    /// Yellow
+    Yellow,
+    /// Blue
+    Blue,
+    /// Magenta
+    Magenta,
+    /// Cyan
+    Cyan,
+    /// White
+    White,
+    /// Bright black
+    BrightBlack,
+    /// Bright red
+    BrightRed,
+    /// Bright green
+    BrightGreen,
+    /// Bright yellow
+    BrightYellow,
+    /// Bright blue
+    BrightBlue,
+    /// Bright magenta
+    BrightMagenta,
+    /// Bright cyan
+    BrightCyan,
+    /// Bright white
+    BrightWhite,
+}
+
+impl Color {
+    /// Return the terminal color
+    pub fn to_term(self) -> color::Color {
+        match self {
+            Color::Black => color::BLACK,
+            Color::Red => color::RED,
+            Color::Green => color::GREEN,
+            Color::Yellow => color::YELLOW,
+            Color::Blue => color::BLUE,
+            Color::Magenta => color::MAGENTA,
+            Color::Cyan => color::CYAN,
+            Color::White => color::WHITE,
+            Color::BrightBlack => color::BRIGHT_BLACK,
+            Color::BrightRed => color::BRIGHT_RED,
+            Color::BrightGreen => color::BRIGHT_GREEN,
+            Color::BrightYellow => color::BRIGHT_YELLOW,
+            Color::BrightBlue => color::BRIGHT_BLUE,
+            Color::BrightMagenta => color::BRIGHT_MAGENTA,
+            Color::BrightCyan => color::BRIGHT_CYAN,
+            Color::BrightWhite => color::BRIGHT_WHITE,
+        }
+    }
+
+    /// Return the color specifier of this color in style spec strings
+    fn spec(self) -> char {
+        match self {
+            Color::Black => 'd',
+            Color::Red => 'r',
+            Color::Green => 'g',
+            Color::Yellow => 'y',
+            Color::Blue => 'b',
+            Color::Magenta => 'm',
+            Color::Cyan => 'c',
+            Color::White => 'w',
+            Color::BrightBlack => 'D',
+            Color::BrightRed => 'R',
+            Color::BrightGreen => 'G',
+            Color::BrightYellow => 'Y',
+            Color::BrightBlue => 'B',
+            Color::BrightMagenta => 'M',
+            Color::BrightCyan => 'C',
+            Color::BrightWhite => 'W',
+        }
+    }
+
+    /// Return the color for the color specifier `c` of style spec strings
+    pub(crate) fn from_spec(c: char) -> Option<Color> {
+        Some(match c {
+            'd' => Color::Black,
+            'r' => Color::Red,
+            'g' => Color::Green,
+            'y' => Color::Yellow,
+            'b' => Color::Blue,
+            'm' => Color::Magenta,
+            'c' => Color::Cyan,
+            'w' => Color::White,
+            'D' => Color::BrightBlack,
+            'R' => Color::BrightRed,
+            'G' => Color::BrightGreen,
+            'Y' => Color::BrightYellow,
+            'B' => Color::BrightBlue,
+            'M' => Color::BrightMagenta,
+            'C' => Color::BrightCyan,
+            'W' => Color::BrightWhite,
+            _ => return None,
+        })
+    }
+}
+
+/// Style of a cell, built with typed methods instead of a style spec string.
+///
+/// A `CellStyle` can be converted to and from the equivalent spec string
+/// (see `Cell::style_spec()`), and both give the same cells.
+///
+/// # Examples
+/// ```rust
+/// use prettytable::format::Alignment;
+/// use prettytable::{Cell, CellStyle, Color};
+/// let style = CellStyle::new().fg(Color::Red).bg(Color::BrightYellow).bold().align(Alignment::CENTER);
+/// assert_eq!(style.to_string(), "FrBYbc");
+/// assert_eq!(Cell::new("x").with_cell_style(style), Cell::new("x").style_spec("FrBYbc"));
+/// ```
+#[derive(Clone, Debug, Copy, Hash, PartialEq, Eq, Default)]
+pub struct CellStyle {
+    fg: Option<Color>,
+    bg: Option<Color>,
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    align: Option<Alignment>,
+    hspan: Option<usize>,
+}
+
+impl CellStyle {
+    /// Create an empty style, with the default `LEFT` alignment
+    pub fn new() -> CellStyle {
+        CellStyle::default()
+    }
+
+    /// Set the foreground color
+    pub fn fg(mut self, color: Color) -> CellStyle {
+        self.fg = Some(color);
+        self
+    }
+
+    /// Set the background color
+    pub fn bg(mut self, color: Color) -> CellStyle {
+        self.bg = Some(color);
+        self
+    }
+
+    /// Make the text bold
+    pub fn bold(mut self) -> CellStyle {
+        self.bold = true;
+        self
+    }
+
+    /// Make the text italic
+    pub fn italic(mut self) -> CellStyle {
+        self.italic = true;
+        self
+    }
+
+    /// Underline the text
+    pub fn underline(mut self) -> CellStyle {
+        self.underline = true;
+        self
+    }
+
+    /// Set the text alignment
+    pub fn align(mut self, align: Alignment) -> CellStyle {
+        self.align = Some(align);
+        self
+    }
+
+    /// Make the cell span `hspan` columns
+    pub fn hspan(mut self, hspan: usize) -> CellStyle {
+        self.hspan = Some(hspan);
+        self
+    }
+
+    /// Parse a style spec string, reporting the first invalid specifier.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use prettytable::CellStyle;
+    /// let err = CellStyle::try_from_spec("bFz").unwrap_err();
+    /// assert_eq!(err.position(), 2);
+    /// assert_eq!(err.character(), Some('z'));
+    /// ```
+    pub fn try_from_spec(spec: &str) -> Result<CellStyle, StyleParseError> {
+        let mut style = CellStyle::new();
+        let mut it = spec.chars().enumerate().peekable();
+        let error = |position, character, expected| StyleParseError {
+            position,
+            character,
+            expected,
+        };
+        while let Some((i, c)) = it.next() {
+            match c {
+                'F' | 'B' => {
+                    let color = match it.next() {
+                        Some((j, c)) => {
+                            Color::from_spec(c).ok_or_else(|| error(j, Some(c), "color"))?
+                        }
+                        None => return Err(error(i + 1, None, "color")),
+                    };
+                    style = if c == 'F' {
+                        style.fg(color)
+                    } else {
+                        style.bg(color)
+                    };
+                }
+                'H' => {
+                    let mut span = None;
+                    while let Some(d) = it.peek().and_then(|&(_, d)| d.to_digit(10)) {
+                        span = Some(span.unwrap_or(0) * 10 + d as usize);
+                        it.next();
+                    }
+                    match span {
+                        Some(span) => style = style.hspan(span),
+                        None => {
+                            let next = it.peek().map(|&(_, c)| c);
+                            return Err(error(i + 1, next, "span"));
+                        }
+                    }
+                }
+                'b' | 'i' | 'u' | 'c' | 'l' | 'r' | 'd' => style = style.apply_flag(c),
+                _ => return Err(error(i, Some(c), "style specifier")),
+            }
+        }
+        Ok(style)
+    }
+
+    /// Apply the single char specifier `c`
+    fn apply_flag(self, c: char) -> CellStyle {
+        match c {
+            'b' => self.bold(),
+            'i' => self.italic(),
+            'u' => self.underline(),
+            'c' => self.align(Alignment::CENTER),
+            'l' => self.align(Alignment::LEFT),
+            'r' => self.align(Alignment::RIGHT),
+            _ => self,
+        }
+    }
+
+    /// Parse a style spec string, ignoring unknown specifiers like `Cell::style_spec()`
+    pub fn from_spec(spec: &str) -> CellStyle {
+        let mut style = CellStyle::new();
+        let mut it = spec.chars().peekable();
+        while let Some(c) = it.next() {
+            match c {
+                'F' | 'B' => {
+                    let color = it.next().and_then(Color::from_spec);
+                    if let Some(color) = color {
+                        style = if c == 'F' {
+                            style.fg(color)
+                        } else {
+                            style.bg(color)
+                        };
+                    }
+                }
+                'H' => {
+                    let mut span = 0;
+                    while let Some(d) = it.peek().and_then(|d| d.to_digit(10)) {
+                        span = span * 10 + d as usize;
+                        it.next();
+                    }
+                    style = style.hspan(span);
+                }
+                _ => style = style.apply_flag(c),
+            }
+        }
+        style
+    }
+
+    /// Return the equivalent style spec string
+    pub fn to_spec(&self) -> String {
+        self.to_string()
+    }
+
+    /// Get the alignment set by this style, if any
+    pub(crate) fn get_align(&self) -> Option<Alignment> {
+        self.align
+    }
+
+    /// Return the terminal attributes of this style
+    pub(crate) fn attrs(&self) -> Vec<Attr> {
+        let mut attrs = Vec::new();
+        if let Some(fg) = self.fg {
+            attrs.push(Attr::ForegroundColor(fg.to_term()));
+        }
+        if let Some(bg) = self.bg {
+            attrs.push(Attr::BackgroundColor(bg.to_term()));
+        }
+        if self.bold {
+            attrs.push(Attr::Bold);
+        }
+        if self.italic {
+            attrs.push(Attr::Italic(true));
+        }
+        if self.underline {
+            attrs.push(Attr::Underline(true));
+        }
+        attrs
+    }
+}
+
+impl fmt::Display for CellStyle {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(fg) = self.fg {
+            write!(fmt, "F{}", fg.spec())?;
+        }
+        if let Some(bg) = self.bg {
+            write!(fmt, "B{}", bg.spec())?;
+        }
+        for (set, spec) in [(self.bold, 'b'), (self.italic, 'i'), (self.underline, 'u')] {
+            if set {
+                write!(fmt, "{}", spec)?;
+            }
+        }
+        match self.align {
+            Some(Alignment::LEFT) => fmt.write_str("l")?,
+            Some(Alignment::CENTER) => fmt.write_str("c")?,
+            Some(Alignment::RIGHT) => fmt.write_str("r")?,
+            None => {}
+        }
+        if let Some(hspan) = self.hspan {
+            write!(fmt, "H{}", hspan)?;
+        }
+        Ok(())
+    }
+}
+
+impl Cell {
+    /// Set the cell's style by applying the given specifier string, like `style_spec()`,
+    /// but fail on the first unknown specifier instead of ignoring it.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use prettytable::Cell;
+    /// assert!(Cell::new("x").try_style_spec("Frb").is_ok());
+    /// let err = Cell::new("x").try_style_spec("Fz").unwrap_err();
+    /// assert_eq!(err.to_string(), "invalid color 'z' at position 1 in style spec");
+    /// ```
+    pub fn try_style_spec(self, spec: &str) -> Result<Cell, StyleParseError> {
+        CellStyle::try_from_spec(spec)?;
+        Ok(self.style_spec(spec))
+    }
+
+    /// Set the cell's style from `style`, replacing the current style attributes and
+    /// alignment like `style_spec()` does. Can be chained
+    pub fn with_cell_style(mut self, style: CellStyle) -> Cell {
+        self.reset_style();
+        for attr in style.attrs() {
+            self.style(attr);
+        }
+        if let Some(align) = style.align {
+            self.align(align);
+        }
+        if let Some(hspan) = style.hspan {
+            self.set_hspan(hspan);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CellStyle, Color};
+    use crate::format::Alignment;
+    use crate::Cell;
+
+    #[test]
+    fn spec_round_trip() {
+        let style = CellStyle::new()
+            .fg(Color::BrightBlack)
+            .bg(Color::Cyan)
+            .bold()
+            .italic()
+            .underline()
+            .align(Alignment::RIGHT)
+            .hspan(12);
+        assert_eq!(style.to_spec(), "FDBcbiurH12");
+        assert_eq!(CellStyle::from_spec(&style.to_spec()), style);
+        assert_eq!(CellStyle::new().to_spec(), "");
+        // Unknown specifiers are ignored, like in `Cell::style_spec()`
+        assert_eq!(
+            CellStyle::from_spec("FzBrx"),
+            CellStyle::new().bg(Color::Red)
+        );
+    }
+
+    #[test]
+    fn spec_errors() {
+        let err = |spec| CellStyle::try_from_spec(spec).unwrap_err();
+        assert_eq!(err("x").position(), 0);
+        assert_eq!(err("x").character(), Some('x'));
+        assert_eq!(
+            err("bFz").to_string(),
+            "invalid color 'z' at position 2 in style spec"
+        );
+        assert_eq!(
+            err("ibF").to_string(),
+            "missing color at position 3 in style spec"
+        );
+        assert_eq!(
+            err("Hc").to_string(),
+            "invalid span 'c' at position 1 in style spec"
+        );
+        assert_eq!(err("bH").character(), None);
+        assert_eq!(
+            CellStyle::try_from_spec("FrBYbcH2"),
+            Ok(CellStyle::from_spec("FrBYbcH2"))
+        );
+        assert_eq!(
+            Cell::new("x").try_style_spec("FDBwr").unwrap(),
+            Cell::new("x").style_spec("FDBwr")
+        );
+    }
+
+    #[test]
+    fn same_cells_as_spec() {
+        for spec in &["", "FrBYbc", "FDBwH03r", "Fwiu", "FgBgl"] {
+            assert_eq!(
+                Cell::new("x").with_cell_style(CellStyle::from_spec(spec)),
+                Cell::new("x").style_spec(spec),
+                "spec {:?}",
+                spec
+            );
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! Summary rows aggregating the values of table columns
+
+use std::collections::HashSet;
+
+use super::number::parse_number;
+use super::utils::strip_ansi;
+use super::{column_count, Cell, Row, Table};
+
+/// Aggregation of the values of a column, used by `Table::summarize()`
+#[derive(Clone, Debug, Copy, Hash, PartialEq, Eq)]
+pub enum Aggregation {
+    /// Sum of the numbers
+    Sum,
+    /// Arithmetic mean of the numbers
+    Mean,
+    /// Smallest number
+    Min,
+    /// Largest number
+    Max,
+    /// Count of non-empty cells
+    Count,
+    /// Count of distinct non-empty contents
+    CountDistinct,
+}
+
+/// Characters removed from numbers before parsing them, as they separate
+/// groups of thousands
+const THOUSANDS_SEPARATORS: &[char] = &[',', '\'', '_', ' ', '\u{a0}', '\u{202f}'];
+
+/// Parse the number written in `text`, ignoring ANSI escape sequences and
+/// separators between groups of thousands. Return the number and its count of
+/// decimal places.
+pub(crate) fn parse_value(text: &str) -> Option<(f64, usize)> {
+    let text = strip_ansi(text).replace(THOUSANDS_SEPARATORS, "");
+    let value = parse_number(&text)?;
+    let text = text.trim();
+    let decimals = match text.find('.') {
+        Some(_) if text.contains(['e', 'E']) => 0,
+        Some(dot) => text.len() - dot - 1,
+        None => 0,
+    };
+    Some((value, decimals))
+}
+
+impl Table {
+    /// Return the contents of the cells of column `column`, without their ANSI escape
+    /// sequences. Group headers are skipped.
+    pub(crate) fn column_texts(&self, column: usize) -> impl Iterator<Item = String> + '_ {
+        self.rows
+            .iter()
+            .filter(|row| !row.is_group_header())
+            .filter_map(move |row| row.get_cell(column))
+            .map(|cell| strip_ansi(&cell.get_content()))
+    }
+
+    /// Compute `aggregation` on the cells of column `column`, and return it formatted,
+    /// or `None` if there is no value to aggregate
+    fn aggregate(&self, column: usize, aggregation: Aggregation) -> Option<String> {
+        let texts = self.column_texts(column).filter(|t| !t.trim().is_empty());
+        match aggregation {
+            Aggregation::Count => return Some(texts.count().to_string()),
+            Aggregation::CountDistinct => {
+                let distinct = texts.map(|t| t.trim().to_string()).collect::<HashSet<_>>();
+                return Some(distinct.len().to_string());
+            }
+            _ => {}
+        }
+        let values = texts.filter_map(|t| parse_value(&t)).collect::<Vec<_>>();
+        let decimals = values.iter().map(|&(_, d)| d).max().unwrap_or(0);
+        let numbers = values.iter().map(|&(v, _)| v);
+        let (value, decimals) = match aggregation {
+            Aggregation::Sum => (numbers.fold(0.0, |a, b| a + b), decimals),
+            _ if values.is_empty() => return None,
+            Aggregation::Mean => (numbers.sum::<f64>() / values.len() as f64, decimals.max(2)),
+            Aggregation::Min => (numbers.fold(f64::INFINITY, f64::min), decimals),
+            _ => (numbers.fold(f64::NEG_INFINITY, f64::max), decimals),
+        };
+        Some(format!("{:.*}", decimals, value))
+    }
+
+    /// Return a row summarizing the columns listed in `col_aggregations`, each one with
+    /// its aggregation. The row can be used as footer with `set_footer()`, or added to
+    /// the table.
+    ///
+    /// Cell contents are parsed as numbers once their ANSI escape sequences and separators
+    /// between groups of thousands are removed. Numeric aggregations skip the cells which
+    /// are not numbers, see `skipped_values()`, and format their result with as many
+    /// decimal places as the values, at least 2 for means. Titles and group headers are
+    /// not aggregated, and the other columns of the row are left empty.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// use prettytable::Aggregation;
+    /// # fn main() {
+    /// let table = table![["Bolts", "1,500"], ["Nuts", "250"], ["Screws", "n/a"]];
+    /// let summary = table.summarize(&[(1, Aggregation::Sum), (0, Aggregation::Count)]);
+    /// assert_eq!(summary, ["3", "1750"]);
+    /// assert_eq!(table.skipped_values(1), 1);
+    /// # }
+    /// ```
+    pub fn summarize(&self, col_aggregations: &[(usize, Aggregation)]) -> Row {
+        let columns = column_count(&self.titles, &self.rows);
+        let mut cells = vec![Cell::default(); columns];
+        for &(column, aggregation) in col_aggregations {
+            if column >= cells.len() {
+                cells.resize(column + 1, Cell::default());
+            }
+            if let Some(value) = self.aggregate(column, aggregation) {
+                cells[column] = Cell::new_number(value);
+            }
+        }
+        Row::new(cells)
+    }
+
+    /// Like `summarize()`, with `label` in the first column, unless it is aggregated
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// use prettytable::Aggregation;
+    /// # fn main() {
+    /// let mut table = table![["Bolts", 3.5], ["Nuts", 2]];
+    /// table.set_footer(table.summarize_labeled("Total", &[(1, Aggregation::Sum)]));
+    /// assert_eq!(table.get_footer().unwrap(), &["Total", "5.5"]);
+    /// # }
+    /// ```
+    pub fn summarize_labeled(&self, label: &str, col_aggregations: &[(usize, Aggregation)]) -> Row {
+        let mut row = self.summarize(col_aggregations);
+        if col_aggregations.iter().all(|&(column, _)| column != 0) {
+            match row.get_mut_cell(0) {
+                Some(cell) => *cell = Cell::new(label),
+                None => row.add_cell(Cell::new(label)),
+            }
+        }
+        row
+    }
+
+    /// Return the count of non-empty cells of column `column` which are not numbers,
+    /// and are skipped by the numeric aggregations of `summarize()`
+    pub fn skipped_values(&self, column: usize) -> usize {
+        self.column_texts(column)
+            .filter(|t| !t.trim().is_empty() && parse_value(t).is_none())
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Aggregation;
+    use crate::format::Alignment;
+    use crate::{Cell, Row, Table};
+
+    #[test]
+    fn summarize() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("Item"), Cell::new("1000")]));
+        for (item, qty) in [
+            ("a", "1'000.5"),
+            ("b", "\x1b[31m-2\x1b[0m"),
+            ("a", ""),
+            ("c", "?"),
+        ] {
+            table.add_row(Row::new(vec![Cell::new(item), Cell::new(qty)]));
+        }
+        table.add_row(Row::group_header("Group"));
+        let summary = table.summarize(&[
+            (1, Aggregation::Sum),
+            (0, Aggregation::CountDistinct),
+            (3, Aggregation::Max),
+        ]);
+        assert_eq!(summary, ["3", "998.5", "", ""]);
+        assert_eq!(summary[1].get_align(), Alignment::RIGHT);
+        let stats = [
+            Aggregation::Mean,
+            Aggregation::Min,
+            Aggregation::Max,
+            Aggregation::Count,
+        ]
+        .iter()
+        .map(|&a| table.summarize(&[(1, a)])[1].get_content())
+        .collect::<Vec<_>>();
+        assert_eq!(stats, ["499.25", "-2.0", "1000.5", "3"]);
+        assert_eq!(table.skipped_values(1), 1);
+        assert_eq!(table.skipped_values(0), 4);
+
+        let labeled = table.summarize_labeled("Total", &[(1, Aggregation::Min)]);
+        assert_eq!(labeled, ["Total", "-2.0"]);
+        let labeled = table.summarize_labeled("Total", &[(0, Aggregation::Count)]);
+        assert_eq!(labeled, ["4", ""]);
+        assert_eq!(Table::new().summarize_labeled("Total", &[]), ["Total"]);
+        assert_eq!(Table::new().summarize(&[(0, Aggregation::Sum)]), ["0"]);
+    }
+}
@@ -0,0 +1,136 @@
+//! Textile markup output for tables
+
+use std::io::{Error, Write};
+
+use super::format::Alignment;
+use super::utils::NEWLINE;
+use super::{AsTableSlice, Cell, Row, Table, TableSlice};
+
+/// Format `cell` with its modifiers, `_` marking header cells
+fn format_cell(cell: &Cell, header: bool) -> String {
+    let mut modifiers = String::new();
+    if header {
+        modifiers.push('_');
+    }
+    if cell.get_hspan() > 1 {
+        modifiers += &format!("\\{}", cell.get_hspan());
+    }
+    match cell.get_align() {
+        Alignment::LEFT => {}
+        Alignment::CENTER => modifiers.push('='),
+        Alignment::RIGHT => modifiers.push('>'),
+    }
+    let content = cell
+        .get_content()
+        .replace('|', "&#124;")
+        .replace('\n', "<br />");
+    match (modifiers.is_empty(), content.is_empty()) {
+        (true, true) => " ".to_string(),
+        (true, false) => content,
+        (false, _) => format!("{}. {}", modifiers, content),
+    }
+}
+
+/// Print a single row, padded with empty cells up to `col_num` columns
+fn print_row<T: Write + ?Sized>(
+    out: &mut T,
+    row: &Row,
+    col_num: usize,
+    header: bool,
+) -> Result<(), Error> {
+    let mut line = String::from("|");
+    for cell in row {
+        line += &format_cell(cell, header);
+        line.push('|');
+    }
+    let printed_columns: usize = row.iter().map(Cell::get_hspan).sum();
+    for _ in printed_columns..col_num {
+        line += if header { "_. |" } else { " |" };
+    }
+    out.write_all(line.as_bytes())?;
+    out.write_all(NEWLINE)
+}
+
+impl<'a> TableSlice<'a> {
+    /// Print table in Textile markup to `out`.
+    ///
+    /// Titles are printed as header cells, and alignment markers are added to
+    /// centered and right aligned cells. The footer is printed as the last row, and
+    /// the caption in a `|=.` line above the table. Nothing is printed for an empty table.
+    pub fn print_textile<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        let table = self.exported();
+        let slice = table.as_slice();
+        let column_num = slice.get_column_num();
+        if column_num == 0 {
+            return Ok(());
+        }
+        if let Some(caption) = table.caption.line() {
+            out.write_all(format!("|=. {}", caption.replace('|', "&#124;")).as_bytes())?;
+            out.write_all(NEWLINE)?;
+        }
+        if let Some(ref t) = *slice.titles {
+            print_row(out, t, column_num, true)?;
+        }
+        for r in slice.rows {
+            print_row(out, r, column_num, false)?;
+        }
+        out.flush()
+    }
+}
+
+impl Table {
+    /// Print table in Textile markup to `out`.
+    ///
+    /// See `TableSlice::print_textile()` for details.
+    pub fn print_textile<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        self.as_slice().print_textile(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::Alignment;
+    use crate::utils::StringWriter;
+    use crate::{Cell, Row, Table};
+
+    #[test]
+    fn textile() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![
+            Cell::new("t1"),
+            Cell::new_align("t2", Alignment::CENTER),
+            Cell::new("t3"),
+        ]));
+        table.add_row(Row::new(vec![
+            Cell::new("a|b"),
+            Cell::new_align("c", Alignment::RIGHT),
+            Cell::new(""),
+        ]));
+        table.add_row(Row::new(vec![Cell::new("d\ne").with_hspan(2)]));
+        let out = "\
+|_. t1|_=. t2|_. t3|
+|a&#124;b|>. c| |
+|\\2. d<br />e| |
+";
+        let mut writer = StringWriter::new();
+        assert!(table.print_textile(&mut writer).is_ok());
+        assert_eq!(writer.as_string().replace("\r\n", "\n"), out);
+
+        table.set_footer(Row::new(vec![Cell::new("f")]));
+        table.set_caption("Letters", Alignment::CENTER);
+        let mut writer = StringWriter::new();
+        assert!(table.print_textile(&mut writer).is_ok());
+        let out = writer.as_string().replace("\r\n", "\n");
+        assert!(out.starts_with("|=. Letters\n|_. t1|"));
+        assert!(out.ends_with("|\\2. d<br />e| |\n|f| | |\n"));
+    }
+
+    #[test]
+    fn empty_textile() {
+        let mut table = Table::new();
+        table.add_empty_row();
+        let mut writer = StringWriter::new();
+        assert!(table.print_textile(&mut writer).is_ok());
+        assert_eq!(writer.as_string(), "");
+    }
+}
@@ -1,11 +1,11 @@
 //! Internal only utilities
 use std::fmt;
-use std::io::{Error, ErrorKind, Write};
+use std::io::{Error, Write};
 use std::str;
 
-use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use unicode_width::UnicodeWidthChar;
 
-use super::format::Alignment;
+use super::format::{Alignment, WidthMode};
 
 #[cfg(any(not(windows), not(feature = "win_crlf")))]
 pub static NEWLINE: &[u8] = b"\n";
@@ -35,12 +35,7 @@ impl Write for StringWriter {
     fn write(&mut self, data: &[u8]) -> Result<usize, Error> {
         let string = match str::from_utf8(data) {
             Ok(s) => s,
-            Err(e) => {
-                return Err(Error::new(
-                    ErrorKind::Other,
-                    format!("Cannot decode utf8 string : {}", e),
-                ))
-            }
+            Err(e) => return Err(Error::other(format!("Cannot decode utf8 string : {}", e))),
         };
         self.string.push_str(string);
         Ok(data.len())
@@ -52,31 +47,42 @@ impl Write for StringWriter {
     }
 }
 
-/// Align/fill a string and print it to `out`
-/// If `skip_right_fill` is set to `true`, then no space will be added after the string
+/// Write `width` display columns of `fill` to `out`. When `fill` is a wide char,
+/// the columns it cannot cover are filled with spaces
+fn write_fill<T: Write + ?Sized>(out: &mut T, fill: char, width: usize) -> Result<(), Error> {
+    let fill_width = UnicodeWidthChar::width(fill).unwrap_or(1).max(1);
+    let mut buf = [0; 4];
+    let fill = fill.encode_utf8(&mut buf).as_bytes();
+    out.write_all(&fill.repeat(width / fill_width))?;
+    out.write_all(&vec![b' '; width % fill_width])
+}
+
+/// Align/fill a string and print it to `out`, `text_width` being the width the string
+/// takes on screen.
+/// If `skip_right_fill` is set to `true`, then no fill char will be added after the string
 /// to complete alignment
 pub fn print_align<T: Write + ?Sized>(
     out: &mut T,
     align: Alignment,
     text: &str,
+    text_width: usize,
     fill: char,
     size: usize,
     skip_right_fill: bool,
 ) -> Result<(), Error> {
-    let text_len = display_width(text);
-    let mut nfill = if text_len < size { size - text_len } else { 0 };
+    let mut nfill = size.saturating_sub(text_width);
     let n = match align {
         Alignment::LEFT => 0,
         Alignment::RIGHT => nfill,
         Alignment::CENTER => nfill / 2,
     };
     if n > 0 {
-        out.write_all(&vec![fill as u8; n])?;
+        write_fill(out, fill, n)?;
         nfill -= n;
     }
     out.write_all(text.as_bytes())?;
     if nfill > 0 && !skip_right_fill {
-        out.write_all(&vec![fill as u8; nfill])?;
+        write_fill(out, fill, nfill)?;
     }
     Ok(())
 }
@@ -84,103 +90,403 @@ pub fn print_align<T: Write + ?Sized>(
 /// Return the display width of a unicode string.
 /// This functions takes ANSI-escaped color codes into account.
 pub fn display_width(text: &str) -> usize {
-    #[derive(PartialEq, Eq, Clone, Copy)]
-    enum State {
-        /// We are not inside any terminal escape.
-        Normal,
-        /// We have just seen a \u{1b}
-        EscapeChar,
-        /// We have just seen a [
-        OpenBracket,
-        /// We just ended the escape by seeing an m
-        AfterEscape,
-        /// We are inside an OSC sequence: ESC ] ...
-        Osc,
-        /// We saw ESC inside an OSC sequence, need to check if it's followed by '\'.
-        OscEscapeChar,
-    }
-
-    let width = UnicodeWidthStr::width(text);
-    let mut state = State::Normal;
-    let mut hidden = 0;
-
-    for c in text.chars() {
-        let normalized = c.to_string();
-        match state {
-            State::Normal => {
-                if c == '\u{1b}' {
-                    state = State::EscapeChar;
+    VisibleChars::new(text)
+        .map(|c| UnicodeWidthChar::width(c).unwrap_or(0))
+        .sum()
+}
+
+/// Return the display width of a unicode string, measured by character clusters.
+///
+/// Each cluster (see `segments`) takes the width of its widest char, and clusters
+/// ending an emoji presentation selector take 2 columns. This matches terminals
+/// drawing zero-width-joined emoji sequences as a single glyph.
+/// This functions takes ANSI-escaped color codes into account.
+pub fn grapheme_width(text: &str) -> usize {
+    segments(text)
+        .into_iter()
+        .map(|segment| match segment {
+            Segment::Escape(..) => 0,
+            Segment::Cluster(cluster, _) => {
+                let width = cluster
+                    .chars()
+                    .map(|c| UnicodeWidthChar::width(c).unwrap_or(0))
+                    .max()
+                    .unwrap_or(0);
+                if cluster.contains('\u{fe0f}') {
+                    width.max(2)
+                } else {
+                    width
                 }
             }
-            State::EscapeChar => {
-                if c == '[' {
-                    // CSI sequence
-                    state = State::OpenBracket;
-                } else if c == ']' {
-                    // OSC sequence
-                    state = State::Osc;
-                    hidden += 2;
-                } else {
-                    // Not recognized, return to normal
-                    state = State::Normal;
+        })
+        .sum()
+}
+
+/// Return a copy of `text` with tabs replaced by spaces up to the next multiple
+/// of `tab_width` columns, or by a visible `\t` if `tab_width` is 0.
+/// Columns are counted from the start of each line, ignoring escape sequences.
+pub fn expand_tabs(text: &str, tab_width: usize) -> String {
+    if tab_width == 0 {
+        return text.replace('\t', "\\t");
+    }
+    let mut expanded = String::with_capacity(text.len());
+    let mut column = 0;
+    for segment in segments(text) {
+        match segment {
+            Segment::Escape(esc) => expanded.push_str(esc),
+            Segment::Cluster(cluster, width) if !cluster.contains(['\t', '\n']) => {
+                expanded.push_str(cluster);
+                column += width;
+            }
+            // Control chars have no width, so they stick to the previous cluster
+            Segment::Cluster(cluster, _) => {
+                for c in cluster.chars() {
+                    match c {
+                        '\t' => {
+                            let n = tab_width - column % tab_width;
+                            expanded.extend(std::iter::repeat_n(' ', n));
+                            column += n;
+                        }
+                        '\n' => {
+                            expanded.push('\n');
+                            column = 0;
+                        }
+                        c => {
+                            expanded.push(c);
+                            column += UnicodeWidthChar::width(c).unwrap_or(0);
+                        }
+                    }
                 }
             }
-            State::OpenBracket => {
-                // If we still see printable characters here,
-                // count them as hidden (ANSI code).
-                if c == 'm' {
-                    // End of a typical CSI sequence
-                    state = State::AfterEscape;
-                } else if c == '\u{1b}' {
-                    // Another escape inside
-                    state = State::EscapeChar;
+        }
+    }
+    expanded
+}
+
+/// Return a copy of `text` with all terminal escape sequences removed.
+pub fn strip_ansi(text: &str) -> String {
+    VisibleChars::new(text).collect()
+}
+
+/// Iterator over the characters of a string which are not part of a terminal
+/// escape sequence (CSI sequences like colors, and OSC sequences like hyperlinks)
+pub struct VisibleChars<'a> {
+    chars: str::Chars<'a>,
+    state: EscapeState,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum EscapeState {
+    /// We are not inside any terminal escape.
+    Normal,
+    /// We have just seen a \u{1b}
+    EscapeChar,
+    /// We are inside a CSI sequence: ESC [ ...
+    Csi,
+    /// We are inside an OSC sequence: ESC ] ...
+    Osc,
+    /// We saw ESC inside an OSC sequence, need to check if it's followed by '\'.
+    OscEscapeChar,
+}
+
+impl<'a> VisibleChars<'a> {
+    /// Create a new iterator over visible chars of `text`
+    pub fn new(text: &'a str) -> VisibleChars<'a> {
+        VisibleChars {
+            chars: text.chars(),
+            state: EscapeState::Normal,
+        }
+    }
+}
+
+impl<'a> Iterator for VisibleChars<'a> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        for c in self.chars.by_ref() {
+            self.state = match (self.state, c) {
+                (EscapeState::Normal, '\u{1b}') => EscapeState::EscapeChar,
+                (EscapeState::Normal, _) => return Some(c),
+                (EscapeState::EscapeChar, '[') => EscapeState::Csi,
+                (EscapeState::EscapeChar, ']') => EscapeState::Osc,
+                // Not recognized, return to normal
+                (EscapeState::EscapeChar, _) => EscapeState::Normal,
+                // Another escape inside
+                (EscapeState::Csi, '\u{1b}') => EscapeState::EscapeChar,
+                // A CSI sequence ends with a char in the '@'..='~' range, like 'm' for SGR
+                (EscapeState::Csi, '@'..='~') => EscapeState::Normal,
+                (EscapeState::Csi, _) => EscapeState::Csi,
+                // Inside an OSC sequence, skip everything until we see BEL or ESC \
+                (EscapeState::Osc, '\u{7}') => EscapeState::Normal,
+                (EscapeState::Osc, '\u{1b}') => EscapeState::OscEscapeChar,
+                (EscapeState::Osc, _) => EscapeState::Osc,
+                (EscapeState::OscEscapeChar, '\\') => EscapeState::Normal,
+                (EscapeState::OscEscapeChar, _) => EscapeState::Osc,
+            };
+        }
+        None
+    }
+}
+
+/// A piece of text as seen by the wrapping and truncation code
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Segment<'a> {
+    /// A terminal escape sequence, taking no room on screen
+    Escape(&'a str),
+    /// A user-perceived character, with a base char followed by its combining marks,
+    /// variation selectors and zero-width-joined chars, along with its display width
+    Cluster(&'a str, usize),
+}
+
+/// Split `text` into escape sequences and character clusters.
+///
+/// Clusters are approximated without the full Unicode segmentation tables:
+/// zero-width chars stick to the previous char, and a zero width joiner also
+/// glues the following char, which keeps CJK, accented letters and most emoji
+/// sequences in one piece.
+pub fn segments(text: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if c == '\u{1b}' {
+            match chars.next() {
+                Some((_, '[')) => {
+                    for (_, c) in chars.by_ref() {
+                        if ('@'..='~').contains(&c) {
+                            break;
+                        }
+                    }
                 }
-                if UnicodeWidthChar::width(c).unwrap_or(0) > 0 {
-                    hidden += 1;
+                Some((_, ']')) => {
+                    while let Some((_, c)) = chars.next() {
+                        if c == '\u{7}' {
+                            break;
+                        }
+                        if c == '\u{1b}' {
+                            if let Some(&(_, '\\')) = chars.peek() {
+                                chars.next();
+                                break;
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+            let end = chars.peek().map(|&(i, _)| i).unwrap_or(text.len());
+            segments.push(Segment::Escape(&text[start..end]));
+            continue;
+        }
+        let mut width = UnicodeWidthChar::width(c).unwrap_or(0);
+        let mut joined = c == '\u{200d}';
+        while let Some(&(_, next)) = chars.peek() {
+            let w = UnicodeWidthChar::width(next).unwrap_or(0);
+            if next == '\u{1b}' || (w > 0 && !joined) {
+                break;
+            }
+            width += w;
+            joined = next == '\u{200d}';
+            chars.next();
+        }
+        let end = chars.peek().map(|&(i, _)| i).unwrap_or(text.len());
+        segments.push(Segment::Cluster(&text[start..end], width));
+    }
+    segments
+}
+
+/// Split `text` like `segments()`, with the width of each cluster measured with `mode`
+fn segments_with(text: &str, mode: WidthMode) -> Vec<Segment<'_>> {
+    let mut segments = segments(text);
+    if !matches!(mode, WidthMode::Unicode) {
+        for segment in &mut segments {
+            if let Segment::Cluster(s, ref mut w) = *segment {
+                *w = mode.width(s);
+            }
+        }
+    }
+    segments
+}
+
+/// Keeps track of the SGR sequences (colors and attributes) in effect,
+/// so they can be closed at the end of a line and reopened on the next one
+#[derive(Default)]
+pub struct SgrState {
+    active: Vec<String>,
+}
+
+impl SgrState {
+    /// Update the state with escape sequence `esc`
+    pub fn update(&mut self, esc: &str) {
+        if !esc.starts_with("\u{1b}[") || !esc.ends_with('m') {
+            return;
+        }
+        if esc == "\u{1b}[m" || esc == "\u{1b}[0m" {
+            self.active.clear();
+        } else {
+            self.active.push(esc.to_string());
+        }
+    }
+
+    /// Tell if some style is currently applied
+    pub fn is_active(&self) -> bool {
+        !self.active.is_empty()
+    }
+
+    /// Return the sequences reopening the styles in effect
+    pub fn prefix(&self) -> String {
+        self.active.concat()
+    }
+}
+
+/// Assemble wrapped lines, carrying terminal styles across them
+struct LineBuilder {
+    lines: Vec<String>,
+    line: String,
+    width: usize,
+    state: SgrState,
+}
+
+impl LineBuilder {
+    fn push(&mut self, segment: Segment) {
+        match segment {
+            Segment::Escape(esc) => {
+                self.state.update(esc);
+                self.line.push_str(esc);
+            }
+            Segment::Cluster(s, w) => {
+                self.line.push_str(s);
+                self.width += w;
+            }
+        }
+    }
+
+    fn break_line(&mut self) {
+        let mut line = std::mem::replace(&mut self.line, self.state.prefix());
+        if self.state.is_active() {
+            line.push_str("\u{1b}[0m");
+        }
+        self.lines.push(line);
+        self.width = 0;
+    }
+}
+
+/// Wrap `text` so that no line is wider than `width` display columns.
+///
+/// Lines are broken at whitespace when possible, and words too long to fit are
+/// broken between character clusters. Existing line breaks are kept. Terminal color
+/// sequences are closed at the end of each wrapped line and reopened on the next one.
+/// A cluster wider than `width` is put alone on its line. Text widths are measured
+/// with `mode`.
+pub fn wrap_text(text: &str, width: usize, mode: WidthMode) -> Vec<String> {
+    let mut builder = LineBuilder {
+        lines: Vec::new(),
+        line: String::new(),
+        width: 0,
+        state: SgrState::default(),
+    };
+    for (n, source) in text.lines().enumerate() {
+        if n > 0 {
+            builder.break_line();
+        }
+        let segments = segments_with(source, mode);
+        let mut i = 0;
+        let mut started = false;
+        while i < segments.len() {
+            // Collect the whitespace before the next word, then the word itself
+            let mut spaces = Vec::new();
+            let mut space_width = 0;
+            while let Some(&seg) = segments.get(i) {
+                match seg {
+                    Segment::Cluster(s, w) if s.chars().all(char::is_whitespace) => {
+                        space_width += w;
+                    }
+                    Segment::Cluster(..) => break,
+                    Segment::Escape(..) => {}
                 }
+                spaces.push(seg);
+                i += 1;
             }
-            State::AfterEscape => {
-                // Transition back to normal
-                state = State::Normal;
-                // The character that ended the escape is hidden as well
-                if UnicodeWidthChar::width(c).unwrap_or(0) > 0 {
-                    hidden += 1;
+            let mut word = Vec::new();
+            let mut word_width = 0;
+            while let Some(&seg) = segments.get(i) {
+                match seg {
+                    Segment::Cluster(s, _) if s.chars().all(char::is_whitespace) => break,
+                    Segment::Cluster(_, w) => word_width += w,
+                    Segment::Escape(..) => {}
                 }
+                word.push(seg);
+                i += 1;
             }
-            State::Osc => {
-                // Inside an OSC sequence, skip everything until we see ESC \
-                if c == '\u{1b}' {
-                    state = State::OscEscapeChar;
+            if !started || builder.width + space_width + word_width <= width {
+                // Leading indentation is kept, and so are spaces between words on the same line
+                if started && word.is_empty() {
+                    // Trailing whitespace is dropped
+                    spaces.retain(|s| matches!(s, Segment::Escape(..)));
                 }
-                // If it's printable, hide it (it's part of the OSC sequence).
-                if UnicodeWidthChar::width(c).unwrap_or(0) > 0 {
-                    hidden += 1;
+                started = true;
+                word.splice(0..0, spaces);
+            } else {
+                for seg in spaces {
+                    if let Segment::Escape(..) = seg {
+                        builder.push(seg);
+                    }
+                }
+                if word_width > 0 && builder.width > 0 {
+                    builder.break_line();
                 }
             }
-            State::OscEscapeChar => {
-                // If we see '\', it ends the OSC sequence, otherwise stay in OSC
-                if c == '\\' {
-                    state = State::Normal;
-                    hidden += 2;
-                } else {
-                    state = State::Osc;
-                    if UnicodeWidthChar::width(c).unwrap_or(0) > 0 {
-                        hidden += 1;
+            for seg in word {
+                if let Segment::Cluster(_, w) = seg {
+                    if builder.width > 0 && builder.width + w > width {
+                        builder.break_line();
                     }
                 }
+                builder.push(seg);
             }
         }
     }
+    builder.break_line();
+    builder.lines
+}
 
-    assert!(
-        width >= hidden,
-        "internal error: width {} less than hidden {} on string {:?}",
-        width,
-        hidden,
-        text
-    );
-
-    width - hidden
+/// Cut `text` so that it is at most `width` display columns wide, ending it with `marker`
+/// when something was cut.
+///
+/// Characters are never split, and colors still applied at the cut are closed
+/// with a reset sequence before the marker. If `marker` itself is wider than `width`,
+/// the text is cut without it. Text widths are measured with `mode`.
+pub fn truncate_text(text: &str, width: usize, marker: &str, mode: WidthMode) -> String {
+    if mode.width(text) <= width {
+        return text.to_string();
+    }
+    let marker_width = mode.width(marker);
+    let (marker, budget) = if marker_width > width {
+        ("", width)
+    } else {
+        (marker, width - marker_width)
+    };
+    let mut truncated = String::new();
+    let mut used = 0;
+    let mut state = SgrState::default();
+    for segment in segments_with(text, mode) {
+        match segment {
+            Segment::Escape(esc) => {
+                state.update(esc);
+                truncated.push_str(esc);
+            }
+            Segment::Cluster(s, w) => {
+                if used + w > budget {
+                    break;
+                }
+                used += w;
+                truncated.push_str(s);
+            }
+        }
+    }
+    if state.is_active() {
+        truncated.push_str("\u{1b}[0m");
+    }
+    truncated.push_str(marker);
+    truncated
 }
 
 /// Wrapper struct which will emit the HTML-escaped version of the contained
@@ -261,41 +567,180 @@ mod tests {
         // assert_eq!(display_width(nested), display_width("Bold Link"));
     }
 
+    #[test]
+    fn strip_ansi_sequences() {
+        assert_eq!(strip_ansi("\x1B[31mred\x1B[0m text"), "red text");
+        assert_eq!(
+            strip_ansi("\x1B]8;;https://example.com\x1B\\link\x1B]8;;\x1B\\"),
+            "link"
+        );
+        assert_eq!(strip_ansi("plain"), "plain");
+    }
+
+    #[test]
+    fn wrap_words() {
+        assert_eq!(
+            wrap_text("The quick brown fox", 9, WidthMode::Unicode),
+            ["The quick", "brown fox"]
+        );
+        assert_eq!(
+            wrap_text("a  b   c", 3, WidthMode::Unicode),
+            ["a", "b", "c"]
+        );
+        assert_eq!(
+            wrap_text("  indented text", 10, WidthMode::Unicode),
+            ["  indented", "text"]
+        );
+        assert_eq!(wrap_text("", 5, WidthMode::Unicode), [""]);
+        assert_eq!(
+            wrap_text("ab\n\ncd", 5, WidthMode::Unicode),
+            ["ab", "", "cd"]
+        );
+    }
+
+    #[test]
+    fn wrap_long_words() {
+        assert_eq!(
+            wrap_text("ab abcdefgh", 4, WidthMode::Unicode),
+            ["ab", "abcd", "efgh"]
+        );
+        assert_eq!(
+            wrap_text("由系统自动更新", 5, WidthMode::Unicode),
+            ["由系", "统自", "动更", "新"]
+        );
+        // Combining marks and joined emoji stay with their base char
+        assert_eq!(
+            wrap_text("e\u{301}e\u{301}e\u{301}", 2, WidthMode::Unicode),
+            ["e\u{301}e\u{301}", "e\u{301}"]
+        );
+        assert_eq!(
+            segments("👩\u{200d}🔬x"),
+            [
+                Segment::Cluster("👩\u{200d}🔬", 4),
+                Segment::Cluster("x", 1)
+            ]
+        );
+        // A char wider than the limit is kept alone on its line
+        assert_eq!(wrap_text("由a", 1, WidthMode::Unicode), ["由", "a"]);
+    }
+
+    #[test]
+    fn wrap_colors() {
+        assert_eq!(
+            wrap_text("\x1b[31mred text\x1b[0m plain", 5, WidthMode::Unicode),
+            ["\x1b[31mred\x1b[0m", "\x1b[31mtext\x1b[0m", "plain"]
+        );
+        for line in wrap_text("\x1b[1;32mbold green words\x1b[0m", 5, WidthMode::Unicode) {
+            assert!(display_width(&line) <= 5);
+        }
+    }
+
+    #[test]
+    fn truncate() {
+        assert_eq!(truncate_text("short", 5, "…", WidthMode::Unicode), "short");
+        assert_eq!(
+            truncate_text("too long", 5, "…", WidthMode::Unicode),
+            "too …"
+        );
+        assert_eq!(
+            truncate_text("由系统自动更新", 6, "…", WidthMode::Unicode),
+            "由系…"
+        );
+        assert_eq!(
+            truncate_text("abcdef", 3, "[...]", WidthMode::Unicode),
+            "abc"
+        );
+        assert_eq!(
+            truncate_text("\x1b[31mred text\x1b[0m", 4, "…", WidthMode::Unicode),
+            "\x1b[31mred\x1b[0m…"
+        );
+    }
+
     #[test]
     fn fill_align() {
         let mut out = StringWriter::new();
-        print_align(&mut out, Alignment::RIGHT, "foo", '*', 10, false).unwrap();
+        print_align(&mut out, Alignment::RIGHT, "foo", 3, '*', 10, false).unwrap();
         assert_eq!(out.as_string(), "*******foo");
 
         let mut out = StringWriter::new();
-        print_align(&mut out, Alignment::LEFT, "foo", '*', 10, false).unwrap();
+        print_align(&mut out, Alignment::LEFT, "foo", 3, '*', 10, false).unwrap();
         assert_eq!(out.as_string(), "foo*******");
 
         let mut out = StringWriter::new();
-        print_align(&mut out, Alignment::CENTER, "foo", '*', 10, false).unwrap();
+        print_align(&mut out, Alignment::CENTER, "foo", 3, '*', 10, false).unwrap();
         assert_eq!(out.as_string(), "***foo****");
 
         let mut out = StringWriter::new();
-        print_align(&mut out, Alignment::CENTER, "foo", '*', 1, false).unwrap();
+        print_align(&mut out, Alignment::CENTER, "foo", 3, '*', 1, false).unwrap();
         assert_eq!(out.as_string(), "foo");
     }
 
+    #[test]
+    fn fill_unicode() {
+        let mut out = StringWriter::new();
+        print_align(&mut out, Alignment::LEFT, "foo", 3, '·', 6, false).unwrap();
+        assert_eq!(out.as_string(), "foo···");
+
+        let mut out = StringWriter::new();
+        print_align(&mut out, Alignment::RIGHT, "foo", 3, '由', 8, false).unwrap();
+        assert_eq!(out.as_string(), "由由 foo");
+    }
+
+    #[test]
+    fn wrap_and_truncate_with_width_mode() {
+        let double = WidthMode::Custom(|s| 2 * s.chars().count());
+        assert_eq!(wrap_text("ab cd", 2, double), ["a", "b", "c", "d"]);
+        assert_eq!(wrap_text("ab cd", 5, double), ["ab", "cd"]);
+        assert_eq!(truncate_text("abcd", 4, "…", double), "a…");
+        assert_eq!(truncate_text("ab", 4, "…", double), "ab");
+    }
+
+    #[test]
+    fn grapheme_widths() {
+        assert_eq!(grapheme_width("abc"), 3);
+        assert_eq!(grapheme_width("e\u{301}t\u{e9}"), 3);
+        assert_eq!(
+            display_width("\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}"),
+            6
+        );
+        assert_eq!(
+            grapheme_width("\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}"),
+            2
+        );
+        assert_eq!(grapheme_width("\u{2764}\u{fe0f}"), 2);
+        assert_eq!(grapheme_width("\x1b[31m\u{4e2d}\x1b[0m"), 2);
+    }
+
+    #[test]
+    fn tabs() {
+        assert_eq!(expand_tabs("a\tb", 4), "a   b");
+        assert_eq!(expand_tabs("\tab\tc", 4), "    ab  c");
+        assert_eq!(expand_tabs("abcd\te", 4), "abcd    e");
+        assert_eq!(expand_tabs("ab\n\tc", 3), "ab\n   c");
+        assert_eq!(
+            expand_tabs("\x1b[1mab\x1b[0m\tc", 8),
+            "\x1b[1mab\x1b[0m      c"
+        );
+        assert_eq!(expand_tabs("\u{4e2d}\tx", 4), "\u{4e2d}  x");
+        assert_eq!(expand_tabs("a\tb", 0), "a\\tb");
+    }
+
     #[test]
     fn skip_right_fill() {
         let mut out = StringWriter::new();
-        print_align(&mut out, Alignment::RIGHT, "foo", '*', 10, true).unwrap();
+        print_align(&mut out, Alignment::RIGHT, "foo", 3, '*', 10, true).unwrap();
         assert_eq!(out.as_string(), "*******foo");
 
         let mut out = StringWriter::new();
-        print_align(&mut out, Alignment::LEFT, "foo", '*', 10, true).unwrap();
+        print_align(&mut out, Alignment::LEFT, "foo", 3, '*', 10, true).unwrap();
         assert_eq!(out.as_string(), "foo");
 
         let mut out = StringWriter::new();
-        print_align(&mut out, Alignment::CENTER, "foo", '*', 10, true).unwrap();
+        print_align(&mut out, Alignment::CENTER, "foo", 3, '*', 10, true).unwrap();
         assert_eq!(out.as_string(), "***foo");
 
         let mut out = StringWriter::new();
-        print_align(&mut out, Alignment::CENTER, "foo", '*', 1, false).unwrap();
+        print_align(&mut out, Alignment::CENTER, "foo", 3, '*', 1, false).unwrap();
         assert_eq!(out.as_string(), "foo");
     }
 
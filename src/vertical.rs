@@ -0,0 +1,206 @@
+//! Vertical layout of tables, printing each row as a record of labeled values
+
+use std::io::{Error, Write};
+
+use super::fit::{terminal_width, DEFAULT_MIN_COLUMN_WIDTH};
+use super::format::{Alignment, TableFormat};
+use super::render::expand_row_tabs;
+use super::utils::NEWLINE;
+use super::{column_widths, Cell, Row, Table, Terminal};
+
+/// Count of stars on each side of the record separators
+const SEPARATOR_STARS: usize = 27;
+
+impl Table {
+    /// Internal only
+    fn __print_vertical<T: Write + ?Sized, F>(
+        &self,
+        out: &mut T,
+        width: usize,
+        f: F,
+    ) -> Result<usize, Error>
+    where
+        F: Fn(&Row, &mut T, &TableFormat, &[usize]) -> Result<usize, Error>,
+    {
+        let table = self.rendered();
+        let tab_width = table.format.get_tab_width();
+        let mut format = TableFormat::new();
+        format.padding(0, 0);
+        format.indent(table.format.get_indent());
+        format.width_mode(table.format.get_width_mode());
+        // Labels of the columns, the titles spanning several columns labelling all of them
+        let mut labels = Vec::new();
+        for cell in table.titles.iter().flat_map(Row::iter) {
+            let mut label = cell.with_content(&format!("{}:", cell.get_content()));
+            label.align(Alignment::RIGHT);
+            for _ in 0..cell.get_hspan() {
+                labels.push(label.clone().with_hspan(1));
+            }
+        }
+        let columns = table.column_count();
+        labels.extend(
+            (labels.len()..columns)
+                .map(|c| Cell::new_align(&format!("col {}:", c + 1), Alignment::RIGHT)),
+        );
+        let label_width = labels
+            .iter()
+            .map(|l| l.get_width_with(format.get_width_mode()))
+            .max()
+            .unwrap_or(0);
+        // Room left to the values, after the label and a space
+        let value_width = width
+            .saturating_sub(format.get_indent() + label_width + 1)
+            .max(DEFAULT_MIN_COLUMN_WIDTH);
+        let mut height = 0;
+        for (i, row) in table.rows.iter().enumerate() {
+            let row = expand_row_tabs(&row.styled(true).unwrap_or_else(|| row.clone()), tab_width);
+            let mut column = 0;
+            let mut fields = Vec::with_capacity(row.len());
+            for cell in row.iter() {
+                // Empty values are not padded, to leave no trailing space after the label
+                let padding = usize::from(!cell.get_content().is_empty());
+                let mut value = cell.clone().with_hspan(1).with_padding(padding, 0);
+                value.fit_width(value_width);
+                let value = value
+                    .measured_with(format.get_width_mode())
+                    .unwrap_or(value);
+                fields.push(Row::new(vec![labels[column].clone(), value]));
+                column += cell.get_hspan();
+            }
+            let separator = "*".repeat(SEPARATOR_STARS);
+            write!(out, "{} {}. row {}", separator, i + 1, separator)?;
+            out.write_all(NEWLINE)?;
+            height += 1;
+            // Labels are aligned across all the records
+            let mut col_width = column_widths(&format, &None, &fields);
+            if let Some(width) = col_width.first_mut() {
+                *width = label_width;
+            }
+            for field in &fields {
+                height += f(field, out, &format, &col_width)?;
+            }
+        }
+        out.flush()?;
+        Ok(height)
+    }
+
+    /// Print the rows of the table vertically to `out`, as records separated by a line of
+    /// stars, and return the number of lines printed, or an error.
+    ///
+    /// Each cell is printed on its own line after its label, which is the title of its
+    /// column, or `col N` for columns without a title. Labels are right aligned, and values
+    /// are wrapped to the width of the terminal, as for `print_fit_tty()`. The titles above
+    /// the last one and the footer are not printed.
+    pub fn print_vertical<T: Write + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
+        self.print_vertical_width(out, terminal_width())
+    }
+
+    /// Print the rows of the table vertically to `out`, like `print_vertical()`, wrapping
+    /// the values so that the lines are at most `width` display columns wide when possible
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate prettytable;
+    /// # fn main() {
+    /// let mut table = table![["Alice", 34], ["Bob", 12, "extra"]];
+    /// table.set_titles(row!["Name", "Age"]);
+    /// let mut out = Vec::new();
+    /// table.print_vertical_width(&mut out, 80).unwrap();
+    /// let stars = "*".repeat(27);
+    /// let expected = format!(
+    ///     "{0} 1. row {0}\n Name: Alice\n  Age: 34\n{0} 2. row {0}\n Name: Bob\n  Age: 12\ncol 3: extra\n",
+    ///     stars
+    /// );
+    /// assert_eq!(String::from_utf8(out).unwrap().replace("\r\n", "\n"), expected);
+    /// # }
+    /// ```
+    pub fn print_vertical_width<T: Write + ?Sized>(
+        &self,
+        out: &mut T,
+        width: usize,
+    ) -> Result<usize, Error> {
+        self.__print_vertical(out, width, Row::print)
+    }
+
+    /// Print the rows of the table vertically to terminal `out`, like `print_vertical()`,
+    /// applying the styles of the cells to the values
+    pub fn print_vertical_term<T: Terminal + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
+        self.__print_vertical(out, terminal_width(), Row::print_term)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cell, Row, Table};
+
+    fn print(table: &Table, width: usize) -> String {
+        let mut out = Vec::new();
+        table.print_vertical_width(&mut out, width).unwrap();
+        String::from_utf8(out).unwrap().replace("\r\n", "\n")
+    }
+
+    #[test]
+    fn vertical() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![
+            Cell::new("id"),
+            Cell::new("description").with_hspan(2),
+        ]));
+        table.add_row(Row::new(vec![
+            Cell::new("1"),
+            Cell::new("a rather long description"),
+            Cell::new("two\nlines"),
+        ]));
+        table.add_row(Row::new(vec![
+            Cell::new("2").with_hspan(2),
+            Cell::new(""),
+            Cell::new("extra"),
+        ]));
+        let out = "\
+*************************** 1. row ***************************
+         id: 1
+description: a rather
+             long
+             description
+description: two
+             lines
+*************************** 2. row ***************************
+         id: 2
+description:
+      col 4: extra
+";
+        assert_eq!(print(&table, 25), out);
+        assert_eq!(print(&Table::new(), 25), "");
+    }
+
+    /// Return a terminal writing into a buffer, supporting colors
+    fn test_terminal() -> term::TerminfoTerminal<Vec<u8>> {
+        let mut info = term::terminfo::TermInfo {
+            names: Vec::new(),
+            bools: Default::default(),
+            numbers: Default::default(),
+            strings: Default::default(),
+        };
+        info.numbers.insert("colors", 8);
+        info.strings.insert("setaf", b"\x1b[3%p1%dm".to_vec());
+        info.strings.insert("setab", b"\x1b[4%p1%dm".to_vec());
+        info.strings.insert("sgr0", b"\x1b[0m".to_vec());
+        term::TerminfoTerminal::new_with_terminfo(Vec::new(), info)
+    }
+
+    #[test]
+    fn vertical_styles() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("a")]));
+        let mut row = Row::new(vec![Cell::new("x")]);
+        row.set_style_spec("Fr");
+        table.add_row(row);
+        table.set_empty_cell_placeholder("-");
+        table.add_row(Row::new(vec![Cell::new("")]));
+        let mut out = test_terminal();
+        table.print_vertical_term(&mut out).unwrap();
+        let out = String::from_utf8_lossy(term::Terminal::get_ref(&out)).into_owned();
+        assert!(out.contains("a:\x1b[0m \x1b[31mx\x1b[0m\n"));
+        assert!(out.contains("a:\x1b[0m -\x1b[0m\n"));
+    }
+}
@@ -0,0 +1,312 @@
+//! Lazy views combining column selection, row filtering, sorting and limits
+
+use std::borrow::Borrow;
+use std::fmt;
+use std::io::{self, Error, Write};
+
+use super::format::TableFormat;
+use super::sort::sort_rows;
+use super::utils::StringWriter;
+use super::{print_rows, stdout, AsTableSlice, Row, SortOrder, Table, Terminal};
+
+/// Predicate on the rows of a table, kept by a view
+type RowFilter<'a> = Box<dyn Fn(&Row) -> bool + 'a>;
+
+/// A row of the table kept by a view, along with its position in the table
+struct ViewRow<'a> {
+    position: usize,
+    row: &'a Row,
+}
+
+impl Borrow<Row> for ViewRow<'_> {
+    fn borrow(&self) -> &Row {
+        self.row
+    }
+}
+
+/// A lazy view on a table, selecting some of its columns and rows, as obtained with
+/// `Table::view()`.
+///
+/// Building the view only records the steps. The rows are filtered, sorted and limited
+/// when the view is printed, or turned into a table with `to_table()`. Columns given to
+/// the steps are the columns of the table, whatever the columns selected for display.
+///
+/// # Examples
+/// ```rust
+/// # #[macro_use] extern crate prettytable;
+/// use prettytable::SortOrder;
+/// # fn main() {
+/// let table = table![["db", "eu", 12], ["web", "us", 250], ["cache", "eu", 40], ["api", "eu", 7]];
+/// let view = table
+///     .view()
+///     .columns(&[0, 2])
+///     .filter(|row| row[1].get_content() == "eu")
+///     .sort_by_column(2, SortOrder::Descending)
+///     .limit(2);
+/// assert_eq!(view.to_table(), table![["cache", 40], ["db", 12]]);
+/// # }
+/// ```
+pub struct TableView<'a> {
+    table: &'a Table,
+    columns: Option<Vec<usize>>,
+    filters: Vec<RowFilter<'a>>,
+    sorts: Vec<(usize, SortOrder)>,
+    limit: Option<usize>,
+}
+
+impl<'a> TableView<'a> {
+    /// Display only the columns `columns`, in that order. Columns beyond the ones of
+    /// the table are displayed empty.
+    pub fn columns(mut self, columns: &[usize]) -> TableView<'a> {
+        self.columns = Some(columns.to_vec());
+        self
+    }
+
+    /// Keep only the rows for which `f` returns `true`. `f` receives the rows of the
+    /// table with all their columns. Several filters keep the rows matching all of them.
+    pub fn filter<F: Fn(&Row) -> bool + 'a>(mut self, f: F) -> TableView<'a> {
+        self.filters.push(Box::new(f));
+        self
+    }
+
+    /// Sort the rows by column `column`, like `Table::sort_by_column()`. As with successive
+    /// calls to `Table::sort_by_column()`, the last sort added is the most significant one.
+    pub fn sort_by_column(mut self, column: usize, order: SortOrder) -> TableView<'a> {
+        self.sorts.push((column, order));
+        self
+    }
+
+    /// Keep only the first `rows` rows, once filtered and sorted
+    pub fn limit(mut self, rows: usize) -> TableView<'a> {
+        self.limit = Some(rows);
+        self
+    }
+
+    /// Return the rows of the table kept by the view, in order
+    fn rows(&self) -> Vec<ViewRow<'a>> {
+        let mut rows = self
+            .table
+            .rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| self.filters.iter().all(|f| f(row)))
+            .map(|(position, row)| ViewRow { position, row })
+            .collect::<Vec<_>>();
+        for &(column, order) in &self.sorts {
+            rows = sort_rows(rows, column, order);
+        }
+        if let Some(limit) = self.limit {
+            rows.truncate(limit);
+        }
+        rows
+    }
+
+    /// Return a new table holding a copy of the rows and columns of the view, with the
+    /// titles, footer, format and display settings of the table
+    pub fn to_table(&self) -> Table {
+        self.table_of(&self.rows())
+    }
+
+    /// Return a new table holding a copy of `rows` and of the columns of the view,
+    /// like `to_table()`
+    fn table_of(&self, rows: &[ViewRow]) -> Table {
+        let mut table = self.table.as_slice().slice(0..0).to_table();
+        table.rows = rows.iter().map(|r| r.row.clone()).collect();
+        if let Some(ref columns) = self.columns {
+            table.reorder_columns(columns);
+        }
+        table
+    }
+
+    /// Internal only
+    fn __print<T: Write + ?Sized, F>(&self, out: &mut T, f: F) -> Result<usize, Error>
+    where
+        F: Fn(&Row, &mut T, &TableFormat, &[usize]) -> Result<usize, Error>,
+    {
+        let rows = self.rows();
+        let positions = rows.iter().map(|r| r.position).collect::<Vec<_>>();
+        // Rows are only copied when some columns are left out
+        match self.columns {
+            Some(_) => {
+                let table = self.table_of(&rows);
+                print_rows(out, &table.as_slice(), &table.rows, &positions, None, f)
+            }
+            None => print_rows(out, &self.table.as_slice(), &rows, &positions, None, f),
+        }
+    }
+
+    /// Print the view to `out` and returns the number of
+    /// line printed, or an error
+    pub fn print<T: Write + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
+        self.__print(out, Row::print)
+    }
+
+    /// Print the view to terminal `out`, applying styles when needed and returns the number of
+    /// line printed, or an error
+    pub fn print_term<T: Terminal + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
+        self.__print(out, Row::print_term)
+    }
+
+    /// Print the view to standard output, like `TableSlice::print_tty()`
+    pub fn print_tty(&self, force_colorize: bool) -> Result<usize, Error> {
+        use is_terminal::IsTerminal;
+        match (stdout(), io::stdout().is_terminal() || force_colorize) {
+            (Some(mut o), true) => self.print_term(&mut *o),
+            _ => self.print(&mut io::stdout()),
+        }
+    }
+
+    /// Print the view to standard output, like `TableSlice::printstd()`.
+    /// Any failure to print is ignored.
+    pub fn printstd(&self) {
+        let _ = self.print_tty(false); // Ignore result
+    }
+}
+
+impl fmt::Debug for TableView<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("TableView")
+            .field("table", &self.table)
+            .field("columns", &self.columns)
+            .field("filters", &self.filters.len())
+            .field("sorts", &self.sorts)
+            .field("limit", &self.limit)
+            .finish()
+    }
+}
+
+impl fmt::Display for TableView<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let mut writer = StringWriter::new();
+        if self.print(&mut writer).is_err() {
+            return Err(fmt::Error);
+        }
+        fmt.write_str(writer.as_string())
+    }
+}
+
+impl Table {
+    /// Return a view on the whole table, to select some of its columns and rows
+    /// without copying them. See `TableView` for details.
+    pub fn view(&self) -> TableView<'_> {
+        TableView {
+            table: self,
+            columns: None,
+            filters: Vec::new(),
+            sorts: Vec::new(),
+            limit: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cell, Row, SortOrder, Table};
+
+    fn services() -> Table {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![
+            Cell::new("service"),
+            Cell::new("region"),
+            Cell::new("errors"),
+        ]));
+        for (service, region, errors) in [
+            ("db", "eu", 12),
+            ("web", "us", 250),
+            ("cache", "eu", 40),
+            ("api", "eu", 12),
+        ] {
+            table.add_row(Row::new(vec![
+                Cell::new(service),
+                Cell::new(region),
+                Cell::from(errors),
+            ]));
+        }
+        table
+    }
+
+    #[test]
+    fn view() {
+        let table = services();
+        let view = table
+            .view()
+            .filter(|row| row[1].get_content() == "eu")
+            .sort_by_column(0, SortOrder::Ascending)
+            .sort_by_column(2, SortOrder::Descending)
+            .limit(3);
+        let out = "\
++---------+--------+--------+
+| service | region | errors |
++=========+========+========+
+| cache   | eu     | 40     |
++---------+--------+--------+
+| api     | eu     | 12     |
++---------+--------+--------+
+| db      | eu     | 12     |
++---------+--------+--------+
+";
+        assert_eq!(view.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(view.to_table().to_string(), view.to_string());
+
+        let view = table.view().columns(&[2, 0]).limit(1);
+        let out = "\
++--------+---------+
+| errors | service |
++========+=========+
+| 12     | db      |
++--------+---------+
+";
+        assert_eq!(view.to_string().replace("\r\n", "\n"), out);
+        assert!(table.view().filter(|_| false).to_table().is_empty());
+        assert_eq!(table.len(), 4);
+    }
+
+    #[test]
+    fn view_render() {
+        let mut table = services();
+        table.set_column_alignment(2, crate::format::Alignment::RIGHT);
+        let view = table.view().columns(&[2]).limit(2);
+        let out = "\
++--------+
+| errors |
++========+
+|     12 |
++--------+
+|    250 |
++--------+
+";
+        assert_eq!(view.to_string().replace("\r\n", "\n"), out);
+    }
+
+    #[test]
+    fn view_row_numbers() {
+        let mut table = services();
+        table.set_row_numbers(crate::RowNumbers {
+            original: true,
+            ..crate::RowNumbers::default()
+        });
+        let view = table
+            .view()
+            .columns(&[0])
+            .filter(|r| r[1].get_content() == "eu")
+            .sort_by_column(0, SortOrder::Ascending)
+            .limit(2);
+        let out = "\
++---+---------+
+| # | service |
++===+=========+
+| 4 | api     |
++---+---------+
+| 3 | cache   |
++---+---------+
+";
+        assert_eq!(view.to_string().replace("\r\n", "\n"), out);
+        let sorted = table
+            .view()
+            .sort_by_column(0, SortOrder::Descending)
+            .limit(1);
+        assert!(sorted.to_string().contains("| 2 | web "));
+        let filtered = table.filtered(|r| r[1].get_content() == "us");
+        assert!(filtered.to_string().contains("| 2 | web "));
+    }
+}
@@ -0,0 +1,417 @@
+//! XLSX export of tables
+//!
+//! Workbooks are written as an uncompressed zip archive containing a single worksheet,
+//! without relying on any external dependency.
+
+use std::error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use super::format::Alignment;
+use super::utils::{strip_ansi, HtmlEscape};
+use super::{AsTableSlice, Cell, Row, Table, TableSlice};
+
+/// Characters not allowed in an Excel sheet name
+const FORBIDDEN_SHEET_CHARS: &[char] = &['[', ']', ':', '*', '?', '/', '\\'];
+
+/// Error returned when writing a table as an XLSX workbook
+#[derive(Debug)]
+pub enum XlsxError {
+    /// An IO error occurred while writing the workbook
+    Io(io::Error),
+    /// The sheet name is empty, longer than 31 characters, or contains one of `[]:*?/\`
+    InvalidSheetName(String),
+}
+
+impl fmt::Display for XlsxError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            XlsxError::Io(ref e) => write!(fmt, "IO error while writing XLSX: {}", e),
+            XlsxError::InvalidSheetName(ref name) => write!(fmt, "Invalid sheet name {:?}", name),
+        }
+    }
+}
+
+impl error::Error for XlsxError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            XlsxError::Io(ref e) => Some(e),
+            XlsxError::InvalidSheetName(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for XlsxError {
+    fn from(e: io::Error) -> XlsxError {
+        XlsxError::Io(e)
+    }
+}
+
+/// Options controlling the XLSX output of a table
+#[derive(Clone, Copy, Debug)]
+pub struct XlsxOptions {
+    /// Write cells holding numbers as numeric cells instead of strings, unless the
+    /// spreadsheet would display them differently, like `007`, `1.50` or numbers with
+    /// more than 15 digits. Enabled by default
+    pub numbers: bool,
+}
+
+impl Default for XlsxOptions {
+    fn default() -> XlsxOptions {
+        XlsxOptions { numbers: true }
+    }
+}
+
+/// Compute the CRC-32 checksum of `data`, as required by the zip format
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Minimal writer for zip archives with stored (uncompressed) entries
+struct ZipWriter<W: Write> {
+    out: W,
+    offset: u32,
+    central_directory: Vec<u8>,
+    entries: u16,
+}
+
+impl<W: Write> ZipWriter<W> {
+    fn new(out: W) -> ZipWriter<W> {
+        ZipWriter {
+            out,
+            offset: 0,
+            central_directory: Vec::new(),
+            entries: 0,
+        }
+    }
+
+    /// Write the fields shared by local file headers and central directory entries
+    fn common_header(header: &mut Vec<u8>, name: &str, data: &[u8]) {
+        // Version needed, flags, method (stored), time, date (1980-01-01)
+        for field in &[20u16, 0, 0, 0, 0x21] {
+            header.extend_from_slice(&field.to_le_bytes());
+        }
+        header.extend_from_slice(&crc32(data).to_le_bytes());
+        header.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        header.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        header.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        // Extra field length
+        header.extend_from_slice(&0u16.to_le_bytes());
+    }
+
+    fn add_file(&mut self, name: &str, data: &[u8]) -> io::Result<()> {
+        let mut header = 0x0403_4b50u32.to_le_bytes().to_vec();
+        Self::common_header(&mut header, name, data);
+        header.extend_from_slice(name.as_bytes());
+        self.out.write_all(&header)?;
+        self.out.write_all(data)?;
+
+        let cd = &mut self.central_directory;
+        cd.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        // Version made by
+        cd.extend_from_slice(&20u16.to_le_bytes());
+        Self::common_header(cd, name, data);
+        // Comment length, disk number, internal and external attributes
+        cd.extend_from_slice(&[0; 10]);
+        cd.extend_from_slice(&self.offset.to_le_bytes());
+        cd.extend_from_slice(name.as_bytes());
+
+        self.offset += (header.len() + data.len()) as u32;
+        self.entries += 1;
+        Ok(())
+    }
+
+    fn finish(mut self) -> io::Result<W> {
+        self.out.write_all(&self.central_directory)?;
+        let mut end = 0x0605_4b50u32.to_le_bytes().to_vec();
+        // Disk numbers
+        end.extend_from_slice(&[0; 4]);
+        end.extend_from_slice(&self.entries.to_le_bytes());
+        end.extend_from_slice(&self.entries.to_le_bytes());
+        end.extend_from_slice(&(self.central_directory.len() as u32).to_le_bytes());
+        end.extend_from_slice(&self.offset.to_le_bytes());
+        // Comment length
+        end.extend_from_slice(&0u16.to_le_bytes());
+        self.out.write_all(&end)?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/><Override PartName="/xl/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.styles+xml"/></Types>"#;
+
+const ROOT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/></Relationships>"#;
+
+const WORKBOOK_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/></Relationships>"#;
+
+/// Cell formats are indexed as `bold * 3 + alignment`, with alignments ordered
+/// left, center, right
+const STYLES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<styleSheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><fonts count="2"><font><sz val="11"/><name val="Calibri"/></font><font><b/><sz val="11"/><name val="Calibri"/></font></fonts><fills count="2"><fill><patternFill patternType="none"/></fill><fill><patternFill patternType="gray125"/></fill></fills><borders count="1"><border><left/><right/><top/><bottom/><diagonal/></border></borders><cellStyleXfs count="1"><xf numFmtId="0" fontId="0" fillId="0" borderId="0"/></cellStyleXfs><cellXfs count="6"><xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="0" applyAlignment="1"><alignment horizontal="left"/></xf><xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="0" applyAlignment="1"><alignment horizontal="center"/></xf><xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="0" applyAlignment="1"><alignment horizontal="right"/></xf><xf numFmtId="0" fontId="1" fillId="0" borderId="0" xfId="0" applyFont="1" applyAlignment="1"><alignment horizontal="left"/></xf><xf numFmtId="0" fontId="1" fillId="0" borderId="0" xfId="0" applyFont="1" applyAlignment="1"><alignment horizontal="center"/></xf><xf numFmtId="0" fontId="1" fillId="0" borderId="0" xfId="0" applyFont="1" applyAlignment="1"><alignment horizontal="right"/></xf></cellXfs></styleSheet>"#;
+
+/// Return the letters naming the column at `idx`, starting from `A`
+fn column_name(mut idx: usize) -> String {
+    let mut name = Vec::new();
+    loop {
+        name.push(b'A' + (idx % 26) as u8);
+        if idx < 26 {
+            break;
+        }
+        idx = idx / 26 - 1;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap()
+}
+
+/// Maximum number of significant digits kept by spreadsheets
+const MAX_DIGITS: usize = 15;
+
+/// Return the numeric value of `content` if it should be written as a number, which is
+/// when the number is displayed back as `content`. Other values, such as identifiers
+/// with leading zeros or with more digits than a spreadsheet keeps, are kept as strings
+fn as_number(content: &str) -> Option<f64> {
+    if content.chars().filter(char::is_ascii_digit).count() > MAX_DIGITS {
+        return None;
+    }
+    content
+        .parse::<f64>()
+        .ok()
+        .filter(|n| n.is_finite() && n.to_string() == content)
+}
+
+/// Format the worksheet XML holding `rows`, the first one being a bold frozen header
+/// when `header` is set
+fn sheet_xml(rows: &[&Row], header: bool, options: &XlsxOptions) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+         <worksheet xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\">",
+    );
+    if header {
+        xml += "<sheetViews><sheetView workbookViewId=\"0\">\
+                <pane ySplit=\"1\" topLeftCell=\"A2\" activePane=\"bottomLeft\" state=\"frozen\"/>\
+                </sheetView></sheetViews>";
+    }
+    xml += "<sheetData>";
+    let mut merges = Vec::new();
+    for (i, row) in rows.iter().enumerate() {
+        let bold = header && i == 0;
+        xml += &format!("<row r=\"{}\">", i + 1);
+        let mut col = 0;
+        for cell in row.iter() {
+            xml += &cell_xml(
+                cell,
+                &format!("{}{}", column_name(col), i + 1),
+                bold,
+                options,
+            );
+            if cell.get_hspan() > 1 {
+                merges.push(format!(
+                    "{}{}:{}{}",
+                    column_name(col),
+                    i + 1,
+                    column_name(col + cell.get_hspan() - 1),
+                    i + 1
+                ));
+            }
+            col += cell.get_hspan();
+        }
+        xml += "</row>";
+    }
+    xml += "</sheetData>";
+    if !merges.is_empty() {
+        xml += &format!("<mergeCells count=\"{}\">", merges.len());
+        for merge in merges {
+            xml += &format!("<mergeCell ref=\"{}\"/>", merge);
+        }
+        xml += "</mergeCells>";
+    }
+    xml += "</worksheet>";
+    xml
+}
+
+/// Format `cell` at `reference` as a worksheet cell element
+fn cell_xml(cell: &Cell, reference: &str, bold: bool, options: &XlsxOptions) -> String {
+    let align = match cell.get_align() {
+        Alignment::LEFT => 0,
+        Alignment::CENTER => 1,
+        Alignment::RIGHT => 2,
+    };
+    let style = if bold { 3 } else { 0 } + align;
+    let content: String = strip_ansi(&cell.get_content())
+        .chars()
+        .filter(|&c| !c.is_control() || c == '\n' || c == '\t')
+        .collect();
+    match as_number(&content).filter(|_| options.numbers && !bold) {
+        Some(n) => format!("<c r=\"{}\" s=\"{}\"><v>{}</v></c>", reference, style, n),
+        None => format!(
+            "<c r=\"{}\" s=\"{}\" t=\"inlineStr\"><is><t xml:space=\"preserve\">{}</t></is></c>",
+            reference,
+            style,
+            HtmlEscape(&content)
+        ),
+    }
+}
+
+impl<'a> TableSlice<'a> {
+    /// Write the table as an XLSX workbook with a single sheet named `sheet_name` to `out`,
+    /// following `options`.
+    ///
+    /// Titles are written as a bold frozen header row, cell alignments are kept
+    /// and horizontally spanning cells are merged.
+    pub fn write_xlsx_to<W: Write>(
+        &self,
+        out: W,
+        sheet_name: &str,
+        options: &XlsxOptions,
+    ) -> Result<W, XlsxError> {
+        if sheet_name.is_empty()
+            || sheet_name.chars().count() > 31
+            || sheet_name.contains(FORBIDDEN_SHEET_CHARS)
+        {
+            return Err(XlsxError::InvalidSheetName(sheet_name.to_string()));
+        }
+        let workbook = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+             <workbook xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\" \
+             xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\">\
+             <sheets><sheet name=\"{}\" sheetId=\"1\" r:id=\"rId1\"/></sheets></workbook>",
+            HtmlEscape(sheet_name)
+        );
+        let rows: Vec<&Row> = self.titles.iter().chain(self.rows.iter()).collect();
+        let sheet = sheet_xml(&rows, self.titles.is_some(), options);
+
+        let mut zip = ZipWriter::new(out);
+        zip.add_file("[Content_Types].xml", CONTENT_TYPES.as_bytes())?;
+        zip.add_file("_rels/.rels", ROOT_RELS.as_bytes())?;
+        zip.add_file("xl/workbook.xml", workbook.as_bytes())?;
+        zip.add_file("xl/_rels/workbook.xml.rels", WORKBOOK_RELS.as_bytes())?;
+        zip.add_file("xl/styles.xml", STYLES.as_bytes())?;
+        zip.add_file("xl/worksheets/sheet1.xml", sheet.as_bytes())?;
+        Ok(zip.finish()?)
+    }
+
+    /// Write the table as an XLSX workbook with a single sheet named `sheet_name`
+    /// to the file at `path`.
+    ///
+    /// Cells looking like numbers are written as numeric cells; use `write_xlsx_to()`
+    /// with `XlsxOptions` to disable this.
+    pub fn write_xlsx(&self, path: &Path, sheet_name: &str) -> Result<(), XlsxError> {
+        let file = BufWriter::new(File::create(path)?);
+        self.write_xlsx_to(file, sheet_name, &XlsxOptions::default())?;
+        Ok(())
+    }
+}
+
+impl Table {
+    /// Write the table as an XLSX workbook with a single sheet named `sheet_name` to `out`,
+    /// following `options`.
+    ///
+    /// See `TableSlice::write_xlsx_to()` for details.
+    pub fn write_xlsx_to<W: Write>(
+        &self,
+        out: W,
+        sheet_name: &str,
+        options: &XlsxOptions,
+    ) -> Result<W, XlsxError> {
+        self.as_slice().write_xlsx_to(out, sheet_name, options)
+    }
+
+    /// Write the table as an XLSX workbook with a single sheet named `sheet_name`
+    /// to the file at `path`.
+    ///
+    /// See `TableSlice::write_xlsx()` for details.
+    pub fn write_xlsx(&self, path: &Path, sheet_name: &str) -> Result<(), XlsxError> {
+        self.as_slice().write_xlsx(path, sheet_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cell, Row, Table};
+
+    #[test]
+    fn checksum() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn column_names() {
+        assert_eq!(column_name(0), "A");
+        assert_eq!(column_name(25), "Z");
+        assert_eq!(column_name(26), "AA");
+        assert_eq!(column_name(701), "ZZ");
+        assert_eq!(column_name(702), "AAA");
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(as_number("42"), Some(42.0));
+        assert_eq!(as_number("-1500"), Some(-1500.0));
+        assert_eq!(as_number("0.25"), Some(0.25));
+        assert_eq!(as_number("123456789012345"), Some(123456789012345.0));
+        assert_eq!(as_number("007"), None);
+        assert_eq!(as_number("1234567890123456"), None);
+        assert_eq!(as_number("-1.5e3"), None);
+        assert_eq!(as_number("1.50"), None);
+        assert_eq!(as_number("+1"), None);
+        assert_eq!(as_number("inf"), None);
+        assert_eq!(as_number("1-2"), None);
+        assert_eq!(as_number(""), None);
+    }
+
+    #[test]
+    fn sheet() {
+        let titles = Row::new(vec![Cell::new("t1"), Cell::new("2")]);
+        let row = Row::new(vec![
+            Cell::new_align("1.5", Alignment::RIGHT),
+            Cell::new("a<b"),
+        ]);
+        let span = Row::new(vec![Cell::new("x").with_hspan(2)]);
+        let xml = sheet_xml(&[&titles, &row, &span], true, &XlsxOptions::default());
+        assert!(xml.contains("state=\"frozen\""));
+        assert!(xml.contains(
+            "<c r=\"B1\" s=\"3\" t=\"inlineStr\"><is><t xml:space=\"preserve\">2</t></is></c>"
+        ));
+        assert!(xml.contains("<c r=\"A2\" s=\"2\"><v>1.5</v></c>"));
+        assert!(xml.contains("a&lt;b"));
+        assert!(xml.contains("<mergeCell ref=\"A3:B3\"/>"));
+
+        let xml = sheet_xml(&[&row], false, &XlsxOptions { numbers: false });
+        assert!(!xml.contains("sheetView"));
+        assert!(xml.contains("<c r=\"A1\" s=\"2\" t=\"inlineStr\">"));
+    }
+
+    #[test]
+    fn workbook() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("t1")]));
+        table.add_row(Row::new(vec![Cell::new("1")]));
+        let zip = table
+            .write_xlsx_to(Vec::new(), "Sheet 1", &XlsxOptions::default())
+            .unwrap();
+        assert!(zip.starts_with(b"PK\x03\x04"));
+        let eocd = &zip[zip.len() - 22..];
+        assert!(eocd.starts_with(b"PK\x05\x06"));
+        assert_eq!(&eocd[8..10], &6u16.to_le_bytes());
+
+        match table.write_xlsx_to(Vec::new(), "a/b", &XlsxOptions::default()) {
+            Err(XlsxError::InvalidSheetName(name)) => assert_eq!(name, "a/b"),
+            _ => panic!("Expected an invalid sheet name error"),
+        }
+    }
+}
@@ -0,0 +1,236 @@
+//! YAML export of tables
+
+use super::utils::strip_ansi;
+use super::{AsTableSlice, Row, Table, TableSlice};
+
+/// Words which would not be read back as strings if left unquoted
+const RESERVED: &[&str] = &[
+    "~", "null", "Null", "NULL", "true", "True", "TRUE", "false", "False", "FALSE", "yes", "Yes",
+    "YES", "no", "No", "NO", "on", "On", "ON", "off", "Off", "OFF", "y", "Y", "n", "N", "=", "<<",
+];
+
+/// Tell if `s` would be read as a number by a YAML 1.1 or 1.2 parser if left unquoted,
+/// including the YAML 1.1 `_` digit separators and `:` sexagesimal numbers
+fn is_number(s: &str) -> bool {
+    let s = s.strip_prefix(|c| c == '-' || c == '+').unwrap_or(s);
+    let lower = s.to_ascii_lowercase();
+    if lower == ".inf" || lower == ".nan" {
+        return true;
+    }
+    let digits: String = lower.chars().filter(|&c| c != '_' && c != ':').collect();
+    let radix = [("0x", 16), ("0o", 8), ("0b", 2)]
+        .iter()
+        .find_map(|&(prefix, radix)| digits.strip_prefix(prefix).map(|d| (d, radix)));
+    match radix {
+        Some((d, radix)) => !d.is_empty() && d.chars().all(|c| c.is_digit(radix)),
+        None => {
+            digits.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+                && digits.parse::<f64>().is_ok()
+        }
+    }
+}
+
+/// Tell if `s` starts like a YAML 1.1 timestamp, as in `2001-12-14`
+fn is_timestamp(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    bytes.len() > 5
+        && bytes[..4].iter().all(u8::is_ascii_digit)
+        && bytes[4] == b'-'
+        && bytes[5].is_ascii_digit()
+}
+
+/// Format `s` as a YAML scalar. Multi-line content is written as a literal block scalar,
+/// indented by `indent` spaces
+fn scalar(s: &str, indent: usize) -> String {
+    let has_control = s.chars().any(|c| c.is_control() && c != '\n');
+    if s.contains('\n')
+        && !has_control
+        && !s.lines().any(|l| l.starts_with(' ') || l.starts_with('\t'))
+    {
+        let mut block = String::from("|-");
+        for line in s.split('\n') {
+            block.push('\n');
+            if !line.is_empty() {
+                block += &" ".repeat(indent);
+                block += line;
+            }
+        }
+        return block;
+    }
+    if has_control || s.contains('\n') {
+        let mut quoted = String::from("\"");
+        for c in s.chars() {
+            match c {
+                '"' => quoted.push_str("\\\""),
+                '\\' => quoted.push_str("\\\\"),
+                '\n' => quoted.push_str("\\n"),
+                '\t' => quoted.push_str("\\t"),
+                c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+                c => quoted.push(c),
+            }
+        }
+        quoted.push('"');
+        return quoted;
+    }
+    let needs_quotes = s.is_empty()
+        || RESERVED.contains(&s)
+        || is_number(s)
+        || is_timestamp(s)
+        || s.starts_with(|c: char| c.is_whitespace() || "-?:,[]{}#&*!|>'\"%@`".contains(c))
+        || s.ends_with(|c: char| c.is_whitespace() || c == ':')
+        || s.contains(": ")
+        || s.contains(" #");
+    if needs_quotes {
+        format!("'{}'", s.replace('\'', "''"))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Get the plain text content of the cell at `idx` in `row`, or an empty string
+fn cell_text(row: &Row, idx: usize) -> String {
+    row.get_cell(idx)
+        .map(|c| strip_ansi(&c.get_content()))
+        .unwrap_or_default()
+}
+
+impl<'a> TableSlice<'a> {
+    /// Return the table content as a YAML document.
+    ///
+    /// When titles are set, the table is written as a sequence of mappings keyed by the titles.
+    /// Every mapping has all the keys, rows missing a cell get an empty string, and cells
+    /// beyond the titles are keyed by their column index.
+    /// Without titles, the table is written as a sequence of sequences of strings.
+    ///
+    /// Values are written as plain scalars when possible, and quoted otherwise, including
+    /// the ones a YAML 1.1 or 1.2 parser would not read as strings, like `1`, `1e3`,
+    /// `null`, `yes` or `2001-12-14`, so that every value is read back as a string.
+    /// Multi-line content is written as literal block scalars. ANSI escape sequences
+    /// are stripped from the content.
+    pub fn to_yaml(&self) -> String {
+        if self.rows.is_empty() {
+            return "[]\n".to_string();
+        }
+        let mut yaml = String::new();
+        for r in self.rows {
+            match *self.titles {
+                Some(ref t) => {
+                    let col_num = t.len().max(r.len());
+                    if col_num == 0 {
+                        yaml += "- {}\n";
+                    }
+                    for j in 0..col_num {
+                        let key = match t.get_cell(j) {
+                            Some(c) => strip_ansi(&c.get_content()),
+                            None => j.to_string(),
+                        };
+                        yaml += if j == 0 { "- " } else { "  " };
+                        yaml += &format!(
+                            "{}: {}\n",
+                            scalar(&key.replace('\n', " "), 4),
+                            scalar(&cell_text(r, j), 4)
+                        );
+                    }
+                }
+                None => {
+                    if r.is_empty() {
+                        yaml += "- []\n";
+                    }
+                    for j in 0..r.len() {
+                        yaml += if j == 0 { "- - " } else { "  - " };
+                        yaml += &scalar(&cell_text(r, j), 4);
+                        yaml.push('\n');
+                    }
+                }
+            }
+        }
+        yaml
+    }
+}
+
+impl Table {
+    /// Return the table content as a YAML document.
+    ///
+    /// See `TableSlice::to_yaml()` for details.
+    pub fn to_yaml(&self) -> String {
+        self.as_slice().to_yaml()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cell, Row, Table};
+
+    #[test]
+    fn to_yaml() {
+        let mut table = Table::new();
+        assert_eq!(table.to_yaml(), "[]\n");
+        table.add_row(Row::new(vec![
+            Cell::new("\x1B[31ma\x1B[0m"),
+            Cell::new("b: c"),
+        ]));
+        table.add_row(Row::new(vec![Cell::new("d\n\ne"), Cell::new("")]));
+        let out = "\
+- - a
+  - 'b: c'
+- - |-
+    d
+
+    e
+  - ''
+";
+        assert_eq!(table.to_yaml(), out);
+
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("true")]));
+        table.add_row(Row::new(vec![Cell::new("1")]));
+        let out = "\
+- t1: a
+  'true': 'b: c'
+- t1: |-
+    d
+
+    e
+  'true': ''
+- t1: '1'
+  'true': ''
+";
+        assert_eq!(table.to_yaml(), out);
+    }
+
+    #[test]
+    fn yaml_quoting() {
+        use super::scalar;
+        assert_eq!(scalar("plain text", 0), "plain text");
+        assert_eq!(scalar("it's", 0), "it's");
+        assert_eq!(scalar("- item", 0), "'- item'");
+        assert_eq!(scalar("'quoted'", 0), "'''quoted'''");
+        assert_eq!(scalar("a #comment", 0), "'a #comment'");
+        assert_eq!(scalar(" a\nb", 2), "\" a\\nb\"");
+        assert_eq!(scalar("a\tb", 0), "\"a\\tb\"");
+        for s in &[
+            "1",
+            "-2",
+            "+3.5",
+            ".5",
+            "1e3",
+            "1_000",
+            "0x1F",
+            "0o17",
+            "0b101",
+            "1:20",
+            ".inf",
+            "-.Inf",
+            ".NaN",
+            "y",
+            "N",
+            "=",
+            "<<",
+            "2001-12-14",
+        ] {
+            assert_eq!(scalar(s, 0), format!("'{}'", s));
+        }
+        for s in &["1a", "0x", "inf", "nan", "e3", "1.2.3", "v1.0"] {
+            assert_eq!(scalar(s, 0), *s);
+        }
+    }
+}